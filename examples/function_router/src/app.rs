@@ -1,7 +1,4 @@
-use std::collections::HashMap;
-
 use yew::prelude::*;
-use yew_router::history::{AnyHistory, History, MemoryHistory};
 use yew_router::prelude::*;
 
 use crate::components::nav::Nav;
@@ -52,21 +49,16 @@ pub fn App() -> Html {
     }
 }
 
-#[derive(Properties, PartialEq, Eq, Debug)]
+#[derive(Properties, PartialEq, Debug)]
 pub struct ServerAppProps {
     pub url: AttrValue,
-    pub queries: HashMap<String, String>,
+    pub response: ServerResponse,
 }
 
 #[function_component]
 pub fn ServerApp(props: &ServerAppProps) -> Html {
-    let history = AnyHistory::from(MemoryHistory::new());
-    history
-        .push_with_query(&*props.url, &props.queries)
-        .unwrap();
-
     html! {
-        <Router history={history}>
+        <StaticRouter url={props.url.clone()} response={props.response.clone()}>
             <Nav />
 
             <main>
@@ -82,7 +74,7 @@ pub fn ServerApp(props: &ServerAppProps) -> Html {
                     <a href="https://unsplash.com">{ "Unsplash" }</a>
                 </div>
             </footer>
-        </Router>
+        </StaticRouter>
     }
 }
 
@@ -1,23 +1,20 @@
-use std::collections::HashMap;
-use std::convert::Infallible;
 use std::future::Future;
 use std::path::PathBuf;
 
-use axum::body::StreamBody;
 use axum::error_handling::HandleError;
-use axum::extract::{Query, State};
+use axum::extract::State;
 use axum::handler::HandlerWithoutStateExt;
-use axum::http::{StatusCode, Uri};
-use axum::response::IntoResponse;
+use axum::http::{header, StatusCode, Uri};
+use axum::response::{Html, IntoResponse, Response};
 use axum::routing::get;
 use axum::Router;
 use clap::Parser;
 use function_router::{ServerApp, ServerAppProps};
-use futures::stream::{self, StreamExt};
 use hyper::server::Server;
 use tower::ServiceExt;
 use tower_http::services::ServeDir;
 use yew::platform::Runtime;
+use yew_router::response::ServerResponse;
 
 // We use jemalloc as it produces better performance.
 #[global_allocator]
@@ -33,22 +30,38 @@ struct Opt {
 
 async fn render(
     url: Uri,
-    Query(queries): Query<HashMap<String, String>>,
     State((index_html_before, index_html_after)): State<(String, String)>,
-) -> impl IntoResponse {
+) -> Response {
     let url = url.to_string();
-
-    let renderer = yew::ServerRenderer::<ServerApp>::with_props(move || ServerAppProps {
-        url: url.into(),
-        queries,
+    let response = ServerResponse::new();
+
+    let renderer = yew::ServerRenderer::<ServerApp>::with_props({
+        let response = response.clone();
+        move || ServerAppProps {
+            url: url.into(),
+            response,
+        }
     });
 
-    StreamBody::new(
-        stream::once(async move { index_html_before })
-            .chain(renderer.render_stream())
-            .chain(stream::once(async move { index_html_after }))
-            .map(Result::<_, Infallible>::Ok),
+    // The status code and redirect are only known once the application has been rendered, so we
+    // render the whole page before responding instead of streaming it.
+    let body = renderer.render().await;
+
+    if let Some(redirect) = response.redirect() {
+        let status = StatusCode::from_u16(redirect.status().as_u16()).unwrap();
+        return (status, [(header::LOCATION, redirect.location().to_owned())]).into_response();
+    }
+
+    let status = response
+        .status()
+        .and_then(|status| StatusCode::from_u16(status).ok())
+        .unwrap_or(StatusCode::OK);
+
+    (
+        status,
+        Html(format!("{index_html_before}{body}{index_html_after}")),
     )
+        .into_response()
 }
 
 // An executor to process requests on the Yew runtime.
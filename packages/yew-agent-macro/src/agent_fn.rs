@@ -223,7 +223,7 @@ where
 }
 
 pub struct AgentName {
-    agent_name: Option<Ident>,
+    pub agent_name: Option<Ident>,
 }
 
 impl Parse for AgentName {
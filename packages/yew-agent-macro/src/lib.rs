@@ -4,10 +4,12 @@ use syn::parse_macro_input;
 mod agent_fn;
 mod oneshot;
 mod reactor;
+mod service;
 
 use agent_fn::{AgentFn, AgentName};
 use oneshot::{oneshot_impl, OneshotFn};
 use reactor::{reactor_impl, ReactorFn};
+use service::{agent_service_impl, ServiceTrait};
 
 #[proc_macro_attribute]
 pub fn reactor(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -28,3 +30,13 @@ pub fn oneshot(attr: TokenStream, item: TokenStream) -> TokenStream {
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
+
+#[proc_macro_attribute]
+pub fn agent_service(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ServiceTrait);
+    let attr = parse_macro_input!(attr as AgentName);
+
+    agent_service_impl(attr, item)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
@@ -6,9 +6,15 @@ use crate::agent_fn::{AgentFn, AgentFnType, AgentName};
 
 pub struct OneshotFn {}
 
+pub struct OneshotRecv {
+    input_type: Type,
+    // Whether the function accepts a `CancellationToken` as its second argument.
+    with_token: bool,
+}
+
 impl AgentFnType for OneshotFn {
     type OutputType = Type;
-    type RecvType = Type;
+    type RecvType = OneshotRecv;
 
     fn attr_name() -> &'static str {
         "oneshot"
@@ -24,11 +30,22 @@ impl AgentFnType for OneshotFn {
             .next()
             .ok_or_else(|| syn::Error::new_spanned(&sig.ident, "expected 1 argument"))?;
 
-        let ty = Self::extract_fn_arg_type(arg)?;
+        let input_type = Self::extract_fn_arg_type(arg)?;
+
+        let with_token = match inputs.next() {
+            Some(m) => {
+                Self::extract_fn_arg_type(m)?;
+                true
+            }
+            None => false,
+        };
 
-        Self::assert_no_left_argument(inputs, 1)?;
+        Self::assert_no_left_argument(inputs, 2)?;
 
-        Ok(ty)
+        Ok(OneshotRecv {
+            input_type,
+            with_token,
+        })
     }
 
     fn parse_output_type(sig: &Signature) -> syn::Result<Self::OutputType> {
@@ -54,7 +71,10 @@ pub fn oneshot_impl(name: AgentName, mut agent_fn: AgentFn<OneshotFn>) -> syn::R
     let inner_fn = agent_fn.print_inner_fn();
 
     let AgentFn {
-        recv_type: input_type,
+        recv_type: OneshotRecv {
+            input_type,
+            with_token,
+        },
         generics,
         output_type,
         vis,
@@ -65,13 +85,23 @@ pub fn oneshot_impl(name: AgentName, mut agent_fn: AgentFn<OneshotFn>) -> syn::R
     let fn_generics = ty_generics.as_turbofish();
 
     let in_ident = Ident::new("_input", Span::mixed_site());
+    let token_ident = Ident::new("_token", Span::mixed_site());
+    let crate_name = quote! { yew_agent };
+
+    let (token, fn_args) = if with_token {
+        (
+            quote! { let #token_ident = ::#crate_name::oneshot::CancellationToken::current(); },
+            quote! { #in_ident, #token_ident },
+        )
+    } else {
+        (quote! {}, quote! { #in_ident })
+    };
 
     let fn_call = if is_async {
-        quote! { #fn_name #fn_generics (#in_ident).await }
+        quote! { #fn_name #fn_generics (#fn_args).await }
     } else {
-        quote! { #fn_name #fn_generics (#in_ident) }
+        quote! { #fn_name #fn_generics (#fn_args) }
     };
-    let crate_name = quote! { yew_agent };
 
     let quoted = quote! {
         #(#struct_attrs)*
@@ -89,6 +119,7 @@ pub fn oneshot_impl(name: AgentName, mut agent_fn: AgentFn<OneshotFn>) -> syn::R
 
             fn create(#in_ident: Self::Input) -> Self {
                 #inner_fn
+                #token
 
                 Self {
                     inner: ::std::boxed::Box::pin(
@@ -0,0 +1,300 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    parse_quote, Attribute, FnArg, Ident, ItemTrait, Pat, ReturnType, TraitItem, TraitItemFn, Type,
+};
+
+use crate::agent_fn::AgentName;
+
+pub struct ServiceTrait {
+    item: ItemTrait,
+    methods: Vec<ServiceMethod>,
+}
+
+struct ServiceMethod {
+    attrs: Vec<Attribute>,
+    ident: Ident,
+    args: Vec<(Ident, Type)>,
+    output: Type,
+}
+
+impl Parse for ServiceTrait {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let item = input.parse::<ItemTrait>().map_err(|e| {
+            syn::Error::new(
+                e.span(),
+                "`agent_service` attribute can only be applied to traits",
+            )
+        })?;
+
+        if !item.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &item.generics,
+                "agent services can't have generic parameters",
+            ));
+        }
+
+        let methods = item
+            .items
+            .iter()
+            .map(|m| match m {
+                TraitItem::Fn(m) => ServiceMethod::parse(m),
+                m => Err(syn::Error::new_spanned(
+                    m,
+                    "agent services can only contain async methods",
+                )),
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        Ok(Self { item, methods })
+    }
+}
+
+impl ServiceMethod {
+    fn parse(item: &TraitItemFn) -> syn::Result<Self> {
+        let sig = &item.sig;
+
+        if sig.asyncness.is_none() {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "methods of agent services must be async",
+            ));
+        }
+
+        if !sig.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &sig.generics,
+                "methods of agent services can't have generic parameters",
+            ));
+        }
+
+        let mut inputs = sig.inputs.iter();
+
+        match inputs.next() {
+            Some(FnArg::Receiver(m)) if m.reference.is_some() && m.mutability.is_none() => {}
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    sig,
+                    "methods of agent services must take `&self` as the first argument",
+                ))
+            }
+        }
+
+        let args = inputs
+            .map(|m| match m {
+                FnArg::Typed(arg) => match &*arg.pat {
+                    Pat::Ident(pat) => Ok((pat.ident.clone(), (*arg.ty).clone())),
+                    pat => Err(syn::Error::new_spanned(
+                        pat,
+                        "arguments of agent service methods must be identifiers",
+                    )),
+                },
+                FnArg::Receiver(m) => Err(syn::Error::new_spanned(m, "unexpected receiver")),
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let output = match &sig.output {
+            ReturnType::Default => parse_quote! { () },
+            ReturnType::Type(_, ty) => (**ty).clone(),
+        };
+
+        Ok(Self {
+            attrs: item
+                .attrs
+                .iter()
+                .filter(|m| m.path().is_ident("doc"))
+                .cloned()
+                .collect(),
+            ident: sig.ident.clone(),
+            args,
+            output,
+        })
+    }
+}
+
+pub fn agent_service_impl(name: AgentName, service: ServiceTrait) -> syn::Result<TokenStream> {
+    let ServiceTrait { item, methods } = service;
+    let crate_name = quote! { yew_agent };
+    let item = desugar_trait(item, &crate_name);
+
+    let trait_ident = &item.ident;
+    let vis = &item.vis;
+    let service_ident = name
+        .agent_name
+        .unwrap_or_else(|| format_ident!("{}Service", trait_ident));
+    let client_ident = format_ident!("{}Client", trait_ident);
+    let request_ident = format_ident!("{}Request", trait_ident);
+    let response_ident = format_ident!("{}Response", trait_ident);
+
+    if service_ident == *trait_ident {
+        return Err(syn::Error::new_spanned(
+            service_ident,
+            "the service must not have the same name as the trait",
+        ));
+    }
+
+    let serde = quote! { ::#crate_name::__vendored::serde };
+    let serde_crate = format!("::{crate_name}::__vendored::serde");
+    let handler = Ident::new("handler", Span::mixed_site());
+    let request = Ident::new("request", Span::mixed_site());
+    let call = Ident::new("call", Span::mixed_site());
+    let handler_ty = Ident::new("__YewAgentHandler", Span::mixed_site());
+
+    let variants = methods.iter().map(|m| &m.ident).collect::<Vec<_>>();
+    let arg_types = methods
+        .iter()
+        .map(|m| m.args.iter().map(|(_, ty)| ty).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let arg_idents = methods
+        .iter()
+        .map(|m| m.args.iter().map(|(ident, _)| ident).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let outputs = methods.iter().map(|m| &m.output).collect::<Vec<_>>();
+    let method_attrs = methods.iter().map(|m| &m.attrs).collect::<Vec<_>>();
+
+    let service_doc = format!(
+        " The service agent of [`{trait_ident}`].\n\n Use it with `ServiceProvider` and \
+         `use_service`, and register it in a worker with [`{service_ident}::registrar`]."
+    );
+    let client_doc = format!(" The client of [`{service_ident}`].");
+
+    let quoted = quote! {
+        #item
+
+        #[doc = #service_doc]
+        #[derive(Debug)]
+        #vis struct #service_ident {
+            _private: (),
+        }
+
+        impl #service_ident {
+            /// Creates a registrar serving the service with `handler`.
+            #vis fn registrar<#handler_ty>(
+                #handler: #handler_ty,
+            ) -> ::#crate_name::service::ServiceRegistrar<Self>
+            where
+                #handler_ty: #trait_ident + 'static,
+            {
+                ::#crate_name::service::ServiceRegistrar::new(#handler)
+            }
+        }
+
+        #[doc(hidden)]
+        #[allow(non_camel_case_types, unused_parens)]
+        #[derive(#serde::Serialize, #serde::Deserialize)]
+        #[serde(crate = #serde_crate)]
+        #vis enum #request_ident {
+            #( #variants ( #(#arg_types,)* ), )*
+        }
+
+        #[doc(hidden)]
+        #[allow(non_camel_case_types, unused_parens)]
+        #[derive(#serde::Serialize, #serde::Deserialize)]
+        #[serde(crate = #serde_crate)]
+        #vis enum #response_ident {
+            #( #variants ( #outputs ), )*
+        }
+
+        impl ::#crate_name::service::Service for #service_ident {
+            type Request = #request_ident;
+            type Response = #response_ident;
+            type Client = #client_ident;
+        }
+
+        impl<#handler_ty> ::#crate_name::service::ServiceFor<#handler_ty> for #service_ident
+        where
+            #handler_ty: #trait_ident + 'static,
+        {
+            fn dispatch(
+                #handler: &::std::rc::Rc<#handler_ty>,
+                #request: Self::Request,
+            ) -> ::#crate_name::__vendored::futures::future::LocalBoxFuture<'static, Self::Response> {
+                let #handler = ::std::rc::Rc::clone(#handler);
+
+                ::std::boxed::Box::pin(async move {
+                    match #request {
+                        #(
+                            #request_ident::#variants ( #(#arg_idents,)* ) => {
+                                #response_ident::#variants (
+                                    #trait_ident::#variants(&*#handler, #(#arg_idents,)*).await
+                                )
+                            }
+                        )*
+                    }
+                })
+            }
+        }
+
+        #[doc = #client_doc]
+        #[derive(Debug, Clone, PartialEq)]
+        #vis struct #client_ident {
+            inner: ::#crate_name::service::ServiceClient<#service_ident>,
+        }
+
+        impl ::std::convert::From<::#crate_name::service::ServiceClient<#service_ident>> for #client_ident {
+            fn from(inner: ::#crate_name::service::ServiceClient<#service_ident>) -> Self {
+                Self { inner }
+            }
+        }
+
+        impl #client_ident {
+            /// Returns the number of calls waiting for a response.
+            #vis fn in_flight(&self) -> usize {
+                self.inner.in_flight()
+            }
+
+            #(
+                #(#method_attrs)*
+                #[allow(unreachable_patterns)]
+                #vis fn #variants(
+                    &self,
+                    #(#arg_idents: #arg_types,)*
+                ) -> impl ::std::future::Future<
+                    Output = ::std::result::Result<#outputs, ::#crate_name::service::ServiceError>,
+                > + 'static {
+                    let #call = self.inner.call(#request_ident::#variants ( #(#arg_idents,)* ));
+
+                    async move {
+                        match #call.await? {
+                            #response_ident::#variants(m) => ::std::result::Result::Ok(m),
+                            _ => ::std::result::Result::Err(
+                                ::#crate_name::service::ServiceError::UnexpectedResponse,
+                            ),
+                        }
+                    }
+                }
+            )*
+        }
+    };
+
+    Ok(quoted)
+}
+
+/// Replaces the async methods of the trait with methods returning boxed futures, as async
+/// functions in traits are not supported by the minimum supported Rust version.
+fn desugar_trait(mut item: ItemTrait, crate_name: &TokenStream) -> ItemTrait {
+    for m in item.items.iter_mut() {
+        if let TraitItem::Fn(m) = m {
+            let output = match &m.sig.output {
+                ReturnType::Default => parse_quote! { () },
+                ReturnType::Type(_, ty) => (**ty).clone(),
+            };
+
+            m.sig.asyncness = None;
+            m.sig.output = parse_quote! {
+                -> ::#crate_name::__vendored::futures::future::LocalBoxFuture<'_, #output>
+            };
+
+            if let Some(block) = m.default.take() {
+                m.default = Some(parse_quote! {
+                    {
+                        ::std::boxed::Box::pin(async move #block)
+                    }
+                });
+            }
+        }
+    }
+
+    item
+}
@@ -0,0 +1,51 @@
+use yew_agent::service::agent_service;
+
+#[agent_service]
+pub trait NotAsync {
+    fn add(&self, a: i32, b: i32) -> i32;
+}
+
+#[agent_service]
+pub trait Constants {
+    const ZERO: i32;
+}
+
+#[agent_service]
+pub trait Types {
+    type Output;
+}
+
+#[agent_service]
+pub trait NoReceiver {
+    async fn add(a: i32, b: i32) -> i32;
+}
+
+#[agent_service]
+pub trait MutReceiver {
+    async fn add(&mut self, a: i32, b: i32) -> i32;
+}
+
+#[agent_service]
+pub trait GenericMethod {
+    async fn add<T>(&self, a: T, b: T) -> i32;
+}
+
+#[agent_service]
+pub trait Generic<T> {
+    async fn add(&self, a: T, b: T) -> i32;
+}
+
+#[agent_service]
+pub trait Patterns {
+    async fn add(&self, (a, b): (i32, i32)) -> i32;
+}
+
+#[agent_service(Same)]
+pub trait Same {
+    async fn add(&self, a: i32, b: i32) -> i32;
+}
+
+#[agent_service]
+pub struct NotATrait;
+
+fn main() {}
@@ -0,0 +1,56 @@
+use yew_agent::service::{agent_service, LocalBoxFuture};
+
+#[agent_service]
+pub trait Math {
+    /// Adds two numbers.
+    async fn add(&self, a: i32, b: i32) -> i32;
+    async fn checked_div(&self, a: i32, b: i32) -> ::std::result::Result<i32, ::std::string::String>;
+    async fn reset(&self);
+    async fn double(&self, a: i32) -> i32 {
+        self.add(a, a).await
+    }
+}
+
+#[agent_service(Strings)]
+trait Text {
+    async fn len(&self, text: ::std::string::String) -> usize;
+}
+
+struct MathWorker;
+
+impl Math for MathWorker {
+    fn add(&self, a: i32, b: i32) -> LocalBoxFuture<'_, i32> {
+        ::std::boxed::Box::pin(async move { a + b })
+    }
+
+    fn checked_div(
+        &self,
+        a: i32,
+        b: i32,
+    ) -> LocalBoxFuture<'_, ::std::result::Result<i32, ::std::string::String>> {
+        ::std::boxed::Box::pin(async move { a.checked_div(b).ok_or_else(|| "division by zero".into()) })
+    }
+
+    fn reset(&self) -> LocalBoxFuture<'_, ()> {
+        ::std::boxed::Box::pin(async {})
+    }
+}
+
+struct TextWorker;
+
+impl Text for TextWorker {
+    fn len(&self, text: ::std::string::String) -> LocalBoxFuture<'_, usize> {
+        ::std::boxed::Box::pin(async move { text.len() })
+    }
+}
+
+fn main() {
+    let _ = MathService::registrar(MathWorker);
+    let _ = Strings::registrar(TextWorker);
+
+    fn _calls(client: MathClient) {
+        let _ = client.add(1, 2);
+        let _ = client.double(1);
+        let _ = client.reset();
+    }
+}
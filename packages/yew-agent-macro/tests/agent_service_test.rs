@@ -0,0 +1,7 @@
+#[allow(dead_code)]
+#[rustversion::attr(stable(1.64), test)]
+fn tests() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/agent_service/*-pass.rs");
+    t.compile_fail("tests/agent_service/*-fail.rs");
+}
@@ -0,0 +1,284 @@
+//! Bridges to agents running in dedicated workers spawned by providers.
+//!
+//! gloo-worker does not expose the workers it spawns, so providers spawn them here and speak the
+//! protocol of its registrars themselves. This lets providers listen to the errors of their
+//! workers, and post inputs with a transfer list.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::{Rc, Weak};
+
+use js_sys::Array;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, MessageEvent, Url, Worker};
+
+use crate::transfer::transfer_list;
+use crate::utils::get_next_id;
+use crate::Codec;
+
+/// The id of a bridge, as `HandlerId` of gloo-worker.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy)]
+struct BridgeId(usize);
+
+/// A message to a worker, as `ToWorker` of gloo-worker.
+#[derive(Serialize, Deserialize)]
+enum ToWorker<I> {
+    Connected(BridgeId),
+    ProcessInput(BridgeId, I),
+    Disconnected(BridgeId),
+    Destroy,
+}
+
+/// A message from a worker, as `FromWorker` of gloo-worker.
+#[derive(Serialize, Deserialize)]
+enum FromWorker<O> {
+    WorkerLoaded,
+    ProcessOutput(BridgeId, O),
+}
+
+/// The input of a reactor worker, as `ReactorInput` of gloo-worker.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum ReactorInput<I> {
+    Input(I),
+}
+
+/// The output of a reactor worker, as `ReactorOutput` of gloo-worker.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum ReactorOutput<O> {
+    Output(O),
+    Finish,
+}
+
+type Callbacks<O> = HashMap<BridgeId, Weak<dyn Fn(O)>>;
+
+/// A worker spawned by a provider.
+pub(crate) struct DedicatedWorker {
+    pub worker: Worker,
+    /// Whether the objects listed in the inputs are transferred.
+    pub transfer: bool,
+}
+
+impl DedicatedWorker {
+    /// Spawns a worker running the agent at `path`, the path of the JavaScript shim of its
+    /// WebAssembly module.
+    pub fn spawn(path: &str, transfer: bool) -> Self {
+        let href = web_sys::window()
+            .expect_throw("failed to find window")
+            .location()
+            .href()
+            .expect_throw("failed to read href");
+        let shim_url = Url::new_with_base(path, &href)
+            .expect_throw("failed to create url for javascript entrypoint")
+            .to_string();
+        let wasm_url = shim_url.replace(".js", "_bg.wasm");
+
+        let script = Array::of1(
+            &format!(r#"importScripts("{shim_url}");wasm_bindgen("{wasm_url}");"#).into(),
+        );
+        let blob = Blob::new_with_str_sequence_and_options(
+            &script,
+            BlobPropertyBag::new().type_("application/javascript"),
+        )
+        .expect_throw("failed to create worker script");
+        let url = Url::create_object_url_with_blob(&blob).expect_throw("failed to create url");
+
+        Self::spawn_with_loader(&url, transfer)
+    }
+
+    /// Spawns a worker running the loader script at `loader_path`.
+    pub fn spawn_with_loader(loader_path: &str, transfer: bool) -> Self {
+        Self {
+            worker: Worker::new(loader_path).expect_throw("failed to spawn worker"),
+            transfer,
+        }
+    }
+
+    /// Connects a bridge to the worker, which calls `callback` with its outputs.
+    ///
+    /// Inputs are queued until the worker has loaded.
+    pub fn connect<I, O, CODEC>(&self, callback: Option<Rc<dyn Fn(O)>>) -> DedicatedBridge<I, O>
+    where
+        I: Serialize + for<'de> Deserialize<'de> + 'static,
+        O: Serialize + for<'de> Deserialize<'de> + 'static,
+        CODEC: Codec,
+    {
+        let id = BridgeId(get_next_id());
+        let pending: Rc<RefCell<Option<Vec<ToWorker<I>>>>> =
+            Rc::new(RefCell::new(Some(Vec::new())));
+        let callbacks: Rc<RefCell<Callbacks<O>>> = Rc::default();
+        if let Some(m) = callback.as_ref() {
+            callbacks.borrow_mut().insert(id, Rc::downgrade(m));
+        }
+
+        let post: Rc<dyn Fn(ToWorker<I>)> = {
+            let worker = self.worker.clone();
+            let transfer = self.transfer;
+            Rc::new(move |message| {
+                let message = CODEC::encode(message);
+                match transfer.then(|| transfer_list(&message)).flatten() {
+                    Some(m) => worker.post_message_with_transfer(&message, &m),
+                    None => worker.post_message(&message),
+                }
+                .expect_throw("failed to post message");
+            })
+        };
+
+        let on_message = {
+            let pending = pending.clone();
+            let callbacks = callbacks.clone();
+            let post = post.clone();
+            Closure::<dyn Fn(MessageEvent)>::new(move |e: MessageEvent| {
+                match CODEC::decode::<FromWorker<O>>(e.data()) {
+                    FromWorker::WorkerLoaded => {
+                        let queued = pending.borrow_mut().take();
+                        for message in queued.into_iter().flatten() {
+                            post(message);
+                        }
+                    }
+                    FromWorker::ProcessOutput(id, output) => {
+                        let f = callbacks.borrow().get(&id).and_then(Weak::upgrade);
+                        match f {
+                            Some(f) => f(output),
+                            None => {
+                                callbacks.borrow_mut().remove(&id);
+                            }
+                        }
+                    }
+                }
+            })
+        };
+        self.worker
+            .set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        let bridge = DedicatedBridge {
+            inner: Rc::new(BridgeInner {
+                worker: self.worker.clone(),
+                pending,
+                callbacks,
+                post,
+                _on_message: on_message,
+            }),
+            id,
+            callback,
+            _marker: PhantomData,
+        };
+        bridge.send_message(ToWorker::Connected(id));
+
+        bridge
+    }
+}
+
+impl fmt::Debug for DedicatedWorker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DedicatedWorker")
+            .field("transfer", &self.transfer)
+            .finish_non_exhaustive()
+    }
+}
+
+struct BridgeInner<I, O> {
+    worker: Worker,
+    // Messages posted before the worker has loaded, `None` once it has.
+    pending: Rc<RefCell<Option<Vec<ToWorker<I>>>>>,
+    callbacks: Rc<RefCell<Callbacks<O>>>,
+    post: Rc<dyn Fn(ToWorker<I>)>,
+    _on_message: Closure<dyn Fn(MessageEvent)>,
+}
+
+impl<I, O> BridgeInner<I, O> {
+    fn send_message(&self, message: ToWorker<I>) {
+        let mut pending = self.pending.borrow_mut();
+        match pending.as_mut() {
+            Some(m) => m.push(message),
+            None => {
+                drop(pending);
+                (self.post)(message);
+            }
+        }
+    }
+}
+
+impl<I, O> Drop for BridgeInner<I, O> {
+    fn drop(&mut self) {
+        self.send_message(ToWorker::Destroy);
+        self.worker.set_onmessage(None);
+    }
+}
+
+/// A bridge to an agent running in a [`DedicatedWorker`], like `WorkerBridge` of gloo-worker.
+pub(crate) struct DedicatedBridge<I, O> {
+    inner: Rc<BridgeInner<I, O>>,
+    id: BridgeId,
+    callback: Option<Rc<dyn Fn(O)>>,
+    _marker: PhantomData<fn(I)>,
+}
+
+impl<I, O> DedicatedBridge<I, O> {
+    fn send_message(&self, message: ToWorker<I>) {
+        self.inner.send_message(message);
+    }
+
+    /// Sends an input to the agent.
+    pub fn send(&self, input: I) {
+        self.send_message(ToWorker::ProcessInput(self.id, input));
+    }
+
+    /// Connects a new bridge to the same worker, which calls `callback` with its outputs.
+    pub fn fork<F>(&self, callback: Option<F>) -> Self
+    where
+        F: Fn(O) + 'static,
+        O: 'static,
+    {
+        let id = BridgeId(get_next_id());
+        let callback = callback.map(|m| Rc::new(m) as Rc<dyn Fn(O)>);
+        if let Some(m) = callback.as_ref() {
+            self.inner
+                .callbacks
+                .borrow_mut()
+                .insert(id, Rc::downgrade(m));
+        }
+
+        let bridge = Self {
+            inner: self.inner.clone(),
+            id,
+            callback,
+            _marker: PhantomData,
+        };
+        bridge.send_message(ToWorker::Connected(id));
+
+        bridge
+    }
+}
+
+impl<I, O> Clone for DedicatedBridge<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            id: self.id,
+            callback: self.callback.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I, O> Drop for DedicatedBridge<I, O> {
+    fn drop(&mut self) {
+        self.send_message(ToWorker::Disconnected(self.id));
+    }
+}
+
+impl<I, O> fmt::Debug for DedicatedBridge<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DedicatedBridge<_>")
+    }
+}
+
+impl<I, O> PartialEq for DedicatedBridge<I, O> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.id == rhs.id
+    }
+}
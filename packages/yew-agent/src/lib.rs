@@ -12,6 +12,10 @@
 //!
 //! A kind of agent that can send many inputs and receive many outputs over a single bridge.
 //!
+//! #### Service
+//!
+//! A kind of agent that exposes multiple async methods, defined by a trait, over a single bridge.
+//!
 //! #### Worker
 //!
 //! The low-level implementation of agents that provides an actor model and communicates with
@@ -31,6 +35,19 @@
 //! Public agents are shared among all children of a provider.
 //! Only 1 instance will be spawned for each public agents provider.
 //!
+//! #### Pool
+//!
+//! Pooled agents are shared among all children of a provider, like public agents, but up to a
+//! fixed number of instances are spawned. Oneshot tasks are queued until an instance is idle,
+//! while bridges to other agents are distributed among the instances in turn. See the
+//! [`pool`] module.
+//!
+//! #### Shared and Service Worker
+//!
+//! A single instance runs in a shared worker or in the service worker of the application, and is
+//! shared among all tabs of the origin. See the [`shared`] module. Only reactor and oneshot agents
+//! can be shared, the providers of worker agents take a [`WorkerReach`] without these variants.
+//!
 //! ### Provider
 //!
 //! Each Agent requires a provider to provide communications and maintain bridges.
@@ -62,6 +79,13 @@
 //!
 //! Unlike other agents, oneshot bridges provide a `use_oneshot_runner` hook to execute oneshot
 //! agents on demand.
+//!
+//! #### Service Client
+//!
+//! See: [`use_service`](service::use_service)
+//!
+//! Service agents provide a `use_service` hook that returns a client with a method for each method
+//! of the service.
 
 #![deny(
     clippy::all,
@@ -75,22 +99,34 @@
 extern crate self as yew_agent;
 
 pub mod oneshot;
+pub mod pool;
 pub mod reactor;
+pub mod service;
+pub mod shared;
+pub mod topic;
+pub mod transfer;
 pub mod worker;
 
 #[doc(inline)]
 pub use gloo_worker::{Bincode, Codec, Registrable, Spawnable};
+pub use transfer::StructuredClone;
 
+mod dedicated;
 mod reach;
 pub mod scope_ext;
 
-pub use reach::Reach;
+pub use reach::{Reach, WorkerReach};
+
+mod supervisor;
+
+pub use supervisor::{AgentCrash, RestartPolicy};
 
 mod utils;
 
 #[doc(hidden)]
 pub mod __vendored {
     pub use futures;
+    pub use serde;
 }
 
 pub mod prelude {
@@ -98,15 +134,19 @@ pub mod prelude {
     //!
     //! This module re-exports the frequently used types from the crate.
     pub use crate::oneshot::{oneshot, use_oneshot_runner, UseOneshotRunnerHandle};
-    pub use crate::reach::Reach;
+    pub use crate::reach::{Reach, WorkerReach};
     pub use crate::reactor::{
         reactor, use_reactor_bridge, use_reactor_subscription, ReactorEvent, ReactorScope,
-        UseReactorBridgeHandle, UseReactorSubscriptionHandle,
+        ReactorStream, UseReactorBridgeHandle, UseReactorSubscriptionHandle,
     };
     pub use crate::scope_ext::{AgentScopeExt, ReactorBridgeHandle, WorkerBridgeHandle};
+    pub use crate::service::{agent_service, use_service, ServiceError};
+    pub use crate::shared::RegisterShared;
+    pub use crate::supervisor::RestartPolicy;
+    pub use crate::topic::{use_agent_topic, Topic, UseAgentTopicHandle};
     pub use crate::worker::{
-        use_worker_bridge, use_worker_subscription, UseWorkerBridgeHandle,
-        UseWorkerSubscriptionHandle, WorkerScope,
+        use_worker_bridge, use_worker_subscription, PersistScopeExt, RegisterPersistent,
+        UseWorkerBridgeHandle, UseWorkerSubscriptionHandle, WorkerScope,
     };
     pub use crate::{Registrable, Spawnable};
 }
@@ -0,0 +1,114 @@
+use std::fmt;
+use std::rc::Rc;
+
+use futures::channel::mpsc;
+use futures::stream::StreamExt;
+
+use super::Oneshot;
+use crate::dedicated::DedicatedBridge;
+use crate::shared::PortBridge;
+
+enum Connection<N>
+where
+    N: Oneshot + 'static,
+{
+    Worker {
+        bridge: DedicatedBridge<N::Input, N::Output>,
+        rx: mpsc::UnboundedReceiver<N::Output>,
+    },
+    // A connection to an agent shared by tabs.
+    Port(PortBridge<N::Input, N::Output>),
+}
+
+impl<N> fmt::Debug for Connection<N>
+where
+    N: Oneshot + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Worker { bridge, .. } => bridge.fmt(f),
+            Self::Port(m) => m.fmt(f),
+        }
+    }
+}
+
+/// A connection to an oneshot agent.
+#[derive(Debug)]
+pub struct OneshotBridge<N>
+where
+    N: Oneshot + 'static,
+{
+    inner: Connection<N>,
+    // Shared by all forks of a bridge.
+    worker: Rc<()>,
+}
+
+impl<N> OneshotBridge<N>
+where
+    N: Oneshot + 'static,
+{
+    pub(crate) fn new(
+        inner: DedicatedBridge<N::Input, N::Output>,
+        rx: mpsc::UnboundedReceiver<N::Output>,
+    ) -> Self {
+        Self {
+            inner: Connection::Worker { bridge: inner, rx },
+            worker: Rc::default(),
+        }
+    }
+
+    pub(crate) fn from_port(inner: PortBridge<N::Input, N::Output>) -> Self {
+        Self {
+            inner: Connection::Port(inner),
+            worker: Rc::default(),
+        }
+    }
+
+    /// Forks the bridge.
+    ///
+    /// This method creates a new bridge connected to the same worker instance.
+    pub fn fork(&self) -> Self {
+        let inner = match self.inner {
+            Connection::Worker { ref bridge, .. } => {
+                let (tx, rx) = mpsc::unbounded();
+                let bridge = bridge.fork(Some(move |output| {
+                    let _ = tx.unbounded_send(output);
+                }));
+
+                Connection::Worker { bridge, rx }
+            }
+            Connection::Port(ref m) => Connection::Port(m.fork()),
+        };
+
+        Self {
+            inner,
+            worker: self.worker.clone(),
+        }
+    }
+
+    /// Returns `true` if both bridges are connected to the same worker instance.
+    pub(crate) fn same_worker(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.worker, &other.worker)
+    }
+
+    /// Run the the current oneshot worker once in the connected worker.
+    ///
+    /// Dropping the returned future before it completes cancels the run.
+    pub async fn run(&mut self, input: N::Input) -> N::Output {
+        let output = match self.inner {
+            Connection::Worker {
+                ref bridge,
+                ref mut rx,
+            } => {
+                bridge.send(input);
+                rx.next().await
+            }
+            Connection::Port(ref mut m) => {
+                m.send_input(input);
+                m.next().await
+            }
+        };
+
+        output.expect("failed to receive result from worker")
+    }
+}
@@ -0,0 +1,180 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+thread_local! {
+    // The token of the task being created and whether the task has taken it.
+    static CURRENT: RefCell<Option<(CancellationToken, bool)>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct TokenInner {
+    cancelled: Cell<bool>,
+    wakers: RefCell<Vec<Waker>>,
+}
+
+/// A token signalling that the run of an oneshot agent has been cancelled.
+///
+/// A run is cancelled when the future returned by
+/// [`UseOneshotRunnerHandle::run`](super::UseOneshotRunnerHandle::run) is dropped, or when it
+/// times out.
+///
+/// Oneshot agents created with the [`oneshot`](super::oneshot) attribute receive the token of
+/// their run if they accept it as a second argument. An agent that takes its token is
+/// responsible for stopping when the run is cancelled, for example to clean up after itself.
+/// Other agents are dropped at the next `.await` point after their run is cancelled.
+///
+/// # Example
+///
+/// ```
+/// use yew_agent::oneshot::{oneshot, CancellationToken};
+///
+/// #[oneshot]
+/// async fn Primes(limit: u64, token: CancellationToken) -> Option<Vec<u64>> {
+///     let mut primes = Vec::new();
+///
+///     for n in 2..limit {
+///         if n % 1024 == 0 {
+///             // Yield to the worker so it can receive the cancellation.
+///             yew::platform::time::sleep(std::time::Duration::ZERO).await;
+///
+///             if token.is_cancelled() {
+///                 return None;
+///             }
+///         }
+///
+///         if primes.iter().all(|p| n % p != 0) {
+///             primes.push(n);
+///         }
+///     }
+///
+///     Some(primes)
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Rc<TokenInner>,
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+impl PartialEq for CancellationToken {
+    fn eq(&self, rhs: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &rhs.inner)
+    }
+}
+
+impl CancellationToken {
+    /// Returns the token of the run being created.
+    ///
+    /// This method should be called in [`Oneshot::create`](super::Oneshot::create). Calling it
+    /// marks the run as observing its token, so it is not dropped when cancelled. Outside of an
+    /// oneshot worker, returns a token that is never cancelled.
+    pub fn current() -> Self {
+        CURRENT.with(|m| match m.borrow_mut().as_mut() {
+            Some((token, taken)) => {
+                *taken = true;
+                token.clone()
+            }
+            None => Self::default(),
+        })
+    }
+
+    /// Returns `true` if the run has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.get()
+    }
+
+    /// Returns a future that resolves when the run is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            token: self.clone(),
+        }
+    }
+
+    pub(crate) fn cancel(&self) {
+        if !self.inner.cancelled.replace(true) {
+            for waker in self.inner.wakers.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Calls `f` with `self` as the current token.
+    ///
+    /// Returns whether the token was taken with [`current`](Self::current).
+    pub(crate) fn scope<O>(&self, f: impl FnOnce() -> O) -> (O, bool) {
+        let prev = CURRENT.with(|m| m.replace(Some((self.clone(), false))));
+        let output = f();
+        let taken = CURRENT
+            .with(|m| m.replace(prev))
+            .map_or(false, |(_, taken)| taken);
+
+        (output, taken)
+    }
+}
+
+/// A future that resolves when a run is cancelled.
+///
+/// See [`CancellationToken::cancelled`].
+#[derive(Debug)]
+pub struct Cancelled {
+    token: CancellationToken,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+
+        let mut wakers = self.token.inner.wakers.borrow_mut();
+        if !wakers.iter().any(|m| m.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[test]
+    fn current_token_is_scoped() {
+        let token = CancellationToken::default();
+
+        let (current, taken) = token.scope(CancellationToken::current);
+        assert!(taken);
+        assert_eq!(current, token);
+
+        let ((), taken) = token.scope(|| {});
+        assert!(!taken);
+        assert_ne!(CancellationToken::current(), token);
+    }
+
+    #[test]
+    fn cancelled_resolves_on_cancel() {
+        let token = CancellationToken::default();
+        let mut cancelled = token.cancelled();
+        assert!((&mut cancelled).now_or_never().is_none());
+
+        token.cancel();
+        assert!(token.is_cancelled());
+        assert!(cancelled.now_or_never().is_some());
+    }
+}
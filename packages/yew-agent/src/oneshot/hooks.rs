@@ -1,7 +1,35 @@
+use std::fmt;
+use std::time::Duration;
+
 use yew::prelude::*;
 
 use super::provider::OneshotProviderState;
 use super::Oneshot;
+use crate::pool::PoolMetrics;
+use crate::AgentCrash;
+
+/// An error returned by [`UseOneshotRunnerHandle::try_run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    /// The agent is pooled and the queue of its pool is full.
+    QueueFull,
+    /// The run did not finish before its deadline and has been cancelled.
+    TimedOut,
+    /// The worker crashed during the run.
+    Crashed(AgentCrash),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::QueueFull => f.write_str("the queue of the worker pool is full"),
+            Self::TimedOut => f.write_str("the run did not finish before its deadline"),
+            Self::Crashed(crash) => write!(f, "the run failed, {crash}"),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
 
 /// Hook handle for [`use_oneshot_runner`]
 #[derive(Debug)]
@@ -17,8 +45,45 @@ where
     T: Oneshot + 'static,
 {
     /// Runs an oneshot agent.
+    ///
+    /// If the agent is pooled, waits for an idle worker. Dropping the returned future cancels the
+    /// run. The run has no deadline, even if the provider has a
+    /// [`timeout`](crate::oneshot::OneshotProviderProps::timeout).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the worker crashes during the run. Use [`try_run`](Self::try_run) to handle
+    /// crashes.
     pub async fn run(&self, input: T::Input) -> T::Output {
-        self.state.create_bridge().run(input).await
+        self.state.run(input).await
+    }
+
+    /// Runs an oneshot agent, unless the agent is pooled and the queue of its pool is full.
+    ///
+    /// The size of the queue is limited by the
+    /// [`queue_capacity`](crate::oneshot::OneshotProviderProps::queue_capacity) of the provider.
+    /// The run is cancelled if it takes longer than the
+    /// [`timeout`](crate::oneshot::OneshotProviderProps::timeout) of the provider.
+    pub async fn try_run(&self, input: T::Input) -> Result<T::Output, RunError> {
+        self.state.try_run(input, self.state.timeout()).await
+    }
+
+    /// Like [`try_run`](Self::try_run), but the run is cancelled if it takes longer than
+    /// `timeout`.
+    ///
+    /// The deadline starts when the run is sent to a worker, after waiting in the queue of a
+    /// pooled agent.
+    pub async fn run_with_timeout(
+        &self,
+        input: T::Input,
+        timeout: Duration,
+    ) -> Result<T::Output, RunError> {
+        self.state.try_run(input, Some(timeout)).await
+    }
+
+    /// Returns the state of the worker pool, if the agent is pooled.
+    pub fn pool_metrics(&self) -> Option<PoolMetrics> {
+        self.state.pool_metrics()
     }
 }
 
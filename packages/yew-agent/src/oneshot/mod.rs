@@ -1,12 +1,32 @@
 //! This module provides task agent implementation.
+//!
+//! ## Cancellation
+//!
+//! Dropping the future of a run, for example when the component that started it is destroyed,
+//! cancels the run in the worker. See [`CancellationToken`].
+//!
+//! Runs can also be given a deadline with
+//! [`UseOneshotRunnerHandle::run_with_timeout`] or the
+//! [`timeout`](crate::oneshot::OneshotProviderProps::timeout) of the provider. A run that exceeds
+//! its deadline is cancelled, and the worker is released so the next run starts on a new worker.
 
+mod bridge;
+mod cancellation;
 mod hooks;
 mod provider;
+mod registrar;
+mod spawner;
+mod worker;
 
+pub use bridge::OneshotBridge;
+pub use cancellation::{CancellationToken, Cancelled};
 #[doc(inline)]
-pub use gloo_worker::oneshot::{Oneshot, OneshotBridge, OneshotRegistrar, OneshotSpawner};
-pub use hooks::{use_oneshot_runner, UseOneshotRunnerHandle};
-pub use provider::OneshotProvider;
+pub use gloo_worker::oneshot::Oneshot;
+pub use hooks::{use_oneshot_runner, RunError, UseOneshotRunnerHandle};
 pub(crate) use provider::OneshotProviderState;
+pub use provider::{OneshotProvider, OneshotProviderProps};
+pub use registrar::OneshotRegistrar;
+pub use spawner::OneshotSpawner;
+pub(crate) use worker::Run;
 /// A procedural macro to create oneshot agents.
 pub use yew_agent_macro::oneshot;
@@ -1,24 +1,119 @@
 use core::fmt;
 use std::any::type_name;
 use std::cell::RefCell;
+use std::future::Future;
 use std::rc::Rc;
+use std::time::Duration;
 
+use futures::future::{select, Either, LocalBoxFuture};
+use futures::FutureExt;
 use serde::{Deserialize, Serialize};
+use yew::platform::time::sleep;
 use yew::prelude::*;
 
-use super::{Oneshot, OneshotBridge, OneshotSpawner};
-use crate::utils::get_next_id;
-use crate::worker::WorkerProviderProps;
-use crate::{Bincode, Codec, Reach};
+use super::{CancellationToken, Oneshot, OneshotBridge, OneshotSpawner, RunError};
+use crate::pool::{PoolMetrics, PoolPermit, WorkerPool};
+use crate::shared;
+use crate::supervisor::{Supervised, Supervisor};
+use crate::utils::{get_next_id, roundtrip};
+use crate::{AgentCrash, Bincode, Codec, Reach, RestartPolicy};
+
+/// Properties for [OneshotProvider].
+#[derive(Debug, Properties, PartialEq, Clone)]
+pub struct OneshotProviderProps {
+    /// The path to an agent.
+    pub path: AttrValue,
+
+    /// The reachability of an agent.
+    ///
+    /// Default: [`Public`](Reach::Public).
+    #[prop_or(Reach::Public)]
+    pub reach: Reach,
+
+    /// Lazily spawn the agent.
+    ///
+    /// The agent will be spawned when the first time a hook requests a bridge.
+    ///
+    /// Does not affect private agents.
+    ///
+    /// Default: `true`
+    #[prop_or(true)]
+    pub lazy: bool,
+
+    /// The maximum number of tasks waiting for a pooled agent before
+    /// [`try_run`](super::UseOneshotRunnerHandle::try_run) rejects new tasks.
+    ///
+    /// Only agents with [`Reach::Pool`] queue tasks, other reaches ignore it.
+    ///
+    /// Default: `None` (unbounded)
+    #[prop_or_default]
+    pub queue_capacity: Option<usize>,
+
+    /// The time a run may take before [`try_run`](super::UseOneshotRunnerHandle::try_run) cancels
+    /// it.
+    ///
+    /// The worker of a run that times out is released, so the next run starts on a new worker.
+    ///
+    /// Default: `None` (no timeout)
+    #[prop_or_default]
+    pub timeout: Option<Duration>,
+
+    /// Whether workers that crash are respawned.
+    ///
+    /// Runs that crash fail with [`RunError::Crashed`](super::RunError::Crashed), and the next
+    /// runs start on a new worker.
+    ///
+    /// Default: [`Never`](RestartPolicy::Never).
+    #[prop_or_default]
+    pub restart: RestartPolicy,
+
+    /// Called when a worker crashes.
+    #[prop_or_default]
+    pub on_crash: Option<Callback<AgentCrash>>,
+
+    /// Transfers the [`Transfer`](crate::transfer::Transfer) fields of inputs to workers instead
+    /// of copying them.
+    ///
+    /// Only affects agents encoded with [`StructuredClone`](crate::StructuredClone). Workers
+    /// transfer their outputs once they call
+    /// [`transfer_outputs`](crate::transfer::transfer_outputs).
+    #[prop_or_default]
+    pub transfer: bool,
+
+    /// Runs the agent on the local runtime instead of a worker.
+    ///
+    /// Messages are still serialized and deserialized, with bincode whatever the codec of the
+    /// provider, so local agents behave like agents running in a worker. This allows components
+    /// using agents to be rendered on the server and tested outside of the browser. The
+    /// reachability of local agents is ignored. Applications rendered on the server can set it
+    /// with `local={cfg!(not(target_arch = "wasm32"))}`.
+    ///
+    /// Default: `false`
+    #[prop_or_default]
+    pub local: bool,
+
+    /// Children of the provider.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+type RunLocalFn<T> =
+    Rc<dyn Fn(<T as Oneshot>::Input) -> LocalBoxFuture<'static, <T as Future>::Output>>;
+type SpawnBridgeFn<T> = Rc<dyn Fn() -> Supervised<OneshotBridge<T>>>;
 
 pub(crate) struct OneshotProviderState<T>
 where
     T: Oneshot + 'static,
 {
     id: usize,
-    spawn_bridge_fn: Rc<dyn Fn() -> OneshotBridge<T>>,
+    spawn_bridge_fn: SpawnBridgeFn<T>,
+    // Set if the agent runs on the local runtime.
+    run_local_fn: Option<RunLocalFn<T>>,
     reach: Reach,
-    held_bridge: Rc<RefCell<Option<OneshotBridge<T>>>>,
+    held_bridge: Rc<RefCell<Option<Supervised<OneshotBridge<T>>>>>,
+    pool: Option<WorkerPool<Supervised<OneshotBridge<T>>>>,
+    timeout: Option<Duration>,
+    supervisor: Supervisor,
 }
 
 impl<T> fmt::Debug for OneshotProviderState<T>
@@ -34,28 +129,156 @@ impl<T> OneshotProviderState<T>
 where
     T: Oneshot,
 {
-    fn get_held_bridge(&self) -> OneshotBridge<T> {
+    fn get_held_bridge(&self) -> Supervised<OneshotBridge<T>> {
         let mut held_bridge = self.held_bridge.borrow_mut();
 
         match held_bridge.as_mut() {
-            Some(m) => m.fork(),
-            None => {
+            Some(m) if !m.worker.should_respawn() => m.map(|m| m.fork()),
+            _ => {
                 let bridge = (self.spawn_bridge_fn)();
-                *held_bridge = Some(bridge.fork());
+                *held_bridge = Some(bridge.map(|m| m.fork()));
                 bridge
             }
         }
     }
 
     /// Creates a bridge, uses "fork" for public agents.
-    pub fn create_bridge(&self) -> OneshotBridge<T> {
+    fn create_bridge(&self) -> Supervised<OneshotBridge<T>> {
         match self.reach {
-            Reach::Public => {
+            Reach::Public | Reach::Shared | Reach::ServiceWorker => {
                 let held_bridge = self.get_held_bridge();
-                held_bridge.fork()
+                held_bridge.map(|m| m.fork())
             }
             Reach::Private => (self.spawn_bridge_fn)(),
+            Reach::Pool { .. } => unreachable!("pooled agents run on the workers of their pool"),
+        }
+    }
+
+    fn acquire(
+        &self,
+    ) -> Option<impl Future<Output = PoolPermit<Supervised<OneshotBridge<T>>>> + 'static> {
+        self.pool.as_ref().map(|pool| {
+            pool.respawn_if(|m| m.worker.should_respawn());
+            pool.acquire()
+        })
+    }
+
+    /// Runs the agent, waiting for an idle worker if the agent is pooled.
+    ///
+    /// Panics if the worker crashes during the run.
+    pub async fn run(&self, input: T::Input) -> T::Output {
+        let permit = match self.acquire() {
+            Some(m) => Some(m.await),
+            None => None,
+        };
+
+        match self.run_with(permit, input, None).await {
+            Ok(m) => m,
+            Err(RunError::Crashed(crash)) => panic!("{}", crash),
+            Err(_) => unreachable!("runs without a deadline never time out"),
+        }
+    }
+
+    /// Runs the agent, unless the agent is pooled and its queue is full.
+    ///
+    /// The run is cancelled if it takes longer than `timeout`.
+    pub async fn try_run(
+        &self,
+        input: T::Input,
+        timeout: Option<Duration>,
+    ) -> Result<T::Output, RunError> {
+        let permit = match self.pool.as_ref() {
+            Some(pool) => {
+                pool.respawn_if(|m| m.worker.should_respawn());
+                Some(pool.try_acquire().map_err(|_| RunError::QueueFull)?.await)
+            }
+            None => None,
+        };
+
+        self.run_with(permit, input, timeout).await
+    }
+
+    async fn run_with(
+        &self,
+        permit: Option<PoolPermit<Supervised<OneshotBridge<T>>>>,
+        input: T::Input,
+        timeout: Option<Duration>,
+    ) -> Result<T::Output, RunError> {
+        if let Some(ref run_local) = self.run_local_fn {
+            let run = run_local(input);
+            return match timeout {
+                Some(m) => match select(run, Box::pin(sleep(m))).await {
+                    Either::Left((output, _)) => Ok(output),
+                    Either::Right(_) => Err(RunError::TimedOut),
+                },
+                None => Ok(run.await),
+            };
+        }
+
+        let Supervised { mut bridge, worker } = match permit.as_ref() {
+            Some(permit) => permit.with_worker(|m| m.map(|m| m.fork())),
+            None => self.create_bridge(),
+        };
+
+        let output = {
+            let run = Box::pin(bridge.run(input));
+            let crashed = Box::pin(worker.crashed());
+            let deadline = Box::pin(async move {
+                match timeout {
+                    Some(m) => sleep(m).await,
+                    None => futures::future::pending().await,
+                }
+            });
+
+            match select(run, select(crashed, deadline)).await {
+                Either::Left((output, _)) => Ok(output),
+                Either::Right((Either::Left((crash, _)), _)) => Err(RunError::Crashed(crash)),
+                Either::Right((Either::Right(_), _)) => Err(RunError::TimedOut),
+            }
+        };
+
+        if output.is_err() {
+            self.release_worker(permit.as_ref(), &bridge);
         }
+
+        output
+    }
+
+    /// Stops using the worker of a run that timed out or crashed, as it may never become idle.
+    ///
+    /// The worker closes when its last bridge is dropped.
+    fn release_worker(
+        &self,
+        permit: Option<&PoolPermit<Supervised<OneshotBridge<T>>>>,
+        bridge: &OneshotBridge<T>,
+    ) {
+        match self.reach {
+            // A shared agent keeps running, but the run is cancelled with its bridge.
+            Reach::Public | Reach::Shared | Reach::ServiceWorker => {
+                let mut held_bridge = self.held_bridge.borrow_mut();
+                if held_bridge
+                    .as_ref()
+                    .map_or(false, |m| m.bridge.same_worker(bridge))
+                {
+                    *held_bridge = None;
+                }
+            }
+            // Private workers are only connected to the bridge of the run.
+            Reach::Private => {}
+            Reach::Pool { .. } => {
+                if let Some(m) = permit {
+                    m.respawn();
+                }
+            }
+        }
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn pool_metrics(&self) -> Option<PoolMetrics> {
+        self.pool.as_ref().map(|m| m.metrics())
     }
 }
 
@@ -67,8 +290,12 @@ where
         Self {
             id: self.id,
             spawn_bridge_fn: self.spawn_bridge_fn.clone(),
+            run_local_fn: self.run_local_fn.clone(),
             reach: self.reach,
             held_bridge: self.held_bridge.clone(),
+            pool: self.pool.clone(),
+            timeout: self.timeout,
+            supervisor: self.supervisor.clone(),
         }
     }
 }
@@ -86,45 +313,108 @@ where
 ///
 /// This component provides its children access to an oneshot agent.
 #[function_component]
-pub fn OneshotProvider<T, C = Bincode>(props: &WorkerProviderProps) -> Html
+pub fn OneshotProvider<T, C = Bincode>(props: &OneshotProviderProps) -> Html
 where
     T: Oneshot + 'static,
     T::Input: Serialize + for<'de> Deserialize<'de> + 'static,
     T::Output: Serialize + for<'de> Deserialize<'de> + 'static,
     C: Codec + 'static,
 {
-    let WorkerProviderProps {
+    let OneshotProviderProps {
         children,
         path,
         lazy,
         reach,
+        queue_capacity,
+        timeout,
+        restart,
+        on_crash,
+        transfer,
+        local,
     } = props.clone();
 
-    // Creates a spawning function so Codec is can be erased from contexts.
-    let spawn_bridge_fn: Rc<dyn Fn() -> OneshotBridge<T>> = {
-        let path = path.clone();
-        Rc::new(move || OneshotSpawner::<T>::new().encoding::<C>().spawn(&path))
-    };
-
     let state = {
-        use_memo((path, lazy, reach), move |(_path, lazy, reach)| {
-            let state = OneshotProviderState::<T> {
-                id: get_next_id(),
-                spawn_bridge_fn,
-                reach: *reach,
-                held_bridge: Rc::default(),
-            };
+        use_memo(
+            (path, lazy, reach, queue_capacity, timeout, transfer, local),
+            move |(path, lazy, reach, queue_capacity, timeout, transfer, local)| {
+                let supervisor = Supervisor::new(*transfer);
 
-            if *reach == Reach::Public && !*lazy {
-                state.get_held_bridge();
-            }
-            state
-        })
+                // Creates a spawning function so Codec is can be erased from contexts.
+                let spawn_bridge_fn: SpawnBridgeFn<T> = {
+                    let path = path.clone();
+                    let supervisor = supervisor.clone();
+                    let reach = *reach;
+                    let transfer = *transfer;
+                    Rc::new(move || {
+                        if reach.is_shared() {
+                            let (bridge, target) =
+                                shared::connect::<C, _, _>(reach, &path, transfer);
+                            return supervisor.supervise(OneshotBridge::from_port(bridge), &target);
+                        }
+
+                        supervisor.spawn(&path, OneshotSpawner::<T, C>::connect)
+                    })
+                };
+
+                let run_local_fn: Option<RunLocalFn<T>> = local
+                    .then(|| Rc::new(|input| run_local::<T>(input).boxed_local()) as RunLocalFn<T>);
+                let reach = match local {
+                    // Local runs do not use workers.
+                    true => &Reach::Private,
+                    false => reach,
+                };
+
+                let state = OneshotProviderState::<T> {
+                    id: get_next_id(),
+                    spawn_bridge_fn: spawn_bridge_fn.clone(),
+                    run_local_fn,
+                    reach: *reach,
+                    held_bridge: Rc::default(),
+                    pool: match *reach {
+                        Reach::Pool { size } => {
+                            Some(WorkerPool::new(size, *queue_capacity, spawn_bridge_fn))
+                        }
+                        _ => None,
+                    },
+                    timeout: *timeout,
+                    supervisor,
+                };
+
+                match (reach, lazy) {
+                    (reach, false) if reach.is_single() => {
+                        state.get_held_bridge();
+                    }
+                    (Reach::Pool { .. }, false) => {
+                        if let Some(pool) = state.pool.as_ref() {
+                            pool.spawn_all();
+                        }
+                    }
+                    _ => {}
+                }
+                state
+            },
+        )
     };
 
+    state.supervisor.configure(restart, on_crash);
+
     html! {
         <ContextProvider<OneshotProviderState<T>> context={(*state).clone()}>
             {children}
         </ContextProvider<OneshotProviderState<T>>>
     }
 }
+
+/// Runs the agent on the local runtime, serializing and deserializing its input and output.
+async fn run_local<T>(input: T::Input) -> T::Output
+where
+    T: Oneshot + 'static,
+    T::Input: Serialize + for<'de> Deserialize<'de>,
+    T::Output: Serialize + for<'de> Deserialize<'de>,
+{
+    let input = roundtrip(input);
+    // The run is dropped when cancelled, so its token is never cancelled.
+    let (run, _) = CancellationToken::default().scope(|| T::create(input));
+
+    roundtrip(run.await)
+}
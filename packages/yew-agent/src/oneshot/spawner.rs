@@ -0,0 +1,76 @@
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use futures::channel::mpsc;
+use serde::de::Deserialize;
+use serde::ser::Serialize;
+
+use super::bridge::OneshotBridge;
+use super::Oneshot;
+use crate::dedicated::DedicatedWorker;
+use crate::{Bincode, Codec};
+
+/// A spawner to create oneshot workers.
+#[derive(Debug, Default)]
+pub struct OneshotSpawner<N, CODEC = Bincode>
+where
+    N: Oneshot + 'static,
+    CODEC: Codec,
+{
+    _marker: PhantomData<(N, CODEC)>,
+}
+
+impl<N, CODEC> OneshotSpawner<N, CODEC>
+where
+    N: Oneshot + 'static,
+    CODEC: Codec,
+{
+    /// Creates a [OneshotSpawner].
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets a new message encoding.
+    pub const fn encoding<C>(&self) -> OneshotSpawner<N, C>
+    where
+        C: Codec,
+    {
+        OneshotSpawner {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Spawns an Oneshot Worker.
+    pub fn spawn(self, path: &str) -> OneshotBridge<N>
+    where
+        N::Input: Serialize + for<'de> Deserialize<'de>,
+        N::Output: Serialize + for<'de> Deserialize<'de>,
+    {
+        Self::connect(&DedicatedWorker::spawn(path, false))
+    }
+
+    /// Spawns an Oneshot Worker with a loader shim script.
+    pub fn spawn_with_loader(self, loader_path: &str) -> OneshotBridge<N>
+    where
+        N::Input: Serialize + for<'de> Deserialize<'de>,
+        N::Output: Serialize + for<'de> Deserialize<'de>,
+    {
+        Self::connect(&DedicatedWorker::spawn_with_loader(loader_path, false))
+    }
+
+    /// Connects a bridge to an oneshot agent running in `worker`.
+    pub(crate) fn connect(worker: &DedicatedWorker) -> OneshotBridge<N>
+    where
+        N::Input: Serialize + for<'de> Deserialize<'de>,
+        N::Output: Serialize + for<'de> Deserialize<'de>,
+    {
+        let (tx, rx) = mpsc::unbounded();
+        let callback = Rc::new(move |output| {
+            let _ = tx.unbounded_send(output);
+        });
+
+        OneshotBridge::new(worker.connect::<_, _, CODEC>(Some(callback)), rx)
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::future::Future;
+
+use futures::future::{AbortHandle, Abortable, Either};
+
+use super::{CancellationToken, Oneshot};
+use crate::worker::{HandlerId, Worker, WorkerDestroyHandle, WorkerScope};
+
+pub(crate) enum Message<T>
+where
+    T: Oneshot,
+{
+    Finished {
+        task_id: usize,
+        // `None` if the task was dropped after being cancelled.
+        output: Option<T::Output>,
+    },
+}
+
+/// A run of an oneshot agent, which can be cancelled.
+pub(crate) struct Run {
+    token: CancellationToken,
+    // Runs observing their token are not aborted.
+    abort_handle: Option<AbortHandle>,
+}
+
+impl Run {
+    /// Starts a run of `T` with `input`, returning the future of its output.
+    ///
+    /// The output is `None` if the run was dropped after being cancelled.
+    pub fn start<T>(input: T::Input) -> (Self, impl Future<Output = Option<T::Output>>)
+    where
+        T: Oneshot,
+    {
+        let token = CancellationToken::default();
+        let (task, observed) = token.scope(|| T::create(input));
+
+        let (task, abort_handle) = if observed {
+            (Either::Left(task), None)
+        } else {
+            let (abort_handle, registration) = AbortHandle::new_pair();
+            (
+                Either::Right(Abortable::new(task, registration)),
+                Some(abort_handle),
+            )
+        };
+
+        let output = async move {
+            match task {
+                Either::Left(task) => Some(task.await),
+                Either::Right(task) => task.await.ok(),
+            }
+        };
+
+        (
+            Self {
+                token,
+                abort_handle,
+            },
+            output,
+        )
+    }
+
+    pub fn cancel(&self) {
+        self.token.cancel();
+
+        if let Some(ref m) = self.abort_handle {
+            m.abort();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+struct RunningTask {
+    handler_id: HandlerId,
+    run: Run,
+}
+
+pub(crate) struct OneshotWorker<T>
+where
+    T: 'static + Oneshot,
+{
+    next_task_id: usize,
+    running_tasks: HashMap<usize, RunningTask>,
+    destruct_handle: Option<WorkerDestroyHandle<Self>>,
+}
+
+impl<T> Worker for OneshotWorker<T>
+where
+    T: 'static + Oneshot,
+{
+    type Input = T::Input;
+    type Message = Message<T>;
+    type Output = T::Output;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        Self {
+            next_task_id: 0,
+            running_tasks: HashMap::new(),
+            destruct_handle: None,
+        }
+    }
+
+    fn update(&mut self, scope: &WorkerScope<Self>, msg: Self::Message) {
+        let Message::Finished { task_id, output } = msg;
+
+        if let Some(task) = self.running_tasks.remove(&task_id) {
+            // Outputs of cancelled tasks have no bridge to go to.
+            if let (Some(output), false) = (output, task.run.is_cancelled()) {
+                scope.respond(task.handler_id, output);
+            }
+        }
+
+        if self.running_tasks.is_empty() {
+            self.destruct_handle = None;
+        }
+    }
+
+    fn received(&mut self, scope: &WorkerScope<Self>, input: Self::Input, handler_id: HandlerId) {
+        let task_id = self.next_task_id;
+        self.next_task_id += 1;
+
+        let (run, output) = Run::start::<T>(input);
+        self.running_tasks
+            .insert(task_id, RunningTask { handler_id, run });
+
+        scope.send_future(async move {
+            let output = output.await;
+
+            Message::Finished { task_id, output }
+        });
+    }
+
+    fn disconnected(&mut self, _scope: &WorkerScope<Self>, handler_id: HandlerId) {
+        for task in self
+            .running_tasks
+            .values()
+            .filter(|m| m.handler_id == handler_id)
+        {
+            task.run.cancel();
+        }
+    }
+
+    fn destroy(&mut self, _scope: &WorkerScope<Self>, destruct: WorkerDestroyHandle<Self>) {
+        if !self.running_tasks.is_empty() {
+            self.destruct_handle = Some(destruct);
+        }
+    }
+}
@@ -0,0 +1,385 @@
+//! This module contains the worker pool used by agents with [`Reach::Pool`](crate::Reach::Pool).
+//!
+//! A pool spawns up to a fixed number of workers, when they are first needed.
+//!
+//! Oneshot agents run one task at a time on each worker of the pool. When all workers are busy,
+//! tasks wait in a queue and run on the first worker to become idle. Reactor and worker agents
+//! connect each bridge to the next worker of the pool in turn.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::rc::Rc;
+
+use futures::channel::oneshot;
+
+/// A snapshot of the state of a worker pool.
+///
+/// See [`UseOneshotRunnerHandle::pool_metrics`](crate::oneshot::UseOneshotRunnerHandle::pool_metrics).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoolMetrics {
+    /// The number of workers the pool spawns at most.
+    pub size: usize,
+    /// The number of workers that have been spawned.
+    pub spawned: usize,
+    /// The number of workers running a task.
+    pub busy: usize,
+    /// The number of tasks waiting for a worker.
+    pub queued: usize,
+    /// The highest number of tasks that have waited for a worker at the same time.
+    pub peak_queued: usize,
+}
+
+struct PoolWorker<B> {
+    bridge: B,
+    busy: bool,
+}
+
+struct PoolInner<B> {
+    size: usize,
+    capacity: Option<usize>,
+    spawn_bridge_fn: Rc<dyn Fn() -> B>,
+    workers: Vec<PoolWorker<B>>,
+    queue: VecDeque<oneshot::Sender<PoolPermit<B>>>,
+    next: usize,
+    peak_queued: usize,
+}
+
+impl<B> PoolInner<B> {
+    fn spawn(&mut self) -> usize {
+        self.workers.push(PoolWorker {
+            bridge: (self.spawn_bridge_fn)(),
+            busy: false,
+        });
+
+        self.workers.len() - 1
+    }
+
+    fn idle_worker(&mut self) -> Option<usize> {
+        match self.workers.iter().position(|m| !m.busy) {
+            Some(index) => Some(index),
+            None if self.workers.len() < self.size => Some(self.spawn()),
+            None => None,
+        }
+    }
+
+    fn queued(&self) -> usize {
+        self.queue.iter().filter(|m| !m.is_canceled()).count()
+    }
+}
+
+/// A pool of workers.
+pub(crate) struct WorkerPool<B> {
+    inner: Rc<RefCell<PoolInner<B>>>,
+}
+
+impl<B> Clone for WorkerPool<B> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<B> fmt::Debug for WorkerPool<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerPool")
+            .field("metrics", &self.metrics())
+            .finish()
+    }
+}
+
+/// The error returned when a task is rejected because the queue of a pool is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct QueueFull;
+
+impl<B> WorkerPool<B> {
+    /// Creates a pool of `size` workers, spawned with `spawn_bridge_fn`.
+    ///
+    /// `capacity` limits the number of tasks queued by [`try_acquire`](Self::try_acquire).
+    pub fn new(size: usize, capacity: Option<usize>, spawn_bridge_fn: Rc<dyn Fn() -> B>) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(PoolInner {
+                size: size.max(1),
+                capacity,
+                spawn_bridge_fn,
+                workers: Vec::new(),
+                queue: VecDeque::new(),
+                next: 0,
+                peak_queued: 0,
+            })),
+        }
+    }
+
+    /// Spawns all workers of the pool.
+    pub fn spawn_all(&self) {
+        let mut inner = self.inner.borrow_mut();
+
+        while inner.workers.len() < inner.size {
+            inner.spawn();
+        }
+    }
+
+    /// Calls `f` with the next worker in turn.
+    pub fn round_robin<O>(&self, f: impl FnOnce(&B) -> O) -> O {
+        let mut inner = self.inner.borrow_mut();
+
+        let index = inner.next % inner.size;
+        inner.next = index + 1;
+        if index == inner.workers.len() {
+            inner.spawn();
+        }
+
+        f(&inner.workers[index].bridge)
+    }
+
+    /// Replaces the spawned workers for which `f` returns `true` with new workers.
+    pub fn respawn_if(&self, f: impl Fn(&B) -> bool) {
+        let mut inner = self.inner.borrow_mut();
+
+        for index in 0..inner.workers.len() {
+            if f(&inner.workers[index].bridge) {
+                let bridge = (inner.spawn_bridge_fn)();
+                inner.workers[index].bridge = bridge;
+            }
+        }
+    }
+
+    /// Waits for an idle worker and reserves it until the permit is dropped.
+    ///
+    /// The task is queued when this method is called, not when the future is first polled.
+    pub fn acquire(&self) -> impl Future<Output = PoolPermit<B>> {
+        match self.request(false) {
+            Ok(m) => Self::wait(m),
+            Err(QueueFull) => unreachable!("unbounded requests are always queued"),
+        }
+    }
+
+    /// Like [`acquire`](Self::acquire), but fails immediately if the queue is full.
+    pub fn try_acquire(&self) -> Result<impl Future<Output = PoolPermit<B>>, QueueFull> {
+        self.request(true).map(Self::wait)
+    }
+
+    async fn wait(
+        request: Result<PoolPermit<B>, oneshot::Receiver<PoolPermit<B>>>,
+    ) -> PoolPermit<B> {
+        match request {
+            Ok(permit) => permit,
+            Err(rx) => rx.await.expect("worker pool dropped while queued"),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn request(
+        &self,
+        bounded: bool,
+    ) -> Result<Result<PoolPermit<B>, oneshot::Receiver<PoolPermit<B>>>, QueueFull> {
+        let mut inner = self.inner.borrow_mut();
+
+        if let Some(index) = inner.idle_worker() {
+            inner.workers[index].busy = true;
+            return Ok(Ok(PoolPermit {
+                inner: self.inner.clone(),
+                index: Some(index),
+            }));
+        }
+
+        let queued = inner.queued();
+        if bounded && inner.capacity.map_or(false, |m| queued >= m) {
+            return Err(QueueFull);
+        }
+
+        // Waiters that gave up are removed from the queue.
+        inner.queue.retain(|m| !m.is_canceled());
+        let (tx, rx) = oneshot::channel();
+        inner.queue.push_back(tx);
+        inner.peak_queued = inner.peak_queued.max(queued + 1);
+
+        Ok(Err(rx))
+    }
+
+    /// Returns a snapshot of the state of the pool.
+    pub fn metrics(&self) -> PoolMetrics {
+        let inner = self.inner.borrow();
+
+        PoolMetrics {
+            size: inner.size,
+            spawned: inner.workers.len(),
+            busy: inner.workers.iter().filter(|m| m.busy).count(),
+            queued: inner.queued(),
+            peak_queued: inner.peak_queued,
+        }
+    }
+}
+
+/// A worker of a pool reserved for a task.
+///
+/// When dropped, the worker is handed to the next queued task or becomes idle.
+pub(crate) struct PoolPermit<B> {
+    inner: Rc<RefCell<PoolInner<B>>>,
+    index: Option<usize>,
+}
+
+impl<B> PoolPermit<B> {
+    /// Calls `f` with the reserved worker.
+    pub fn with_worker<O>(&self, f: impl FnOnce(&B) -> O) -> O {
+        let index = self.index.expect("permit released");
+        f(&self.inner.borrow().workers[index].bridge)
+    }
+
+    /// Replaces the reserved worker with a newly spawned one.
+    pub fn respawn(&self) {
+        let index = self.index.expect("permit released");
+        let mut inner = self.inner.borrow_mut();
+
+        let bridge = (inner.spawn_bridge_fn)();
+        inner.workers[index].bridge = bridge;
+    }
+}
+
+impl<B> Drop for PoolPermit<B> {
+    fn drop(&mut self) {
+        let index = match self.index.take() {
+            Some(m) => m,
+            None => return,
+        };
+
+        let mut permit = PoolPermit {
+            inner: self.inner.clone(),
+            index: Some(index),
+        };
+
+        loop {
+            let tx = match self.inner.borrow_mut().queue.pop_front() {
+                Some(m) => m,
+                None => break,
+            };
+
+            // A waiter that gave up returns the permit.
+            match tx.send(permit) {
+                Ok(()) => return,
+                Err(m) => permit = m,
+            }
+        }
+
+        permit.index = None;
+        self.inner.borrow_mut().workers[index].busy = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use futures::FutureExt;
+
+    use super::*;
+
+    fn pool(size: usize, capacity: Option<usize>) -> (WorkerPool<usize>, Rc<Cell<usize>>) {
+        let spawned = Rc::new(Cell::new(0));
+        let pool = WorkerPool::new(size, capacity, {
+            let spawned = spawned.clone();
+            Rc::new(move || {
+                spawned.set(spawned.get() + 1);
+                spawned.get() - 1
+            })
+        });
+
+        (pool, spawned)
+    }
+
+    #[test]
+    fn spawns_workers_on_demand() {
+        let (pool, spawned) = pool(2, None);
+        assert_eq!(spawned.get(), 0);
+
+        let first = pool.acquire().now_or_never().unwrap();
+        let second = pool.acquire().now_or_never().unwrap();
+        assert_eq!(first.with_worker(|m| *m), 0);
+        assert_eq!(second.with_worker(|m| *m), 1);
+        assert_eq!(spawned.get(), 2);
+
+        drop(first);
+        let third = pool.acquire().now_or_never().unwrap();
+        assert_eq!(third.with_worker(|m| *m), 0);
+        assert_eq!(spawned.get(), 2);
+    }
+
+    #[test]
+    fn queues_tasks_when_busy() {
+        let (pool, _) = pool(1, Some(1));
+
+        let first = pool.acquire().now_or_never().unwrap();
+        let mut queued = pool.try_acquire().unwrap().boxed_local();
+        assert!((&mut queued).now_or_never().is_none());
+        assert!(pool.try_acquire().is_err());
+        assert_eq!(
+            pool.metrics(),
+            PoolMetrics {
+                size: 1,
+                spawned: 1,
+                busy: 1,
+                queued: 1,
+                peak_queued: 1,
+            }
+        );
+
+        drop(first);
+        let second = queued.now_or_never().unwrap();
+        assert_eq!(second.with_worker(|m| *m), 0);
+        assert_eq!(pool.metrics().queued, 0);
+
+        drop(second);
+        assert_eq!(pool.metrics().busy, 0);
+    }
+
+    #[test]
+    fn abandoned_waiters_are_skipped() {
+        let (pool, _) = pool(1, None);
+
+        let first = pool.acquire().now_or_never().unwrap();
+        let abandoned = pool.acquire();
+        let mut waiting = pool.acquire().boxed_local();
+        assert!((&mut waiting).now_or_never().is_none());
+        drop(abandoned);
+        assert_eq!(pool.metrics().queued, 1);
+
+        drop(first);
+        assert!(waiting.now_or_never().is_some());
+    }
+
+    #[test]
+    fn respawn_replaces_reserved_worker() {
+        let (pool, spawned) = pool(2, None);
+
+        let first = pool.acquire().now_or_never().unwrap();
+        first.respawn();
+        assert_eq!(first.with_worker(|m| *m), 1);
+        assert_eq!(spawned.get(), 2);
+
+        drop(first);
+        let metrics = pool.metrics();
+        assert_eq!((metrics.spawned, metrics.busy), (1, 0));
+    }
+
+    #[test]
+    fn round_robin_cycles_through_workers() {
+        let (pool, spawned) = pool(3, None);
+
+        let picked = (0..4).map(|_| pool.round_robin(|m| *m)).collect::<Vec<_>>();
+        assert_eq!(picked, vec![0, 1, 2, 0]);
+        assert_eq!(spawned.get(), 3);
+    }
+
+    #[test]
+    fn respawn_if_replaces_matching_workers() {
+        let (pool, spawned) = pool(3, None);
+        pool.spawn_all();
+
+        pool.respawn_if(|m| *m == 1);
+        let picked = (0..3).map(|_| pool.round_robin(|m| *m)).collect::<Vec<_>>();
+        assert_eq!(picked, vec![0, 3, 2]);
+        assert_eq!(spawned.get(), 4);
+    }
+}
@@ -5,4 +5,64 @@ pub enum Reach {
     Public,
     /// Private Reachability.
     Private,
+    /// Pooled Reachability.
+    ///
+    /// Up to `size` instances are spawned and shared among all children of a provider.
+    Pool {
+        /// The maximum number of instances.
+        size: usize,
+    },
+    /// Shared Reachability.
+    ///
+    /// A single instance runs in a `SharedWorker` and is shared among all tabs of the origin. The
+    /// path of the provider is a loader script, see the [`shared`](crate::shared) module. Only
+    /// reactor and oneshot agents can be shared.
+    Shared,
+    /// Service Worker Reachability.
+    ///
+    /// A single instance runs in the service worker of the application and is shared among all
+    /// tabs it controls. The path of the provider is a loader script registered as the service
+    /// worker, see the [`shared`](crate::shared) module. Only reactor and oneshot agents can be
+    /// shared.
+    ServiceWorker,
+}
+
+/// The reachability of a worker agent.
+///
+/// Worker agents can not be shared by tabs, so unlike [`Reach`], it has no shared variants.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+pub enum WorkerReach {
+    /// Public Reachability.
+    Public,
+    /// Private Reachability.
+    Private,
+    /// Pooled Reachability.
+    ///
+    /// Up to `size` instances are spawned and shared among all children of a provider.
+    Pool {
+        /// The maximum number of instances.
+        size: usize,
+    },
+}
+
+impl From<WorkerReach> for Reach {
+    fn from(reach: WorkerReach) -> Self {
+        match reach {
+            WorkerReach::Public => Self::Public,
+            WorkerReach::Private => Self::Private,
+            WorkerReach::Pool { size } => Self::Pool { size },
+        }
+    }
+}
+
+impl Reach {
+    /// Returns `true` if a single instance is shared among all bridges of a provider.
+    pub(crate) fn is_single(&self) -> bool {
+        matches!(self, Self::Public | Self::Shared | Self::ServiceWorker)
+    }
+
+    /// Returns `true` if the agent is shared among all tabs.
+    pub(crate) fn is_shared(&self) -> bool {
+        matches!(self, Self::Shared | Self::ServiceWorker)
+    }
 }
@@ -10,14 +10,18 @@ use yew::platform::pinned::RwLock;
 use yew::platform::spawn_local;
 use yew::prelude::*;
 
+use super::local::ReactorConnection;
 use super::provider::ReactorProviderState;
-use super::{Reactor, ReactorBridge, ReactorScoped};
+use super::{Reactor, ReactorScoped};
+use crate::supervisor::{CrashWatcher, Supervised};
 use crate::utils::{BridgeIdState, OutputsAction, OutputsState};
+use crate::AgentCrash;
 
 type ReactorTx<R> =
-    Rc<RwLock<SplitSink<ReactorBridge<R>, <<R as Reactor>::Scope as ReactorScoped>::Input>>>;
+    Rc<RwLock<SplitSink<ReactorConnection<R>, <<R as Reactor>::Scope as ReactorScoped>::Input>>>;
 
 /// A type that represents events from a reactor.
+#[non_exhaustive]
 pub enum ReactorEvent<R>
 where
     R: Reactor,
@@ -26,6 +30,10 @@ where
     Output(<R::Scope as ReactorScoped>::Output),
     /// The reactor agent has exited.
     Finished,
+    /// The worker running the reactor agent has crashed.
+    ///
+    /// If the worker is respawned, bridges created with hooks reconnect to the new worker.
+    Crashed(AgentCrash),
 }
 
 impl<R> fmt::Debug for ReactorEvent<R>
@@ -37,6 +45,7 @@ where
         match self {
             Self::Output(m) => f.debug_tuple("ReactorEvent::Output").field(&m).finish(),
             Self::Finished => f.debug_tuple("ReactorEvent::Finished").finish(),
+            Self::Crashed(m) => f.debug_tuple("ReactorEvent::Crashed").field(&m).finish(),
         }
     }
 }
@@ -136,26 +145,48 @@ where
         *on_output_ref = on_output;
     }
 
-    let tx = use_memo((worker_state, ctr.inner), |(state, _ctr)| {
-        let bridge = state.create_bridge();
+    let reset = ctr.dispatcher();
+    let bridge = use_memo((worker_state, ctr.inner), |(state, _ctr)| {
+        let Supervised { bridge, worker } = state.create_bridge();
 
         let (tx, mut rx) = bridge.split();
 
-        spawn_local(async move {
-            while let Some(m) = rx.next().await {
+        {
+            let on_output_ref = on_output_ref.clone();
+            spawn_local(async move {
+                while let Some(m) = rx.next().await {
+                    let on_output = on_output_ref.borrow().clone();
+                    on_output(ReactorEvent::<R>::Output(m));
+                }
+
                 let on_output = on_output_ref.borrow().clone();
-                on_output(ReactorEvent::<R>::Output(m));
-            }
+                on_output(ReactorEvent::<R>::Finished);
+            });
+        }
 
+        let on_crash: Rc<CrashWatcher> = Rc::new(move |crash: &AgentCrash| {
             let on_output = on_output_ref.borrow().clone();
-            on_output(ReactorEvent::<R>::Finished);
+            on_output(ReactorEvent::<R>::Crashed(crash.clone()));
+
+            if crash.restarted {
+                reset.dispatch(());
+            }
         });
 
-        RwLock::new(tx)
+        match worker.crash() {
+            // The worker crashed before the bridge was created and is not respawned.
+            Some(crash) => {
+                let on_crash = on_crash.clone();
+                spawn_local(async move { on_crash(&crash) });
+            }
+            None => worker.watch(&on_crash),
+        }
+
+        (Rc::new(RwLock::new(tx)), on_crash)
     });
 
     UseReactorBridgeHandle {
-        tx: tx.clone(),
+        tx: bridge.0.clone(),
         ctr: ctr.dispatcher(),
     }
 }
@@ -257,7 +288,7 @@ where
         use_reactor_bridge::<R, _>(move |output| {
             outputs.dispatch(match output {
                 ReactorEvent::Output(m) => OutputsAction::Push(m.into()),
-                ReactorEvent::Finished => OutputsAction::Close,
+                ReactorEvent::Finished | ReactorEvent::Crashed(_) => OutputsAction::Close,
             })
         })
     };
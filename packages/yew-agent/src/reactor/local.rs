@@ -0,0 +1,282 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use futures::channel::mpsc;
+use futures::sink::{self, Sink};
+use futures::stream::{FusedStream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use yew::platform::spawn_local;
+
+use super::{Reactor, ReactorBridge, ReactorBridgeSinkError, ReactorScoped};
+use crate::dedicated::{DedicatedBridge, DedicatedWorker, ReactorInput, ReactorOutput};
+use crate::shared::PortBridge;
+use crate::utils::roundtrip;
+use crate::Codec;
+
+type Input<R> = <<R as Reactor>::Scope as ReactorScoped>::Input;
+type Output<R> = <<R as Reactor>::Scope as ReactorScoped>::Output;
+
+/// A bridge to a reactor running on the local runtime.
+pub(crate) struct LocalReactorBridge<R>
+where
+    R: Reactor + 'static,
+{
+    spawn: fn() -> Self,
+    tx: mpsc::UnboundedSender<Input<R>>,
+    rx: mpsc::UnboundedReceiver<Output<R>>,
+}
+
+impl<R> LocalReactorBridge<R>
+where
+    R: Reactor + 'static,
+{
+    /// Spawns a reactor on the local runtime, serializing and deserializing its inputs and
+    /// outputs.
+    pub fn spawn() -> Self
+    where
+        Input<R>: Serialize + for<'de> Deserialize<'de> + 'static,
+        Output<R>: Serialize + for<'de> Deserialize<'de> + 'static,
+    {
+        let (tx, inputs) = mpsc::unbounded();
+        let (outputs, rx) = mpsc::unbounded();
+
+        let inputs = inputs.map(roundtrip);
+        let outputs = sink::unfold((), move |_, output: Output<R>| {
+            // The bridge may have been dropped.
+            let _ = outputs.unbounded_send(roundtrip(output));
+            async { Ok::<_, Infallible>(()) }
+        });
+
+        // The outputs close when the reactor exits and drops its scope.
+        spawn_local(R::create(R::Scope::new(inputs, outputs)));
+
+        Self {
+            spawn: Self::spawn,
+            tx,
+            rx,
+        }
+    }
+}
+
+/// A bridge to a reactor running in a worker spawned by a provider.
+pub(crate) struct DedicatedReactorBridge<R>
+where
+    R: Reactor + 'static,
+{
+    bridge: DedicatedBridge<ReactorInput<Input<R>>, ReactorOutput<Output<R>>>,
+    rx: mpsc::UnboundedReceiver<Output<R>>,
+}
+
+impl<R> DedicatedReactorBridge<R>
+where
+    R: Reactor + 'static,
+{
+    fn output_callback(
+        tx: mpsc::UnboundedSender<Output<R>>,
+    ) -> impl Fn(ReactorOutput<Output<R>>) + 'static {
+        move |output| match output {
+            ReactorOutput::Output(m) => {
+                let _ = tx.unbounded_send(m);
+            }
+            ReactorOutput::Finish => tx.close_channel(),
+        }
+    }
+
+    /// Connects a bridge to a new reactor of `worker`.
+    pub fn connect<C>(worker: &DedicatedWorker) -> Self
+    where
+        C: Codec,
+        Input<R>: Serialize + for<'de> Deserialize<'de> + 'static,
+        Output<R>: Serialize + for<'de> Deserialize<'de> + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded();
+        let bridge = worker.connect::<_, _, C>(Some(Rc::new(Self::output_callback(tx))));
+
+        Self { bridge, rx }
+    }
+
+    fn fork(&self) -> Self {
+        let (tx, rx) = mpsc::unbounded();
+        let bridge = self.bridge.fork(Some(Self::output_callback(tx)));
+
+        Self { bridge, rx }
+    }
+}
+
+/// A bridge to a reactor, running in a worker, in a worker shared by tabs or on the local runtime.
+pub(crate) enum ReactorConnection<R>
+where
+    R: Reactor + 'static,
+{
+    Worker(ReactorBridge<R>),
+    Dedicated(DedicatedReactorBridge<R>),
+    Port(PortBridge<Input<R>, Output<R>>),
+    Local(LocalReactorBridge<R>),
+}
+
+impl<R> fmt::Debug for ReactorConnection<R>
+where
+    R: Reactor + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Worker(m) => m.fmt(f),
+            Self::Dedicated(m) => m.bridge.fmt(f),
+            Self::Port(m) => m.fmt(f),
+            Self::Local(_) => f.write_str("LocalReactorBridge<_>"),
+        }
+    }
+}
+
+impl<R> ReactorConnection<R>
+where
+    R: Reactor + 'static,
+{
+    /// Creates a bridge to a new reactor of the same worker, or a new local reactor.
+    pub fn fork(&self) -> Self {
+        match self {
+            Self::Worker(m) => Self::Worker(m.fork()),
+            Self::Dedicated(m) => Self::Dedicated(m.fork()),
+            Self::Port(m) => Self::Port(m.fork()),
+            Self::Local(m) => Self::Local((m.spawn)()),
+        }
+    }
+
+    pub fn send_input(&self, input: Input<R>) {
+        match self {
+            Self::Worker(m) => m.send_input(input),
+            Self::Dedicated(m) => m.bridge.send(ReactorInput::Input(input)),
+            Self::Port(m) => m.send_input(input),
+            Self::Local(m) => {
+                let _ = m.tx.unbounded_send(input);
+            }
+        }
+    }
+}
+
+impl<R> Stream for ReactorConnection<R>
+where
+    R: Reactor + 'static,
+{
+    type Item = Output<R>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.get_mut() {
+            Self::Worker(m) => m.poll_next_unpin(cx),
+            Self::Dedicated(m) => m.rx.poll_next_unpin(cx),
+            Self::Port(m) => m.poll_next_unpin(cx),
+            Self::Local(m) => m.rx.poll_next_unpin(cx),
+        }
+    }
+}
+
+impl<R> FusedStream for ReactorConnection<R>
+where
+    R: Reactor + 'static,
+{
+    fn is_terminated(&self) -> bool {
+        match self {
+            Self::Worker(m) => m.is_terminated(),
+            Self::Dedicated(m) => m.rx.is_terminated(),
+            Self::Port(m) => m.is_terminated(),
+            Self::Local(m) => m.rx.is_terminated(),
+        }
+    }
+}
+
+impl<R> Sink<Input<R>> for ReactorConnection<R>
+where
+    R: Reactor + 'static,
+{
+    type Error = ReactorBridgeSinkError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.get_mut() {
+            Self::Worker(m) => Pin::new(m).poll_ready(cx),
+            Self::Dedicated(_) | Self::Port(_) | Self::Local(_) => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Input<R>) -> Result<(), Self::Error> {
+        self.send_input(item);
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.get_mut() {
+            Self::Worker(m) => Pin::new(m).poll_flush(cx),
+            Self::Dedicated(_) | Self::Port(_) | Self::Local(_) => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Like bridges to workers, other bridges are closed by dropping them.
+        Poll::Ready(Err(ReactorBridgeSinkError::AttemptClosure))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+
+    use futures::future::LocalBoxFuture;
+    use futures::{FutureExt, SinkExt};
+    use yew::platform::Runtime;
+
+    use super::*;
+    use crate::reactor::ReactorScope;
+
+    struct Double(LocalBoxFuture<'static, ()>);
+
+    impl Future for Double {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            self.0.poll_unpin(cx)
+        }
+    }
+
+    impl Reactor for Double {
+        type Scope = ReactorScope<u32, u32>;
+
+        fn create(mut scope: Self::Scope) -> Self {
+            Self(
+                async move {
+                    while let Some(m) = scope.next().await {
+                        if scope.send(m * 2).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                .boxed_local(),
+            )
+        }
+    }
+
+    #[test]
+    fn local_reactors_run_on_the_runtime() {
+        let (tx, rx) = futures::channel::oneshot::channel();
+
+        Runtime::default().spawn_pinned(move || async move {
+            let bridge = ReactorConnection::Local(LocalReactorBridge::<Double>::spawn());
+            let fork = bridge.fork();
+
+            bridge.send_input(1);
+            fork.send_input(2);
+            let outputs = futures::future::join(
+                bridge.take(1).collect::<Vec<_>>(),
+                fork.take(1).collect::<Vec<_>>(),
+            )
+            .await;
+
+            let _ = tx.send(outputs);
+        });
+
+        let outputs = futures::executor::block_on(rx).unwrap();
+        assert_eq!(outputs, (vec![2], vec![4]));
+    }
+}
@@ -37,17 +37,22 @@
 //! ```
 
 mod hooks;
+mod local;
 mod provider;
+mod stream;
 
 #[doc(inline)]
 pub use gloo_worker::reactor::{
-    Reactor, ReactorBridge, ReactorRegistrar, ReactorScope, ReactorScoped, ReactorSpawner,
+    Reactor, ReactorBridge, ReactorBridgeSinkError, ReactorRegistrar, ReactorScope, ReactorScoped,
+    ReactorSpawner,
 };
 pub use hooks::{
     use_reactor_bridge, use_reactor_subscription, ReactorEvent, UseReactorBridgeHandle,
     UseReactorSubscriptionHandle,
 };
-pub use provider::ReactorProvider;
+pub(crate) use local::ReactorConnection;
 pub(crate) use provider::ReactorProviderState;
+pub use provider::{ReactorProvider, ReactorProviderProps};
+pub use stream::ReactorStream;
 /// A procedural macro to create reactor agents.
 pub use yew_agent_macro::reactor;
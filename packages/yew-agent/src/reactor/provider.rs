@@ -7,19 +7,84 @@ use gloo_worker::reactor::ReactorScoped;
 use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
-use super::{Reactor, ReactorBridge, ReactorSpawner};
+use super::local::{DedicatedReactorBridge, LocalReactorBridge, ReactorConnection};
+use super::Reactor;
+use crate::pool::WorkerPool;
+use crate::shared;
+use crate::supervisor::{Supervised, Supervisor, WorkerHandle};
 use crate::utils::get_next_id;
-use crate::worker::WorkerProviderProps;
-use crate::{Bincode, Codec, Reach};
+use crate::{AgentCrash, Bincode, Codec, Reach, RestartPolicy};
+
+/// Properties for [ReactorProvider].
+#[derive(Debug, Properties, PartialEq, Clone)]
+pub struct ReactorProviderProps {
+    /// The path to an agent.
+    pub path: AttrValue,
+
+    /// The reachability of an agent.
+    ///
+    /// Default: [`Public`](Reach::Public).
+    #[prop_or(Reach::Public)]
+    pub reach: Reach,
+
+    /// Lazily spawn the agent.
+    ///
+    /// The agent will be spawned when the first time a hook requests a bridge.
+    ///
+    /// Does not affect private agents.
+    ///
+    /// Default: `true`
+    #[prop_or(true)]
+    pub lazy: bool,
+
+    /// Whether workers that crash are respawned.
+    ///
+    /// Bridges created with hooks reconnect when their worker is respawned.
+    ///
+    /// Default: [`Never`](RestartPolicy::Never).
+    #[prop_or_default]
+    pub restart: RestartPolicy,
+
+    /// Called when a worker crashes.
+    #[prop_or_default]
+    pub on_crash: Option<Callback<AgentCrash>>,
+
+    /// Transfers the [`Transfer`](crate::transfer::Transfer) fields of inputs to workers instead
+    /// of copying them.
+    ///
+    /// Only affects agents encoded with [`StructuredClone`](crate::StructuredClone). Workers
+    /// transfer their outputs once they call
+    /// [`transfer_outputs`](crate::transfer::transfer_outputs).
+    #[prop_or_default]
+    pub transfer: bool,
+
+    /// Runs the agent on the local runtime instead of a worker.
+    ///
+    /// Messages are still serialized and deserialized, with bincode whatever the codec of the
+    /// provider, so local agents behave like agents running in a worker. This allows components
+    /// using agents to be rendered on the server and tested outside of the browser. The
+    /// reachability of local agents is ignored. Applications rendered on the server can set it
+    /// with `local={cfg!(not(target_arch = "wasm32"))}`.
+    ///
+    /// Default: `false`
+    #[prop_or_default]
+    pub local: bool,
+
+    /// Children of the provider.
+    #[prop_or_default]
+    pub children: Html,
+}
 
 pub(crate) struct ReactorProviderState<T>
 where
     T: Reactor + 'static,
 {
     id: usize,
-    spawn_bridge_fn: Rc<dyn Fn() -> ReactorBridge<T>>,
+    spawn_bridge_fn: Rc<dyn Fn() -> Supervised<ReactorConnection<T>>>,
     reach: Reach,
-    held_bridge: Rc<RefCell<Option<ReactorBridge<T>>>>,
+    held_bridge: Rc<RefCell<Option<Supervised<ReactorConnection<T>>>>>,
+    pool: Option<WorkerPool<Supervised<ReactorConnection<T>>>>,
+    supervisor: Supervisor,
 }
 
 impl<T> fmt::Debug for ReactorProviderState<T>
@@ -35,27 +100,32 @@ impl<T> ReactorProviderState<T>
 where
     T: Reactor,
 {
-    fn get_held_bridge(&self) -> ReactorBridge<T> {
+    fn get_held_bridge(&self) -> Supervised<ReactorConnection<T>> {
         let mut held_bridge = self.held_bridge.borrow_mut();
 
         match held_bridge.as_mut() {
-            Some(m) => m.fork(),
-            None => {
+            Some(m) if !m.worker.should_respawn() => m.map(|m| m.fork()),
+            _ => {
                 let bridge = (self.spawn_bridge_fn)();
-                *held_bridge = Some(bridge.fork());
+                *held_bridge = Some(bridge.map(|m| m.fork()));
                 bridge
             }
         }
     }
 
     /// Creates a bridge, uses "fork" for public agents.
-    pub fn create_bridge(&self) -> ReactorBridge<T> {
+    pub fn create_bridge(&self) -> Supervised<ReactorConnection<T>> {
         match self.reach {
-            Reach::Public => {
+            Reach::Public | Reach::Shared | Reach::ServiceWorker => {
                 let held_bridge = self.get_held_bridge();
-                held_bridge.fork()
+                held_bridge.map(|m| m.fork())
             }
             Reach::Private => (self.spawn_bridge_fn)(),
+            Reach::Pool { .. } => {
+                let pool = self.pool.as_ref().expect("pooled agent without a pool");
+                pool.respawn_if(|m| m.worker.should_respawn());
+                pool.round_robin(|m| m.map(|m| m.fork()))
+            }
         }
     }
 }
@@ -70,6 +140,8 @@ where
             spawn_bridge_fn: self.spawn_bridge_fn.clone(),
             reach: self.reach,
             held_bridge: self.held_bridge.clone(),
+            pool: self.pool.clone(),
+            supervisor: self.supervisor.clone(),
         }
     }
 }
@@ -87,7 +159,7 @@ where
 ///
 /// This component provides its children access to a reactor agent.
 #[function_component]
-pub fn ReactorProvider<R, C = Bincode>(props: &WorkerProviderProps) -> Html
+pub fn ReactorProvider<R, C = Bincode>(props: &ReactorProviderProps) -> Html
 where
     R: 'static + Reactor,
     <<R as Reactor>::Scope as ReactorScoped>::Input:
@@ -96,35 +168,83 @@ where
         Serialize + for<'de> Deserialize<'de> + 'static,
     C: Codec + 'static,
 {
-    let WorkerProviderProps {
+    let ReactorProviderProps {
         children,
         path,
         lazy,
         reach,
+        restart,
+        on_crash,
+        transfer,
+        local,
     } = props.clone();
 
-    // Creates a spawning function so Codec is can be erased from contexts.
-    let spawn_bridge_fn: Rc<dyn Fn() -> ReactorBridge<R>> = {
-        let path = path.clone();
-        Rc::new(move || ReactorSpawner::<R>::new().encoding::<C>().spawn(&path))
-    };
-
     let state = {
-        use_memo((path, lazy, reach), move |(_path, lazy, reach)| {
-            let state = ReactorProviderState::<R> {
-                id: get_next_id(),
-                spawn_bridge_fn,
-                reach: *reach,
-                held_bridge: Rc::default(),
-            };
-
-            if *reach == Reach::Public && !*lazy {
-                state.get_held_bridge();
-            }
-            state
-        })
+        use_memo(
+            (path, lazy, reach, transfer, local),
+            move |(path, lazy, reach, transfer, local)| {
+                let supervisor = Supervisor::new(*transfer);
+
+                // Creates a spawning function so Codec is can be erased from contexts.
+                let spawn_bridge_fn: Rc<dyn Fn() -> Supervised<ReactorConnection<R>>> = {
+                    let path = path.clone();
+                    let supervisor = supervisor.clone();
+                    let reach = *reach;
+                    let transfer = *transfer;
+                    let local = *local;
+                    Rc::new(move || {
+                        if local {
+                            return Supervised {
+                                bridge: ReactorConnection::Local(LocalReactorBridge::spawn()),
+                                worker: WorkerHandle::default(),
+                            };
+                        }
+
+                        if reach.is_shared() {
+                            let (bridge, target) =
+                                shared::connect::<C, _, _>(reach, &path, transfer);
+                            return supervisor.supervise(ReactorConnection::Port(bridge), &target);
+                        }
+
+                        supervisor.spawn(&path, |worker| {
+                            ReactorConnection::Dedicated(DedicatedReactorBridge::connect::<C>(
+                                worker,
+                            ))
+                        })
+                    })
+                };
+
+                let state = ReactorProviderState::<R> {
+                    id: get_next_id(),
+                    spawn_bridge_fn: spawn_bridge_fn.clone(),
+                    reach: *reach,
+                    held_bridge: Rc::default(),
+                    pool: match *reach {
+                        Reach::Pool { size } => Some(WorkerPool::new(size, None, spawn_bridge_fn)),
+                        _ => None,
+                    },
+                    supervisor,
+                };
+
+                match (reach, lazy) {
+                    (reach, false) if reach.is_single() => {
+                        state.get_held_bridge();
+                    }
+                    (Reach::Pool { .. }, false) => {
+                        if let Some(pool) = state.pool.as_ref() {
+                            pool.spawn_all();
+                        }
+                    }
+                    _ => {}
+                }
+                state
+            },
+        )
     };
 
+    // The restart policy and crash callback apply to workers that have already been spawned.
+    state.supervisor.configure(restart, on_crash);
+
     html! {
         <ContextProvider<ReactorProviderState<R>> context={(*state).clone()}>
             {children}
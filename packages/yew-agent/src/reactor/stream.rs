@@ -0,0 +1,337 @@
+use std::any::type_name;
+use std::cell::RefCell;
+use std::fmt;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use futures::sink::Sink;
+use futures::stream::{FusedStream, Stream, StreamExt};
+
+use super::local::ReactorConnection;
+use super::{Reactor, ReactorBridge, ReactorBridgeSinkError, ReactorEvent, ReactorScoped};
+use crate::supervisor::{CrashWatcher, WorkerHandle};
+use crate::AgentCrash;
+
+#[derive(Default)]
+struct CrashState {
+    crash: RefCell<Option<AgentCrash>>,
+    waker: RefCell<Option<Waker>>,
+}
+
+/// A bridge to a reactor agent that is a [`Stream`] of [`ReactorEvent`]s and a [`Sink`] of inputs.
+///
+/// The stream yields [`ReactorEvent::Output`] for each output of the reactor, followed by a single
+/// [`ReactorEvent::Finished`] when the reactor exits, or [`ReactorEvent::Crashed`] if its worker
+/// crashes. The stream ends after either of them.
+///
+/// Bridges created with [`AgentScopeExt::bridge_reactor_stream`](crate::scope_ext::AgentScopeExt::bridge_reactor_stream)
+/// report crashes of the worker. A bridge created from a [`ReactorBridge`] does not.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut bridge = ctx.link().bridge_reactor_stream::<Counter>();
+///
+/// bridge.send(Step::Increment).await?;
+/// while let Some(event) = bridge.next().await {
+///     // handle each event
+/// }
+/// ```
+pub struct ReactorStream<R>
+where
+    R: Reactor + 'static,
+{
+    bridge: ReactorConnection<R>,
+    crash: Rc<CrashState>,
+    _on_crash: Option<Rc<CrashWatcher>>,
+    terminated: bool,
+}
+
+impl<R> fmt::Debug for ReactorStream<R>
+where
+    R: Reactor + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("terminated", &self.terminated)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R> ReactorStream<R>
+where
+    R: Reactor + 'static,
+{
+    pub(crate) fn new(bridge: ReactorConnection<R>, worker: Option<&WorkerHandle>) -> Self {
+        let crash = Rc::new(CrashState::default());
+
+        let on_crash = worker.map(|worker| {
+            let on_crash: Rc<CrashWatcher> = {
+                let crash = crash.clone();
+                Rc::new(move |m: &AgentCrash| {
+                    *crash.crash.borrow_mut() = Some(m.clone());
+
+                    if let Some(waker) = crash.waker.take() {
+                        waker.wake();
+                    }
+                })
+            };
+
+            match worker.crash() {
+                Some(m) => on_crash(&m),
+                None => worker.watch(&on_crash),
+            }
+
+            on_crash
+        });
+
+        Self {
+            bridge,
+            crash,
+            _on_crash: on_crash,
+            terminated: false,
+        }
+    }
+
+    /// Sends an input to the reactor.
+    ///
+    /// Unlike [`SinkExt::send`](futures::sink::SinkExt::send), this method does not wait.
+    pub fn send_input(&self, input: <R::Scope as ReactorScoped>::Input) {
+        self.bridge.send_input(input);
+    }
+
+    /// Sends an input to the reactor and collects its outputs until it finishes.
+    ///
+    /// This is meant for reactors that exit after handling an input. Returns an error if the
+    /// worker crashes before the reactor finishes.
+    pub async fn send_and_collect(
+        mut self,
+        input: <R::Scope as ReactorScoped>::Input,
+    ) -> Result<Vec<<R::Scope as ReactorScoped>::Output>, AgentCrash> {
+        self.send_input(input);
+
+        let mut outputs = Vec::new();
+        while let Some(m) = self.next().await {
+            match m {
+                ReactorEvent::Output(m) => outputs.push(m),
+                ReactorEvent::Finished => break,
+                ReactorEvent::Crashed(m) => return Err(m),
+            }
+        }
+
+        Ok(outputs)
+    }
+}
+
+impl<R> From<ReactorBridge<R>> for ReactorStream<R>
+where
+    R: Reactor + 'static,
+{
+    fn from(bridge: ReactorBridge<R>) -> Self {
+        Self::new(ReactorConnection::Worker(bridge), None)
+    }
+}
+
+impl<R> Stream for ReactorStream<R>
+where
+    R: Reactor + 'static,
+{
+    type Item = ReactorEvent<R>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+
+        // Outputs received before a crash are delivered first.
+        match self.bridge.poll_next_unpin(cx) {
+            Poll::Ready(Some(m)) => return Poll::Ready(Some(ReactorEvent::Output(m))),
+            Poll::Ready(None) => {
+                self.terminated = true;
+                return Poll::Ready(Some(ReactorEvent::Finished));
+            }
+            Poll::Pending => {}
+        }
+
+        let crash = self.crash.crash.take();
+        match crash {
+            Some(m) => {
+                self.terminated = true;
+                Poll::Ready(Some(ReactorEvent::Crashed(m)))
+            }
+            None => {
+                *self.crash.waker.borrow_mut() = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<R> FusedStream for ReactorStream<R>
+where
+    R: Reactor + 'static,
+{
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<R> Sink<<R::Scope as ReactorScoped>::Input> for ReactorStream<R>
+where
+    R: Reactor + 'static,
+{
+    type Error = ReactorBridgeSinkError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.bridge).poll_ready(cx)
+    }
+
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        item: <R::Scope as ReactorScoped>::Input,
+    ) -> Result<(), Self::Error> {
+        Pin::new(&mut self.bridge).start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.bridge).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.bridge).poll_close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+
+    use futures::channel::oneshot;
+    use futures::future::LocalBoxFuture;
+    use futures::{FutureExt, SinkExt};
+    use yew::platform::Runtime;
+
+    use super::*;
+    use crate::reactor::local::LocalReactorBridge;
+    use crate::reactor::ReactorScope;
+    use crate::supervisor::Supervisor;
+
+    /// Counts up to its first input and exits.
+    struct Count(LocalBoxFuture<'static, ()>);
+
+    impl Future for Count {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            self.0.poll_unpin(cx)
+        }
+    }
+
+    impl Reactor for Count {
+        type Scope = ReactorScope<u32, u32>;
+
+        fn create(mut scope: Self::Scope) -> Self {
+            Self(
+                async move {
+                    if let Some(n) = scope.next().await {
+                        for i in 0..n {
+                            let _ = scope.send(i).await;
+                        }
+                    }
+                }
+                .boxed_local(),
+            )
+        }
+    }
+
+    fn stream(worker: Option<&WorkerHandle>) -> ReactorStream<Count> {
+        ReactorStream::new(
+            ReactorConnection::Local(LocalReactorBridge::spawn()),
+            worker,
+        )
+    }
+
+    /// Runs `f` on the local runtime and returns its output.
+    fn run<F, O>(f: impl FnOnce() -> F + Send + 'static) -> O
+    where
+        F: Future<Output = O> + 'static,
+        O: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        Runtime::default().spawn_pinned(move || async move {
+            let _ = tx.send(f().await);
+        });
+
+        futures::executor::block_on(rx).unwrap()
+    }
+
+    #[test]
+    fn outputs_are_followed_by_finished() {
+        let events = run(|| async {
+            let mut bridge = stream(None);
+            bridge.send_input(2);
+
+            let mut events = Vec::new();
+            while let Some(m) = bridge.next().await {
+                events.push(match m {
+                    ReactorEvent::Output(m) => Some(m),
+                    ReactorEvent::Finished => None,
+                    ReactorEvent::Crashed(_) => panic!("the reactor did not crash"),
+                });
+            }
+
+            (events, bridge.is_terminated())
+        });
+
+        assert_eq!(events, (vec![Some(0), Some(1), None], true));
+    }
+
+    #[test]
+    fn send_and_collect_returns_outputs() {
+        let outputs = run(|| stream(None).send_and_collect(3));
+
+        assert_eq!(outputs, Ok(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn crashes_end_the_stream() {
+        let events = run(|| async {
+            let supervisor = Supervisor::default();
+            let worker = WorkerHandle::default();
+
+            // Without an input, the reactor never finishes.
+            let mut bridge = stream(Some(&worker));
+            supervisor.crashed(&worker, "unreachable".into());
+
+            let mut events = Vec::new();
+            while let Some(m) = bridge.next().await {
+                events.push(match m {
+                    ReactorEvent::Crashed(m) => m.message,
+                    _ => panic!("the reactor crashed"),
+                });
+            }
+
+            events
+        });
+
+        assert_eq!(events, vec!["unreachable".to_string()]);
+    }
+
+    #[test]
+    fn send_and_collect_fails_on_crash() {
+        let result = run(|| async {
+            let supervisor = Supervisor::default();
+            let worker = WorkerHandle::default();
+            let bridge = stream(Some(&worker));
+
+            supervisor.crashed(&worker, "unreachable".into());
+            bridge.send_and_collect(2).await
+        });
+
+        assert_eq!(
+            result.map_err(|m| m.message),
+            Err("unreachable".to_string())
+        );
+    }
+}
@@ -13,16 +13,31 @@ use yew::platform::spawn_local;
 use yew::prelude::*;
 
 use crate::oneshot::{Oneshot, OneshotProviderState};
-use crate::reactor::{Reactor, ReactorBridge, ReactorEvent, ReactorProviderState, ReactorScoped};
-use crate::worker::{Worker, WorkerBridge, WorkerProviderState};
+use crate::reactor::{
+    Reactor, ReactorConnection, ReactorEvent, ReactorProviderState, ReactorScoped, ReactorStream,
+};
+use crate::supervisor::{CrashWatcher, Supervised};
+use crate::worker::{Worker, WorkerConnection, WorkerProviderState};
+use crate::AgentCrash;
 
 /// A Worker Bridge Handle.
-#[derive(Debug)]
 pub struct WorkerBridgeHandle<W>
 where
     W: Worker,
 {
-    inner: WorkerBridge<W>,
+    inner: WorkerConnection<W>,
+    _on_crash: Rc<CrashWatcher>,
+}
+
+impl<W> fmt::Debug for WorkerBridgeHandle<W>
+where
+    W: Worker,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<W> WorkerBridgeHandle<W>
@@ -36,7 +51,7 @@ where
 }
 
 type ReactorTx<R> =
-    Rc<RwLock<SplitSink<ReactorBridge<R>, <<R as Reactor>::Scope as ReactorScoped>::Input>>>;
+    Rc<RwLock<SplitSink<ReactorConnection<R>, <<R as Reactor>::Scope as ReactorScoped>::Input>>>;
 
 /// A Reactor Bridge Handle.
 pub struct ReactorBridgeHandle<R>
@@ -44,6 +59,7 @@ where
     R: Reactor + 'static,
 {
     tx: ReactorTx<R>,
+    _on_crash: Rc<CrashWatcher>,
 }
 
 impl<R> fmt::Debug for ReactorBridgeHandle<R>
@@ -78,12 +94,32 @@ pub trait AgentScopeExt {
     where
         W: Worker + 'static;
 
+    /// Bridges to a Worker Agent, calling `on_crash` if its worker crashes.
+    ///
+    /// Struct components reconnect to a respawned worker by bridging again.
+    fn bridge_worker_with_crash<W>(
+        &self,
+        callback: Callback<W::Output>,
+        on_crash: Callback<AgentCrash>,
+    ) -> WorkerBridgeHandle<W>
+    where
+        W: Worker + 'static;
+
     /// Bridges to a Reactor Agent.
     fn bridge_reactor<R>(&self, callback: Callback<ReactorEvent<R>>) -> ReactorBridgeHandle<R>
     where
         R: Reactor + 'static,
         <R::Scope as ReactorScoped>::Output: 'static;
 
+    /// Bridges to a Reactor Agent, returning a bridge that is a [`Stream`](futures::stream::Stream)
+    /// of events and a [`Sink`](futures::sink::Sink) of inputs.
+    ///
+    /// The bridge can be moved into async code, for example to run an input to completion with
+    /// [`ReactorStream::send_and_collect`].
+    fn bridge_reactor_stream<R>(&self) -> ReactorStream<R>
+    where
+        R: Reactor + 'static;
+
     /// Runs an oneshot in an Oneshot Agent.
     fn run_oneshot<T>(&self, input: T::Input, callback: Callback<T::Output>)
     where
@@ -98,13 +134,37 @@ where
     where
         W: Worker + 'static,
     {
-        let inner = self
+        self.bridge_worker_with_crash(callback, Callback::noop())
+    }
+
+    fn bridge_worker_with_crash<W>(
+        &self,
+        callback: Callback<W::Output>,
+        on_crash: Callback<AgentCrash>,
+    ) -> WorkerBridgeHandle<W>
+    where
+        W: Worker + 'static,
+    {
+        let Supervised { bridge, worker } = self
             .context::<WorkerProviderState<W>>((|_| {}).into())
             .expect_throw("failed to bridge to agent.")
             .0
             .create_bridge(callback);
 
-        WorkerBridgeHandle { inner }
+        let on_crash: Rc<CrashWatcher> =
+            Rc::new(move |crash: &AgentCrash| on_crash.emit(crash.clone()));
+        match worker.crash() {
+            Some(crash) => {
+                let on_crash = on_crash.clone();
+                spawn_local(async move { on_crash(&crash) });
+            }
+            None => worker.watch(&on_crash),
+        }
+
+        WorkerBridgeHandle {
+            inner: bridge,
+            _on_crash: on_crash,
+        }
     }
 
     fn bridge_reactor<R>(&self, callback: Callback<ReactorEvent<R>>) -> ReactorBridgeHandle<R>
@@ -112,24 +172,54 @@ where
         R: Reactor + 'static,
         <R::Scope as ReactorScoped>::Output: 'static,
     {
-        let (tx, mut rx) = self
+        let Supervised { bridge, worker } = self
             .context::<ReactorProviderState<R>>((|_| {}).into())
             .expect_throw("failed to bridge to agent.")
             .0
-            .create_bridge()
-            .split();
-
-        spawn_local(async move {
-            while let Some(m) = rx.next().await {
-                callback.emit(ReactorEvent::<R>::Output(m));
+            .create_bridge();
+        let (tx, mut rx) = bridge.split();
+
+        {
+            let callback = callback.clone();
+            spawn_local(async move {
+                while let Some(m) = rx.next().await {
+                    callback.emit(ReactorEvent::<R>::Output(m));
+                }
+
+                callback.emit(ReactorEvent::<R>::Finished);
+            });
+        }
+
+        // Struct components reconnect by bridging again.
+        let on_crash: Rc<CrashWatcher> =
+            Rc::new(move |crash: &AgentCrash| callback.emit(ReactorEvent::Crashed(crash.clone())));
+        match worker.crash() {
+            Some(crash) => {
+                let on_crash = on_crash.clone();
+                spawn_local(async move { on_crash(&crash) });
             }
-
-            callback.emit(ReactorEvent::<R>::Finished);
-        });
+            None => worker.watch(&on_crash),
+        }
 
         let tx = Rc::new(RwLock::new(tx));
 
-        ReactorBridgeHandle { tx }
+        ReactorBridgeHandle {
+            tx,
+            _on_crash: on_crash,
+        }
+    }
+
+    fn bridge_reactor_stream<R>(&self) -> ReactorStream<R>
+    where
+        R: Reactor + 'static,
+    {
+        let Supervised { bridge, worker } = self
+            .context::<ReactorProviderState<R>>((|_| {}).into())
+            .expect_throw("failed to bridge to agent.")
+            .0
+            .create_bridge();
+
+        ReactorStream::new(bridge, Some(&worker))
     }
 
     fn run_oneshot<T>(&self, input: T::Input, callback: Callback<T::Output>)
@@ -140,6 +230,6 @@ where
             .context::<OneshotProviderState<T>>((|_| {}).into())
             .expect_throw("failed to bridge to agent.");
 
-        spawn_local(async move { callback.emit(inner.create_bridge().run(input).await) });
+        spawn_local(async move { callback.emit(inner.run(input).await) });
     }
 }
@@ -0,0 +1,192 @@
+use std::any::type_name;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::rc::Rc;
+
+use futures::channel::oneshot;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::UnwrapThrowExt;
+use yew::prelude::*;
+
+use super::{Service, ServiceError, ServiceRequest, ServiceResponse, ServiceWorker};
+use crate::supervisor::{CrashWatcher, Supervised, WorkerHandle};
+use crate::worker::{WorkerConnection, WorkerProvider, WorkerProviderProps, WorkerProviderState};
+use crate::{AgentCrash, Bincode, Codec};
+
+type Pending<S> = Rc<RefCell<HashMap<u64, oneshot::Sender<<S as Service>::Response>>>>;
+
+struct ClientInner<S>
+where
+    S: Service,
+{
+    state: WorkerProviderState<ServiceWorker<S>>,
+    bridge: RefCell<WorkerConnection<ServiceWorker<S>>>,
+    worker: RefCell<WorkerHandle>,
+    on_crash: Rc<CrashWatcher>,
+    next_id: Cell<u64>,
+    pending: Pending<S>,
+}
+
+impl<S> ClientInner<S>
+where
+    S: Service,
+{
+    fn connect(&self) {
+        let pending = self.pending.clone();
+        let Supervised { bridge, worker } = self.state.create_bridge(Callback::from(
+            move |ServiceResponse { id, response }: ServiceResponse<S::Response>| {
+                if let Some(tx) = pending.borrow_mut().remove(&id) {
+                    let _ = tx.send(response);
+                }
+            },
+        ));
+
+        worker.watch(&self.on_crash);
+        *self.bridge.borrow_mut() = bridge;
+        *self.worker.borrow_mut() = worker;
+    }
+}
+
+/// A connection to a service agent.
+///
+/// Clients generated by [`agent_service`](super::agent_service) wrap this type.
+pub struct ServiceClient<S>
+where
+    S: Service,
+{
+    inner: Rc<ClientInner<S>>,
+}
+
+impl<S> ServiceClient<S>
+where
+    S: Service,
+{
+    pub(crate) fn new(state: &WorkerProviderState<ServiceWorker<S>>) -> Self {
+        let pending: Pending<S> = Rc::default();
+
+        let Supervised { bridge, worker } = {
+            let pending = pending.clone();
+            state.create_bridge(Callback::from(
+                move |ServiceResponse { id, response }: ServiceResponse<S::Response>| {
+                    if let Some(tx) = pending.borrow_mut().remove(&id) {
+                        let _ = tx.send(response);
+                    }
+                },
+            ))
+        };
+
+        // Calls waiting for a crashed worker fail with `ServiceError::Disconnected`.
+        let on_crash: Rc<CrashWatcher> = {
+            let pending = pending.clone();
+            Rc::new(move |_: &AgentCrash| pending.borrow_mut().clear())
+        };
+        worker.watch(&on_crash);
+
+        Self {
+            inner: Rc::new(ClientInner {
+                state: state.clone(),
+                bridge: RefCell::new(bridge),
+                worker: RefCell::new(worker),
+                on_crash,
+                next_id: Cell::new(0),
+                pending,
+            }),
+        }
+    }
+
+    /// Sends a request to the service and waits for its response.
+    ///
+    /// The request is sent when this method is called, not when the future is first polled. If
+    /// the worker of the service has crashed and is respawned, the client reconnects first.
+    pub fn call(
+        &self,
+        request: S::Request,
+    ) -> impl Future<Output = Result<S::Response, ServiceError>> + 'static {
+        let should_reconnect = self.inner.worker.borrow().should_respawn();
+        if should_reconnect {
+            self.inner.connect();
+        }
+
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+
+        let (tx, rx) = oneshot::channel();
+        self.inner.pending.borrow_mut().insert(id, tx);
+        self.inner
+            .bridge
+            .borrow()
+            .send(ServiceRequest { id, request });
+
+        async move { rx.await.map_err(|_| ServiceError::Disconnected) }
+    }
+
+    /// Returns the number of calls waiting for a response.
+    pub fn in_flight(&self) -> usize {
+        self.inner.pending.borrow().len()
+    }
+}
+
+impl<S> Clone for ServiceClient<S>
+where
+    S: Service,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<S> PartialEq for ServiceClient<S>
+where
+    S: Service,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &rhs.inner)
+    }
+}
+
+impl<S> fmt::Debug for ServiceClient<S>
+where
+    S: Service,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("in_flight", &self.in_flight())
+            .finish()
+    }
+}
+
+/// The Service Agent Provider.
+///
+/// This component provides its children access to a service agent.
+#[function_component]
+pub fn ServiceProvider<S, C = Bincode>(props: &WorkerProviderProps) -> Html
+where
+    S: Service,
+    S::Request: Serialize + for<'de> Deserialize<'de>,
+    S::Response: Serialize + for<'de> Deserialize<'de>,
+    C: Codec + 'static,
+{
+    html! {
+        <WorkerProvider<ServiceWorker<S>, C> ..props.clone() />
+    }
+}
+
+/// A hook to access the client of a service agent.
+///
+/// The client is created once over the entire component lifecycle.
+#[hook]
+pub fn use_service<S>() -> S::Client
+where
+    S: Service,
+{
+    let state = use_context::<WorkerProviderState<ServiceWorker<S>>>()
+        .expect_throw("cannot find a provider for current agent.");
+
+    let client = use_memo(state, ServiceClient::new);
+
+    (*client).clone().into()
+}
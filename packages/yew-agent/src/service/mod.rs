@@ -0,0 +1,205 @@
+//! This module contains the service agent implementation.
+//!
+//! Service agents expose multiple methods over a single worker. A service is defined as a trait
+//! with async methods, annotated with [`agent_service`]. The macro generates:
+//!
+//! - a service type, named after the trait with a `Service` suffix unless a name is passed to the
+//!   attribute, that is used with [`ServiceProvider`] and [`use_service`],
+//! - a client, named after the trait with a `Client` suffix, that has a method for each method of
+//!   the trait. Calls are sent to the worker immediately and can be in flight concurrently.
+//!
+//! Each method of the client returns a [`Result`] that fails with a [`ServiceError`] if the
+//! worker does not respond. Errors of a method itself are expressed with its return type.
+//!
+//! The async methods of the trait are turned into methods returning a [`LocalBoxFuture`], which
+//! handlers implement by boxing an async block.
+//!
+//! # Example
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! use yew::prelude::*;
+//! use yew_agent::service::{
+//!     agent_service, use_service, LocalBoxFuture, ServiceError, ServiceProvider,
+//! };
+//!
+//! #[derive(Debug, Serialize, Deserialize)]
+//! pub enum ResizeError {
+//!     InvalidImage,
+//! }
+//!
+//! #[agent_service]
+//! pub trait Images {
+//!     async fn resize(&self, image: Vec<u8>, width: u32) -> Result<Vec<u8>, ResizeError>;
+//!     async fn histogram(&self, image: Vec<u8>) -> Vec<u32>;
+//! }
+//!
+//! // In the worker:
+//! pub struct ImageWorker;
+//!
+//! impl Images for ImageWorker {
+//!     fn resize(
+//!         &self,
+//!         image: Vec<u8>,
+//!         width: u32,
+//!     ) -> LocalBoxFuture<'_, Result<Vec<u8>, ResizeError>> {
+//!         Box::pin(async move {
+//!             // ...
+//! #           Ok(image)
+//!         })
+//!     }
+//!
+//!     fn histogram(&self, image: Vec<u8>) -> LocalBoxFuture<'_, Vec<u32>> {
+//!         Box::pin(async move {
+//!             // ...
+//! #           Vec::new()
+//!         })
+//!     }
+//! }
+//!
+//! # fn worker_main() {
+//! ImagesService::registrar(ImageWorker).register();
+//! # }
+//!
+//! // In the application:
+//! #[function_component]
+//! fn Editor() -> Html {
+//!     let images = use_service::<ImagesService>();
+//!
+//!     let onclick = Callback::from(move |_| {
+//!         let images = images.clone();
+//!         yew::platform::spawn_local(async move {
+//!             let (resized, histogram) =
+//!                 futures::join!(images.resize(vec![], 640), images.histogram(vec![]));
+//!             // ...
+//!         });
+//!     });
+//!
+//!     html! { <button {onclick}>{ "Resize" }</button> }
+//! }
+//!
+//! #[function_component]
+//! fn App() -> Html {
+//!     html! {
+//!         <ServiceProvider<ImagesService> path="/worker.js">
+//!             <Editor />
+//!         </ServiceProvider<ImagesService>>
+//!     }
+//! }
+//! ```
+
+mod hooks;
+mod worker;
+
+use std::fmt;
+use std::rc::Rc;
+
+pub use futures::future::LocalBoxFuture;
+use serde::{Deserialize, Serialize};
+
+pub use hooks::{use_service, ServiceClient, ServiceProvider};
+pub use worker::{ServiceRegistrar, ServiceRequest, ServiceResponse, ServiceWorker};
+/// A procedural macro to create service agents.
+pub use yew_agent_macro::agent_service;
+
+/// A service agent.
+///
+/// This trait is implemented by [`agent_service`] and should not be implemented manually.
+pub trait Service: Sized + 'static {
+    /// The requests accepted by the service, one for each method.
+    type Request: Serialize + for<'de> Deserialize<'de> + 'static;
+    /// The responses sent by the service, one for each method.
+    type Response: Serialize + for<'de> Deserialize<'de> + 'static;
+    /// The client handle of the service.
+    type Client: From<ServiceClient<Self>>;
+}
+
+/// A service agent that can be served by a handler of type `H`.
+///
+/// This trait is implemented by [`agent_service`] and should not be implemented manually.
+pub trait ServiceFor<H>: Service
+where
+    H: 'static,
+{
+    /// Calls the method of the handler matching the request.
+    fn dispatch(handler: &Rc<H>, request: Self::Request)
+        -> LocalBoxFuture<'static, Self::Response>;
+}
+
+/// An error returned by the methods of a service client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceError {
+    /// The worker disconnected before responding.
+    Disconnected,
+    /// The worker responded to a call with the response of another method.
+    ///
+    /// This happens when the worker has been built from a different version of the service.
+    UnexpectedResponse,
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disconnected => f.write_str("the service disconnected before responding"),
+            Self::UnexpectedResponse => f.write_str("the service sent an unexpected response"),
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    #[agent_service(Calculator)]
+    trait Calc {
+        async fn add(&self, a: i32, b: i32) -> i32;
+        async fn checked_div(&self, a: i32, b: i32) -> Result<i32, String>;
+        async fn reset(&self);
+    }
+
+    struct Impl;
+
+    impl Calc for Impl {
+        fn add(&self, a: i32, b: i32) -> LocalBoxFuture<'_, i32> {
+            Box::pin(async move { a + b })
+        }
+
+        fn checked_div(&self, a: i32, b: i32) -> LocalBoxFuture<'_, Result<i32, String>> {
+            Box::pin(async move {
+                a.checked_div(b)
+                    .ok_or_else(|| "division by zero".to_string())
+            })
+        }
+
+        fn reset(&self) -> LocalBoxFuture<'_, ()> {
+            Box::pin(async {})
+        }
+    }
+
+    #[test]
+    fn dispatches_to_methods() {
+        let handler = Rc::new(Impl);
+
+        match block_on(Calculator::dispatch(&handler, CalcRequest::add(1, 2))) {
+            CalcResponse::add(m) => assert_eq!(m, 3),
+            _ => panic!("unexpected response"),
+        }
+
+        match block_on(Calculator::dispatch(
+            &handler,
+            CalcRequest::checked_div(1, 0),
+        )) {
+            CalcResponse::checked_div(m) => assert_eq!(m, Err("division by zero".to_string())),
+            _ => panic!("unexpected response"),
+        }
+
+        assert!(matches!(
+            block_on(Calculator::dispatch(&handler, CalcRequest::reset())),
+            CalcResponse::reset(())
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use futures::future::LocalBoxFuture;
+use serde::{Deserialize, Serialize};
+
+use super::{Service, ServiceFor};
+use crate::worker::{HandlerId, Worker, WorkerRegistrar, WorkerScope};
+use crate::{Bincode, Codec, Registrable};
+
+type ServiceFn<S> =
+    Rc<dyn Fn(<S as Service>::Request) -> LocalBoxFuture<'static, <S as Service>::Response>>;
+
+thread_local! {
+    // The handlers registered in the current worker, by the type id of their service.
+    static HANDLERS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::default();
+}
+
+/// A call to a service, sent from a client to the worker.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceRequest<T> {
+    pub(crate) id: u64,
+    pub(crate) request: T,
+}
+
+/// The response to a [`ServiceRequest`], sent from the worker to the client.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceResponse<T> {
+    pub(crate) id: u64,
+    pub(crate) response: T,
+}
+
+/// The worker serving a [`Service`].
+pub struct ServiceWorker<S>
+where
+    S: Service,
+{
+    handler: ServiceFn<S>,
+}
+
+impl<S> fmt::Debug for ServiceWorker<S>
+where
+    S: Service,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ServiceWorker<_>")
+    }
+}
+
+impl<S> Worker for ServiceWorker<S>
+where
+    S: Service,
+{
+    type Input = ServiceRequest<S::Request>;
+    type Message = (HandlerId, ServiceResponse<S::Response>);
+    type Output = ServiceResponse<S::Response>;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        let handler = HANDLERS.with(|m| {
+            m.borrow()
+                .get(&TypeId::of::<S>())
+                .and_then(|m| m.downcast_ref::<ServiceFn<S>>())
+                .cloned()
+                .expect("the service is not registered, register it with ServiceRegistrar.")
+        });
+
+        Self { handler }
+    }
+
+    fn update(&mut self, scope: &WorkerScope<Self>, (handler_id, response): Self::Message) {
+        scope.respond(handler_id, response);
+    }
+
+    fn received(&mut self, scope: &WorkerScope<Self>, input: Self::Input, handler_id: HandlerId) {
+        let ServiceRequest { id, request } = input;
+        let response = (self.handler)(request);
+
+        scope.send_future(async move {
+            let response = response.await;
+
+            (handler_id, ServiceResponse { id, response })
+        });
+    }
+}
+
+/// A registrar for service workers.
+pub struct ServiceRegistrar<S, CODEC = Bincode>
+where
+    S: Service,
+    CODEC: Codec + 'static,
+{
+    handler: ServiceFn<S>,
+    inner: WorkerRegistrar<ServiceWorker<S>, CODEC>,
+}
+
+impl<S, CODEC> fmt::Debug for ServiceRegistrar<S, CODEC>
+where
+    S: Service,
+    CODEC: Codec + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ServiceRegistrar<_>")
+    }
+}
+
+impl<S> ServiceRegistrar<S>
+where
+    S: Service,
+{
+    /// Creates a registrar serving the service with `handler`.
+    pub fn new<H>(handler: H) -> Self
+    where
+        S: ServiceFor<H>,
+        H: 'static,
+    {
+        let handler = Rc::new(handler);
+
+        Self {
+            handler: Rc::new(move |request| S::dispatch(&handler, request)),
+            inner: ServiceWorker::<S>::registrar(),
+        }
+    }
+}
+
+impl<S, CODEC> ServiceRegistrar<S, CODEC>
+where
+    S: Service,
+    CODEC: Codec + 'static,
+{
+    /// Sets the encoding.
+    pub fn encoding<C>(self) -> ServiceRegistrar<S, C>
+    where
+        C: Codec + 'static,
+    {
+        ServiceRegistrar {
+            handler: self.handler,
+            inner: self.inner.encoding::<C>(),
+        }
+    }
+
+    /// Registers the worker.
+    pub fn register(&self) {
+        HANDLERS.with(|m| {
+            m.borrow_mut()
+                .insert(TypeId::of::<S>(), Box::new(self.handler.clone()));
+        });
+
+        self.inner.register();
+    }
+}
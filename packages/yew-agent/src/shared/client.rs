@@ -0,0 +1,293 @@
+//! The tab side of shared agents.
+//!
+//! A tab connects to a shared agent with a `MessagePort`, which carries the messages of all
+//! bridges of the tab. Each bridge opens a session on the port, and closes it when it is dropped.
+//! The port is closed when the last bridge of the tab is dropped.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use futures::channel::mpsc;
+use futures::stream::{FusedStream, Stream, StreamExt};
+use js_sys::{Array, Reflect};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    ErrorEvent, ErrorEventInit, EventTarget, MessageChannel, MessageEvent, MessagePort, Navigator,
+    ServiceWorkerContainer, ServiceWorkerRegistration, SharedWorker,
+};
+use yew::platform::spawn_local;
+
+use super::messages::{FromHost, ToHost};
+use super::PostFn;
+use crate::transfer::post_to_port;
+use crate::{Codec, Reach};
+
+/// The message a tab sends to a service worker to connect to its agent.
+pub(crate) const CONNECT_MESSAGE: &str = "yew-agent:connect";
+
+/// The sessions of the bridges of a tab.
+struct Connection<I, O> {
+    post: Box<PostFn<ToHost<I>>>,
+    next_session: Cell<u32>,
+    sessions: RefCell<HashMap<u32, mpsc::UnboundedSender<O>>>,
+    port: Option<MessagePort>,
+}
+
+impl<I, O> Connection<I, O> {
+    fn new(post: Box<PostFn<ToHost<I>>>, port: Option<MessagePort>) -> Self {
+        Self {
+            post,
+            next_session: Cell::new(0),
+            sessions: RefCell::default(),
+            port,
+        }
+    }
+
+    fn open(self: &Rc<Self>) -> PortBridge<I, O> {
+        let session = self.next_session.get();
+        self.next_session.set(session.wrapping_add(1));
+
+        let (tx, rx) = mpsc::unbounded();
+        self.sessions.borrow_mut().insert(session, tx);
+        (self.post)(ToHost::Open(session));
+
+        PortBridge {
+            connection: self.clone(),
+            session,
+            rx,
+        }
+    }
+
+    fn receive(&self, message: FromHost<O>) {
+        match message {
+            FromHost::Output(session, output) => {
+                // The bridge may have been dropped.
+                if let Some(m) = self.sessions.borrow().get(&session) {
+                    let _ = m.unbounded_send(output);
+                }
+            }
+            FromHost::Finished(session) => {
+                self.sessions.borrow_mut().remove(&session);
+            }
+        }
+    }
+}
+
+impl<I, O> Drop for Connection<I, O> {
+    fn drop(&mut self) {
+        if let Some(ref m) = self.port {
+            m.close();
+        }
+    }
+}
+
+/// A bridge to a shared agent.
+pub(crate) struct PortBridge<I, O> {
+    connection: Rc<Connection<I, O>>,
+    session: u32,
+    rx: mpsc::UnboundedReceiver<O>,
+}
+
+impl<I, O> fmt::Debug for PortBridge<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PortBridge<_>")
+            .field("session", &self.session)
+            .finish()
+    }
+}
+
+impl<I, O> PortBridge<I, O> {
+    /// Creates a bridge with a new session on the same port.
+    pub fn fork(&self) -> Self {
+        self.connection.open()
+    }
+
+    pub fn send_input(&self, input: I) {
+        (self.connection.post)(ToHost::Input(self.session, input));
+    }
+}
+
+impl<I, O> Drop for PortBridge<I, O> {
+    fn drop(&mut self) {
+        self.connection.sessions.borrow_mut().remove(&self.session);
+        (self.connection.post)(ToHost::Close(self.session));
+    }
+}
+
+impl<I, O> Stream for PortBridge<I, O> {
+    type Item = O;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_next_unpin(cx)
+    }
+}
+
+impl<I, O> FusedStream for PortBridge<I, O> {
+    fn is_terminated(&self) -> bool {
+        self.rx.is_terminated()
+    }
+}
+
+/// Connects to the shared agent at `path`, returning a bridge to it and the target of the errors
+/// of its worker.
+///
+/// If `transfer` is set, the [`Transfer`](crate::transfer::Transfer) fields of inputs are
+/// transferred to the agent.
+pub(crate) fn connect<C, I, O>(
+    reach: Reach,
+    path: &str,
+    transfer: bool,
+) -> (PortBridge<I, O>, EventTarget)
+where
+    C: Codec,
+    I: Serialize + 'static,
+    O: for<'de> Deserialize<'de> + 'static,
+{
+    let (port, target) = match reach {
+        Reach::Shared => {
+            let worker = SharedWorker::new(path).expect_throw("failed to spawn shared worker");
+            (worker.port(), worker.into())
+        }
+        Reach::ServiceWorker => connect_service_worker(path),
+        _ => unreachable!("connecting to an agent that is not shared"),
+    };
+
+    let post: Box<PostFn<ToHost<I>>> = {
+        let port = port.clone();
+        Box::new(move |m| {
+            post_to_port(&port, &C::encode(m), transfer).expect_throw("failed to post message")
+        })
+    };
+    let connection = Rc::new(Connection::new(post, Some(port.clone())));
+
+    let on_message = {
+        // The port does not keep the bridges of the tab alive.
+        let connection = Rc::downgrade(&connection);
+        Closure::<dyn Fn(MessageEvent)>::new(move |e: MessageEvent| {
+            if let Some(m) = connection.upgrade() {
+                m.receive(C::decode(e.data()));
+            }
+        })
+    }
+    .into_js_value();
+    port.set_onmessage(Some(on_message.unchecked_ref()));
+    port.start();
+
+    (connection.open(), target)
+}
+
+/// Registers the service worker at `path` and sends it a port, once it is active.
+///
+/// A failed registration is reported as an error of the returned target.
+fn connect_service_worker(path: &str) -> (MessagePort, EventTarget) {
+    let channel = MessageChannel::new().expect_throw("failed to create message channel");
+    let target = EventTarget::new().expect_throw("failed to create event target");
+
+    {
+        let path = path.to_owned();
+        let port = channel.port2();
+        let target = target.clone();
+
+        spawn_local(async move {
+            let connected = async {
+                let navigator: Navigator =
+                    Reflect::get(&js_sys::global(), &JsValue::from_str("navigator"))?
+                        .unchecked_into();
+                let container: ServiceWorkerContainer = navigator.service_worker();
+
+                JsFuture::from(container.register(&path)).await?;
+                let registration: ServiceWorkerRegistration =
+                    JsFuture::from(container.ready()?).await?.unchecked_into();
+                let worker = registration
+                    .active()
+                    .ok_or_else(|| JsValue::from_str("the service worker is not active"))?;
+
+                worker.post_message_with_transferable(
+                    &JsValue::from_str(CONNECT_MESSAGE),
+                    &Array::of1(&port),
+                )
+            };
+
+            if let Err(e) = connected.await {
+                let message = match e.dyn_ref::<js_sys::Error>() {
+                    Some(m) => String::from(m.message()),
+                    None => e
+                        .as_string()
+                        .unwrap_or_else(|| "failed to connect to the service worker".into()),
+                };
+                let mut init = ErrorEventInit::new();
+                init.message(&message);
+                if let Ok(event) = ErrorEvent::new_with_event_init_dict("error", &init) {
+                    let _ = target.dispatch_event(&event);
+                }
+            }
+        });
+    }
+
+    (channel.port1(), target)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Creates a connection posting its messages with `post`.
+    pub(crate) fn connection<I, O>(
+        post: impl Fn(ToHost<I>) + 'static,
+    ) -> (PortBridge<I, O>, impl Fn(FromHost<O>)) {
+        let connection = Rc::new(Connection::new(Box::new(post), None));
+        let bridge = connection.open();
+        let connection = Rc::downgrade(&connection);
+
+        (bridge, move |m| {
+            if let Some(connection) = connection.upgrade() {
+                connection.receive(m);
+            }
+        })
+    }
+
+    #[test]
+    fn bridges_open_and_close_sessions() {
+        let posted = Rc::new(RefCell::new(Vec::new()));
+        let (bridge, receive) = connection::<u32, u32>({
+            let posted = posted.clone();
+            move |m| posted.borrow_mut().push(format!("{m:?}"))
+        });
+
+        let fork = bridge.fork();
+        fork.send_input(1);
+        drop(fork);
+
+        receive(FromHost::Output(0, 2));
+        receive(FromHost::Finished(0));
+        let outputs = futures::executor::block_on(bridge.collect::<Vec<_>>());
+
+        assert_eq!(outputs, vec![2]);
+        assert_eq!(
+            *posted.borrow(),
+            vec!["Open(0)", "Open(1)", "Input(1, 1)", "Close(1)", "Close(0)"]
+        );
+    }
+
+    #[test]
+    fn outputs_of_dropped_bridges_are_ignored() {
+        let (bridge, receive) = connection::<u32, u32>(|_| {});
+        let fork = bridge.fork();
+        drop(fork);
+
+        receive(FromHost::Output(1, 2));
+        receive(FromHost::Output(0, 3));
+
+        assert_eq!(
+            futures::executor::block_on(bridge.take(1).collect::<Vec<_>>()),
+            vec![3]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! The worker side of shared agents.
+//!
+//! Each connected tab has a `MessagePort` to the shared worker or service worker. The bridges of
+//! the tab open sessions on the port, which are served by the agent until they are closed.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use js_sys::{Array, Object, Reflect};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{MessageEvent, MessagePort};
+
+use super::messages::{FromHost, ToHost};
+use super::PostFn;
+use crate::transfer::{post_to_port, transfers_outputs};
+use crate::Codec;
+
+/// The global holding the ports that connected before the agent was registered.
+///
+/// Loader scripts push connecting ports to it, see the [module level documentation](super).
+pub(crate) const PENDING_PORTS: &str = "__yewAgentPorts";
+
+/// The session of a bridge, which is closed when it is dropped.
+pub(crate) trait Session<I> {
+    /// Handles an input of the bridge.
+    fn received(&mut self, input: I);
+}
+
+/// Posts the outputs of a session to its bridge.
+pub(crate) struct Responder<O> {
+    post: Rc<PostFn<FromHost<O>>>,
+    session: u32,
+}
+
+impl<O> Clone for Responder<O> {
+    fn clone(&self) -> Self {
+        Self {
+            post: self.post.clone(),
+            session: self.session,
+        }
+    }
+}
+
+impl<O> Responder<O> {
+    pub fn respond(&self, output: O) {
+        (self.post)(FromHost::Output(self.session, output));
+    }
+
+    /// Tells the bridge that the agent has finished, and will not send more outputs.
+    pub fn finish(&self) {
+        (self.post)(FromHost::Finished(self.session));
+    }
+}
+
+/// Opens the session of a bridge, which responds with a [`Responder`].
+pub(crate) type OpenFn<I, O> = dyn Fn(Responder<O>) -> Box<dyn Session<I>>;
+
+// (port, session of the port) -> session
+type Sessions<I> = HashMap<(usize, u32), Box<dyn Session<I>>>;
+
+/// Routes the messages of all ports to the sessions of their bridges.
+pub(crate) struct Router<I, O> {
+    open: Box<OpenFn<I, O>>,
+    sessions: RefCell<Sessions<I>>,
+}
+
+impl<I, O> Router<I, O> {
+    pub fn new(open: Box<OpenFn<I, O>>) -> Self {
+        Self {
+            open,
+            sessions: RefCell::default(),
+        }
+    }
+
+    /// Handles a message received from the port `port`, which is responded to with `post`.
+    pub fn receive(&self, port: usize, post: &Rc<PostFn<FromHost<O>>>, message: ToHost<I>) {
+        match message {
+            ToHost::Open(session) => {
+                let opened = (self.open)(Responder {
+                    post: post.clone(),
+                    session,
+                });
+                self.sessions.borrow_mut().insert((port, session), opened);
+            }
+            ToHost::Input(session, input) => {
+                if let Some(m) = self.sessions.borrow_mut().get_mut(&(port, session)) {
+                    m.received(input);
+                }
+            }
+            ToHost::Close(session) => {
+                // Dropped after the sessions are released.
+                let closed = self.sessions.borrow_mut().remove(&(port, session));
+                drop(closed);
+            }
+        }
+    }
+}
+
+/// Serves the sessions opened by `open` to the ports of the current shared worker or service
+/// worker.
+pub(crate) fn serve<C, I, O>(open: Box<OpenFn<I, O>>)
+where
+    C: Codec,
+    I: for<'de> Deserialize<'de> + 'static,
+    O: Serialize + 'static,
+{
+    let router = Rc::new(Router::new(open));
+    let next_port = Cell::new(0);
+    let push_port = move |port: MessagePort| {
+        let port_id = next_port.get();
+        next_port.set(port_id + 1);
+
+        connect::<C, _, _>(&router, port_id, port);
+    };
+
+    // Ports connecting after this point are connected as they are pushed.
+    let global = js_sys::global();
+    let key = JsValue::from_str(PENDING_PORTS);
+    let pending = Reflect::get(&global, &key)
+        .ok()
+        .and_then(|m| m.dyn_into::<Array>().ok());
+
+    if let Some(pending) = pending {
+        for port in pending.iter() {
+            push_port(port.unchecked_into());
+        }
+    }
+
+    let ports = Object::new();
+    let push = Closure::<dyn Fn(MessagePort)>::new(push_port).into_js_value();
+    Reflect::set(&ports, &JsValue::from_str("push"), &push).expect_throw("failed to set push");
+    Reflect::set(&global, &key, &ports).expect_throw("failed to set pending ports");
+}
+
+fn connect<C, I, O>(router: &Rc<Router<I, O>>, port_id: usize, port: MessagePort)
+where
+    C: Codec,
+    I: for<'de> Deserialize<'de> + 'static,
+    O: Serialize + 'static,
+{
+    let post: Rc<PostFn<FromHost<O>>> = {
+        let port = port.clone();
+        let transfer = transfers_outputs();
+        Rc::new(move |m| {
+            // A port of a closed tab drops messages silently.
+            post_to_port(&port, &C::encode(m), transfer).expect_throw("failed to post message")
+        })
+    };
+
+    let on_message = {
+        let router = router.clone();
+        Closure::<dyn Fn(MessageEvent)>::new(move |e: MessageEvent| {
+            router.receive(port_id, &post, C::decode(e.data()));
+        })
+    }
+    .into_js_value();
+    port.set_onmessage(Some(on_message.unchecked_ref()));
+    port.start();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo(Responder<u32>);
+
+    impl Session<u32> for Echo {
+        fn received(&mut self, input: u32) {
+            self.0.respond(input);
+        }
+    }
+
+    impl Drop for Echo {
+        fn drop(&mut self) {
+            self.0.finish();
+        }
+    }
+
+    /// Creates a port posting the messages of the router to `posted`.
+    fn port(posted: &Rc<RefCell<Vec<String>>>, name: &'static str) -> Rc<PostFn<FromHost<u32>>> {
+        let posted = posted.clone();
+        Rc::new(move |m| posted.borrow_mut().push(format!("{name}: {m:?}")))
+    }
+
+    #[test]
+    fn sessions_are_unique_to_their_port() {
+        let router = Router::<u32, u32>::new(Box::new(|m| Box::new(Echo(m))));
+        let posted = Rc::default();
+        let first = port(&posted, "first");
+        let second = port(&posted, "second");
+
+        router.receive(0, &first, ToHost::Open(0));
+        router.receive(1, &second, ToHost::Open(0));
+        router.receive(0, &first, ToHost::Input(0, 1));
+        router.receive(1, &second, ToHost::Input(0, 2));
+        router.receive(1, &second, ToHost::Close(0));
+        // Closed
+        router.receive(1, &second, ToHost::Input(0, 3));
+
+        assert_eq!(
+            *posted.borrow(),
+            vec![
+                "first: Output(0, 1)",
+                "second: Output(0, 2)",
+                "second: Finished(0)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Messages sent by the bridges of a tab to a shared agent.
+///
+/// Each bridge opens a session, identified by an id unique to the port of its tab.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum ToHost<I> {
+    /// A bridge is connected.
+    Open(u32),
+    /// An input of a bridge.
+    Input(u32, I),
+    /// A bridge is dropped.
+    Close(u32),
+}
+
+/// Messages sent by a shared agent to the bridges of a tab.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum FromHost<O> {
+    /// An output for a bridge.
+    Output(u32, O),
+    /// The agent of a bridge has finished, no more outputs will be sent to it.
+    Finished(u32),
+}
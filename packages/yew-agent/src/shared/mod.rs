@@ -0,0 +1,271 @@
+//! This module contains the support for agents shared by all tabs of an origin.
+//!
+//! Agents with [`Reach::Shared`](crate::Reach::Shared) run in a
+//! [`SharedWorker`](https://developer.mozilla.org/en-US/docs/Web/API/SharedWorker), and agents with
+//! [`Reach::ServiceWorker`](crate::Reach::ServiceWorker) run in the
+//! [service worker](https://developer.mozilla.org/en-US/docs/Web/API/Service_Worker_API) of the
+//! application. A single instance of the agent is shared by all tabs, for example to hold one
+//! websocket connection for all of them, or to manage offline caches.
+//!
+//! Shared agents are registered with [`RegisterShared::register_shared`] instead of `register`,
+//! with the same codec as their provider. The `path` of the provider is the path of a loader
+//! script, which must collect the ports of tabs connecting before the agent is registered.
+//!
+//! Tabs connect to the agent with a `MessagePort`, and each bridge of a tab opens a session on
+//! it, which is served by a new reactor, or runs of the oneshot agent. Only reactor and oneshot
+//! agents can be shared, and a worker or service worker hosts a single shared agent.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // in the application
+//! <ReactorProvider<Chat> path="/chat-loader.js" reach={Reach::Shared}>
+//!
+//! // in the worker
+//! Chat::registrar().register_shared();
+//! ```
+//!
+//! A loader script for a shared worker:
+//!
+//! ```js
+//! self.__yewAgentPorts = [];
+//! self.onconnect = (e) => self.__yewAgentPorts.push(e.ports[0]);
+//!
+//! importScripts("/worker.js");
+//! wasm_bindgen("/worker_bg.wasm");
+//! ```
+//!
+//! A loader script for a service worker, which must be served from a path that controls the
+//! application:
+//!
+//! ```js
+//! self.__yewAgentPorts = [];
+//! self.addEventListener("message", (e) => {
+//!     if (e.data === "yew-agent:connect") {
+//!         self.__yewAgentPorts.push(e.ports[0]);
+//!     }
+//! });
+//!
+//! importScripts("/worker.js");
+//! wasm_bindgen("/worker_bg.wasm");
+//! ```
+//!
+//! Browsers do not notify shared workers when a tab is closed, so agents are not notified when the
+//! bridges of a closed tab disconnect.
+
+mod client;
+mod host;
+mod messages;
+mod sessions;
+
+use serde::de::Deserialize;
+use serde::ser::Serialize;
+
+pub(crate) use client::{connect, PortBridge};
+
+use crate::oneshot::Oneshot;
+use crate::reactor::{Reactor, ReactorRegistrar, ReactorScoped};
+use crate::Codec;
+
+/// Posts a message to the other side of a port.
+pub(crate) type PostFn<T> = dyn Fn(T);
+
+/// Registers agents shared by the tabs of an origin.
+///
+/// This trait is implemented by the registrars of reactor and oneshot agents.
+pub trait RegisterShared {
+    /// Registers the agent in the current shared worker or service worker.
+    ///
+    /// See the [module level documentation](self).
+    fn register_shared(&self);
+}
+
+impl<R, CODEC> RegisterShared for ReactorRegistrar<R, CODEC>
+where
+    R: Reactor + 'static,
+    <R::Scope as ReactorScoped>::Input: Serialize + for<'de> Deserialize<'de>,
+    <R::Scope as ReactorScoped>::Output: Serialize + for<'de> Deserialize<'de>,
+    CODEC: Codec + 'static,
+{
+    fn register_shared(&self) {
+        host::serve::<CODEC, _, _>(Box::new(sessions::open_reactor::<R>));
+    }
+}
+
+/// Registers an oneshot agent in the current shared worker or service worker.
+pub(crate) fn serve_oneshot<T, CODEC>()
+where
+    T: Oneshot + 'static,
+    T::Input: Serialize + for<'de> Deserialize<'de>,
+    T::Output: Serialize + for<'de> Deserialize<'de>,
+    CODEC: Codec + 'static,
+{
+    host::serve::<CODEC, _, _>(Box::new(sessions::open_oneshot::<T>));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    use futures::channel::oneshot;
+    use futures::future::{self, LocalBoxFuture};
+    use futures::{FutureExt, SinkExt, StreamExt};
+    use yew::platform::time::sleep;
+    use yew::platform::Runtime;
+
+    use super::host::Router;
+    use super::messages::FromHost;
+    use super::*;
+    use crate::reactor::ReactorScope;
+
+    /// Counts up to each input, and exits after its second input.
+    struct Count(LocalBoxFuture<'static, ()>);
+
+    impl Future for Count {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            self.0.poll_unpin(cx)
+        }
+    }
+
+    impl Reactor for Count {
+        type Scope = ReactorScope<u32, u32>;
+
+        fn create(scope: Self::Scope) -> Self {
+            Self(
+                async move {
+                    let (mut outputs, inputs) = scope.split();
+                    let mut inputs = inputs.take(2);
+                    while let Some(n) = inputs.next().await {
+                        for i in 0..n {
+                            let _ = outputs.send(i).await;
+                        }
+                    }
+                }
+                .boxed_local(),
+            )
+        }
+    }
+
+    thread_local! {
+        static DROPPED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Doubles its input, or never finishes if its input is 0.
+    struct Double(LocalBoxFuture<'static, u32>, Dropped);
+
+    struct Dropped;
+
+    impl Drop for Dropped {
+        fn drop(&mut self) {
+            DROPPED.with(|m| m.set(m.get() + 1));
+        }
+    }
+
+    impl Future for Double {
+        type Output = u32;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+            self.0.poll_unpin(cx)
+        }
+    }
+
+    impl Oneshot for Double {
+        type Input = u32;
+
+        fn create(input: u32) -> Self {
+            let run = match input {
+                0 => future::pending().boxed_local(),
+                n => async move { n * 2 }.boxed_local(),
+            };
+
+            Self(run, Dropped)
+        }
+    }
+
+    // Receives the messages of the router, once the bridge is connected.
+    type Receiver<O> = RefCell<Option<Box<PostFn<FromHost<O>>>>>;
+
+    /// Connects a bridge to `router`, as if through a port.
+    fn connect<I, O>(router: Router<I, O>) -> PortBridge<I, O>
+    where
+        I: 'static,
+        O: 'static,
+    {
+        let receive: Rc<Receiver<O>> = Rc::default();
+        let post: Rc<PostFn<FromHost<O>>> = {
+            let receive = receive.clone();
+            Rc::new(move |m| {
+                if let Some(ref f) = *receive.borrow() {
+                    f(m);
+                }
+            })
+        };
+
+        let (bridge, f) = client::tests::connection(move |m| router.receive(0, &post, m));
+        *receive.borrow_mut() = Some(Box::new(f));
+
+        bridge
+    }
+
+    /// Runs `f` on the local runtime and returns its output.
+    fn run<F, O>(f: impl FnOnce() -> F + Send + 'static) -> O
+    where
+        F: Future<Output = O> + 'static,
+        O: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        Runtime::default().spawn_pinned(move || async move {
+            let _ = tx.send(f().await);
+        });
+
+        futures::executor::block_on(rx).unwrap()
+    }
+
+    #[test]
+    fn bridges_have_their_own_reactor() {
+        let outputs = run(|| async {
+            let bridge = connect(Router::new(Box::new(sessions::open_reactor::<Count>)));
+            let fork = bridge.fork();
+
+            bridge.send_input(2);
+            bridge.send_input(1);
+            fork.send_input(3);
+            fork.send_input(0);
+
+            future::join(bridge.collect::<Vec<_>>(), fork.collect::<Vec<_>>()).await
+        });
+
+        // The reactors exit after their second input, which finishes their bridge.
+        assert_eq!(outputs, (vec![0, 1, 0], vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn runs_are_answered_and_cancelled_with_their_bridge() {
+        let (output, dropped) = run(|| async {
+            let mut bridge = connect(Router::new(Box::new(sessions::open_oneshot::<Double>)));
+            let fork = bridge.fork();
+
+            bridge.send_input(2);
+            let output = bridge.next().await;
+
+            fork.send_input(0);
+            sleep(Duration::ZERO).await;
+            let before = DROPPED.with(|m| m.get());
+            drop(fork);
+            sleep(Duration::ZERO).await;
+
+            (output, DROPPED.with(|m| m.get()) - before)
+        });
+
+        assert_eq!(output, Some(4));
+        // The pending run of the fork is dropped with it.
+        assert_eq!(dropped, 1);
+    }
+}
@@ -0,0 +1,106 @@
+//! The sessions of shared reactor and oneshot agents.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use futures::channel::mpsc;
+use futures::sink;
+use yew::platform::spawn_local;
+
+use super::host::{Responder, Session};
+use crate::oneshot::{Oneshot, Run};
+use crate::reactor::{Reactor, ReactorScoped};
+
+type Input<R> = <<R as Reactor>::Scope as ReactorScoped>::Input;
+type Output<R> = <<R as Reactor>::Scope as ReactorScoped>::Output;
+
+/// A bridge to a reactor, which closes the inputs of the reactor when it is closed.
+struct ReactorSession<I> {
+    inputs: mpsc::UnboundedSender<I>,
+}
+
+impl<I> Session<I> for ReactorSession<I> {
+    fn received(&mut self, input: I) {
+        let _ = self.inputs.unbounded_send(input);
+    }
+}
+
+/// Creates a reactor for a bridge.
+pub(crate) fn open_reactor<R>(responder: Responder<Output<R>>) -> Box<dyn Session<Input<R>>>
+where
+    R: Reactor + 'static,
+{
+    let (tx, inputs) = mpsc::unbounded();
+    let outputs = sink::unfold(responder.clone(), |responder, output| {
+        responder.respond(output);
+        async move { Ok::<_, Infallible>(responder) }
+    });
+
+    let reactor = R::create(R::Scope::new(inputs, outputs));
+    spawn_local(async move {
+        reactor.await;
+        responder.finish();
+    });
+
+    Box::new(ReactorSession { inputs: tx })
+}
+
+/// A bridge to an oneshot agent, which cancels its runs when it is closed.
+struct OneshotSession<T>
+where
+    T: Oneshot,
+{
+    responder: Responder<T::Output>,
+    next_run: usize,
+    runs: Rc<RefCell<HashMap<usize, Run>>>,
+}
+
+impl<T> Session<T::Input> for OneshotSession<T>
+where
+    T: Oneshot + 'static,
+{
+    fn received(&mut self, input: T::Input) {
+        let run_id = self.next_run;
+        self.next_run += 1;
+
+        let (run, output) = Run::start::<T>(input);
+        self.runs.borrow_mut().insert(run_id, run);
+
+        let runs = self.runs.clone();
+        let responder = self.responder.clone();
+        spawn_local(async move {
+            let output = output.await;
+            let run = runs.borrow_mut().remove(&run_id);
+
+            // Outputs of cancelled runs have no bridge to go to.
+            if let (Some(output), Some(false)) = (output, run.map(|m| m.is_cancelled())) {
+                responder.respond(output);
+            }
+        });
+    }
+}
+
+impl<T> Drop for OneshotSession<T>
+where
+    T: Oneshot,
+{
+    fn drop(&mut self) {
+        for run in self.runs.borrow().values() {
+            run.cancel();
+        }
+    }
+}
+
+/// Creates a session running an oneshot agent for each input of a bridge.
+pub(crate) fn open_oneshot<T>(responder: Responder<T::Output>) -> Box<dyn Session<T::Input>>
+where
+    T: Oneshot + 'static,
+{
+    Box::new(OneshotSession::<T> {
+        responder,
+        next_run: 0,
+        runs: Rc::default(),
+    })
+}
@@ -0,0 +1,367 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::Future;
+use std::rc::{Rc, Weak};
+
+use futures::channel::oneshot;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{ErrorEvent, EventTarget};
+use yew::Callback;
+
+use crate::dedicated::DedicatedWorker;
+
+/// How a provider responds when one of its workers crashes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RestartPolicy {
+    /// Crashed workers are not respawned.
+    #[default]
+    Never,
+    /// Crashed workers are respawned, at most `max_restarts` times over the lifetime of the
+    /// provider.
+    Limited {
+        /// The maximum number of restarts.
+        max_restarts: usize,
+    },
+    /// Crashed workers are always respawned.
+    Always,
+}
+
+/// The crash of a worker, reported by its provider.
+///
+/// A worker crashes when it panics or throws an uncaught error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentCrash {
+    /// The message of the error that crashed the worker.
+    pub message: String,
+    /// Whether the worker is respawned, as allowed by the [`RestartPolicy`] of the provider.
+    ///
+    /// Bridges created with hooks reconnect to the respawned worker.
+    pub restarted: bool,
+}
+
+impl fmt::Display for AgentCrash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "agent crashed: {}", self.message)
+    }
+}
+
+/// A function called when a worker crashes.
+pub(crate) type CrashWatcher = dyn Fn(&AgentCrash);
+
+/// A listener to the errors of a worker, removed when dropped.
+struct ErrorListener {
+    target: EventTarget,
+    closure: Closure<dyn Fn(ErrorEvent)>,
+}
+
+impl Drop for ErrorListener {
+    fn drop(&mut self) {
+        let _ = self
+            .target
+            .remove_event_listener_with_callback("error", self.closure.as_ref().unchecked_ref());
+    }
+}
+
+#[derive(Default)]
+struct WorkerStatus {
+    crash: RefCell<Option<AgentCrash>>,
+    watchers: RefCell<Vec<Weak<CrashWatcher>>>,
+    // Kept until the last bridge to the worker is dropped.
+    listener: RefCell<Option<ErrorListener>>,
+}
+
+/// The status of a worker spawned by a [`Supervisor`], shared by all bridges to the worker.
+#[derive(Clone, Default)]
+pub(crate) struct WorkerHandle {
+    inner: Rc<WorkerStatus>,
+}
+
+impl WorkerHandle {
+    /// Returns the crash of the worker, if it has crashed.
+    pub fn crash(&self) -> Option<AgentCrash> {
+        self.inner.crash.borrow().clone()
+    }
+
+    /// Returns `true` if the worker has crashed and should be replaced by a new worker.
+    pub fn should_respawn(&self) -> bool {
+        self.inner
+            .crash
+            .borrow()
+            .as_ref()
+            .map_or(false, |m| m.restarted)
+    }
+
+    /// Calls `f` when the worker crashes, unless `f` has been dropped.
+    pub fn watch(&self, f: &Rc<CrashWatcher>) {
+        let mut watchers = self.inner.watchers.borrow_mut();
+
+        watchers.retain(|m| m.strong_count() > 0);
+        watchers.push(Rc::downgrade(f));
+    }
+
+    /// Waits for the worker to crash.
+    ///
+    /// Resolves immediately if the worker has already crashed.
+    pub fn crashed(&self) -> impl Future<Output = AgentCrash> + 'static {
+        let (tx, rx) = oneshot::channel();
+        let tx = Cell::new(Some(tx));
+        let f: Rc<CrashWatcher> = Rc::new(move |crash: &AgentCrash| {
+            if let Some(tx) = tx.take() {
+                let _ = tx.send(crash.clone());
+            }
+        });
+
+        match self.crash() {
+            Some(crash) => f(&crash),
+            None => self.watch(&f),
+        }
+
+        async move {
+            // The sender is kept alive by the watcher.
+            let _f = f;
+            rx.await.expect("the crash watcher was dropped")
+        }
+    }
+}
+
+/// A bridge to a worker spawned by a [`Supervisor`].
+pub(crate) struct Supervised<B> {
+    pub bridge: B,
+    pub worker: WorkerHandle,
+}
+
+impl<B> Supervised<B> {
+    /// Creates a bridge to the same worker with `f`.
+    pub fn map<O>(&self, f: impl FnOnce(&B) -> O) -> Supervised<O> {
+        Supervised {
+            bridge: f(&self.bridge),
+            worker: self.worker.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct SupervisorInner {
+    // Whether the objects listed in the inputs of workers are transferred.
+    transfer: bool,
+    policy: Cell<RestartPolicy>,
+    restarts: Cell<usize>,
+    on_crash: RefCell<Option<Callback<AgentCrash>>>,
+}
+
+/// Spawns the workers of a provider and applies its [`RestartPolicy`] when they crash.
+#[derive(Clone, Default)]
+pub(crate) struct Supervisor {
+    inner: Rc<SupervisorInner>,
+}
+
+impl Supervisor {
+    /// Creates a supervisor, which makes the workers it spawns transfer the
+    /// [`Transfer`](crate::transfer::Transfer) fields of their inputs if `transfer` is set.
+    pub fn new(transfer: bool) -> Self {
+        Self {
+            inner: Rc::new(SupervisorInner {
+                transfer,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Updates the restart policy and crash callback of the provider.
+    pub fn configure(&self, policy: RestartPolicy, on_crash: Option<Callback<AgentCrash>>) {
+        self.inner.policy.set(policy);
+        *self.inner.on_crash.borrow_mut() = on_crash;
+    }
+
+    /// Spawns a worker running the agent at `path` and connects to it with `connect`, watching
+    /// the worker for crashes.
+    pub fn spawn<B>(
+        &self,
+        path: &str,
+        connect: impl FnOnce(&DedicatedWorker) -> B,
+    ) -> Supervised<B> {
+        let spawned = DedicatedWorker::spawn(path, self.inner.transfer);
+        let worker = WorkerHandle::default();
+        self.listen(&spawned.worker, &worker);
+
+        Supervised {
+            bridge: connect(&spawned),
+            worker,
+        }
+    }
+
+    /// Watches a bridge for crashes, which are reported as errors of `target`.
+    pub fn supervise<B>(&self, bridge: B, target: &EventTarget) -> Supervised<B> {
+        let worker = WorkerHandle::default();
+        self.listen(target, &worker);
+
+        Supervised { bridge, worker }
+    }
+
+    fn listen(&self, target: &EventTarget, worker: &WorkerHandle) {
+        // Neither is kept alive by the worker.
+        let supervisor = Rc::downgrade(&self.inner);
+        let status = Rc::downgrade(&worker.inner);
+
+        let closure = Closure::<dyn Fn(ErrorEvent)>::new(move |e: ErrorEvent| {
+            if let (Some(inner), Some(status)) = (supervisor.upgrade(), status.upgrade()) {
+                Supervisor { inner }.crashed(&WorkerHandle { inner: status }, e.message());
+            }
+        });
+
+        target
+            .add_event_listener_with_callback("error", closure.as_ref().unchecked_ref())
+            .expect_throw("failed to listen to worker errors");
+
+        *worker.inner.listener.borrow_mut() = Some(ErrorListener {
+            target: target.clone(),
+            closure,
+        });
+    }
+
+    /// Records the crash of `worker`, and whether the restart policy allows respawning it.
+    pub fn crashed(&self, worker: &WorkerHandle, message: String) {
+        if worker.crash().is_some() {
+            return;
+        }
+
+        let restarts = self.inner.restarts.get();
+        let restarted = match self.inner.policy.get() {
+            RestartPolicy::Never => false,
+            RestartPolicy::Limited { max_restarts } => restarts < max_restarts,
+            RestartPolicy::Always => true,
+        };
+        if restarted {
+            self.inner.restarts.set(restarts + 1);
+        }
+
+        let crash = AgentCrash { message, restarted };
+        *worker.inner.crash.borrow_mut() = Some(crash.clone());
+
+        let on_crash = self.inner.on_crash.borrow().clone();
+        if let Some(m) = on_crash {
+            m.emit(crash.clone());
+        }
+
+        let watchers = worker
+            .inner
+            .watchers
+            .take()
+            .into_iter()
+            .filter_map(|m| m.upgrade())
+            .collect::<Vec<_>>();
+        for f in watchers {
+            f(&crash);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use futures::FutureExt;
+
+    use super::*;
+
+    fn watch(worker: &WorkerHandle) -> (Rc<CrashWatcher>, Rc<RefCell<Vec<AgentCrash>>>) {
+        let crashes = Rc::new(RefCell::new(Vec::new()));
+        let f: Rc<CrashWatcher> = {
+            let crashes = crashes.clone();
+            Rc::new(move |m: &AgentCrash| crashes.borrow_mut().push(m.clone()))
+        };
+        worker.watch(&f);
+
+        (f, crashes)
+    }
+
+    #[test]
+    fn crashes_are_reported_once() {
+        let supervisor = Supervisor::default();
+        let worker = WorkerHandle::default();
+        let (_f, crashes) = watch(&worker);
+
+        supervisor.crashed(&worker, "unreachable".into());
+        supervisor.crashed(&worker, "unreachable".into());
+
+        assert_eq!(
+            *crashes.borrow(),
+            vec![AgentCrash {
+                message: "unreachable".into(),
+                restarted: false,
+            }]
+        );
+        assert!(worker.crash().is_some());
+        assert!(!worker.should_respawn());
+    }
+
+    #[test]
+    fn restarts_are_limited_by_policy() {
+        let supervisor = Supervisor::default();
+        supervisor.configure(RestartPolicy::Limited { max_restarts: 1 }, None);
+
+        let first = WorkerHandle::default();
+        supervisor.crashed(&first, "first".into());
+        assert!(first.should_respawn());
+
+        let second = WorkerHandle::default();
+        supervisor.crashed(&second, "second".into());
+        assert!(!second.should_respawn());
+    }
+
+    #[test]
+    fn dropped_watchers_are_not_called() {
+        let supervisor = Supervisor::default();
+        let worker = WorkerHandle::default();
+        let (f, crashes) = watch(&worker);
+        drop(f);
+
+        supervisor.crashed(&worker, "unreachable".into());
+        assert!(crashes.borrow().is_empty());
+    }
+
+    #[test]
+    fn crashed_resolves_on_crash() {
+        let supervisor = Supervisor::default();
+        let worker = WorkerHandle::default();
+
+        let mut before = Box::pin(worker.crashed());
+        assert!((&mut before).now_or_never().is_none());
+        supervisor.crashed(&worker, "unreachable".into());
+
+        assert_eq!(block_on(before).message, "unreachable");
+        // The worker has already crashed.
+        assert_eq!(block_on(worker.crashed()).message, "unreachable");
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn errors_are_listened_to_until_bridges_are_dropped() {
+        let crashes = Rc::new(RefCell::new(Vec::new()));
+        let supervisor = Supervisor::default();
+        supervisor.configure(RestartPolicy::Never, {
+            let crashes = crashes.clone();
+            Some(Callback::from(move |m: AgentCrash| {
+                crashes.borrow_mut().push(m.message)
+            }))
+        });
+
+        // `ErrorEvent` can not be constructed outside of browsers.
+        let error = || {
+            let event = web_sys::Event::new("error").unwrap();
+            js_sys::Reflect::set(&event, &"message".into(), &"unreachable".into()).unwrap();
+            event
+        };
+
+        let target = EventTarget::new().unwrap();
+        let supervised = supervisor.supervise((), &target);
+        target.dispatch_event(&error()).unwrap();
+        assert_eq!(*crashes.borrow(), vec!["unreachable".to_string()]);
+
+        drop(supervised);
+        let target = EventTarget::new().unwrap();
+        drop(supervisor.supervise((), &target));
+        target.dispatch_event(&error()).unwrap();
+        assert_eq!(crashes.borrow().len(), 1);
+    }
+}
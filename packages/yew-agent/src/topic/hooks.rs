@@ -0,0 +1,124 @@
+use std::any::type_name;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use super::{last, publish, subscribe, Topic};
+
+struct TopicState<T>
+where
+    T: Topic,
+{
+    value: Option<Rc<T::Value>>,
+}
+
+impl<T> Reducible for TopicState<T>
+where
+    T: Topic,
+{
+    type Action = Rc<T::Value>;
+
+    fn reduce(self: Rc<Self>, value: Self::Action) -> Rc<Self> {
+        match self.value {
+            // The last value is replayed when subscribing.
+            Some(ref m) if Rc::ptr_eq(m, &value) => self,
+            _ => Self { value: Some(value) }.into(),
+        }
+    }
+}
+
+/// Hook handle for the [`use_agent_topic`] hook.
+pub struct UseAgentTopicHandle<T>
+where
+    T: Topic,
+{
+    value: Option<Rc<T::Value>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> UseAgentTopicHandle<T>
+where
+    T: Topic,
+{
+    /// Publishes a value to all subscribers of the topic.
+    pub fn publish(&self, value: T::Value) {
+        publish::<T>(value);
+    }
+}
+
+impl<T> Clone for UseAgentTopicHandle<T>
+where
+    T: Topic,
+{
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for UseAgentTopicHandle<T>
+where
+    T: Topic,
+    T::Value: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T> Deref for UseAgentTopicHandle<T>
+where
+    T: Topic,
+{
+    type Target = Option<Rc<T::Value>>;
+
+    fn deref(&self) -> &Option<Rc<T::Value>> {
+        &self.value
+    }
+}
+
+impl<T> PartialEq for UseAgentTopicHandle<T>
+where
+    T: Topic,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        match (&self.value, &rhs.value) {
+            (Some(lhs), Some(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+/// A hook to subscribe to a [`Topic`].
+///
+/// The handle holds the last value published to the topic, and re-renders the component when a
+/// new value is published.
+#[hook]
+pub fn use_agent_topic<T>() -> UseAgentTopicHandle<T>
+where
+    T: Topic,
+{
+    let state = use_reducer(|| TopicState::<T> { value: last::<T>() });
+
+    {
+        let dispatcher = state.dispatcher();
+        use_effect_with((), move |_| {
+            let subscription = subscribe::<T, _>(move |m| dispatcher.dispatch(m));
+
+            move || drop(subscription)
+        });
+    }
+
+    UseAgentTopicHandle {
+        value: state.value.clone(),
+        _marker: PhantomData,
+    }
+}
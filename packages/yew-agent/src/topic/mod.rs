@@ -0,0 +1,395 @@
+//! This module contains typed topics, broadcasting values to all subscribers.
+//!
+//! Values published to a topic are received by all subscribers of the topic, in all components,
+//! agents and tabs of an origin. Subscribers receive the last value published to a topic when
+//! they subscribe, so they do not miss values published before they were created.
+//!
+//! Topics are broadcast with a
+//! [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel), and
+//! their values are encoded with [`Bincode`](crate::Bincode). Outside of the browser, values are
+//! only received by subscribers on the same thread.
+//!
+//! # Example
+//!
+//! ```
+//! # mod example {
+//! use serde::{Deserialize, Serialize};
+//! use yew::prelude::*;
+//! use yew_agent::topic::{use_agent_topic, Topic};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! pub struct Price {
+//!     pub symbol: String,
+//!     pub cents: u64,
+//! }
+//!
+//! pub struct Prices;
+//!
+//! impl Topic for Prices {
+//!     type Value = Price;
+//!
+//!     const NAME: &'static str = "prices";
+//! }
+//!
+//! #[function_component]
+//! fn Ticker() -> Html {
+//!     let price = use_agent_topic::<Prices>();
+//!
+//!     match price.as_ref() {
+//!         Some(m) => html! { <p>{&m.symbol}{": "}{m.cents}</p> },
+//!         None => html! { <p>{"Waiting for prices..."}</p> },
+//!     }
+//! }
+//!
+//! // in an agent
+//! fn on_trade(symbol: String, cents: u64) {
+//!     yew_agent::topic::publish::<Prices>(Price { symbol, cents });
+//! }
+//! # }
+//! ```
+
+mod hooks;
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+pub use hooks::{use_agent_topic, UseAgentTopicHandle};
+use serde::{Deserialize, Serialize};
+
+thread_local! {
+    // The channels of the topics used on the current thread, by the type id of their topic.
+    static CHANNELS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::default();
+}
+
+/// A typed topic.
+pub trait Topic: 'static {
+    /// The values published to the topic.
+    type Value: Serialize + for<'de> Deserialize<'de> + 'static;
+
+    /// The name of the topic.
+    ///
+    /// Topics with the same name share their values, so names must be unique to an origin.
+    const NAME: &'static str;
+}
+
+/// A message sent to the other threads subscribed to a topic.
+#[derive(Serialize, Deserialize)]
+enum TopicMessage<V> {
+    /// A value, with the time it was published.
+    Value { at: f64, value: V },
+    /// Requests the last value of the topic from other threads.
+    Sync,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<V> TopicMessage<V>
+where
+    V: for<'de> Deserialize<'de>,
+{
+    /// Decodes a message received from the broadcast channel.
+    ///
+    /// Returns `None` for messages that are not [`Bincode`](crate::Bincode) encoded messages of
+    /// the topic, like those posted by other versions of the application.
+    fn decode(data: wasm_bindgen::JsValue) -> Option<Self> {
+        use wasm_bindgen::JsCast;
+
+        let data = data.dyn_into::<js_sys::Uint8Array>().ok()?;
+        bincode::deserialize(&data.to_vec()).ok()
+    }
+}
+
+type Subscriber<T> = Rc<dyn Fn(Rc<<T as Topic>::Value>)>;
+
+struct Channel<T>
+where
+    T: Topic,
+{
+    last: Option<(f64, Rc<T::Value>)>,
+    subscribers: Vec<(usize, Subscriber<T>)>,
+    next_id: usize,
+    #[cfg(target_arch = "wasm32")]
+    broadcast: web_sys::BroadcastChannel,
+}
+
+impl<T> Channel<T>
+where
+    T: Topic,
+{
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open() -> Self {
+        Self {
+            last: None,
+            subscribers: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn open() -> Self {
+        use wasm_bindgen::prelude::*;
+        use wasm_bindgen::JsCast;
+
+        use crate::{Bincode, Codec};
+
+        let broadcast = web_sys::BroadcastChannel::new(&format!("yew-agent:{}", T::NAME))
+            .expect_throw("failed to open a broadcast channel");
+
+        let on_message =
+            Closure::<dyn Fn(web_sys::MessageEvent)>::new(|e: web_sys::MessageEvent| {
+                // Messages that can not be decoded are ignored.
+                match TopicMessage::<T::Value>::decode(e.data()) {
+                    Some(TopicMessage::Value { at, value }) => receive::<T>(at, Rc::new(value)),
+                    Some(TopicMessage::Sync) => {
+                        if let Some((at, value)) = with_channel::<T, _>(|m| m.last.clone()) {
+                            post::<T>(TopicMessage::Value { at, value: &*value });
+                        }
+                    }
+                    None => {}
+                }
+            })
+            .into_js_value();
+        broadcast.set_onmessage(Some(on_message.unchecked_ref()));
+
+        // Threads holding a value reply with it, so late subscribers receive it.
+        let sync = Bincode::encode(TopicMessage::<()>::Sync);
+        broadcast
+            .post_message(&sync)
+            .expect_throw("failed to post a message to a broadcast channel");
+
+        Self {
+            last: None,
+            subscribers: Vec::new(),
+            next_id: 0,
+            broadcast,
+        }
+    }
+}
+
+fn with_channel<T, O>(f: impl FnOnce(&mut Channel<T>) -> O) -> O
+where
+    T: Topic,
+{
+    // The channel is opened outside of the borrow, as its messages may be received synchronously.
+    let exists = CHANNELS.with(|m| m.borrow().contains_key(&TypeId::of::<T>()));
+    if !exists {
+        let channel = Channel::<T>::open();
+        CHANNELS.with(|m| {
+            m.borrow_mut()
+                .entry(TypeId::of::<T>())
+                .or_insert_with(|| Box::new(channel));
+        });
+    }
+
+    CHANNELS.with(|m| {
+        let mut m = m.borrow_mut();
+        let channel = m
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|m| m.downcast_mut::<Channel<T>>())
+            .expect("failed to find the channel of a topic");
+
+        f(channel)
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+fn post<T>(message: TopicMessage<&T::Value>)
+where
+    T: Topic,
+{
+    use wasm_bindgen::UnwrapThrowExt;
+
+    use crate::{Bincode, Codec};
+
+    let message = Bincode::encode(message);
+    with_channel::<T, _>(|m| {
+        m.broadcast
+            .post_message(&message)
+            .expect_throw("failed to post a message to a broadcast channel")
+    });
+}
+
+/// Outside of the browser, topics are local to their thread.
+#[cfg(not(target_arch = "wasm32"))]
+fn post<T>(_message: TopicMessage<&T::Value>)
+where
+    T: Topic,
+{
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> f64 {
+    0.0
+}
+
+/// Stores a value received from another thread and notifies subscribers, unless a newer value
+/// has already been received.
+#[cfg(target_arch = "wasm32")]
+fn receive<T>(at: f64, value: Rc<T::Value>)
+where
+    T: Topic,
+{
+    let subscribers = with_channel::<T, _>(|m| match m.last {
+        Some((last_at, _)) if last_at >= at => None,
+        _ => {
+            m.last = Some((at, value.clone()));
+            Some(m.subscribers.clone())
+        }
+    });
+
+    for (_, subscriber) in subscribers.into_iter().flatten() {
+        subscriber(value.clone());
+    }
+}
+
+/// Publishes a value to all subscribers of topic `T`.
+pub fn publish<T>(value: T::Value)
+where
+    T: Topic,
+{
+    let value = Rc::new(value);
+    let (at, subscribers) = with_channel::<T, _>(|m| {
+        // Values published on this thread are newer than the ones it has received.
+        let at = match m.last {
+            Some((last_at, _)) => now().max(last_at),
+            None => now(),
+        };
+        m.last = Some((at, value.clone()));
+
+        (at, m.subscribers.clone())
+    });
+
+    post::<T>(TopicMessage::Value { at, value: &*value });
+
+    for (_, subscriber) in subscribers {
+        subscriber(value.clone());
+    }
+}
+
+/// Returns the last value published to topic `T`, if any.
+pub fn last<T>() -> Option<Rc<T::Value>>
+where
+    T: Topic,
+{
+    with_channel::<T, _>(|m| m.last.as_ref().map(|(_, value)| value.clone()))
+}
+
+/// Subscribes to topic `T`.
+///
+/// `f` is called with the last value of the topic, if any, and with each value published
+/// afterwards. The subscription ends when the returned [`TopicSubscription`] is dropped.
+pub fn subscribe<T, F>(f: F) -> TopicSubscription
+where
+    T: Topic,
+    F: Fn(Rc<T::Value>) + 'static,
+{
+    let f: Subscriber<T> = Rc::new(f);
+    let (id, last) = with_channel::<T, _>(|m| {
+        let id = m.next_id;
+        m.next_id += 1;
+        m.subscribers.push((id, f.clone()));
+
+        (id, m.last.as_ref().map(|(_, value)| value.clone()))
+    });
+
+    if let Some(m) = last {
+        f(m);
+    }
+
+    TopicSubscription {
+        unsubscribe: Some(Box::new(move || {
+            with_channel::<T, _>(|m| m.subscribers.retain(|(m, _)| *m != id));
+        })),
+    }
+}
+
+/// A subscription to a topic, see [`subscribe`].
+///
+/// The subscription ends when it is dropped.
+pub struct TopicSubscription {
+    unsubscribe: Option<Box<dyn FnOnce()>>,
+}
+
+impl fmt::Debug for TopicSubscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TopicSubscription")
+    }
+}
+
+impl Drop for TopicSubscription {
+    fn drop(&mut self) {
+        if let Some(m) = self.unsubscribe.take() {
+            m();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    struct Counter;
+
+    impl Topic for Counter {
+        type Value = u32;
+
+        const NAME: &'static str = "counter";
+    }
+
+    #[test]
+    fn late_subscribers_receive_the_last_value() {
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        let early = {
+            let received = received.clone();
+            subscribe::<Counter, _>(move |m| received.borrow_mut().push(("early", *m)))
+        };
+        publish::<Counter>(1);
+        publish::<Counter>(2);
+
+        let late = {
+            let received = received.clone();
+            subscribe::<Counter, _>(move |m| received.borrow_mut().push(("late", *m)))
+        };
+        drop(early);
+        publish::<Counter>(3);
+        drop(late);
+        publish::<Counter>(4);
+
+        assert_eq!(
+            *received.borrow(),
+            [("early", 1), ("early", 2), ("late", 2), ("late", 3)]
+        );
+        assert_eq!(last::<Counter>().as_deref(), Some(&4));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn undecodable_messages_are_ignored() {
+        use crate::{Bincode, Codec};
+
+        let value = Bincode::encode(TopicMessage::Value {
+            at: 1.0,
+            value: 2u32,
+        });
+        assert!(matches!(
+            TopicMessage::<u32>::decode(value),
+            Some(TopicMessage::Value { value: 2, .. })
+        ));
+
+        assert!(TopicMessage::<u32>::decode(wasm_bindgen::JsValue::from_str("value")).is_none());
+        assert!(TopicMessage::<u32>::decode(Bincode::encode(7u8)).is_none());
+        assert!(
+            TopicMessage::<String>::decode(Bincode::encode(TopicMessage::<()>::Sync)).is_some()
+        );
+    }
+}
@@ -0,0 +1,490 @@
+//! This module contains the [`StructuredClone`] codec, which passes JavaScript values to and
+//! from agents without serializing them.
+//!
+//! Messages encoded with [`Bincode`](crate::Bincode) are copied into a byte buffer. With
+//! [`StructuredClone`], messages are converted to JavaScript values, and fields annotated with
+//! [`preserve`] are passed as they are. Fields wrapped in [`Transfer`] are transferred to the
+//! receiving side instead of being copied, which makes passing large buffers free.
+//!
+//! Both the provider and the registrar of an agent must use the same codec.
+//!
+//! Transferring is opt-in on both sides: a provider transfers the inputs of its workers if its
+//! [`transfer`](crate::worker::WorkerProviderProps::transfer) prop is set, and a worker transfers
+//! its outputs once [`transfer_outputs`] has been called. Otherwise, `Transfer` fields are copied
+//! like fields annotated with [`preserve`].
+//!
+//! # Example
+//!
+//! ```
+//! use js_sys::{Uint8Array, Uint8ClampedArray};
+//! use serde::{Deserialize, Serialize};
+//! use yew_agent::transfer::{preserve, Transfer};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! pub struct Frame {
+//!     pub width: u32,
+//!     // Moved to the worker, so it can no longer be used by the sender.
+//!     pub pixels: Transfer<Uint8ClampedArray>,
+//!     // Cloned by the browser, without serialization.
+//!     #[serde(with = "preserve")]
+//!     pub palette: Uint8Array,
+//! }
+//! ```
+//!
+//! ```ignore
+//! // in the application
+//! <OneshotProvider<Blur, StructuredClone> path="/worker.js" transfer=true>
+//!
+//! // in the worker
+//! yew_agent::transfer::transfer_outputs();
+//! Blur::registrar().encoding::<StructuredClone>().register();
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use js_sys::{Array, Function, Object, Reflect};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::MessagePort;
+
+use crate::worker::persist::SnapshotCodec;
+use crate::Codec;
+
+/// Serializes and deserializes a field holding a JavaScript value as it is, when the message is
+/// encoded with [`StructuredClone`].
+///
+/// Use it with `#[serde(with = "preserve")]`.
+#[doc(inline)]
+pub use serde_wasm_bindgen::preserve;
+
+thread_local! {
+    // The objects to transfer with the message being encoded, `None` when no message is encoded.
+    static ENCODING: RefCell<Option<Array>> = const { RefCell::new(None) };
+    // Whether the current worker transfers its outputs.
+    static TRANSFER_OUTPUTS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The key of the transfer list in the envelope of messages with objects to transfer.
+const ENVELOPE_TRANSFER: &str = "__yewAgentTransfer";
+/// The key of the message in the envelope of messages with objects to transfer.
+const ENVELOPE_MESSAGE: &str = "message";
+
+/// A codec that converts messages to JavaScript values, which are passed to agents with the
+/// structured clone algorithm of the browser.
+///
+/// See the [module level documentation](self).
+#[derive(Debug)]
+pub struct StructuredClone;
+
+impl Codec for StructuredClone {
+    fn encode<I>(input: I) -> JsValue
+    where
+        I: Serialize,
+    {
+        let prev = ENCODING.with(|m| m.replace(Some(Array::new())));
+
+        let serializer =
+            serde_wasm_bindgen::Serializer::new().serialize_large_number_types_as_bigints(true);
+        let output = input.serialize(&serializer);
+
+        let transfer = ENCODING
+            .with(|m| m.replace(prev))
+            .expect("transfer list taken while encoding");
+        let output = output.expect("can't serialize an worker message");
+
+        match transfer.length() {
+            0 => output,
+            // The transfer list travels with the message, for `postMessage` to pick it up.
+            _ => {
+                let envelope = Object::new();
+                Reflect::set(&envelope, &ENVELOPE_TRANSFER.into(), &transfer)
+                    .expect_throw("failed to create message envelope");
+                Reflect::set(&envelope, &ENVELOPE_MESSAGE.into(), &output)
+                    .expect_throw("failed to create message envelope");
+
+                envelope.into()
+            }
+        }
+    }
+
+    fn decode<O>(input: JsValue) -> O
+    where
+        O: for<'de> Deserialize<'de>,
+    {
+        serde_wasm_bindgen::from_value(open_envelope(input))
+            .expect("can't deserialize an worker message")
+    }
+}
+
+impl SnapshotCodec for StructuredClone {
+    fn try_decode<O>(input: JsValue) -> Option<O>
+    where
+        O: for<'de> Deserialize<'de>,
+    {
+        serde_wasm_bindgen::from_value(open_envelope(input)).ok()
+    }
+}
+
+/// Returns the message in the envelope of `input`, or `input` if it has no envelope.
+fn open_envelope(input: JsValue) -> JsValue {
+    match transfer_list(&input) {
+        Some(_) => Reflect::get(&input, &ENVELOPE_MESSAGE.into())
+            .expect_throw("failed to open message envelope"),
+        None => input,
+    }
+}
+
+/// Returns the objects to transfer with an encoded message, if it has any.
+pub(crate) fn transfer_list(message: &JsValue) -> Option<Array> {
+    if !message.is_object() {
+        return None;
+    }
+
+    Reflect::get(message, &ENVELOPE_TRANSFER.into())
+        .ok()
+        .and_then(|m| m.dyn_into::<Array>().ok())
+}
+
+/// Posts an encoded message to `port`, transferring the objects it lists if `transfer` is set.
+pub(crate) fn post_to_port(
+    port: &MessagePort,
+    message: &JsValue,
+    transfer: bool,
+) -> Result<(), JsValue> {
+    match transfer.then(|| transfer_list(message)).flatten() {
+        Some(m) => port.post_message_with_transferable(message, &m),
+        None => port.post_message(message),
+    }
+}
+
+/// Makes `global` transfer the objects listed in the messages posted with its `postMessage`
+/// method.
+///
+/// gloo-worker posts the outputs of agents with the `postMessage` method of the global scope of
+/// their worker, without a transfer list, so the method of the global scope is wrapped. The
+/// wrapper is a closure rather than a JavaScript snippet, as snippets are not supported by the
+/// `no-modules` target workers are usually built for.
+fn transfer_posted_messages(global: &JsValue) {
+    let key = JsValue::from_str("postMessage");
+    let post = Reflect::get(global, &key)
+        .expect_throw("failed to read postMessage")
+        .dyn_into::<Function>()
+        .expect_throw("postMessage is not a function");
+
+    let wrapped = {
+        let global = global.clone();
+        Closure::<dyn Fn(JsValue, JsValue) -> Result<JsValue, JsValue>>::new(
+            move |message: JsValue, transfer: JsValue| {
+                let transfer = match (transfer.is_undefined(), transfer_list(&message)) {
+                    (true, Some(m)) => m.into(),
+                    _ => transfer,
+                };
+                post.call2(&global, &message, &transfer)
+            },
+        )
+    }
+    .into_js_value();
+
+    Reflect::set(global, &key, &wrapped).expect_throw("failed to wrap postMessage");
+}
+
+/// Makes the current worker transfer the [`Transfer`] fields of its outputs instead of copying
+/// them.
+///
+/// Call it in the worker, before registering agents encoded with [`StructuredClone`].
+pub fn transfer_outputs() {
+    TRANSFER_OUTPUTS.with(|m| m.set(true));
+
+    // Shared workers and service workers post to the ports of their tabs instead.
+    let global = js_sys::global();
+    if Reflect::has(&global, &JsValue::from_str("postMessage")).unwrap_or(false) {
+        transfer_posted_messages(&global);
+    }
+}
+
+/// Returns `true` if [`transfer_outputs`] has been called in the current worker.
+pub(crate) fn transfers_outputs() -> bool {
+    TRANSFER_OUTPUTS.with(|m| m.get())
+}
+
+/// A JavaScript object that can be transferred to or from an agent.
+///
+/// See [`Transfer`].
+pub trait Transferable: JsCast {
+    /// Returns the object to put in the transfer list.
+    fn transferable(&self) -> JsValue;
+}
+
+impl Transferable for js_sys::ArrayBuffer {
+    fn transferable(&self) -> JsValue {
+        self.into()
+    }
+}
+
+macro_rules! impl_transferable_typed_array {
+    ($($ty:ident),+ $(,)?) => {
+        $(
+            impl Transferable for js_sys::$ty {
+                fn transferable(&self) -> JsValue {
+                    self.buffer().into()
+                }
+            }
+        )+
+    };
+}
+
+impl_transferable_typed_array!(
+    Int8Array,
+    Uint8Array,
+    Uint8ClampedArray,
+    Int16Array,
+    Uint16Array,
+    Int32Array,
+    Uint32Array,
+    Float32Array,
+    Float64Array,
+    BigInt64Array,
+    BigUint64Array,
+);
+
+macro_rules! impl_transferable_object {
+    ($($ty:ident),+ $(,)?) => {
+        $(
+            impl Transferable for web_sys::$ty {
+                fn transferable(&self) -> JsValue {
+                    self.into()
+                }
+            }
+        )+
+    };
+}
+
+impl_transferable_object!(ImageBitmap, MessagePort, OffscreenCanvas);
+
+/// A field of a message that is transferred to the receiving side instead of being copied.
+///
+/// Transferring an object detaches it on the sending side, so it can no longer be used there.
+/// Typed arrays transfer their whole buffer, which must not be the memory of a WebAssembly
+/// module: copy views of Rust memory into a new array first, for example with
+/// [`Uint8Array::from`](js_sys::Uint8Array::from).
+///
+/// Messages containing a `Transfer` must be encoded with [`StructuredClone`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct Transfer<T>(pub T);
+
+impl<T> Transfer<T> {
+    /// Returns the wrapped object.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Transfer<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for Transfer<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Transfer<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> fmt::Debug for Transfer<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Transfer").field(&self.0).finish()
+    }
+}
+
+impl<T> Serialize for Transfer<T>
+where
+    T: Transferable,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ENCODING.with(|m| {
+            if let Some(transfer) = m.borrow().as_ref() {
+                // An object can only be listed once, even if several fields share it.
+                let transferable = self.0.transferable();
+                if !transfer.includes(&transferable, 0) {
+                    transfer.push(&transferable);
+                }
+            }
+        });
+
+        preserve::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Transfer<T>
+where
+    T: Transferable,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        preserve::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use std::cell::Cell;
+
+    use futures::channel::oneshot;
+    use js_sys::Uint8Array;
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+    use web_sys::{MessageChannel, MessageEvent};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Frame {
+        width: u32,
+        pixels: Transfer<Uint8Array>,
+        #[serde(with = "preserve")]
+        palette: Uint8Array,
+    }
+
+    fn frame() -> Frame {
+        Frame {
+            width: 2,
+            pixels: Transfer(Uint8Array::from(&[1, 2, 3, 4][..])),
+            palette: Uint8Array::from(&[5, 6][..]),
+        }
+    }
+
+    /// Posts `message` from the first port of `channel` and returns it as received by the second.
+    async fn post(channel: &MessageChannel, message: &JsValue, transfer: bool) -> JsValue {
+        let (tx, rx) = oneshot::channel();
+        let tx = Cell::new(Some(tx));
+        let on_message = Closure::<dyn Fn(MessageEvent)>::new(move |e: MessageEvent| {
+            if let Some(tx) = tx.take() {
+                let _ = tx.send(e.data());
+            }
+        });
+        channel
+            .port2()
+            .set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        post_to_port(&channel.port1(), message, transfer).unwrap();
+        let received = rx.await.unwrap();
+
+        channel.port1().close();
+        channel.port2().close();
+        received
+    }
+
+    #[test]
+    fn messages_without_transfers_are_not_wrapped() {
+        let message = StructuredClone::encode(vec![1u32, 2, 3]);
+
+        assert!(transfer_list(&message).is_none());
+        assert_eq!(StructuredClone::decode::<Vec<u32>>(message), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn transfers_are_listed_once() {
+        let frame = frame();
+        let message = StructuredClone::encode((&frame, &frame.pixels));
+        let transfer = transfer_list(&message).unwrap();
+
+        assert_eq!(transfer.length(), 1);
+        assert_eq!(transfer.get(0), JsValue::from(frame.pixels.buffer()));
+    }
+
+    #[test]
+    fn messages_roundtrip() {
+        let frame = StructuredClone::decode::<Frame>(StructuredClone::encode(frame()));
+
+        assert_eq!(frame.width, 2);
+        assert_eq!(frame.pixels.to_vec(), vec![1, 2, 3, 4]);
+        assert_eq!(frame.palette.to_vec(), vec![5, 6]);
+    }
+
+    #[test]
+    async fn messages_are_copied_by_default() {
+        let sent = frame();
+        let received = post(
+            &MessageChannel::new().unwrap(),
+            &StructuredClone::encode(&sent),
+            false,
+        )
+        .await;
+
+        assert_eq!(sent.pixels.length(), 4);
+        assert_eq!(
+            StructuredClone::decode::<Frame>(received).pixels.to_vec(),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    async fn messages_are_transferred_when_enabled() {
+        let sent = frame();
+        let received = post(
+            &MessageChannel::new().unwrap(),
+            &StructuredClone::encode(&sent),
+            true,
+        )
+        .await;
+
+        // Transferred objects are detached, preserved ones are copied.
+        assert_eq!(sent.pixels.length(), 0);
+        assert_eq!(sent.palette.length(), 2);
+        assert_eq!(
+            StructuredClone::decode::<Frame>(received).pixels.to_vec(),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    async fn messages_posted_by_the_worker_are_transferred() {
+        // A port stands in for the global scope of a worker.
+        let channel = MessageChannel::new().unwrap();
+        transfer_posted_messages(&channel.port1());
+
+        let sent = frame();
+        let message = StructuredClone::encode(&sent);
+        let (tx, rx) = oneshot::channel();
+        let tx = Cell::new(Some(tx));
+        let on_message = Closure::<dyn Fn(MessageEvent)>::new(move |e: MessageEvent| {
+            if let Some(tx) = tx.take() {
+                let _ = tx.send(e.data());
+            }
+        });
+        channel
+            .port2()
+            .set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        // Posted like gloo-worker does, without a transfer list.
+        channel.port1().post_message(&message).unwrap();
+        let received = rx.await.unwrap();
+        channel.port1().close();
+        channel.port2().close();
+
+        assert_eq!(sent.pixels.length(), 0);
+        assert_eq!(
+            StructuredClone::decode::<Frame>(received).pixels.to_vec(),
+            vec![1, 2, 3, 4]
+        );
+    }
+}
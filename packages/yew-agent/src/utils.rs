@@ -1,6 +1,7 @@
 use std::rc::Rc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+use serde::{Deserialize, Serialize};
 use yew::Reducible;
 
 /// Gets a unique worker id
@@ -10,6 +11,18 @@ pub(crate) fn get_next_id() -> usize {
     CTR.fetch_add(1, Ordering::SeqCst)
 }
 
+/// Serializes and deserializes a message, as if it was sent to or received from a worker.
+///
+/// Codecs encode to JavaScript values, which do not exist outside of the browser, so messages are
+/// serialized with bincode on all targets.
+pub(crate) fn roundtrip<T>(message: T) -> T
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    let message = bincode::serialize(&message).expect("can't serialize a local agent message");
+    bincode::deserialize(&message).expect("can't deserialize a local agent message")
+}
+
 #[derive(Default, PartialEq)]
 pub(crate) struct BridgeIdState {
     pub inner: usize,
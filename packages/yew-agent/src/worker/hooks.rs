@@ -6,16 +6,18 @@ use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use yew::prelude::*;
 
+use crate::supervisor::{CrashWatcher, Supervised};
 use crate::utils::{BridgeIdState, OutputsAction, OutputsState};
 use crate::worker::provider::WorkerProviderState;
-use crate::worker::{Worker, WorkerBridge};
+use crate::worker::{Worker, WorkerConnection};
+use crate::AgentCrash;
 
 /// Hook handle for the [`use_worker_bridge`] hook.
 pub struct UseWorkerBridgeHandle<T>
 where
     T: Worker,
 {
-    inner: WorkerBridge<T>,
+    inner: WorkerConnection<T>,
     ctr: UseReducerDispatcher<BridgeIdState>,
 }
 
@@ -98,15 +100,26 @@ where
         *on_output_ref = on_output;
     }
 
+    let reset = ctr.dispatcher();
     let bridge = use_memo((worker_state, ctr.inner), |(state, _ctr)| {
-        state.create_bridge(Callback::from(move |output| {
+        let Supervised { bridge, worker } = state.create_bridge(Callback::from(move |output| {
             let on_output = on_output_ref.borrow().clone();
             on_output(output);
-        }))
+        }));
+
+        // Reconnects to the respawned worker.
+        let on_crash: Rc<CrashWatcher> = Rc::new(move |crash: &AgentCrash| {
+            if crash.restarted {
+                reset.dispatch(());
+            }
+        });
+        worker.watch(&on_crash);
+
+        (bridge, on_crash)
     });
 
     UseWorkerBridgeHandle {
-        inner: (*bridge).clone(),
+        inner: bridge.0.clone(),
         ctr: ctr.dispatcher(),
     }
 }
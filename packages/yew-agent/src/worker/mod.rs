@@ -64,6 +64,7 @@
 //! ```
 
 mod hooks;
+pub mod persist;
 mod provider;
 
 #[doc(inline)]
@@ -73,5 +74,6 @@ pub use gloo_worker::{
 pub use hooks::{
     use_worker_bridge, use_worker_subscription, UseWorkerBridgeHandle, UseWorkerSubscriptionHandle,
 };
-pub(crate) use provider::WorkerProviderState;
+pub use persist::{Persist, PersistScopeExt, RegisterPersistent};
+pub(crate) use provider::{WorkerConnection, WorkerProviderState};
 pub use provider::{WorkerProvider, WorkerProviderProps};
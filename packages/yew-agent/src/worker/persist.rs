@@ -0,0 +1,638 @@
+//! Snapshots of the state of worker agents.
+//!
+//! Workers lose their state when their provider is unmounted or the page is reloaded. Agents
+//! implementing [`Persist`] save snapshots of their state to a [`SnapshotStorage`] and restore the
+//! last one when their worker is spawned again.
+//!
+//! Persistent agents are registered with [`RegisterPersistent::register_persistent`] instead of
+//! `register`, with the same codec as their provider. Snapshots are encoded with this codec, which
+//! must implement [`SnapshotCodec`]. The agent loads its snapshot before it is registered, so it is
+//! available when the agent is created. A snapshot that can not be decoded is removed, and the
+//! agent starts with a new state.
+//!
+//! # Example
+//!
+//! ```ignore
+//! impl Persist for Cache {
+//!     type State = HashMap<String, String>;
+//!
+//!     const KEY: &'static str = "cache-v1";
+//!
+//!     fn snapshot(&self) -> Self::State {
+//!         self.entries.clone()
+//!     }
+//! }
+//!
+//! impl Worker for Cache {
+//!     fn create(scope: &WorkerScope<Self>) -> Self {
+//!         Self {
+//!             entries: scope.restore().unwrap_or_default(),
+//!         }
+//!     }
+//!
+//!     fn received(&mut self, scope: &WorkerScope<Self>, msg: Self::Input, id: HandlerId) {
+//!         self.entries.insert(msg.key, msg.value);
+//!         scope.persist(self);
+//!     }
+//!
+//!     fn destroy(&mut self, scope: &WorkerScope<Self>, destruct: WorkerDestroyHandle<Self>) {
+//!         scope.persist_and_destroy(self, destruct);
+//!     }
+//!
+//!     // ...
+//! }
+//!
+//! // in the worker
+//! Cache::registrar().register_persistent(IndexedDbStorage::default());
+//! ```
+//!
+//! Public agents are destroyed when their provider is unmounted, but not when the page is
+//! reloaded. Agents should persist their state as it changes, snapshots are coalesced so only the
+//! latest one is written.
+
+use std::any::{Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::{fmt, mem};
+
+use futures::channel::oneshot;
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
+use js_sys::Uint8Array;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{IdbDatabase, IdbFactory, IdbRequest, IdbTransactionMode};
+use yew::platform::spawn_local;
+
+use super::{Worker, WorkerDestroyHandle, WorkerRegistrar, WorkerScope};
+use crate::{Bincode, Codec};
+
+thread_local! {
+    // The persistence of the agents registered in the current worker, by the type id of their
+    // worker.
+    static PERSISTENCE: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::default();
+}
+
+/// A worker agent with a state that survives reloads.
+pub trait Persist: Worker + 'static {
+    /// The state saved in snapshots.
+    type State: Serialize + for<'de> Deserialize<'de>;
+
+    /// The key of the snapshots of the agent in storage.
+    ///
+    /// Snapshots of an incompatible state are discarded when they fail to decode, but they may
+    /// also decode to a wrong state, so the key should be changed with the state.
+    const KEY: &'static str;
+
+    /// Takes a snapshot of the state of the agent.
+    fn snapshot(&self) -> Self::State;
+}
+
+/// A storage for snapshots of agents.
+///
+/// Snapshots are encoded with the codec of the agent.
+pub trait SnapshotStorage {
+    /// Loads the snapshot saved with `key`.
+    fn load(&self, key: &str) -> LocalBoxFuture<'static, Result<Option<JsValue>, JsValue>>;
+
+    /// Saves `snapshot` with `key`, replacing the previous one.
+    fn save(&self, key: &str, snapshot: JsValue) -> LocalBoxFuture<'static, Result<(), JsValue>>;
+
+    /// Removes the snapshot saved with `key`, if any.
+    fn remove(&self, key: &str) -> LocalBoxFuture<'static, Result<(), JsValue>>;
+}
+
+/// A codec that decodes snapshots without panicking.
+///
+/// Snapshots outlive the agents that saved them, so they may hold a state the agent no longer
+/// decodes.
+pub trait SnapshotCodec: Codec {
+    /// Decodes a snapshot, returning `None` if it does not hold a value of type `O`.
+    fn try_decode<O>(input: JsValue) -> Option<O>
+    where
+        O: for<'de> Deserialize<'de>;
+}
+
+impl SnapshotCodec for Bincode {
+    fn try_decode<O>(input: JsValue) -> Option<O>
+    where
+        O: for<'de> Deserialize<'de>,
+    {
+        let data = input.dyn_into::<Uint8Array>().ok()?.to_vec();
+        bincode::deserialize(&data).ok()
+    }
+}
+
+type OpenDatabase = Shared<LocalBoxFuture<'static, Result<IdbDatabase, JsValue>>>;
+
+/// A storage saving snapshots in an [IndexedDB](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API)
+/// database.
+///
+/// The database is opened by the first load or save, and shared by the clones of the storage.
+#[derive(Clone)]
+pub struct IndexedDbStorage {
+    database: Rc<str>,
+    opened: Rc<RefCell<Option<OpenDatabase>>>,
+}
+
+impl fmt::Debug for IndexedDbStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexedDbStorage")
+            .field("database", &self.database)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IndexedDbStorage {
+    const STORE: &'static str = "snapshots";
+
+    /// Creates a storage saving snapshots in the database named `database`.
+    pub fn new(database: impl Into<Rc<str>>) -> Self {
+        Self {
+            database: database.into(),
+            opened: Rc::default(),
+        }
+    }
+
+    /// Returns the database, opening it unless it is already open or being opened.
+    fn database(&self) -> OpenDatabase {
+        let mut opened = self.opened.borrow_mut();
+
+        match *opened {
+            // A database that failed to open is opened again.
+            Some(ref m) if !matches!(m.peek(), Some(Err(_))) => m.clone(),
+            _ => {
+                let open = Self::open(self.database.clone()).boxed_local().shared();
+                *opened = Some(open.clone());
+                open
+            }
+        }
+    }
+
+    async fn open(database: Rc<str>) -> Result<IdbDatabase, JsValue> {
+        let factory: IdbFactory = js_sys::Reflect::get(&js_sys::global(), &"indexedDB".into())?
+            .dyn_into()
+            .map_err(|_| JsValue::from_str("IndexedDB is not available"))?;
+
+        let request = factory.open_with_u32(&database, 1)?;
+        let on_upgrade = {
+            let request = request.clone();
+            Closure::once_into_js(move || {
+                if let Ok(database) = request.result() {
+                    let _ = database
+                        .unchecked_into::<IdbDatabase>()
+                        .create_object_store(Self::STORE);
+                }
+            })
+        };
+        request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+
+        Ok(settle(&request).await?.unchecked_into())
+    }
+}
+
+impl Default for IndexedDbStorage {
+    /// Creates a storage saving snapshots in the `yew-agent` database.
+    fn default() -> Self {
+        Self::new("yew-agent")
+    }
+}
+
+impl SnapshotStorage for IndexedDbStorage {
+    fn load(&self, key: &str) -> LocalBoxFuture<'static, Result<Option<JsValue>, JsValue>> {
+        let database = self.database();
+        let key = JsValue::from_str(key);
+
+        Box::pin(async move {
+            let database = database.await?;
+            let request = database
+                .transaction_with_str(Self::STORE)?
+                .object_store(Self::STORE)?
+                .get(&key)?;
+            let snapshot = settle(&request).await?;
+
+            Ok((!snapshot.is_undefined()).then_some(snapshot))
+        })
+    }
+
+    fn save(&self, key: &str, snapshot: JsValue) -> LocalBoxFuture<'static, Result<(), JsValue>> {
+        let database = self.database();
+        let key = JsValue::from_str(key);
+
+        Box::pin(async move {
+            let database = database.await?;
+            let request = database
+                .transaction_with_str_and_mode(Self::STORE, IdbTransactionMode::Readwrite)?
+                .object_store(Self::STORE)?
+                .put_with_key(&snapshot, &key)?;
+            settle(&request).await?;
+
+            Ok(())
+        })
+    }
+
+    fn remove(&self, key: &str) -> LocalBoxFuture<'static, Result<(), JsValue>> {
+        let database = self.database();
+        let key = JsValue::from_str(key);
+
+        Box::pin(async move {
+            let database = database.await?;
+            let request = database
+                .transaction_with_str_and_mode(Self::STORE, IdbTransactionMode::Readwrite)?
+                .object_store(Self::STORE)?
+                .delete(&key)?;
+            settle(&request).await?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Waits for an IndexedDB request to succeed or fail.
+async fn settle(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let (tx, rx) = oneshot::channel();
+    let tx = Rc::new(Cell::new(Some(tx)));
+
+    let on_settle = |succeeded: bool| {
+        let tx = tx.clone();
+        Closure::once_into_js(move || {
+            if let Some(tx) = tx.take() {
+                let _ = tx.send(succeeded);
+            }
+        })
+    };
+    request.set_onsuccess(Some(on_settle(true).unchecked_ref()));
+    request.set_onerror(Some(on_settle(false).unchecked_ref()));
+
+    match rx.await {
+        Ok(true) => request.result(),
+        _ => Err(JsValue::from_str("the IndexedDB request failed")),
+    }
+}
+
+struct Persistence<W>
+where
+    W: Persist,
+{
+    storage: Rc<dyn SnapshotStorage>,
+    encode: fn(W::State) -> JsValue,
+    restored: Option<W::State>,
+    // The latest snapshot that has not been saved.
+    pending: Option<JsValue>,
+    saving: bool,
+    // Called once there are no snapshots left to save, destroys the agent.
+    on_saved: Option<Box<dyn FnOnce()>>,
+}
+
+fn with_persistence<W, T>(f: impl FnOnce(&mut Persistence<W>) -> T) -> T
+where
+    W: Persist,
+{
+    PERSISTENCE.with(|m| {
+        let mut m = m.borrow_mut();
+        let persistence = m
+            .get_mut(&TypeId::of::<W>())
+            .and_then(|m| m.downcast_mut::<Persistence<W>>())
+            .expect("the agent is not persistent, register it with RegisterPersistent.");
+
+        f(persistence)
+    })
+}
+
+/// Loads the last snapshot of the agent from `storage`, which saves its next snapshots.
+async fn load<W, CODEC>(storage: Rc<dyn SnapshotStorage>)
+where
+    W: Persist,
+    CODEC: SnapshotCodec,
+{
+    // An agent starts with a new state if its snapshot fails to load or decode. A snapshot that
+    // fails to decode is removed, so it is not loaded again.
+    let restored = match storage.load(W::KEY).await {
+        Ok(Some(snapshot)) => {
+            let restored = CODEC::try_decode::<W::State>(snapshot);
+            if restored.is_none() {
+                let _ = storage.remove(W::KEY).await;
+            }
+            restored
+        }
+        Ok(None) | Err(_) => None,
+    };
+
+    let persistence = Persistence::<W> {
+        storage,
+        encode: CODEC::encode::<W::State>,
+        restored,
+        pending: None,
+        saving: false,
+        on_saved: None,
+    };
+    PERSISTENCE.with(|m| {
+        m.borrow_mut()
+            .insert(TypeId::of::<W>(), Box::new(persistence))
+    });
+}
+
+fn restore<W>() -> Option<W::State>
+where
+    W: Persist,
+{
+    with_persistence::<W, _>(|m| m.restored.take())
+}
+
+fn persist<W>(worker: &W)
+where
+    W: Persist,
+{
+    let snapshot = worker.snapshot();
+    let idle = with_persistence::<W, _>(|m| {
+        m.pending = Some((m.encode)(snapshot));
+        !mem::replace(&mut m.saving, true)
+    });
+
+    if idle {
+        flush::<W>();
+    }
+}
+
+/// Saves a snapshot of the agent, and calls `on_saved` once there are no snapshots left to save.
+fn persist_then<W>(worker: &W, on_saved: Box<dyn FnOnce()>)
+where
+    W: Persist,
+{
+    with_persistence::<W, _>(|m| m.on_saved = Some(on_saved));
+    persist(worker);
+}
+
+/// Saves pending snapshots until there are none left.
+fn flush<W>()
+where
+    W: Persist,
+{
+    spawn_local(async move {
+        loop {
+            let next = with_persistence::<W, _>(|m| match m.pending.take() {
+                Some(snapshot) => Ok((m.storage.clone(), snapshot)),
+                None => {
+                    m.saving = false;
+                    Err(m.on_saved.take())
+                }
+            });
+
+            match next {
+                // A snapshot that fails to save is replaced by the next one.
+                Ok((storage, snapshot)) => {
+                    let _ = storage.save(W::KEY, snapshot).await;
+                }
+                Err(on_saved) => {
+                    if let Some(f) = on_saved {
+                        f();
+                    }
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// Restores and persists the state of [`Persist`] agents.
+///
+/// This trait is implemented for the scope of persistent agents.
+pub trait PersistScopeExt<W>
+where
+    W: Persist,
+{
+    /// Takes the state restored from the last snapshot of the agent.
+    ///
+    /// Returns `None` if the agent has no snapshot, or after the state has been taken.
+    fn restore(&self) -> Option<W::State>;
+
+    /// Saves a snapshot of the agent.
+    fn persist(&self, worker: &W);
+
+    /// Saves a snapshot of the agent, and destroys it once the snapshot is saved.
+    ///
+    /// This is meant to be called in [`Worker::destroy`].
+    fn persist_and_destroy(&self, worker: &W, destruct: WorkerDestroyHandle<W>);
+}
+
+impl<W> PersistScopeExt<W> for WorkerScope<W>
+where
+    W: Persist,
+{
+    fn restore(&self) -> Option<W::State> {
+        restore::<W>()
+    }
+
+    fn persist(&self, worker: &W) {
+        persist(worker);
+    }
+
+    fn persist_and_destroy(&self, worker: &W, destruct: WorkerDestroyHandle<W>) {
+        persist_then(worker, Box::new(move || drop(destruct)));
+    }
+}
+
+/// Registers agents with a state that survives reloads.
+pub trait RegisterPersistent {
+    /// Registers the agent in the current worker, after loading its last snapshot from `storage`.
+    ///
+    /// See the [module level documentation](self).
+    fn register_persistent<S>(&self, storage: S)
+    where
+        S: SnapshotStorage + 'static;
+}
+
+impl<W, CODEC> RegisterPersistent for WorkerRegistrar<W, CODEC>
+where
+    W: Persist,
+    W::Input: Serialize + for<'de> Deserialize<'de>,
+    W::Output: Serialize + for<'de> Deserialize<'de>,
+    CODEC: SnapshotCodec + 'static,
+{
+    fn register_persistent<S>(&self, storage: S)
+    where
+        S: SnapshotStorage + 'static,
+    {
+        let registrar = self.encoding::<CODEC>();
+
+        // Bridges queue their messages until the agent is registered.
+        spawn_local(async move {
+            load::<W, CODEC>(Rc::new(storage)).await;
+            registrar.register();
+        });
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use std::time::Duration;
+
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+    use yew::platform::time::sleep;
+
+    use super::*;
+    use crate::worker::HandlerId;
+    use crate::Bincode;
+
+    /// A storage keeping snapshots in memory, which saves them once they are released.
+    #[derive(Default)]
+    struct MemoryStorage {
+        snapshots: RefCell<HashMap<String, JsValue>>,
+        // The snapshots being saved, with the senders releasing them.
+        saving: RefCell<Vec<(u32, oneshot::Sender<()>)>>,
+    }
+
+    impl MemoryStorage {
+        /// Returns the counters of the snapshots being saved.
+        fn saving(&self) -> Vec<u32> {
+            self.saving.borrow().iter().map(|(m, _)| *m).collect()
+        }
+
+        /// Finishes saving the snapshots being saved.
+        async fn release(&self) {
+            for (_, tx) in self.saving.take() {
+                let _ = tx.send(());
+            }
+            settle().await;
+        }
+    }
+
+    impl SnapshotStorage for Rc<MemoryStorage> {
+        fn load(&self, key: &str) -> LocalBoxFuture<'static, Result<Option<JsValue>, JsValue>> {
+            let snapshot = self.snapshots.borrow().get(key).cloned();
+            Box::pin(async move { Ok(snapshot) })
+        }
+
+        fn save(
+            &self,
+            key: &str,
+            snapshot: JsValue,
+        ) -> LocalBoxFuture<'static, Result<(), JsValue>> {
+            let (tx, rx) = oneshot::channel();
+            let counter = Bincode::decode::<u32>(snapshot.clone());
+            self.saving.borrow_mut().push((counter, tx));
+
+            let storage = self.clone();
+            let key = key.to_owned();
+            Box::pin(async move {
+                let _ = rx.await;
+                storage.snapshots.borrow_mut().insert(key, snapshot);
+                Ok(())
+            })
+        }
+
+        fn remove(&self, key: &str) -> LocalBoxFuture<'static, Result<(), JsValue>> {
+            self.snapshots.borrow_mut().remove(key);
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    struct Counter(u32);
+
+    impl Worker for Counter {
+        type Input = ();
+        type Message = ();
+        type Output = ();
+
+        fn create(_scope: &WorkerScope<Self>) -> Self {
+            Self(0)
+        }
+
+        fn update(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Message) {}
+
+        fn received(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Input, _id: HandlerId) {}
+    }
+
+    impl Persist for Counter {
+        type State = u32;
+
+        const KEY: &'static str = "counter";
+
+        fn snapshot(&self) -> Self::State {
+            self.0
+        }
+    }
+
+    /// Waits for spawned tasks to run.
+    async fn settle() {
+        sleep(Duration::ZERO).await;
+    }
+
+    async fn storage(restored: Option<JsValue>) -> Rc<MemoryStorage> {
+        let storage = Rc::new(MemoryStorage::default());
+        if let Some(m) = restored {
+            storage
+                .snapshots
+                .borrow_mut()
+                .insert(Counter::KEY.to_owned(), m);
+        }
+        load::<Counter, Bincode>(Rc::new(storage.clone())).await;
+
+        storage
+    }
+
+    #[test]
+    async fn state_is_restored_once() {
+        let _storage = storage(Some(Bincode::encode(3u32))).await;
+
+        assert_eq!(restore::<Counter>(), Some(3));
+        assert_eq!(restore::<Counter>(), None);
+    }
+
+    #[test]
+    async fn mismatched_snapshots_are_removed() {
+        // Saved by a version of the agent with a different state.
+        let storage = storage(Some(Bincode::encode(3u8))).await;
+
+        assert_eq!(restore::<Counter>(), None);
+        assert!(storage.snapshots.borrow().get(Counter::KEY).is_none());
+    }
+
+    #[test]
+    async fn pending_snapshots_are_coalesced() {
+        let storage = storage(None).await;
+
+        persist(&Counter(1));
+        settle().await;
+        assert_eq!(storage.saving(), vec![1]);
+
+        // Replaced by the latest snapshot while the first one is saved.
+        persist(&Counter(2));
+        persist(&Counter(3));
+        storage.release().await;
+        assert_eq!(storage.saving(), vec![3]);
+
+        storage.release().await;
+        assert!(storage.saving().is_empty());
+        assert_eq!(
+            storage
+                .snapshots
+                .borrow()
+                .get(Counter::KEY)
+                .cloned()
+                .map(Bincode::decode::<u32>),
+            Some(3)
+        );
+    }
+
+    #[test]
+    async fn agents_are_destroyed_after_their_last_snapshot() {
+        let storage = storage(None).await;
+        let destroyed = Rc::new(Cell::new(false));
+
+        persist(&Counter(1));
+        settle().await;
+        persist_then(&Counter(2), {
+            let destroyed = destroyed.clone();
+            Box::new(move || destroyed.set(true))
+        });
+
+        storage.release().await;
+        assert_eq!(storage.saving(), vec![2]);
+        assert!(!destroyed.get());
+
+        storage.release().await;
+        assert!(destroyed.get());
+    }
+}
@@ -3,16 +3,18 @@ use std::cell::RefCell;
 use std::fmt;
 use std::rc::Rc;
 
-use gloo_worker::Spawnable;
 use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
-use super::{Worker, WorkerBridge};
-use crate::reach::Reach;
+use super::Worker;
+use crate::dedicated::DedicatedBridge;
+use crate::pool::WorkerPool;
+use crate::reach::WorkerReach;
+use crate::supervisor::{Supervised, Supervisor};
 use crate::utils::get_next_id;
-use crate::{Bincode, Codec};
+use crate::{AgentCrash, Bincode, Codec, RestartPolicy};
 
-/// Properties for [WorkerProvider].
+/// Properties for [WorkerProvider] and [ServiceProvider](crate::service::ServiceProvider).
 #[derive(Debug, Properties, PartialEq, Clone)]
 pub struct WorkerProviderProps {
     /// The path to an agent.
@@ -20,9 +22,9 @@ pub struct WorkerProviderProps {
 
     /// The reachability of an agent.
     ///
-    /// Default: [`Public`](Reach::Public).
-    #[prop_or(Reach::Public)]
-    pub reach: Reach,
+    /// Default: [`Public`](WorkerReach::Public).
+    #[prop_or(WorkerReach::Public)]
+    pub reach: WorkerReach,
 
     /// Lazily spawn the agent.
     ///
@@ -34,19 +36,49 @@ pub struct WorkerProviderProps {
     #[prop_or(true)]
     pub lazy: bool,
 
+    /// Whether workers that crash are respawned.
+    ///
+    /// Bridges created with hooks reconnect when their worker is respawned.
+    ///
+    /// Default: [`Never`](RestartPolicy::Never).
+    #[prop_or_default]
+    pub restart: RestartPolicy,
+
+    /// Called when a worker crashes.
+    #[prop_or_default]
+    pub on_crash: Option<Callback<AgentCrash>>,
+
+    /// Transfers the [`Transfer`](crate::transfer::Transfer) fields of inputs to workers instead
+    /// of copying them.
+    ///
+    /// Only affects agents encoded with [`StructuredClone`](crate::StructuredClone). Workers
+    /// transfer their outputs once they call
+    /// [`transfer_outputs`](crate::transfer::transfer_outputs).
+    #[prop_or_default]
+    pub transfer: bool,
+
     /// Children of the provider.
     #[prop_or_default]
     pub children: Html,
 }
 
+/// A bridge to a worker agent spawned by a provider.
+pub(crate) type WorkerConnection<W> = DedicatedBridge<<W as Worker>::Input, <W as Worker>::Output>;
+
+// Spawns a bridge, with a callback if it is not forked.
+type SpawnBridgeFn<W> =
+    Rc<dyn Fn(Option<Callback<<W as Worker>::Output>>) -> Supervised<WorkerConnection<W>>>;
+
 pub(crate) struct WorkerProviderState<W>
 where
     W: Worker,
 {
     id: usize,
-    spawn_bridge_fn: Rc<dyn Fn() -> WorkerBridge<W>>,
-    reach: Reach,
-    held_bridge: Rc<RefCell<Option<WorkerBridge<W>>>>,
+    spawn_bridge_fn: SpawnBridgeFn<W>,
+    reach: WorkerReach,
+    held_bridge: Rc<RefCell<Option<Supervised<WorkerConnection<W>>>>>,
+    pool: Option<WorkerPool<Supervised<WorkerConnection<W>>>>,
+    supervisor: Supervisor,
 }
 
 impl<W> fmt::Debug for WorkerProviderState<W>
@@ -63,27 +95,32 @@ where
     W: Worker,
     W::Output: 'static,
 {
-    fn get_held_bridge(&self) -> WorkerBridge<W> {
+    fn get_held_bridge(&self) -> Supervised<WorkerConnection<W>> {
         let mut held_bridge = self.held_bridge.borrow_mut();
 
         match held_bridge.as_mut() {
-            Some(m) => m.clone(),
-            None => {
-                let bridge = (self.spawn_bridge_fn)();
-                *held_bridge = Some(bridge.clone());
+            Some(m) if !m.worker.should_respawn() => m.map(|m| m.clone()),
+            _ => {
+                let bridge = (self.spawn_bridge_fn)(None);
+                *held_bridge = Some(bridge.map(|m| m.clone()));
                 bridge
             }
         }
     }
 
     /// Creates a bridge, uses "fork" for public agents.
-    pub fn create_bridge(&self, cb: Callback<W::Output>) -> WorkerBridge<W> {
+    pub fn create_bridge(&self, cb: Callback<W::Output>) -> Supervised<WorkerConnection<W>> {
         match self.reach {
-            Reach::Public => {
+            WorkerReach::Public => {
                 let held_bridge = self.get_held_bridge();
-                held_bridge.fork(Some(move |m| cb.emit(m)))
+                held_bridge.map(|m| m.fork(Some(move |m| cb.emit(m))))
+            }
+            WorkerReach::Private => (self.spawn_bridge_fn)(Some(cb)),
+            WorkerReach::Pool { .. } => {
+                let pool = self.pool.as_ref().expect("pooled agent without a pool");
+                pool.respawn_if(|m| m.worker.should_respawn());
+                pool.round_robin(|m| m.map(|m| m.fork(Some(move |m| cb.emit(m)))))
             }
-            Reach::Private => (self.spawn_bridge_fn)(),
         }
     }
 }
@@ -98,6 +135,8 @@ where
             spawn_bridge_fn: self.spawn_bridge_fn.clone(),
             reach: self.reach,
             held_bridge: self.held_bridge.clone(),
+            pool: self.pool.clone(),
+            supervisor: self.supervisor.clone(),
         }
     }
 }
@@ -127,30 +166,62 @@ where
         path,
         lazy,
         reach,
+        restart,
+        on_crash,
+        transfer,
     } = props.clone();
 
-    // Creates a spawning function so Codec is can be erased from contexts.
-    let spawn_bridge_fn: Rc<dyn Fn() -> WorkerBridge<W>> = {
-        let path = path.clone();
-        Rc::new(move || W::spawner().encoding::<C>().spawn(&path))
-    };
-
     let state = {
-        use_memo((path, lazy, reach), move |(_path, lazy, reach)| {
-            let state = WorkerProviderState::<W> {
-                id: get_next_id(),
-                spawn_bridge_fn,
-                reach: *reach,
-                held_bridge: Rc::default(),
-            };
-
-            if *reach == Reach::Public && !*lazy {
-                state.get_held_bridge();
-            }
-            state
-        })
+        use_memo(
+            (path, lazy, reach, transfer),
+            move |(path, lazy, reach, transfer)| {
+                let supervisor = Supervisor::new(*transfer);
+
+                // Creates a spawning function so Codec is can be erased from contexts.
+                let spawn_bridge_fn: SpawnBridgeFn<W> = {
+                    let path = path.clone();
+                    let supervisor = supervisor.clone();
+                    Rc::new(move |cb| {
+                        let cb = cb.map(|cb| Rc::new(move |m| cb.emit(m)) as Rc<dyn Fn(_)>);
+                        supervisor.spawn(&path, |worker| worker.connect::<_, _, C>(cb))
+                    })
+                };
+
+                let state = WorkerProviderState::<W> {
+                    id: get_next_id(),
+                    spawn_bridge_fn: spawn_bridge_fn.clone(),
+                    reach: *reach,
+                    held_bridge: Rc::default(),
+                    pool: match *reach {
+                        WorkerReach::Pool { size } => Some(WorkerPool::new(
+                            size,
+                            None,
+                            Rc::new(move || spawn_bridge_fn(None)),
+                        )),
+                        _ => None,
+                    },
+                    supervisor,
+                };
+
+                match (reach, lazy) {
+                    (WorkerReach::Public, false) => {
+                        state.get_held_bridge();
+                    }
+                    (WorkerReach::Pool { .. }, false) => {
+                        if let Some(pool) = state.pool.as_ref() {
+                            pool.spawn_all();
+                        }
+                    }
+                    _ => {}
+                }
+                state
+            },
+        )
     };
 
+    // The restart policy and crash callback apply to workers that have already been spawned.
+    state.supervisor.configure(restart, on_crash);
+
     html! {
         <ContextProvider<WorkerProviderState<W>> context={(*state).clone()}>
             {children}
@@ -0,0 +1,118 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::future::Future;
+
+use futures::channel::{mpsc, oneshot};
+use futures::{SinkExt, StreamExt};
+use yew::platform::Runtime;
+use yew::prelude::*;
+use yew::suspense::use_future;
+use yew::LocalServerRenderer;
+use yew_agent::oneshot::{oneshot, use_oneshot_runner, OneshotProvider};
+use yew_agent::reactor::{
+    reactor, use_reactor_bridge, ReactorEvent, ReactorProvider, ReactorScope,
+};
+
+#[oneshot]
+async fn Double(n: u32) -> u32 {
+    n * 2
+}
+
+#[reactor(Count)]
+async fn count(mut scope: ReactorScope<u32, u32>) {
+    if let Some(n) = scope.next().await {
+        for i in 0..n {
+            let _ = scope.send(i).await;
+        }
+    }
+}
+
+/// Runs `f` on the local runtime and returns its output.
+fn run<F, O>(f: impl FnOnce() -> F + Send + 'static) -> O
+where
+    F: Future<Output = O> + 'static,
+    O: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    Runtime::default().spawn_pinned(move || async move {
+        let _ = tx.send(f().await);
+    });
+
+    futures::executor::block_on(rx).unwrap()
+}
+
+#[function_component]
+fn Doubled() -> HtmlResult {
+    let runner = use_oneshot_runner::<Double>();
+    let output = use_future(move || async move { runner.run(21).await })?;
+
+    Ok(html! { <p>{*output}</p> })
+}
+
+#[function_component]
+fn DoubleApp() -> Html {
+    html! {
+        <OneshotProvider<Double> path="/worker.js" local=true>
+            <Suspense>
+                <Doubled />
+            </Suspense>
+        </OneshotProvider<Double>>
+    }
+}
+
+#[test]
+fn oneshot_agents_run_locally() {
+    let html = run(|| {
+        LocalServerRenderer::<DoubleApp>::new()
+            .hydratable(false)
+            .render()
+    });
+
+    assert_eq!(html, "<p>42</p>");
+}
+
+#[derive(Properties, PartialEq)]
+struct CountProps {
+    // `None` once the reactor has finished.
+    on_event: Callback<Option<u32>>,
+}
+
+#[function_component]
+fn Counter(props: &CountProps) -> Html {
+    let on_event = props.on_event.clone();
+    let bridge = use_reactor_bridge::<Count, _>(move |m| match m {
+        ReactorEvent::Output(m) => on_event.emit(Some(m)),
+        ReactorEvent::Finished => on_event.emit(None),
+        ReactorEvent::Crashed(m) => panic!("the reactor crashed: {m}"),
+        _ => {}
+    });
+    bridge.send(3);
+
+    Html::default()
+}
+
+#[function_component]
+fn CountApp(props: &CountProps) -> Html {
+    html! {
+        <ReactorProvider<Count> path="/worker.js" local=true>
+            <Counter on_event={props.on_event.clone()} />
+        </ReactorProvider<Count>>
+    }
+}
+
+#[test]
+fn reactor_agents_run_locally() {
+    let events = run(|| async {
+        let (tx, rx) = mpsc::unbounded();
+        let on_event = Callback::from(move |m| {
+            let _ = tx.unbounded_send(m);
+        });
+
+        LocalServerRenderer::<CountApp>::with_props(CountProps { on_event })
+            .render()
+            .await;
+        rx.take(4).collect::<Vec<_>>().await
+    });
+
+    assert_eq!(events, vec![Some(0), Some(1), Some(2), None]);
+}
@@ -0,0 +1,519 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{Expr, Ident, LitStr, Token};
+
+/// At-rules whose block is scoped like the rule they are nested in.
+const CONDITIONAL_AT_RULES: &[&str] = &["@media", "@supports", "@container", "@layer"];
+/// At-rules whose block is emitted as written.
+const VERBATIM_AT_RULES: &[&str] = &[
+    "@keyframes",
+    "@-webkit-keyframes",
+    "@font-face",
+    "@page",
+    "@property",
+];
+
+/// A style sheet, scoped to a class name derived from its contents.
+pub struct Css {
+    lit: LitStr,
+    class: String,
+    css: String,
+    vars: Vec<(String, TokenStream)>,
+}
+
+struct Argument {
+    name: Ident,
+    expr: Expr,
+}
+
+impl Parse for Argument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let expr = input.parse()?;
+        Ok(Self { name, expr })
+    }
+}
+
+impl Parse for Css {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<LitStr>().map_err(|err| {
+            syn::Error::new(
+                err.span(),
+                "expected a string literal containing CSS (hint: `css!(\"color: red;\")`)",
+            )
+        })?;
+
+        let mut args = HashMap::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let arg = input.parse::<Argument>()?;
+            let name = arg.name.to_string();
+            if args.contains_key(&name) {
+                return Err(syn::Error::new_spanned(
+                    arg.name,
+                    format!("duplicate argument named `{name}`"),
+                ));
+            }
+            args.insert(name, arg);
+        }
+
+        let source = lit.value();
+        let class = format!("yew-{:08x}", hash(&source));
+        let items = strip_comments(&source)
+            .and_then(|source| Parser::new(&source).parse_block(false))
+            .map_err(|msg| syn::Error::new(lit.span(), msg))?;
+
+        let mut css = String::new();
+        let mut interpolations = Vec::new();
+        emit(
+            &items,
+            &[format!(".{class}")],
+            &class,
+            &mut css,
+            &mut interpolations,
+        )
+        .map_err(|msg| syn::Error::new(lit.span(), msg))?;
+
+        let mut vars = Vec::new();
+        for name in interpolations {
+            let var = format!("--{class}-{name}");
+            if vars.iter().any(|(v, _)| *v == var) {
+                continue;
+            }
+            let expr = match args.remove(&name) {
+                Some(Argument { expr, .. }) => quote_spanned! {expr.span()=> &(#expr) },
+                None => {
+                    let ident = syn::parse_str::<Ident>(&name).map_err(|_| {
+                        syn::Error::new(
+                            lit.span(),
+                            format!("`{name}` is not a valid name for an interpolated value"),
+                        )
+                    })?;
+                    let ident = Ident::new(&ident.to_string(), lit.span());
+                    quote! { &#ident }
+                }
+            };
+            vars.push((var, expr));
+        }
+
+        if let Some(Argument { name, .. }) = args.into_values().next() {
+            return Err(syn::Error::new_spanned(
+                &name,
+                format!("argument `{name}` is never used (hint: use it with `${{{name}}}`)"),
+            ));
+        }
+
+        Ok(Self {
+            lit,
+            class,
+            css,
+            vars,
+        })
+    }
+}
+
+impl ToTokens for Css {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            lit,
+            class,
+            css,
+            vars,
+        } = self;
+        let vars = vars.iter().map(|(var, expr)| {
+            quote! { (#var, #expr as &dyn ::std::fmt::Display) }
+        });
+        tokens.extend(quote_spanned! {lit.span()=>
+            {
+                static __YEW_STYLE_SHEET: ::yew::style::StyleSheet =
+                    ::yew::style::StyleSheet::__new(#class, #css);
+                ::yew::style::ScopedStyle::__new(&__YEW_STYLE_SHEET, &[#(#vars),*])
+            }
+        });
+    }
+}
+
+/// FNV-1a, folded to 32 bits. The class name of a style sheet must be the same on the server
+/// and the client, so it only depends on the source of the style sheet.
+fn hash(source: &str) -> u32 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in source.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    (hash ^ (hash >> 32)) as u32
+}
+
+enum Item {
+    /// `property: value;`
+    Declaration(String, String),
+    /// `selector { ... }` or `@rule ... { ... }`
+    Block(String, Vec<Item>),
+}
+
+/// Removes the comments of a style sheet, keeping strings intact.
+fn strip_comments(source: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                out.push(c);
+                let mut escaped = false;
+                for s in chars.by_ref() {
+                    out.push(s);
+                    if !escaped && s == c {
+                        break;
+                    }
+                    escaped = !escaped && s == '\\';
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = None;
+                loop {
+                    match chars.next() {
+                        Some('/') if last == Some('*') => break,
+                        Some(c) => last = Some(c),
+                        None => return Err("unterminated comment in CSS".into()),
+                    }
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self { source, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Parses the contents of a block, up to and including its closing brace.
+    fn parse_block(&mut self, nested: bool) -> Result<Vec<Item>, String> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.rest().chars().next() {
+                None if nested => return Err("unclosed `{` in CSS".into()),
+                None => return Ok(items),
+                Some('}') if nested => {
+                    self.pos += 1;
+                    return Ok(items);
+                }
+                Some('}') => return Err("unexpected `}` in CSS".into()),
+                Some(';') => self.pos += 1,
+                Some(_) => items.push(self.parse_item()?),
+            }
+        }
+    }
+
+    fn parse_item(&mut self) -> Result<Item, String> {
+        let start = self.pos;
+        let mut depth = 0usize;
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' | '\'' => loop {
+                    match chars.next() {
+                        Some((_, '\\')) => {
+                            chars.next();
+                        }
+                        Some((_, q)) if q == c => break,
+                        Some(_) => {}
+                        None => return Err("unterminated string in CSS".into()),
+                    }
+                },
+                // An interpolated value, `${name}`.
+                '$' if self.rest()[i + 1..].starts_with('{') && !chars.any(|(_, c)| c == '}') => {
+                    return Err("unclosed `${` in CSS".into());
+                }
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth = depth.saturating_sub(1),
+                '{' if depth == 0 => {
+                    let prelude = collapse_whitespace(&self.source[start..start + i]);
+                    if prelude.is_empty() {
+                        return Err("expected a selector before `{` in CSS".into());
+                    }
+                    self.pos = start + i + 1;
+                    let items = self.parse_block(true)?;
+                    return Ok(Item::Block(prelude, items));
+                }
+                ';' | '}' if depth == 0 => {
+                    self.pos = start + i + usize::from(c == ';');
+                    return parse_declaration(&self.source[start..start + i]);
+                }
+                _ => {}
+            }
+        }
+        self.pos = self.source.len();
+        parse_declaration(&self.source[start..])
+    }
+}
+
+fn parse_declaration(source: &str) -> Result<Item, String> {
+    let source = collapse_whitespace(source);
+    if source.starts_with('@') {
+        return Err(format!("unsupported at-rule `{source}` in `css!`"));
+    }
+    let (name, value) = source
+        .split_once(':')
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .filter(|(name, value)| is_property_name(name) && !value.is_empty())
+        .ok_or_else(|| format!("expected a declaration `property: value`, found `{source}`"))?;
+    Ok(Item::Declaration(name.to_owned(), value.to_owned()))
+}
+
+fn is_property_name(name: &str) -> bool {
+    let name = name.trim_start_matches('-');
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Collapses runs of whitespace outside of strings into a single space.
+fn collapse_whitespace(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut quote = None;
+    let mut escaped = false;
+    for c in source.trim().chars() {
+        match quote {
+            Some(q) => {
+                if !escaped && c == q {
+                    quote = None;
+                }
+                escaped = !escaped && c == '\\';
+                out.push(c);
+            }
+            None if c.is_whitespace() => {
+                if !out.ends_with(' ') {
+                    out.push(' ');
+                }
+            }
+            None => {
+                if c == '"' || c == '\'' {
+                    quote = Some(c);
+                }
+                out.push(c);
+            }
+        }
+    }
+    out
+}
+
+/// Splits a selector list on its top level commas.
+fn split_selectors(prelude: &str) -> Vec<&str> {
+    let mut selectors = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in prelude.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                selectors.push(prelude[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    selectors.push(prelude[start..].trim());
+    selectors
+}
+
+/// Replaces `${name}` with the CSS variable of the interpolated value.
+fn interpolate(value: &str, class: &str, names: &mut Vec<String>) -> Result<String, String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("unclosed `${{` in `{value}`"))?;
+        let name = rest[start + 2..start + end].trim();
+        if name.is_empty() {
+            return Err(format!("expected a name in `${{}}` in `{value}`"));
+        }
+        let _ = write!(out, "var(--{class}-{name})");
+        names.push(name.to_owned());
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn emit(
+    items: &[Item],
+    parents: &[String],
+    class: &str,
+    out: &mut String,
+    names: &mut Vec<String>,
+) -> Result<(), String> {
+    let mut declarations = String::new();
+    for item in items {
+        if let Item::Declaration(name, value) = item {
+            let value = interpolate(value, class, names)?;
+            let _ = write!(declarations, "{name}:{value};");
+        }
+    }
+    if !declarations.is_empty() {
+        declarations.pop();
+        let _ = write!(out, "{}{{{declarations}}}", parents.join(","));
+    }
+
+    for item in items {
+        let (prelude, items) = match item {
+            Item::Block(prelude, items) => (prelude, items),
+            Item::Declaration(..) => continue,
+        };
+        if prelude.contains("${") {
+            return Err(format!(
+                "values can only be interpolated in declarations, found `{prelude}`"
+            ));
+        }
+        if prelude.contains("</") {
+            return Err(format!("`</` is not allowed in `css!`, found `{prelude}`"));
+        }
+
+        if prelude.starts_with('@') {
+            let name = prelude.split_whitespace().next().unwrap_or_default();
+            let name = name.to_ascii_lowercase();
+            if CONDITIONAL_AT_RULES.contains(&name.as_str()) {
+                let _ = write!(out, "{prelude}{{");
+                emit(items, parents, class, out, names)?;
+                out.push('}');
+            } else if VERBATIM_AT_RULES.contains(&name.as_str()) {
+                let _ = write!(out, "{prelude}{{");
+                emit_verbatim(items, out)?;
+                out.push('}');
+            } else {
+                return Err(format!("unsupported at-rule `{name}` in `css!`"));
+            }
+            continue;
+        }
+
+        let mut selectors = Vec::new();
+        for parent in parents {
+            for selector in split_selectors(prelude) {
+                if selector.is_empty() {
+                    return Err(format!("expected a selector in `{prelude}`"));
+                }
+                selectors.push(if selector.contains('&') {
+                    selector.replace('&', parent)
+                } else {
+                    format!("{parent} {selector}")
+                });
+            }
+        }
+        emit(items, &selectors, class, out, names)?;
+    }
+    Ok(())
+}
+
+fn emit_verbatim(items: &[Item], out: &mut String) -> Result<(), String> {
+    let mut first = true;
+    for item in items {
+        match item {
+            Item::Declaration(name, value) => {
+                if value.contains("${") {
+                    return Err(format!(
+                        "values can't be interpolated in `@` rules which are not scoped, found \
+                         `{value}`"
+                    ));
+                }
+                if !first {
+                    out.push(';');
+                }
+                let _ = write!(out, "{name}:{value}");
+                first = false;
+            }
+            Item::Block(prelude, items) => {
+                if !first {
+                    out.push(';');
+                }
+                let _ = write!(out, "{prelude}{{");
+                emit_verbatim(items, out)?;
+                out.push('}');
+                first = true;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(source: &str) -> Result<String, String> {
+        let items = Parser::new(&strip_comments(source)?).parse_block(false)?;
+        let mut out = String::new();
+        emit(&items, &[".c".to_owned()], "c", &mut out, &mut Vec::new())?;
+        Ok(out)
+    }
+
+    #[test]
+    fn scopes_rules() {
+        assert_eq!(
+            compile(
+                r#"
+                color: red;
+                /* a comment */
+                &:hover, .title { content: "a  b"; }
+                @media (max-width: 600px) { padding: 0 }
+                @keyframes spin { from { rotate: 0deg } to { rotate: 360deg } }
+                "#
+            )
+            .unwrap(),
+            ".c{color:red}.c:hover,.c .title{content:\"a  b\"}\
+             @media (max-width: 600px){.c{padding:0}}\
+             @keyframes spin{from{rotate:0deg}to{rotate:360deg}}"
+        );
+    }
+
+    #[test]
+    fn interpolates_values() {
+        let items = Parser::new("margin: ${x} ${ y };")
+            .parse_block(false)
+            .unwrap();
+        let mut out = String::new();
+        let mut names = Vec::new();
+        emit(&items, &[".c".to_owned()], "c", &mut out, &mut names).unwrap();
+        assert_eq!(out, ".c{margin:var(--c-x) var(--c-y)}");
+        assert_eq!(names, ["x", "y"]);
+    }
+
+    #[test]
+    fn rejects_malformed_css() {
+        assert!(compile("color red;").is_err());
+        assert!(compile(".a { color: red;").is_err());
+        assert!(compile("color: red; }").is_err());
+        assert!(compile("@import url(a.css);").is_err());
+        assert!(compile("/* unterminated").is_err());
+    }
+}
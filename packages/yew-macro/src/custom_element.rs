@@ -0,0 +1,391 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{Attribute, Fields, Ident, ItemStruct, LitStr, Meta, Type};
+
+/// The tag name given to `#[custom_element("tag-name")]`
+pub struct CustomElementName {
+    tag: LitStr,
+}
+
+impl Parse for CustomElementName {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let tag = input.parse::<LitStr>().map_err(|err| {
+            syn::Error::new(
+                err.span(),
+                "expected the tag name of the custom element (hint: \
+                 `#[custom_element(\"my-element\")]`)",
+            )
+        })?;
+
+        // See https://html.spec.whatwg.org/multipage/custom-elements.html#valid-custom-element-name
+        let value = tag.value();
+        let valid = value.starts_with(|c: char| c.is_ascii_lowercase())
+            && value.contains('-')
+            && !value.contains(|c: char| c.is_ascii_uppercase() || c.is_whitespace());
+        if !valid {
+            return Err(syn::Error::new_spanned(
+                tag,
+                "custom element names must start with a lowercase letter and contain a `-`",
+            ));
+        }
+
+        Ok(Self { tag })
+    }
+}
+
+/// How a field is bound to the element
+enum Binding {
+    Attribute(LitStr),
+    Property(LitStr),
+    Event(LitStr),
+    NodeRef,
+    Children,
+    Rest,
+}
+
+struct ElementField {
+    name: Ident,
+    ty: Type,
+    binding: Binding,
+}
+
+impl ElementField {
+    /// Takes the binding attributes out of the field
+    fn new(field: &mut syn::Field) -> syn::Result<Self> {
+        let name = field.ident.clone().expect("fields are named");
+        let unraw = name.unraw().to_string();
+        let mut binding = None;
+        let mut binding_attr: Option<Attribute> = None;
+
+        let mut attrs = Vec::with_capacity(field.attrs.len());
+        for attr in field.attrs.drain(..) {
+            let kind = ["attribute", "property", "event", "node_ref"]
+                .into_iter()
+                .find(|kind| attr.path().is_ident(kind));
+            let kind = match kind {
+                Some(kind) => kind,
+                None => {
+                    attrs.push(attr);
+                    continue;
+                }
+            };
+            if let Some(previous) = binding_attr {
+                return Err(syn::Error::new_spanned(
+                    &attr,
+                    format!(
+                        "`#[{}]` can't be used together with `#[{}]`",
+                        kind,
+                        previous
+                            .path()
+                            .get_ident()
+                            .expect("binding attributes are idents"),
+                    ),
+                ));
+            }
+
+            let custom_name = match &attr.meta {
+                Meta::Path(_) => None,
+                Meta::List(_) if kind != "node_ref" => Some(attr.parse_args::<LitStr>()?),
+                meta => {
+                    let hint = if kind == "node_ref" {
+                        "`#[node_ref]`".to_string()
+                    } else {
+                        format!("`#[{kind}]` or `#[{kind}(\"name\")]`")
+                    };
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        format!("unexpected arguments (hint: use {hint})"),
+                    ));
+                }
+            };
+            let span = attr.span();
+            binding = Some(match kind {
+                "attribute" => Binding::Attribute(
+                    custom_name.unwrap_or_else(|| LitStr::new(&attribute_name(&unraw), span)),
+                ),
+                "property" => Binding::Property(
+                    custom_name.unwrap_or_else(|| LitStr::new(&property_name(&unraw), span)),
+                ),
+                "event" => Binding::Event(
+                    custom_name.unwrap_or_else(|| LitStr::new(&event_name(&unraw), span)),
+                ),
+                _ => Binding::NodeRef,
+            });
+            binding_attr = Some(attr);
+        }
+
+        if attrs.iter().any(|attr| attr.path().is_ident("rest")) {
+            if let Some(attr) = binding_attr {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "the `#[rest]` prop is spread on the element and can't be bound to it",
+                ));
+            }
+            binding = Some(Binding::Rest);
+        }
+        field.attrs = attrs;
+
+        let binding = binding.unwrap_or_else(|| {
+            if unraw == "children" {
+                Binding::Children
+            } else {
+                Binding::Attribute(LitStr::new(&attribute_name(&unraw), name.span()))
+            }
+        });
+
+        Ok(Self {
+            name,
+            ty: field.ty.clone(),
+            binding,
+        })
+    }
+
+    /// Whether the field is a boolean attribute, typed `bool` or `std::primitive::bool`
+    fn is_bool(&self) -> bool {
+        let path = match &self.ty {
+            Type::Path(ty) if ty.qself.is_none() => &ty.path,
+            _ => return false,
+        };
+        let mut segments = path.segments.iter().rev();
+        match (segments.next(), segments.next()) {
+            (Some(last), None) => last.ident == "bool" && path.leading_colon.is_none(),
+            (Some(last), Some(module)) => last.ident == "bool" && module.ident == "primitive",
+            _ => false,
+        }
+    }
+}
+
+/// `max_value` is set as the `max-value` attribute.
+fn attribute_name(field: &str) -> String {
+    field.replace('_', "-")
+}
+
+/// `max_value` is set as the `maxValue` property.
+fn property_name(field: &str) -> String {
+    let mut parts = field.split('_').filter(|part| !part.is_empty());
+    let mut name = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+    name
+}
+
+/// `onvalue_change` listens to the `value-change` events.
+fn event_name(field: &str) -> String {
+    let name = field.strip_prefix("on").unwrap_or(field);
+    attribute_name(name.trim_start_matches('_'))
+}
+
+pub fn custom_element_impl(
+    name: CustomElementName,
+    mut item: ItemStruct,
+) -> syn::Result<TokenStream> {
+    let CustomElementName { tag } = name;
+
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.generics,
+            "custom elements can't have generic parameters",
+        ));
+    }
+    let named = match &mut item.fields {
+        Fields::Named(fields) => &mut fields.named,
+        fields => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                "`custom_element` attribute can only be applied to structs with named fields",
+            ))
+        }
+    };
+
+    let mut fields = Vec::with_capacity(named.len());
+    for field in named.iter_mut() {
+        fields.push(ElementField::new(field)?);
+    }
+    let mut node_refs = fields
+        .iter()
+        .filter(|field| matches!(field.binding, Binding::NodeRef));
+    if let (Some(_), Some(second)) = (node_refs.next(), node_refs.next()) {
+        return Err(syn::Error::new_spanned(
+            &second.name,
+            "only one prop can be marked with `#[node_ref]`",
+        ));
+    }
+
+    let ItemStruct {
+        attrs,
+        vis,
+        struct_token,
+        ident: component_name,
+        fields: props_fields,
+        ..
+    } = item;
+    let props_name = format_ident!("{}Props", component_name);
+    let props_doc = LitStr::new(
+        &format!("The properties of [`{component_name}`]."),
+        Span::mixed_site(),
+    );
+    let debug_lit = LitStr::new(&format!("{component_name}<{}>", tag.value()), tag.span());
+
+    let node_ref_field = Ident::new("__yew_node_ref", Span::mixed_site());
+    let changed_field = Ident::new("__yew_changed_properties", Span::mixed_site());
+    let props = Ident::new("props", Span::mixed_site());
+    let vtag = Ident::new("vtag", Span::mixed_site());
+
+    let node_ref = match fields
+        .iter()
+        .find(|field| matches!(field.binding, Binding::NodeRef))
+    {
+        Some(ElementField { name, ty, .. }) => quote_spanned! {ty.span()=>
+            ::std::clone::Clone::clone(&#props.#name)
+        },
+        None => quote! { ::std::clone::Clone::clone(&self.#node_ref_field) },
+    };
+
+    let mut apply = Vec::new();
+    let mut properties = Vec::new();
+    for field in fields.iter() {
+        let ElementField { name, ty, binding } = field;
+        match binding {
+            Binding::Attribute(attr) if field.is_bool() => apply.push(quote! {
+                if #props.#name {
+                    ::yew::virtual_dom::VTag::add_attribute(&mut #vtag, #attr, #attr);
+                }
+            }),
+            Binding::Attribute(attr) => apply.push(quote_spanned! {ty.span()=>
+                if let ::std::option::Option::Some(value) = ::yew::html::IntoPropValue::<
+                    ::std::option::Option<::yew::virtual_dom::AttrValue>,
+                >::into_prop_value(::std::clone::Clone::clone(&#props.#name))
+                {
+                    ::yew::virtual_dom::VTag::add_attribute(&mut #vtag, #attr, value);
+                }
+            }),
+            Binding::Event(event) => apply.push(quote_spanned! {ty.span()=>
+                if let ::std::option::Option::Some(listener) = ::yew::html::__macro_event_listener(
+                    #event,
+                    ::std::convert::From::from(::std::clone::Clone::clone(&#props.#name)),
+                ) {
+                    ::yew::virtual_dom::VTag::add_listener(&mut #vtag, listener);
+                }
+            }),
+            Binding::Property(property) => properties.push((name, ty, property)),
+            Binding::NodeRef => {}
+            Binding::Children => apply.push(quote_spanned! {ty.span()=>
+                ::yew::virtual_dom::VTag::add_child(
+                    &mut #vtag,
+                    ::std::convert::Into::<::yew::html::Html>::into(
+                        ::std::clone::Clone::clone(&#props.#name),
+                    ),
+                );
+            }),
+            Binding::Rest => apply.push(quote_spanned! {ty.span()=>
+                ::yew::virtual_dom::VTag::__macro_spread(&mut #vtag, &#props.#name);
+            }),
+        }
+    }
+
+    let n_properties = properties.len();
+    let property_methods = if properties.is_empty() {
+        None
+    } else {
+        let mark_changed = properties.iter().enumerate().map(|(i, (name, ..))| {
+            quote! {
+                self.#changed_field[#i] |= #props.#name != old_props.#name;
+            }
+        });
+        let set_properties = properties
+            .iter()
+            .enumerate()
+            .map(|(i, (name, ty, property))| {
+                quote_spanned! {ty.span()=>
+                    if ::std::mem::take(&mut self.#changed_field[#i]) {
+                        ::yew::html::__macro_set_property(&node, #property, &#props.#name);
+                    }
+                }
+            });
+        Some(quote! {
+            fn changed(
+                &mut self,
+                ctx: &::yew::html::Context<Self>,
+                old_props: &Self::Properties,
+            ) -> bool {
+                let #props = ctx.props();
+                #(#mark_changed)*
+                true
+            }
+
+            fn rendered(&mut self, ctx: &::yew::html::Context<Self>, _first_render: bool) {
+                let #props = ctx.props();
+                // properties are set once the element exists, and again when they change
+                if let ::std::option::Option::Some(node) = ::yew::html::NodeRef::get(&#node_ref) {
+                    #(#set_properties)*
+                }
+            }
+        })
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #struct_token #component_name {
+            #node_ref_field: ::yew::html::NodeRef,
+            #changed_field: [::std::primitive::bool; #n_properties],
+        }
+
+        #[doc = #props_doc]
+        #[derive(::yew::html::Properties, ::std::cmp::PartialEq)]
+        #vis #struct_token #props_name #props_fields
+
+        #[automatically_derived]
+        impl ::std::fmt::Debug for #component_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(f, #debug_lit)
+            }
+        }
+
+        #[automatically_derived]
+        impl ::yew::html::Component for #component_name {
+            type Message = ();
+            type Properties = #props_name;
+
+            fn create(_ctx: &::yew::html::Context<Self>) -> Self {
+                Self {
+                    #node_ref_field: ::std::default::Default::default(),
+                    #changed_field: [true; #n_properties],
+                }
+            }
+
+            // `Option<Callback<_>>` events are converted to themselves
+            #[allow(clippy::useless_conversion)]
+            fn view(&self, ctx: &::yew::html::Context<Self>) -> ::yew::html::Html {
+                let #props = ctx.props();
+                let mut #vtag = ::yew::virtual_dom::VTag::new(#tag);
+                #vtag.node_ref = #node_ref;
+                #(#apply)*
+                ::std::convert::Into::<::yew::html::Html>::into(#vtag)
+            }
+
+            #property_methods
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_names_are_converted() {
+        assert_eq!(attribute_name("aria_label"), "aria-label");
+        assert_eq!(property_name("max_value"), "maxValue");
+        assert_eq!(property_name("value"), "value");
+        assert_eq!(event_name("onvalue_change"), "value-change");
+        assert_eq!(event_name("on_select"), "select");
+    }
+}
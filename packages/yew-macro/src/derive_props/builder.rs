@@ -9,6 +9,7 @@ use proc_macro2::{Ident, Span};
 use quote::{format_ident, quote, ToTokens};
 use syn::{parse_quote_spanned, Attribute, GenericParam};
 
+use super::constraints;
 use super::generics::to_arguments;
 use super::DerivePropsInput;
 use crate::derive_props::generics::push_type_param;
@@ -86,6 +87,7 @@ impl PropsBuilder<'_> {
             generics,
             props_name,
             prop_fields,
+            constraints,
             ..
         } = self.props;
 
@@ -107,6 +109,14 @@ impl PropsBuilder<'_> {
         let (impl_generics, _, where_clause) = assert_impl_generics.split_for_impl();
 
         let props_mod_name = format_ident!("_{}", props_name, span = Span::mixed_site());
+        let tracked = prop_fields
+            .iter()
+            .filter(|pf| pf.tracked)
+            .map(|pf| pf.name())
+            .collect::<Vec<_>>();
+        let token_ident = Ident::new("__YewToken", Span::mixed_site());
+        let (constraint_decls, constraint_checks) =
+            constraints::to_checks(constraints, &tracked, &token_ident, &props_mod_name);
         let mut check_impl_generics = assert_impl_generics.clone();
         let mut check_args = vec![];
         let mut check_props = proc_macro2::TokenStream::new();
@@ -120,10 +130,40 @@ impl PropsBuilder<'_> {
                 &mut check_impl_generics,
                 &mut check_args,
                 &props_mod_name,
+                &tracked,
             ));
         }
+        check_impl_generics
+            .make_where_clause()
+            .predicates
+            .extend(constraint_checks);
         let (check_impl_generics, _, check_where_clause) = check_impl_generics.split_for_impl();
 
+        let forward_attributes = prop_fields.iter().find(|pf| pf.is_rest()).map(|pf| {
+            let name = pf.name();
+            quote! {
+                #[automatically_derived]
+                impl #builder_impl_generics ::std::ops::Deref
+                    for #builder_name<#generic_args> #builder_where_clause
+                {
+                    type Target = ::yew::html::ForwardedAttributes;
+
+                    fn deref(&self) -> &Self::Target {
+                        &self.wrapped.#name
+                    }
+                }
+
+                #[automatically_derived]
+                impl #builder_impl_generics ::std::ops::DerefMut
+                    for #builder_name<#generic_args> #builder_where_clause
+                {
+                    fn deref_mut(&mut self) -> &mut Self::Target {
+                        &mut self.wrapped.#name
+                    }
+                }
+            }
+        });
+
         quote! {
             #[automatically_derived]
             #( #extra_attrs )*
@@ -131,10 +171,13 @@ impl PropsBuilder<'_> {
                 #( #prop_fns )*
             }
 
+            #forward_attributes
+
             #[doc(hidden)]
             #[allow(non_snake_case)]
             #vis mod #props_mod_name {
                 #( #prop_name_decls )*
+                #constraint_decls
             }
             #check_props
 
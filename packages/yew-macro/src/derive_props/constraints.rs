@@ -0,0 +1,295 @@
+//! Props can be constrained further than being required or optional:
+//! - `#[prop_group("name", exactly_one)]` puts the prop in a group, of which exactly one (or
+//!   `at_least_one`, or `at_most_one`) prop has to be set.
+//! - `#[prop(requires = "other")]` requires `other` to be set whenever the prop is set.
+//!
+//! The props taking part in a constraint are "tracked": whether they have been set is recorded in
+//! the tokens of the builder with `PropState`. When checking that all props were set, the states
+//! are also required to satisfy the constraints, such as `AtLeastOneOf<Group>`. A group is named
+//! after its props, so the errors list the missing or conflicting props.
+
+use std::collections::BTreeSet;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{parse_quote, Attribute, Error, LitStr, Token, WherePredicate};
+
+use super::field::PropField;
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GroupKind {
+    ExactlyOne,
+    AtLeastOne,
+    AtMostOne,
+}
+
+impl GroupKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::ExactlyOne => "exactly_one",
+            Self::AtLeastOne => "at_least_one",
+            Self::AtMostOne => "at_most_one",
+        }
+    }
+
+    fn at_least_one(self) -> bool {
+        matches!(self, Self::ExactlyOne | Self::AtLeastOne)
+    }
+
+    fn at_most_one(self) -> bool {
+        matches!(self, Self::ExactlyOne | Self::AtMostOne)
+    }
+}
+
+/// `#[prop_group("name", exactly_one)]`
+pub struct PropGroupAttr {
+    name: LitStr,
+    kind: GroupKind,
+    kind_span: Span,
+}
+
+impl Parse for PropGroupAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: LitStr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let kind: Ident = input.parse()?;
+        let kind_span = kind.span();
+        let kind = match kind.to_string().as_str() {
+            "exactly_one" => GroupKind::ExactlyOne,
+            "at_least_one" => GroupKind::AtLeastOne,
+            "at_most_one" => GroupKind::AtMostOne,
+            _ => {
+                return Err(Error::new_spanned(
+                    kind,
+                    "expected one of `exactly_one`, `at_least_one` or `at_most_one`",
+                ))
+            }
+        };
+        Ok(Self {
+            name,
+            kind,
+            kind_span,
+        })
+    }
+}
+
+impl PropGroupAttr {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Vec<Self>> {
+        attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("prop_group"))
+            .map(|attr| attr.parse_args())
+            .collect()
+    }
+}
+
+/// `#[prop(requires = "other")]`
+#[derive(Default)]
+pub struct PropOptions {
+    requires: Vec<LitStr>,
+}
+
+impl PropOptions {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("prop")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("requires") {
+                    options.requires.push(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown prop option, expected `requires`"))
+                }
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+pub enum PropConstraint {
+    Group {
+        name: String,
+        kind: GroupKind,
+        members: Vec<Ident>,
+    },
+    Requires {
+        prop: Ident,
+        requires: Ident,
+    },
+}
+
+impl PropConstraint {
+    /// Collects the constraints declared on the props, and marks the props taking part in them as
+    /// tracked.
+    pub fn collect(prop_fields: &mut [PropField]) -> Result<Vec<Self>> {
+        let names: Vec<Ident> = prop_fields.iter().map(|pf| pf.name().clone()).collect();
+        let mut constraints = Vec::new();
+
+        for pf in prop_fields.iter() {
+            for group in &pf.groups {
+                if pf.is_required() {
+                    return Err(Error::new_spanned(
+                        &group.name,
+                        format!(
+                            "the prop `{}` is in a group, so it needs a default value (hint: add \
+                             `#[prop_or_default]`)",
+                            pf.name()
+                        ),
+                    ));
+                }
+                let name = group.name.value();
+                let existing = constraints.iter_mut().find_map(|c| match c {
+                    Self::Group {
+                        name: n,
+                        kind,
+                        members,
+                    } if *n == name => Some((kind, members)),
+                    _ => None,
+                });
+                match existing {
+                    Some((kind, _)) if *kind != group.kind => {
+                        return Err(Error::new(
+                            group.kind_span,
+                            format!(
+                                "the prop group `{}` is declared as `{}` on another prop",
+                                name,
+                                kind.as_str()
+                            ),
+                        ));
+                    }
+                    Some((_, members)) if members.contains(pf.name()) => {
+                        return Err(Error::new_spanned(
+                            &group.name,
+                            format!("the prop `{}` is in the group `{}` twice", pf.name(), name),
+                        ));
+                    }
+                    Some((_, members)) => members.push(pf.name().clone()),
+                    None => constraints.push(Self::Group {
+                        name,
+                        kind: group.kind,
+                        members: vec![pf.name().clone()],
+                    }),
+                }
+            }
+
+            for requires in &pf.options.requires {
+                let requires_name = requires.value();
+                let target = names.iter().find(|name| **name == requires_name);
+                match target {
+                    None => {
+                        return Err(Error::new_spanned(
+                            requires,
+                            format!("cannot find a prop named `{}`", requires_name),
+                        ));
+                    }
+                    Some(target) if target == pf.name() => {
+                        return Err(Error::new_spanned(requires, "a prop cannot require itself"));
+                    }
+                    Some(target) => constraints.push(Self::Requires {
+                        prop: pf.name().clone(),
+                        requires: target.clone(),
+                    }),
+                }
+            }
+        }
+
+        for pf in prop_fields.iter_mut() {
+            pf.tracked = constraints.iter().any(|c| c.involves(pf.name()));
+        }
+
+        Ok(constraints)
+    }
+
+    fn involves(&self, prop: &Ident) -> bool {
+        match self {
+            Self::Group { members, .. } => members.contains(prop),
+            Self::Requires {
+                prop: p, requires, ..
+            } => p == prop || requires == prop,
+        }
+    }
+}
+
+/// Generates the structs naming the groups, which are placed in the props module, and the bounds
+/// checking the constraints for the token.
+pub fn to_checks(
+    constraints: &[PropConstraint],
+    tracked: &[&Ident],
+    token: &Ident,
+    props_mod: &Ident,
+) -> (TokenStream, Vec<WherePredicate>) {
+    let prop_marker = |prop: &Ident| {
+        let mut prop = prop.clone();
+        prop.set_span(Span::mixed_site());
+        quote! { #props_mod :: #prop }
+    };
+    let state = |prop: &Ident| {
+        let prop = prop_marker(prop);
+        quote! { <#token as ::yew::html::PropState< #prop >>::State }
+    };
+
+    let mut group_decls = TokenStream::new();
+    let mut declared = BTreeSet::new();
+    let mut predicates: Vec<WherePredicate> = tracked
+        .iter()
+        .map(|prop| {
+            let prop = prop_marker(prop);
+            parse_quote! { #token: ::yew::html::PropState< #prop > }
+        })
+        .collect();
+
+    for constraint in constraints {
+        match constraint {
+            PropConstraint::Group { kind, members, .. } => {
+                let count =
+                    members
+                        .iter()
+                        .rev()
+                        .fold(quote! { ::yew::html::NoProp }, |count, prop| {
+                            let state = state(prop);
+                            predicates
+                                .push(parse_quote! { #state: ::yew::html::CountProp<#count> });
+                            quote! { <#state as ::yew::html::CountProp<#count>>::Count }
+                        });
+                let group = format_ident!(
+                    "{}",
+                    members
+                        .iter()
+                        .map(|prop| prop.unraw().to_string())
+                        .collect::<Vec<_>>()
+                        .join("_or_"),
+                    span = Span::mixed_site()
+                );
+                if declared.insert(group.to_string()) {
+                    group_decls.extend(quote! {
+                        #[allow(non_camel_case_types)]
+                        pub struct #group;
+                    });
+                }
+
+                if kind.at_least_one() {
+                    predicates.push(parse_quote! {
+                        #count: ::yew::html::AtLeastOneOf< #props_mod :: #group >
+                    });
+                }
+                if kind.at_most_one() {
+                    predicates.push(parse_quote! {
+                        #count: ::yew::html::AtMostOneOf< #props_mod :: #group >
+                    });
+                }
+            }
+            PropConstraint::Requires { prop, requires } => {
+                let (prop_state, required_state) = (state(prop), state(requires));
+                let (prop, requires) = (prop_marker(prop), prop_marker(requires));
+                predicates.push(parse_quote! {
+                    (#prop_state, #required_state): ::yew::html::PropRequires<#prop, #requires>
+                });
+            }
+        }
+    }
+
+    (group_decls, predicates)
+}
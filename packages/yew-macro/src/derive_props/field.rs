@@ -7,6 +7,7 @@ use syn::parse::Result;
 use syn::spanned::Spanned;
 use syn::{parse_quote, Attribute, Error, Expr, Field, GenericParam, Generics, Type, Visibility};
 
+use super::constraints::{PropGroupAttr, PropOptions};
 use super::should_preserve_attr;
 use crate::derive_props::generics::push_type_param;
 
@@ -19,12 +20,18 @@ enum PropAttr {
     PropOrDefault,
 }
 
-#[derive(Eq)]
 pub struct PropField {
     ty: Type,
     name: Ident,
     attr: PropAttr,
     extra_attrs: Vec<Attribute>,
+    pub(super) groups: Vec<PropGroupAttr>,
+    pub(super) options: PropOptions,
+    /// Set for props taking part in a group or a `requires` constraint, whose state is tracked
+    /// by the builder tokens
+    pub(super) tracked: bool,
+    /// Set for the `#[rest]` prop, collecting the attributes and listeners which aren't props
+    rest: bool,
 }
 
 impl PropField {
@@ -33,6 +40,19 @@ impl PropField {
         matches!(self.attr, PropAttr::Required { .. })
     }
 
+    /// Props which are required or tracked have a check struct, returned by their setter
+    fn has_check(&self) -> bool {
+        self.is_required() || self.tracked
+    }
+
+    pub fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    pub fn is_rest(&self) -> bool {
+        self.rest
+    }
+
     /// This check name is descriptive to help a developer realize they missed a required prop
     fn to_check_name(&self, props_name: &Ident) -> Ident {
         format_ident!("Has{}{}", props_name, self.name, span = Span::mixed_site())
@@ -88,6 +108,11 @@ impl PropField {
                     #name: ::std::option::Option::unwrap_or_else(this.wrapped.#name, #func),
                 }
             }
+            PropAttr::PropOrDefault if self.rest => {
+                quote! {
+                    #name: ::yew::html::ForwardedAttributes::__yew_into_rest(this.wrapped.#name),
+                }
+            }
             PropAttr::PropOrDefault => {
                 quote! {
                     #name: ::std::option::Option::unwrap_or_default(this.wrapped.#name),
@@ -106,6 +131,13 @@ impl PropField {
         let ty = &self.ty;
         let extra_attrs = &self.extra_attrs;
         let wrapped_name = self.wrapped_name();
+        if self.rest {
+            // The builder dereferences to the `#[rest]` prop to forward attributes to it
+            return quote! {
+                #( #extra_attrs )*
+                #wrapped_name: ::yew::html::ForwardedAttributes,
+            };
+        }
         quote! {
             #( #extra_attrs )*
             #wrapped_name: ::std::option::Option<#ty>,
@@ -116,6 +148,12 @@ impl PropField {
     pub fn to_default_setter(&self) -> proc_macro2::TokenStream {
         let wrapped_name = self.wrapped_name();
         let extra_attrs = &self.extra_attrs;
+        if self.rest {
+            return quote! {
+                #( #extra_attrs )*
+                #wrapped_name: ::std::default::Default::default(),
+            };
+        }
         quote! {
             #( #extra_attrs )*
             #wrapped_name: ::std::option::Option::None,
@@ -128,34 +166,42 @@ impl PropField {
         vis: &Visibility,
         props_name: &Ident,
     ) -> proc_macro2::TokenStream {
-        let Self { name, ty, attr, .. } = self;
+        let Self { name, ty, .. } = self;
         let token_ty = Ident::new("__YewTokenTy", Span::mixed_site());
-        let build_fn = match attr {
-            PropAttr::Required { wrapped_name } => {
-                let check_struct = self.to_check_name(props_name);
-                quote! {
-                    #[doc(hidden)]
-                    #vis fn #name<#token_ty>(
-                        &mut self,
-                        token: #token_ty,
-                        value: impl ::yew::html::IntoPropValue<#ty>,
-                    ) -> #check_struct< #token_ty > {
-                        self.wrapped.#wrapped_name = ::std::option::Option::Some(value.into_prop_value());
-                        #check_struct ( ::std::marker::PhantomData )
-                    }
+        let wrapped_name = self.wrapped_name();
+        let set_value = if self.rest {
+            // Forwarded attributes may be set before the rest prop itself, so they are merged
+            quote! {
+                self.wrapped.#wrapped_name.__yew_extend(value.into_prop_value());
+            }
+        } else {
+            quote! {
+                self.wrapped.#wrapped_name = ::std::option::Option::Some(value.into_prop_value());
+            }
+        };
+        let build_fn = if self.has_check() {
+            let check_struct = self.to_check_name(props_name);
+            quote! {
+                #[doc(hidden)]
+                #vis fn #name<#token_ty>(
+                    &mut self,
+                    token: #token_ty,
+                    value: impl ::yew::html::IntoPropValue<#ty>,
+                ) -> #check_struct< #token_ty > {
+                    #set_value
+                    #check_struct ( ::std::marker::PhantomData )
                 }
             }
-            _ => {
-                quote! {
-                    #[doc(hidden)]
-                    #vis fn #name<#token_ty>(
-                        &mut self,
-                        token: #token_ty,
-                        value: impl ::yew::html::IntoPropValue<#ty>,
-                    ) -> #token_ty {
-                        self.wrapped.#name = ::std::option::Option::Some(value.into_prop_value());
-                        token
-                    }
+        } else {
+            quote! {
+                #[doc(hidden)]
+                #vis fn #name<#token_ty>(
+                    &mut self,
+                    token: #token_ty,
+                    value: impl ::yew::html::IntoPropValue<#ty>,
+                ) -> #token_ty {
+                    #set_value
+                    token
                 }
             }
         };
@@ -203,7 +249,7 @@ pub struct PropFieldCheck<'a> {
 impl<'a> PropFieldCheck<'a> {
     pub fn to_fake_prop_decl(&self) -> proc_macro2::TokenStream {
         let Self { this, .. } = self;
-        if !this.is_required() {
+        if !this.has_check() {
             return Default::default();
         }
         let mut prop_check_name = this.name.clone();
@@ -219,6 +265,7 @@ impl<'a> PropFieldCheck<'a> {
         type_generics: &mut Generics,
         check_args: &mut Vec<GenericParam>,
         prop_name_mod: &Ident,
+        tracked: &[&Ident],
     ) -> proc_macro2::TokenStream {
         let Self {
             this,
@@ -227,17 +274,63 @@ impl<'a> PropFieldCheck<'a> {
             check_struct,
             check_arg,
         } = self;
-        if !this.is_required() {
+        if !this.has_check() {
             return Default::default();
         }
         let mut prop_check_name = this.name.clone();
         prop_check_name.set_span(Span::mixed_site());
-        check_args.push(check_arg.clone());
-        push_type_param(type_generics, check_arg.clone());
-        let where_clause = type_generics.make_where_clause();
-        where_clause.predicates.push(parse_quote! {
-            #token: ::yew::html::HasProp< #prop_name_mod :: #prop_check_name, #check_arg >
-        });
+        if this.is_required() {
+            check_args.push(check_arg.clone());
+            push_type_param(type_generics, check_arg.clone());
+            let where_clause = type_generics.make_where_clause();
+            where_clause.predicates.push(parse_quote! {
+                #token: ::yew::html::HasProp< #prop_name_mod :: #prop_check_name, #check_arg >
+            });
+        }
+
+        let prop_states = if !this.tracked {
+            if tracked.is_empty() {
+                Default::default()
+            } else {
+                quote! {
+                    #[automatically_derived]
+                    impl<B, P> ::yew::html::PropState<P> for #check_struct<B>
+                        where B: ::yew::html::PropState<P>
+                    {
+                        type State = <B as ::yew::html::PropState<P>>::State;
+                    }
+                }
+            }
+        } else {
+            tracked
+                .iter()
+                .map(|name| {
+                    let mut name = (*name).clone();
+                    name.set_span(Span::mixed_site());
+                    if name == this.name {
+                        quote! {
+                            #[automatically_derived]
+                            impl<B> ::yew::html::PropState< #prop_name_mod :: #name >
+                                for #check_struct<B>
+                            {
+                                type State = ::yew::html::PropSet;
+                            }
+                        }
+                    } else {
+                        quote! {
+                            #[automatically_derived]
+                            impl<B> ::yew::html::PropState< #prop_name_mod :: #name >
+                                for #check_struct<B>
+                                where B: ::yew::html::PropState< #prop_name_mod :: #name >
+                            {
+                                type State =
+                                    <B as ::yew::html::PropState< #prop_name_mod :: #name >>::State;
+                            }
+                        }
+                    }
+                })
+                .collect()
+        };
 
         quote! {
             #[doc(hidden)]
@@ -252,6 +345,7 @@ impl<'a> PropFieldCheck<'a> {
                 for #check_struct<B>
                 where B: ::yew::html::HasProp<P, How> {}
 
+            #prop_states
         }
     }
 }
@@ -266,10 +360,25 @@ impl TryFrom<Field> for PropField {
             .filter(|a| should_preserve_attr(a))
             .cloned()
             .collect();
+        let attr = Self::attribute(&field)?;
+
+        let rest = field.attrs.iter().find(|attr| attr.path().is_ident("rest"));
+        if let Some(rest) = rest {
+            if attr != PropAttr::PropOrDefault {
+                return Err(Error::new_spanned(
+                    rest,
+                    "the `#[rest]` prop needs a default value (hint: add `#[prop_or_default]`)",
+                ));
+            }
+        }
 
         Ok(PropField {
-            attr: Self::attribute(&field)?,
+            attr,
+            rest: rest.is_some(),
             extra_attrs,
+            groups: PropGroupAttr::from_attrs(&field.attrs)?,
+            options: PropOptions::from_attrs(&field.attrs)?,
+            tracked: false,
             ty: field.ty,
             name: field.ident.unwrap(),
         })
@@ -301,3 +410,5 @@ impl PartialEq for PropField {
         self.name == other.name
     }
 }
+
+impl Eq for PropField {}
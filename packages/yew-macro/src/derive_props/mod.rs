@@ -1,4 +1,5 @@
 mod builder;
+mod constraints;
 mod field;
 mod generics;
 mod wrapper;
@@ -6,6 +7,7 @@ mod wrapper;
 use std::convert::TryInto;
 
 use builder::PropsBuilder;
+use constraints::PropConstraint;
 use field::PropField;
 use proc_macro2::{Ident, Span};
 use quote::{format_ident, quote, ToTokens};
@@ -20,6 +22,7 @@ pub struct DerivePropsInput {
     generics: Generics,
     props_name: Ident,
     prop_fields: Vec<PropField>,
+    constraints: Vec<PropConstraint>,
     preserved_attrs: Vec<Attribute>,
 }
 
@@ -37,7 +40,7 @@ fn should_preserve_attr(attr: &Attribute) -> bool {
 impl Parse for DerivePropsInput {
     fn parse(input: ParseStream) -> Result<Self> {
         let input: DeriveInput = input.parse()?;
-        let prop_fields = match input.data {
+        let mut prop_fields = match input.data {
             syn::Data::Struct(data) => match data.fields {
                 syn::Fields::Named(fields) => {
                     let mut prop_fields: Vec<PropField> = fields
@@ -57,6 +60,15 @@ impl Parse for DerivePropsInput {
             _ => unimplemented!("only structs are supported"),
         };
 
+        if let Some(pf) = prop_fields.iter().filter(|pf| pf.is_rest()).nth(1) {
+            return Err(syn::Error::new_spanned(
+                pf.name(),
+                "only one prop can be marked with `#[rest]`",
+            ));
+        }
+
+        let constraints = PropConstraint::collect(&mut prop_fields)?;
+
         let preserved_attrs = input
             .attrs
             .iter()
@@ -69,6 +81,7 @@ impl Parse for DerivePropsInput {
             props_name: input.ident,
             generics: input.generics,
             prop_fields,
+            constraints,
             preserved_attrs,
         })
     }
@@ -120,5 +133,18 @@ impl ToTokens for DerivePropsInput {
             }
         };
         tokens.extend(properties);
+
+        // Unknown props are checked against the props, unless they are forwarded to a `#[rest]`
+        // prop
+        let labels = if self.prop_fields.iter().any(|pf| pf.is_rest()) {
+            quote! { ::yew::html::ForwardedAttributes }
+        } else {
+            quote! { Self }
+        };
+        tokens.extend(quote! {
+            impl #impl_generics ::yew::html::PropLabels for #props_name #ty_generics #where_clause {
+                type Labels = #labels;
+            }
+        });
     }
 }
@@ -12,7 +12,7 @@ use crate::props::ComponentProps;
 pub struct HtmlComponent {
     ty: Type,
     props: ComponentProps,
-    children: HtmlChildrenTree,
+    pub children: HtmlChildrenTree,
     close: Option<HtmlComponentClose>,
 }
 
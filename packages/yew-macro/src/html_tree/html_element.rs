@@ -117,6 +117,7 @@ impl ToTokens for HtmlElement {
             checked,
             listeners,
             special,
+            ..
         } = &props;
 
         // attributes with special treatment
@@ -252,7 +253,10 @@ impl ToTokens for HtmlElement {
                     Some(PropDirective::ApplyAsProperty(token)) => {
                         quote_spanned!(token.span()=> ::yew::virtual_dom::ApplyAttributeAs::Property)
                     }
-                    None => quote!(::yew::virtual_dom::ApplyAttributeAs::Attribute),
+                    // bindings are taken out of the attributes
+                    Some(PropDirective::Bind(_)) | None => {
+                        quote!(::yew::virtual_dom::ApplyAttributeAs::Attribute)
+                    }
                 }
             }
 
@@ -327,49 +331,55 @@ impl ToTokens for HtmlElement {
                         )
                     )
                 }
-                let node = match &*name {
+                let vtag = match &*name {
                     "input" => {
                         quote! {
-                            ::std::convert::Into::<::yew::virtual_dom::VNode>::into(
-                                ::yew::virtual_dom::VTag::__new_input(
-                                    #value,
-                                    #checked,
-                                    #node_ref,
-                                    #key,
-                                    #attributes,
-                                    #listeners,
-                                ),
+                            ::yew::virtual_dom::VTag::__new_input(
+                                #value,
+                                #checked,
+                                #node_ref,
+                                #key,
+                                #attributes,
+                                #listeners,
                             )
                         }
                     }
                     "textarea" => {
                         quote! {
-                            ::std::convert::Into::<::yew::virtual_dom::VNode>::into(
-                                ::yew::virtual_dom::VTag::__new_textarea(
-                                    #value,
-                                    #node_ref,
-                                    #key,
-                                    #attributes,
-                                    #listeners,
-                                ),
+                            ::yew::virtual_dom::VTag::__new_textarea(
+                                #value,
+                                #node_ref,
+                                #key,
+                                #attributes,
+                                #listeners,
                             )
                         }
                     }
                     _ => {
                         quote! {
-                            ::std::convert::Into::<::yew::virtual_dom::VNode>::into(
-                                ::yew::virtual_dom::VTag::__new_other(
-                                    ::std::borrow::Cow::<'static, ::std::primitive::str>::Borrowed(#name),
-                                    #node_ref,
-                                    #key,
-                                    #attributes,
-                                    #listeners,
-                                    #children,
-                                ),
+                            ::yew::virtual_dom::VTag::__new_other(
+                                ::std::borrow::Cow::<'static, ::std::primitive::str>::Borrowed(#name),
+                                #node_ref,
+                                #key,
+                                #attributes,
+                                #listeners,
+                                #children,
                             )
                         }
                     }
                 };
+                let vtag_ident = Ident::new("__yew_vtag", Span::mixed_site());
+                let vtag = match finish_vtag_tokens(&vtag_ident, props) {
+                    Some(finish) => quote! {{
+                        let mut #vtag_ident = #vtag;
+                        #finish
+                        #vtag_ident
+                    }},
+                    None => vtag,
+                };
+                let node = quote! {
+                    ::std::convert::Into::<::yew::virtual_dom::VNode>::into(#vtag)
+                };
                 // the return value can be inlined without the braces when this is stable:
                 // https://github.com/rust-lang/rust/issues/15701
                 quote_spanned!{
@@ -387,6 +397,7 @@ impl ToTokens for HtmlElement {
                 let vtag_name = Ident::new("__yew_vtag_name", expr.span());
 
                 let void_children = Ident::new("__yew_void_children", Span::mixed_site());
+                let finish = finish_vtag_tokens(&vtag, props);
 
                 // handle special attribute value
                 let handle_value_attr = props.value.as_ref().map(|prop| {
@@ -460,6 +471,7 @@ impl ToTokens for HtmlElement {
                             __yew_vtag
                         }
                     };
+                    #finish
 
                     // These are the runtime-checks exclusive to dynamic tags.
                     // For literal tags this is already done at compile-time.
@@ -487,6 +499,38 @@ impl ToTokens for HtmlElement {
     }
 }
 
+/// Generates the statements applying the spread attributes and the bindings to the created
+/// `VTag`, if any.
+fn finish_vtag_tokens(vtag: &Ident, props: &ElementProps) -> Option<TokenStream> {
+    let spread = props.spread.as_ref().map(|spread| {
+        quote_spanned! {spread.span()=>
+            ::yew::virtual_dom::VTag::__macro_spread(
+                &mut #vtag,
+                ::std::borrow::Borrow::<::yew::html::RestAttributes>::borrow(&#spread),
+            );
+        }
+    });
+    let bind_value = props.bind_value.as_ref().map(|Prop { value, .. }| {
+        quote_spanned! {value.span()=>
+            ::yew::virtual_dom::VTag::__macro_bind_value(&mut #vtag, &#value);
+        }
+    });
+    let bind_checked = props.bind_checked.as_ref().map(|Prop { value, .. }| {
+        quote_spanned! {value.span()=>
+            ::yew::virtual_dom::VTag::__macro_bind_checked(&mut #vtag, &#value);
+        }
+    });
+
+    if spread.is_none() && bind_value.is_none() && bind_checked.is_none() {
+        return None;
+    }
+    Some(quote! {
+        #spread
+        #bind_value
+        #bind_checked
+    })
+}
+
 fn wrap_attr_value<T: ToTokens>(value: T) -> TokenStream {
     quote_spanned! {value.span()=>
         ::yew::html::IntoPropValue::<
@@ -651,6 +695,8 @@ impl Parse for HtmlElementOpen {
                             }
                         }
                     }
+
+                    check_bindings(name, &props)?;
                 }
                 TagName::Expr(name) => {
                     if name.expr.is_none() {
@@ -667,6 +713,31 @@ impl Parse for HtmlElementOpen {
     }
 }
 
+/// Checks the element of a literal tag can be bound
+fn check_bindings(name: &HtmlDashedName, props: &ElementProps) -> syn::Result<()> {
+    let tag = name.to_ascii_lowercase_string();
+    if let Some(prop) = &props.bind_value {
+        if !matches!(tag.as_str(), "input" | "textarea" | "select") {
+            return Err(syn::Error::new_spanned(
+                &prop.label,
+                format!(
+                    "`bind:value` is not supported on `<{name}>` (hint: it can be used on \
+                     `<input>`, `<textarea>` and `<select>`)"
+                ),
+            ));
+        }
+    }
+    if let Some(prop) = &props.bind_checked {
+        if tag != "input" {
+            return Err(syn::Error::new_spanned(
+                &prop.label,
+                format!("`bind:checked` is not supported on `<{name}>` (hint: use an `<input>`)"),
+            ));
+        }
+    }
+    Ok(())
+}
+
 struct HtmlElementClose {
     tag: TagTokens,
     _name: TagName,
@@ -0,0 +1,124 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::buffer::Cursor;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{braced, token, Expr, Pat, Token};
+
+use super::{HtmlChildrenTree, HtmlTree};
+use crate::props::Prop;
+use crate::PeekValue;
+
+pub struct HtmlFor {
+    for_token: Token![for],
+    pat: Pat,
+    expr: Box<Expr>,
+    brace: token::Brace,
+    pub body: HtmlChildrenTree,
+    /// The key of the nodes of an iteration, taken from the first of them.
+    key: Option<Prop>,
+}
+
+impl PeekValue<()> for HtmlFor {
+    fn peek(cursor: Cursor) -> Option<()> {
+        // `for pat in expr { ... }`, as opposed to the `for expr` iterables.
+        let (ident, mut cursor) = cursor.ident()?;
+        if ident != "for" {
+            return None;
+        }
+
+        // The expression of an iterable can't contain `in` outside of a group.
+        while let Some((tt, next)) = cursor.token_tree() {
+            match tt {
+                proc_macro2::TokenTree::Ident(ident) if ident == "in" => return Some(()),
+                _ => cursor = next,
+            }
+        }
+
+        None
+    }
+}
+
+impl Parse for HtmlFor {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let for_token = input.parse()?;
+        let pat = Pat::parse_multi_with_leading_vert(input)?;
+        input.parse::<Token![in]>()?;
+        let expr = Box::new(input.call(Expr::parse_without_eager_brace)?);
+        if input.is_empty() {
+            return Err(syn::Error::new(
+                expr.span(),
+                "this `for` loop has an iterator, but no block",
+            ));
+        }
+
+        let content;
+        let brace = braced!(content in input);
+        let mut body = HtmlChildrenTree::new();
+        while !content.is_empty() {
+            body.parse_child(&content)?;
+        }
+
+        // The nodes of an iteration are wrapped in a fragment, keyed by the key of the first node.
+        let key = match &mut body.0[..] {
+            [HtmlTree::Element(first), _, ..] => first.props.special.key.take(),
+            _ => None,
+        };
+
+        Ok(HtmlFor {
+            for_token,
+            pat,
+            expr,
+            brace,
+            body,
+            key,
+        })
+    }
+}
+
+impl ToTokens for HtmlFor {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let HtmlFor {
+            for_token,
+            pat,
+            expr,
+            brace,
+            body,
+            key,
+        } = self;
+
+        let node = match &body.0[..] {
+            [node] => quote_spanned! {node.span()=>
+                ::std::convert::Into::<::yew::virtual_dom::VNode>::into(#node)
+            },
+            _ => {
+                let key = match key {
+                    Some(key) => {
+                        let value = &key.value;
+                        quote_spanned! {value.span()=>
+                            ::std::option::Option::Some(
+                                ::std::convert::Into::<::yew::virtual_dom::Key>::into(#value)
+                            )
+                        }
+                    }
+                    None => quote! { ::std::option::Option::None },
+                };
+
+                quote_spanned! {brace.span.span()=>
+                    ::yew::virtual_dom::VNode::VList(
+                        ::yew::virtual_dom::VList::with_children(#body, #key)
+                    )
+                }
+            }
+        };
+
+        tokens.extend(quote_spanned! {for_token.span()=>
+            ::std::iter::Iterator::collect::<::yew::virtual_dom::VNode>(
+                ::std::iter::Iterator::map(
+                    ::std::iter::IntoIterator::into_iter(#expr),
+                    |#pat| -> ::yew::virtual_dom::VNode { #node },
+                )
+            )
+        });
+    }
+}
@@ -5,7 +5,7 @@ use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
 use syn::{Expr, Token};
 
-use super::{HtmlRootBraced, ToNodeIterator};
+use super::{HtmlRootBraced, HtmlTree, ToNodeIterator};
 use crate::PeekValue;
 
 pub struct HtmlIf {
@@ -67,6 +67,18 @@ impl Parse for HtmlIf {
     }
 }
 
+impl HtmlIf {
+    /// Calls `f` with the trees of each branch, see [`HtmlTree::visit`].
+    pub fn visit(&self, f: &mut dyn FnMut(&HtmlTree)) {
+        self.then_branch.children.visit(f);
+        match self.else_branch.as_ref().map(|(_, branch)| &**branch) {
+            Some(HtmlRootBracedOrIf::Branch(branch)) => branch.children.visit(f),
+            Some(HtmlRootBracedOrIf::If(branch)) => branch.visit(f),
+            None => {}
+        }
+    }
+}
+
 impl ToTokens for HtmlIf {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let HtmlIf {
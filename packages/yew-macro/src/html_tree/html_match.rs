@@ -0,0 +1,166 @@
+use proc_macro2::TokenStream;
+use quote::{quote_spanned, ToTokens};
+use syn::buffer::Cursor;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{braced, token, Expr, Pat, Token};
+
+use super::{HtmlRootBraced, HtmlTree};
+use crate::PeekValue;
+
+pub struct HtmlMatch {
+    match_token: Token![match],
+    expr: Box<Expr>,
+    brace: token::Brace,
+    arms: Vec<HtmlMatchArm>,
+}
+
+impl PeekValue<()> for HtmlMatch {
+    fn peek(cursor: Cursor) -> Option<()> {
+        let (ident, _) = cursor.ident()?;
+        (ident == "match").then_some(())
+    }
+}
+
+impl Parse for HtmlMatch {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let match_token = input.parse()?;
+        let expr = Box::new(input.call(Expr::parse_without_eager_brace)?);
+        if input.is_empty() {
+            return Err(syn::Error::new(
+                expr.span(),
+                "this `match` expression has a scrutinee, but no arms",
+            ));
+        }
+
+        let content;
+        let brace = braced!(content in input);
+        let mut arms = Vec::new();
+        while !content.is_empty() {
+            arms.push(content.parse()?);
+        }
+
+        Ok(HtmlMatch {
+            match_token,
+            expr,
+            brace,
+            arms,
+        })
+    }
+}
+
+impl HtmlMatch {
+    /// Calls `f` with the trees of each arm, see [`HtmlTree::visit`].
+    pub fn visit(&self, f: &mut dyn FnMut(&HtmlTree)) {
+        for arm in &self.arms {
+            match &arm.body {
+                HtmlMatchArmBody::Braced(braced) => braced.children.visit(f),
+                HtmlMatchArmBody::Tree(tree) => tree.visit(f),
+            }
+        }
+    }
+}
+
+impl ToTokens for HtmlMatch {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let HtmlMatch {
+            match_token,
+            expr,
+            brace,
+            arms,
+        } = self;
+
+        let arms = quote_spanned! {brace.span.span()=> { #(#arms)* } };
+        tokens.extend(quote_spanned! {match_token.span()=>
+            match #expr #arms
+        });
+    }
+}
+
+pub struct HtmlMatchArm {
+    pat: Pat,
+    guard: Option<(Token![if], Box<Expr>)>,
+    fat_arrow: Token![=>],
+    body: HtmlMatchArmBody,
+}
+
+impl Parse for HtmlMatchArm {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let pat = Pat::parse_multi_with_leading_vert(input)?;
+        let guard = match input.parse::<Option<Token![if]>>()? {
+            Some(if_token) => Some((if_token, Box::new(input.parse()?))),
+            None => None,
+        };
+        let fat_arrow = input.parse::<Token![=>]>()?;
+        if input.is_empty() || input.peek(Token![,]) {
+            return Err(syn::Error::new_spanned(
+                fat_arrow,
+                "expected a block or an html node after `=>`",
+            ));
+        }
+
+        let body = input.parse()?;
+        // Like in Rust, the comma after an arm is optional if its body is a block.
+        match body {
+            HtmlMatchArmBody::Braced(_) => {
+                input.parse::<Option<Token![,]>>()?;
+            }
+            HtmlMatchArmBody::Tree(_) if input.is_empty() => {}
+            HtmlMatchArmBody::Tree(_) => {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(HtmlMatchArm {
+            pat,
+            guard,
+            fat_arrow,
+            body,
+        })
+    }
+}
+
+impl ToTokens for HtmlMatchArm {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let HtmlMatchArm {
+            pat,
+            guard,
+            fat_arrow,
+            body,
+        } = self;
+        let guard = guard
+            .as_ref()
+            .map(|(if_token, cond)| quote_spanned! {if_token.span()=> if #cond });
+
+        tokens.extend(quote_spanned! {fat_arrow.span()=>
+            #pat #guard => #body,
+        });
+    }
+}
+
+pub enum HtmlMatchArmBody {
+    Braced(HtmlRootBraced),
+    Tree(Box<HtmlTree>),
+}
+
+impl Parse for HtmlMatchArmBody {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if HtmlRootBraced::peek(input.cursor()).is_some() {
+            input.parse().map(Self::Braced)
+        } else {
+            input.parse().map(|m| Self::Tree(Box::new(m)))
+        }
+    }
+}
+
+impl ToTokens for HtmlMatchArmBody {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Braced(braced) => braced.to_tokens(tokens),
+            // All arms evaluate to a `VNode`, so arms with different types of nodes are allowed.
+            Self::Tree(tree) => tokens.extend(quote_spanned! {tree.span()=>
+                ::std::convert::Into::<::yew::virtual_dom::VNode>::into(#tree)
+            }),
+        }
+    }
+}
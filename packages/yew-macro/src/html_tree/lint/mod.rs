@@ -1,42 +1,174 @@
 //! Lints to catch possible misuse of the `html!` macro use. At the moment these are mostly focused
 //! on accessibility.
+//!
+//! Lints are enabled with `--cfg yew_lints`. On nightly (with `--cfg nightly_yew`) they are
+//! emitted as compiler warnings, and on stable as uses of deprecated items, which the compiler
+//! reports with the message of the lint.
+//!
+//! A lint is disabled for a single invocation of the macro with an inner attribute, e.g.
+//! `html! { #![allow(img_alt)] <img src="spacer.png" /> }`.
 
-use proc_macro_error::emit_warning;
+use std::collections::HashSet;
+
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::parse::ParseStream;
 use syn::spanned::Spanned;
+use syn::{Attribute, Expr, Lit, LitStr};
 
+use super::html_block::BlockContent;
 use super::html_element::{HtmlElement, TagName};
-use super::HtmlTree;
-use crate::props::{ElementProps, Prop};
+use super::{HtmlNode, HtmlTree};
+use crate::props::{unknown_attributes, ElementProps, Prop, UnknownAttribute, UNKNOWN_ATTRIBUTES};
+
+/// The names of all lints, as used in `#![allow(...)]`.
+const LINTS: &[&str] = &[
+    AHrefLint::NAME,
+    ImgAltLint::NAME,
+    LabelForLint::NAME,
+    ButtonTextLint::NAME,
+    AriaAttributeLint::NAME,
+    AriaRoleLint::NAME,
+    ClickEventsLint::NAME,
+    DuplicateIdLint::NAME,
+    UnknownAttributeLint::NAME,
+];
+
+/// Lints HTML elements to check if they are well formed. If an element is not well-formed, the
+/// lint adds a warning to [`Warnings`].
+pub trait Lint: Default {
+    /// The name of the lint, used to allow it.
+    const NAME: &'static str;
+
+    /// Lints an element. Elements of a macro invocation are linted by the same instance, parents
+    /// before their children.
+    fn lint(&mut self, element: &HtmlElement, warnings: &mut Warnings);
+}
+
+/// The lints allowed in a macro invocation, with `#![allow(...)]` attributes.
+#[derive(Default)]
+pub struct AllowedLints(HashSet<String>);
+
+impl AllowedLints {
+    /// Parses the inner attributes at the start of a macro invocation.
+    pub fn parse_attributes(input: ParseStream) -> syn::Result<Self> {
+        let mut allowed = HashSet::new();
+
+        for attr in input.call(Attribute::parse_inner)? {
+            if !attr.path().is_ident("allow") {
+                return Err(syn::Error::new_spanned(
+                    attr.path(),
+                    "only `allow` attributes are supported in `html!`",
+                ));
+            }
+
+            attr.parse_nested_meta(|meta| {
+                let name = meta
+                    .path
+                    .get_ident()
+                    .map(ToString::to_string)
+                    .filter(|m| LINTS.contains(&m.as_str()))
+                    .ok_or_else(|| {
+                        meta.error(format!(
+                            "unknown lint, expected one of: {}",
+                            LINTS.join(", ")
+                        ))
+                    })?;
+                allowed.insert(name);
+
+                Ok(())
+            })?;
+        }
+
+        Ok(Self(allowed))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains(name)
+    }
+}
 
-/// Lints HTML elements to check if they are well formed. If the element is not well-formed, then
-/// use `proc-macro-error` (and the `emit_warning!` macro) to produce a warning. At present, these
-/// are only emitted on nightly.
-pub trait Lint {
-    fn lint(element: &HtmlElement);
+/// The warnings produced by lints.
+#[derive(Default)]
+pub struct Warnings {
+    lint: &'static str,
+    warnings: Vec<(&'static str, Span, String)>,
 }
 
-/// Applies all the lints to the HTML tree.
-pub fn lint_all(tree: &HtmlTree) {
-    lint::<AHrefLint>(tree);
-    lint::<ImgAltLint>(tree);
+impl Warnings {
+    pub fn warn(&mut self, span: Span, message: impl Into<String>) {
+        self.warnings.push((self.lint, span, message.into()));
+    }
+
+    /// Whether there are no warnings to emit. Warnings are only emitted with `--cfg yew_lints`.
+    pub fn is_empty(&self) -> bool {
+        !cfg!(yew_lints) || self.warnings.is_empty()
+    }
 }
 
-/// Applies a specific lint to the HTML tree.
-pub fn lint<L>(tree: &HtmlTree)
+impl ToTokens for Warnings {
+    #[cfg(not(yew_lints))]
+    fn to_tokens(&self, _tokens: &mut TokenStream) {}
+
+    #[cfg(all(yew_lints, nightly_yew))]
+    fn to_tokens(&self, _tokens: &mut TokenStream) {
+        for (_, span, message) in &self.warnings {
+            proc_macro_error::emit_warning!(span, message);
+        }
+    }
+
+    #[cfg(all(yew_lints, not(nightly_yew)))]
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        // Warnings can't be emitted on stable, the compiler warns about the use of a deprecated
+        // function named after the lint instead.
+        for (lint, span, message) in &self.warnings {
+            let lint = proc_macro2::Ident::new(lint, *span);
+            tokens.extend(quote::quote_spanned! {*span=>
+                {
+                    #[deprecated(note = #message)]
+                    fn #lint() {}
+                    #lint();
+                }
+            });
+        }
+    }
+}
+
+/// Applies all the lints to the HTML tree, except the allowed ones.
+pub fn lint_all(tree: &HtmlTree, allowed: &AllowedLints) -> Warnings {
+    let mut warnings = Warnings::default();
+
+    lint::<AHrefLint>(tree, allowed, &mut warnings);
+    lint::<ImgAltLint>(tree, allowed, &mut warnings);
+    lint::<LabelForLint>(tree, allowed, &mut warnings);
+    lint::<ButtonTextLint>(tree, allowed, &mut warnings);
+    lint::<AriaAttributeLint>(tree, allowed, &mut warnings);
+    lint::<AriaRoleLint>(tree, allowed, &mut warnings);
+    lint::<ClickEventsLint>(tree, allowed, &mut warnings);
+    lint::<DuplicateIdLint>(tree, allowed, &mut warnings);
+    lint::<UnknownAttributeLint>(tree, allowed, &mut warnings);
+
+    warnings
+}
+
+/// Applies a specific lint to the HTML tree, unless it is allowed.
+pub fn lint<L>(tree: &HtmlTree, allowed: &AllowedLints, warnings: &mut Warnings)
 where
     L: Lint,
 {
-    #[cfg(not(yew_lints))]
-    let _ = tree;
-    #[cfg(yew_lints)]
-    match tree {
-        HtmlTree::List(list) => {
-            for child in &list.children.0 {
-                lint::<L>(child)
+    if !allowed.contains(L::NAME) {
+        let mut lint = L::default();
+        warnings.lint = L::NAME;
+
+        tree.visit(&mut |tree| {
+            if let HtmlTree::Element(el) = tree {
+                lint.lint(el, warnings);
             }
-        }
-        HtmlTree::Element(el) => L::lint(el),
-        _ => {}
+        });
     }
 }
 
@@ -51,60 +183,459 @@ fn get_attribute<'a>(props: &'a ElementProps, name: &str) -> Option<&'a Prop> {
         .find(|item| item.label.eq_ignore_ascii_case(name))
 }
 
+/// Returns the value of an attribute, if it is a string literal.
+fn get_literal_attribute<'a>(props: &'a ElementProps, name: &str) -> Option<&'a LitStr> {
+    match &get_attribute(props, name)?.value {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(value),
+            ..
+        }) => Some(value),
+        _ => None,
+    }
+}
+
+/// Returns the name of an element with a literal tag name, in lowercase.
+fn get_tag_name(element: &HtmlElement) -> Option<(String, Span)> {
+    match &element.name {
+        TagName::Lit(name) => Some((
+            name.to_ascii_lowercase_string(),
+            quote::quote! {#name}.span(),
+        )),
+        TagName::Expr(_) => None,
+    }
+}
+
 /// Lints to check if anchor (`<a>`) tags have valid `href` attributes defined.
+#[derive(Default)]
 pub struct AHrefLint;
 
 impl Lint for AHrefLint {
-    fn lint(element: &HtmlElement) {
-        if let TagName::Lit(ref tag_name) = element.name {
-            if !tag_name.eq_ignore_ascii_case("a") {
-                return;
-            };
-            if let Some(prop) = get_attribute(&element.props, "href") {
-                if let syn::Expr::Lit(lit) = &prop.value {
-                    if let syn::Lit::Str(href) = &lit.lit {
-                        let href_value = href.value();
-                        match href_value.as_ref() {
-                            "#" | "javascript:void(0)" => emit_warning!(
-                                lit.span(),
-                                format!("'{href_value}' is not a suitable value for the `href` attribute. \
-                                        Without a meaningful attribute assistive technologies \
-                                        will struggle to understand your webpage. \
-                                        https://developer.mozilla.org/en-US/docs/Learn/Accessibility/HTML#onclick_events"
-                            )),
-                            _ => {}
-
-                        }
+    const NAME: &'static str = "a_href";
+
+    fn lint(&mut self, element: &HtmlElement, warnings: &mut Warnings) {
+        let span = match get_tag_name(element) {
+            Some((name, span)) if name == "a" => span,
+            _ => return,
+        };
+
+        if let Some(prop) = get_attribute(&element.props, "href") {
+            if let syn::Expr::Lit(lit) = &prop.value {
+                if let syn::Lit::Str(href) = &lit.lit {
+                    let href_value = href.value();
+                    match href_value.as_ref() {
+                        "#" | "javascript:void(0)" => warnings.warn(
+                            lit.span(),
+                            format!("'{href_value}' is not a suitable value for the `href` attribute. \
+                                    Without a meaningful attribute assistive technologies \
+                                    will struggle to understand your webpage. \
+                                    https://developer.mozilla.org/en-US/docs/Learn/Accessibility/HTML#onclick_events"
+                        )),
+                        _ => {}
+
                     }
-                };
-            } else {
-                emit_warning!(
-                    quote::quote! {#tag_name}.span(),
-                    "All `<a>` elements should have a `href` attribute. This makes it possible \
-                        for assistive technologies to correctly interpret what your links point to. \
-                        https://developer.mozilla.org/en-US/docs/Learn/Accessibility/HTML#more_on_links"
-                )
-            }
+                }
+            };
+        } else {
+            warnings.warn(
+                span,
+                "All `<a>` elements should have a `href` attribute. This makes it possible \
+                    for assistive technologies to correctly interpret what your links point to. \
+                    https://developer.mozilla.org/en-US/docs/Learn/Accessibility/HTML#more_on_links",
+            )
         }
     }
 }
 
 /// Checks to make sure that images have `alt` attributes defined.
+#[derive(Default)]
 pub struct ImgAltLint;
 
 impl Lint for ImgAltLint {
-    fn lint(element: &HtmlElement) {
-        if let super::html_element::TagName::Lit(ref tag_name) = element.name {
-            if !tag_name.eq_ignore_ascii_case("img") {
-                return;
-            };
-            if get_attribute(&element.props, "alt").is_none() {
-                emit_warning!(
-                    quote::quote! {#tag_name}.span(),
-                    "All `<img>` tags should have an `alt` attribute which provides a \
-                     human-readable description "
+    const NAME: &'static str = "img_alt";
+
+    fn lint(&mut self, element: &HtmlElement, warnings: &mut Warnings) {
+        let span = match get_tag_name(element) {
+            Some((name, span)) if name == "img" => span,
+            _ => return,
+        };
+
+        if get_attribute(&element.props, "alt").is_none() {
+            warnings.warn(
+                span,
+                "All `<img>` tags should have an `alt` attribute which provides a \
+                 human-readable description ",
+            )
+        }
+    }
+}
+
+/// Checks that labels are associated with a control, either with a `for` attribute or by
+/// containing the control.
+#[derive(Default)]
+pub struct LabelForLint;
+
+impl Lint for LabelForLint {
+    const NAME: &'static str = "label_for";
+
+    fn lint(&mut self, element: &HtmlElement, warnings: &mut Warnings) {
+        let span = match get_tag_name(element) {
+            Some((name, span)) if name == "label" => span,
+            _ => return,
+        };
+        if get_attribute(&element.props, "for").is_some() {
+            return;
+        }
+
+        // Components, expressions and dynamic tags may render the control, literals can't.
+        let mut labels_control = false;
+        element.children.visit(&mut |tree| match tree {
+            HtmlTree::Element(el) => {
+                labels_control |= get_tag_name(el).map_or(true, |(name, _)| {
+                    matches!(
+                        &*name,
+                        "button"
+                            | "input"
+                            | "meter"
+                            | "output"
+                            | "progress"
+                            | "select"
+                            | "textarea"
+                    )
+                })
+            }
+            HtmlTree::Block(block) => {
+                labels_control |= !matches!(
+                    &block.content,
+                    BlockContent::Node(node) if matches!(**node, HtmlNode::Literal(_))
+                )
+            }
+            HtmlTree::Component(_) => labels_control = true,
+            _ => {}
+        });
+
+        if !labels_control {
+            warnings.warn(
+                span,
+                "All `<label>` elements should have a `for` attribute, or contain the control \
+                 they label. Otherwise assistive technologies can't tell which control the label \
+                 describes. https://developer.mozilla.org/en-US/docs/Web/HTML/Element/label",
+            )
+        }
+    }
+}
+
+/// Checks that buttons have an accessible name.
+#[derive(Default)]
+pub struct ButtonTextLint;
+
+impl Lint for ButtonTextLint {
+    const NAME: &'static str = "button_text";
+
+    fn lint(&mut self, element: &HtmlElement, warnings: &mut Warnings) {
+        let span = match get_tag_name(element) {
+            Some((name, span)) if name == "button" => span,
+            _ => return,
+        };
+
+        let labelled = ["aria-label", "aria-labelledby", "title"]
+            .iter()
+            .any(|m| get_attribute(&element.props, m).is_some());
+        if element.children.is_empty() && !labelled {
+            warnings.warn(
+                span,
+                "All `<button>` elements should have text content, or an `aria-label` attribute \
+                 which describes what the button does.",
+            )
+        }
+    }
+}
+
+/// Checks that `aria-*` attributes are valid ARIA attributes.
+#[derive(Default)]
+pub struct AriaAttributeLint;
+
+impl Lint for AriaAttributeLint {
+    const NAME: &'static str = "aria_attribute";
+
+    fn lint(&mut self, element: &HtmlElement, warnings: &mut Warnings) {
+        for attr in element.props.attributes.iter() {
+            let name = attr.label.to_ascii_lowercase_string();
+            if name.starts_with("aria-") && !ARIA_ATTRIBUTES.contains(&name.as_str()) {
+                warnings.warn(
+                    attr.label.span(),
+                    format!(
+                        "`{name}` is not a valid ARIA attribute. \
+                         https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes"
+                    ),
                 )
             }
         }
     }
 }
+
+/// Checks that literal `role` attributes only contain valid ARIA roles.
+#[derive(Default)]
+pub struct AriaRoleLint;
+
+impl Lint for AriaRoleLint {
+    const NAME: &'static str = "aria_role";
+
+    fn lint(&mut self, element: &HtmlElement, warnings: &mut Warnings) {
+        let role = match get_literal_attribute(&element.props, "role") {
+            Some(m) => m,
+            None => return,
+        };
+
+        // A role may be followed by fallback roles.
+        for role_name in role.value().split_ascii_whitespace() {
+            if !ARIA_ROLES.contains(&role_name) {
+                warnings.warn(
+                    role.span(),
+                    format!(
+                        "`{role_name}` is not a valid ARIA role. \
+                         https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Roles"
+                    ),
+                )
+            }
+        }
+    }
+}
+
+/// Checks that non-interactive elements with an `onclick` listener can be used with a keyboard.
+#[derive(Default)]
+pub struct ClickEventsLint;
+
+impl Lint for ClickEventsLint {
+    const NAME: &'static str = "click_events";
+
+    fn lint(&mut self, element: &HtmlElement, warnings: &mut Warnings) {
+        let span = match get_tag_name(element) {
+            // Custom elements may be interactive.
+            Some((name, span)) if !name.contains('-') && !INTERACTIVE.contains(&name.as_str()) => {
+                span
+            }
+            _ => return,
+        };
+
+        let has_onclick = element
+            .props
+            .listeners
+            .iter()
+            .any(|m| m.label.eq_ignore_ascii_case("onclick"));
+        let has_role = get_attribute(&element.props, "role").is_some();
+        let has_tabindex = get_attribute(&element.props, "tabindex").is_some();
+
+        if has_onclick && !(has_role && has_tabindex) {
+            warnings.warn(
+                span,
+                "Non-interactive elements with an `onclick` listener should have a `role` and a \
+                 `tabindex` attribute, so they can be used with a keyboard and assistive \
+                 technologies. Consider using a `<button>` instead. \
+                 https://developer.mozilla.org/en-US/docs/Learn/Accessibility/HTML#building_keyboard_accessibility_back_in",
+            )
+        }
+    }
+}
+
+/// Checks that literal `id` attributes are unique in a macro invocation.
+#[derive(Default)]
+pub struct DuplicateIdLint {
+    ids: HashSet<String>,
+}
+
+impl Lint for DuplicateIdLint {
+    const NAME: &'static str = "duplicate_id";
+
+    fn lint(&mut self, element: &HtmlElement, warnings: &mut Warnings) {
+        let id = match get_literal_attribute(&element.props, "id") {
+            Some(m) => m,
+            None => return,
+        };
+
+        if !self.ids.insert(id.value()) {
+            warnings.warn(
+                id.span(),
+                format!(
+                    "The id `{}` is used by another element of this `html!`. Ids must be unique \
+                     in a document.",
+                    id.value()
+                ),
+            )
+        }
+    }
+}
+
+/// Checks that attributes are known for the tag of their element.
+///
+/// Unknown listeners fail compilation instead, see [`check_element_props`].
+///
+/// [`check_element_props`]: crate::props::check_element_props
+#[derive(Default)]
+pub struct UnknownAttributeLint;
+
+impl Lint for UnknownAttributeLint {
+    const NAME: &'static str = UNKNOWN_ATTRIBUTES;
+
+    fn lint(&mut self, element: &HtmlElement, warnings: &mut Warnings) {
+        let tag = match &element.name {
+            TagName::Lit(name) => name,
+            TagName::Expr(_) => return,
+        };
+
+        for attr in unknown_attributes(tag, &element.props) {
+            if !attr.is_listener() {
+                let UnknownAttribute { prop, message } = attr;
+                warnings.warn(prop.label.span(), message)
+            }
+        }
+    }
+}
+
+/// Elements that are interactive without a `role`.
+const INTERACTIVE: &[&str] = &[
+    "a", "area", "audio", "button", "details", "embed", "iframe", "input", "label", "option",
+    "select", "summary", "textarea", "video",
+];
+
+/// From: https://www.w3.org/TR/wai-aria-1.2/#state_prop_def
+const ARIA_ATTRIBUTES: &[&str] = &[
+    "aria-activedescendant",
+    "aria-atomic",
+    "aria-autocomplete",
+    "aria-braillelabel",
+    "aria-brailleroledescription",
+    "aria-busy",
+    "aria-checked",
+    "aria-colcount",
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-controls",
+    "aria-current",
+    "aria-describedby",
+    "aria-description",
+    "aria-details",
+    "aria-disabled",
+    "aria-dropeffect",
+    "aria-errormessage",
+    "aria-expanded",
+    "aria-flowto",
+    "aria-grabbed",
+    "aria-haspopup",
+    "aria-hidden",
+    "aria-invalid",
+    "aria-keyshortcuts",
+    "aria-label",
+    "aria-labelledby",
+    "aria-level",
+    "aria-live",
+    "aria-modal",
+    "aria-multiline",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-owns",
+    "aria-placeholder",
+    "aria-posinset",
+    "aria-pressed",
+    "aria-readonly",
+    "aria-relevant",
+    "aria-required",
+    "aria-roledescription",
+    "aria-rowcount",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-selected",
+    "aria-setsize",
+    "aria-sort",
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+];
+
+/// From: https://www.w3.org/TR/wai-aria-1.2/#role_definitions, without abstract roles.
+const ARIA_ROLES: &[&str] = &[
+    "alert",
+    "alertdialog",
+    "application",
+    "article",
+    "banner",
+    "blockquote",
+    "button",
+    "caption",
+    "cell",
+    "checkbox",
+    "code",
+    "columnheader",
+    "combobox",
+    "complementary",
+    "contentinfo",
+    "definition",
+    "deletion",
+    "dialog",
+    "directory",
+    "document",
+    "emphasis",
+    "feed",
+    "figure",
+    "form",
+    "generic",
+    "grid",
+    "gridcell",
+    "group",
+    "heading",
+    "img",
+    "insertion",
+    "link",
+    "list",
+    "listbox",
+    "listitem",
+    "log",
+    "main",
+    "marquee",
+    "math",
+    "menu",
+    "menubar",
+    "menuitem",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "meter",
+    "navigation",
+    "none",
+    "note",
+    "option",
+    "paragraph",
+    "presentation",
+    "progressbar",
+    "radio",
+    "radiogroup",
+    "region",
+    "row",
+    "rowgroup",
+    "rowheader",
+    "scrollbar",
+    "search",
+    "searchbox",
+    "separator",
+    "slider",
+    "spinbutton",
+    "status",
+    "strong",
+    "subscript",
+    "superscript",
+    "switch",
+    "tab",
+    "table",
+    "tablist",
+    "tabpanel",
+    "term",
+    "textbox",
+    "time",
+    "timer",
+    "toolbar",
+    "tooltip",
+    "tree",
+    "treegrid",
+    "treeitem",
+];
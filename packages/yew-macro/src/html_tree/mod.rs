@@ -12,9 +12,11 @@ mod html_block;
 mod html_component;
 mod html_dashed_name;
 mod html_element;
+mod html_for;
 mod html_if;
 mod html_iterable;
 mod html_list;
+mod html_match;
 mod html_node;
 mod lint;
 mod tag;
@@ -22,14 +24,18 @@ mod tag;
 use html_block::HtmlBlock;
 use html_component::HtmlComponent;
 pub use html_dashed_name::HtmlDashedName;
-use html_element::HtmlElement;
+use html_element::{HtmlElement, TagName};
+use html_for::HtmlFor;
 use html_if::HtmlIf;
 use html_iterable::HtmlIterable;
 use html_list::HtmlList;
+use html_match::HtmlMatch;
 use html_node::HtmlNode;
+use lint::AllowedLints;
 use tag::TagTokens;
 
 use self::html_block::BlockContent;
+use crate::props::{check_element_props, UNKNOWN_ATTRIBUTES};
 
 pub enum HtmlType {
     Block,
@@ -37,6 +43,8 @@ pub enum HtmlType {
     List,
     Element,
     If,
+    Match,
+    For,
     Empty,
 }
 
@@ -46,6 +54,8 @@ pub enum HtmlTree {
     List(Box<HtmlList>),
     Element(Box<HtmlElement>),
     If(Box<HtmlIf>),
+    Match(Box<HtmlMatch>),
+    For(Box<HtmlFor>),
     Empty,
 }
 
@@ -60,6 +70,8 @@ impl Parse for HtmlTree {
             HtmlType::Block => HtmlTree::Block(Box::new(input.parse()?)),
             HtmlType::List => HtmlTree::List(Box::new(input.parse()?)),
             HtmlType::If => HtmlTree::If(Box::new(input.parse()?)),
+            HtmlType::Match => HtmlTree::Match(Box::new(input.parse()?)),
+            HtmlType::For => HtmlTree::For(Box::new(input.parse()?)),
         };
         Ok(html_tree)
     }
@@ -83,6 +95,10 @@ impl HtmlTree {
             Some(HtmlType::Block)
         } else if HtmlIf::peek(input.cursor()).is_some() {
             Some(HtmlType::If)
+        } else if HtmlMatch::peek(input.cursor()).is_some() {
+            Some(HtmlType::Match)
+        } else if HtmlFor::peek(input.cursor()).is_some() {
+            Some(HtmlType::For)
         } else if input.peek(Token![<]) {
             let _lt: Token![<] = input.parse().ok()?;
 
@@ -118,9 +134,24 @@ impl HtmlTree {
     }
 }
 
+impl HtmlTree {
+    /// Calls `f` with this tree and each tree nested in it, parents before their children.
+    pub fn visit(&self, f: &mut dyn FnMut(&HtmlTree)) {
+        f(self);
+        match self {
+            HtmlTree::Component(comp) => comp.children.visit(f),
+            HtmlTree::Element(tag) => tag.children.visit(f),
+            HtmlTree::List(list) => list.children.visit(f),
+            HtmlTree::If(block) => block.visit(f),
+            HtmlTree::Match(block) => block.visit(f),
+            HtmlTree::For(block) => block.body.visit(f),
+            HtmlTree::Block(_) | HtmlTree::Empty => {}
+        }
+    }
+}
+
 impl ToTokens for HtmlTree {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        lint::lint_all(self);
         match self {
             HtmlTree::Empty => tokens.extend(quote! {
                 ::yew::virtual_dom::VNode::VList(::yew::virtual_dom::VList::new())
@@ -130,20 +161,29 @@ impl ToTokens for HtmlTree {
             HtmlTree::List(list) => list.to_tokens(tokens),
             HtmlTree::Block(block) => block.to_tokens(tokens),
             HtmlTree::If(block) => block.to_tokens(tokens),
+            HtmlTree::Match(block) => block.to_tokens(tokens),
+            HtmlTree::For(block) => block.to_tokens(tokens),
         }
     }
 }
 
 pub enum HtmlRoot {
-    Tree(HtmlTree),
+    Tree(HtmlTree, AllowedLints),
     Iterable(Box<HtmlIterable>),
     Node(Box<HtmlNode>),
 }
 
 impl Parse for HtmlRoot {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let allowed = AllowedLints::parse_attributes(input)?;
         let html_root = if HtmlTree::peek_html_type(input).is_some() {
-            Self::Tree(input.parse()?)
+            let tree = input.parse()?;
+            if !allowed.contains(UNKNOWN_ATTRIBUTES) {
+                check_attributes(&tree)?;
+            }
+            Self::Tree(tree, allowed)
+        } else if !allowed.is_empty() {
+            return Err(input.error("lints can only be allowed for html nodes"));
         } else if HtmlIterable::peek(input.cursor()).is_some() {
             Self::Iterable(Box::new(input.parse()?))
         } else {
@@ -163,10 +203,37 @@ impl Parse for HtmlRoot {
     }
 }
 
+/// Checks the listeners of the elements of a tree against the schema of their tag, see
+/// [`check_element_props`].
+fn check_attributes(tree: &HtmlTree) -> syn::Result<()> {
+    let mut errors: Option<syn::Error> = None;
+    tree.visit(&mut |tree| {
+        if let HtmlTree::Element(el) = tree {
+            if let TagName::Lit(name) = &el.name {
+                if let Err(e) = check_element_props(name, &el.props) {
+                    match errors {
+                        Some(ref mut errors) => errors.combine(e),
+                        None => errors = Some(e),
+                    }
+                }
+            }
+        }
+    });
+
+    errors.map_or(Ok(()), Err)
+}
+
 impl ToTokens for HtmlRoot {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
-            Self::Tree(tree) => tree.to_tokens(tokens),
+            Self::Tree(tree, allowed) => {
+                let warnings = lint::lint_all(tree, allowed);
+                if warnings.is_empty() {
+                    tree.to_tokens(tokens)
+                } else {
+                    tokens.extend(quote! {{ #warnings #tree }})
+                }
+            }
             Self::Node(node) => node.to_tokens(tokens),
             Self::Iterable(iterable) => iterable.to_tokens(tokens),
         }
@@ -228,6 +295,13 @@ impl HtmlChildrenTree {
         self.0.is_empty()
     }
 
+    /// Calls `f` with each child and each tree nested in them, see [`HtmlTree::visit`].
+    pub fn visit(&self, f: &mut dyn FnMut(&HtmlTree)) {
+        for child in &self.0 {
+            child.visit(f);
+        }
+    }
+
     // Check if each child represents a single node.
     // This is the case when no expressions are used.
     fn only_single_node_children(&self) -> bool {
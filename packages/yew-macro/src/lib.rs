@@ -49,15 +49,19 @@
 //! Please refer to [https://github.com/yewstack/yew](https://github.com/yewstack/yew) for how to set this up.
 
 mod classes;
+mod css;
+mod custom_element;
 mod derive_props;
 mod function_component;
 mod hook;
 mod html_tree;
 mod props;
 mod stringify;
+mod style;
 mod use_prepared_state;
 mod use_transitive_state;
 
+use custom_element::{custom_element_impl, CustomElementName};
 use derive_props::DerivePropsInput;
 use function_component::{function_component_impl, FunctionComponent, FunctionComponentName};
 use hook::{hook_impl, HookFn};
@@ -105,7 +109,10 @@ fn is_ide_completion() -> bool {
     }
 }
 
-#[proc_macro_derive(Properties, attributes(prop_or, prop_or_else, prop_or_default))]
+#[proc_macro_derive(
+    Properties,
+    attributes(prop_or, prop_or_else, prop_or_default, prop_group, prop, rest)
+)]
 pub fn derive_props(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DerivePropsInput);
     TokenStream::from(input.into_token_stream())
@@ -131,12 +138,31 @@ pub fn props(input: TokenStream) -> TokenStream {
     TokenStream::from(props.into_token_stream())
 }
 
+#[doc(hidden)]
+#[proc_macro]
+pub fn __forwarded_attributes(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as props::ForwardedAttributes);
+    TokenStream::from(item.into_token_stream())
+}
+
 #[proc_macro]
 pub fn classes(input: TokenStream) -> TokenStream {
     let classes = parse_macro_input!(input as classes::Classes);
     TokenStream::from(classes.into_token_stream())
 }
 
+#[proc_macro]
+pub fn style(input: TokenStream) -> TokenStream {
+    let style = parse_macro_input!(input as style::Style);
+    TokenStream::from(style.into_token_stream())
+}
+
+#[proc_macro]
+pub fn css(input: TokenStream) -> TokenStream {
+    let css = parse_macro_input!(input as css::Css);
+    TokenStream::from(css.into_token_stream())
+}
+
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
 pub fn function_component(attr: TokenStream, item: TokenStream) -> proc_macro::TokenStream {
@@ -148,6 +174,17 @@ pub fn function_component(attr: TokenStream, item: TokenStream) -> proc_macro::T
         .into()
 }
 
+#[proc_macro_error::proc_macro_error]
+#[proc_macro_attribute]
+pub fn custom_element(attr: TokenStream, item: TokenStream) -> proc_macro::TokenStream {
+    let item = parse_macro_input!(item as syn::ItemStruct);
+    let attr = parse_macro_input!(attr as CustomElementName);
+
+    custom_element_impl(attr, item)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
 pub fn hook(attr: TokenStream, item: TokenStream) -> proc_macro::TokenStream {
@@ -2,12 +2,15 @@ use std::convert::TryFrom;
 
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
 use syn::token::DotDot;
 use syn::Expr;
 
-use super::{Prop, Props, SpecialProps, CHILDREN_LABEL};
+use super::{
+    is_forwarded, is_forwarded_dashed, Prop, PropDirective, Props, SpecialProps, CHILDREN_LABEL,
+};
 
 struct BaseExpr {
     pub dot_dot: DotDot,
@@ -59,15 +62,34 @@ impl ComponentProps {
             None
         };
 
-        let check_props: TokenStream = self
+        // Without a base expression, attributes and listeners may be forwarded to a `#[rest]`
+        // prop instead, so their labels are checked against the `PropLabels` of the props.
+        let labels_ident = Ident::new("__yew_labels", props_ty.span());
+        let (forwarded, props): (Vec<_>, Vec<_>) = self
             .props
             .iter()
+            .filter(|prop| prop.label.extended.is_empty())
+            .partition(|prop| self.base_expr.is_none() && is_forwarded_prop(prop));
+        let check_props: TokenStream = props
+            .into_iter()
             .map(|Prop { label, .. }| {
                 quote_spanned! {Span::call_site().located_at(label.span())=>
                     let _ = &#props_ident.#label;
                 }
             })
             .collect();
+        let check_labels = (!forwarded.is_empty()).then(|| {
+            let checks = forwarded.into_iter().map(|Prop { label, .. }| {
+                quote_spanned! {Span::call_site().located_at(label.span())=>
+                    let _ = &#labels_ident.#label;
+                }
+            });
+            quote_spanned! {props_ty.span()=>
+                let _ = |#labels_ident: <#props_ty as ::yew::html::PropLabels>::Labels| {
+                    #( #checks )*
+                };
+            }
+        });
 
         quote_spanned! {props_ty.span()=>
             #[allow(clippy::no_effect)]
@@ -76,6 +98,7 @@ impl ComponentProps {
                     #check_children
                     #check_props
                 };
+                #check_labels
             };
         }
     }
@@ -100,8 +123,19 @@ impl ComponentProps {
                     let #token_ident = ::yew::html::AssertAllProps;
                 };
                 let set_props = self.props.iter().map(|Prop { label, value, .. }| {
-                    quote_spanned! {value.span()=>
-                        let #token_ident = #builder_ident.#label(#token_ident, #value);
+                    if label.extended.is_empty() {
+                        quote_spanned! {value.span()=>
+                            let #token_ident = #builder_ident.#label(#token_ident, #value);
+                        }
+                    } else {
+                        let name = label.to_lit_str();
+                        quote_spanned! {label.span()=>
+                            let #token_ident = #builder_ident.__yew_rest_attribute(
+                                #token_ident,
+                                #name,
+                                #value,
+                            );
+                        }
                     }
                 });
                 let set_children = children_renderer.map(|children| {
@@ -158,12 +192,13 @@ impl ComponentProps {
 
 impl Parse for ComponentProps {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let props = validate(input.parse()?)?;
+        let props = input.parse::<Props>()?;
         let base_expr = if input.is_empty() {
             None
         } else {
             Some(input.parse::<BaseExpr>()?)
         };
+        let props = validate(props, base_expr.is_some())?;
 
         if input.is_empty() {
             let base_expr = base_expr.map(|base| base.expr);
@@ -182,20 +217,42 @@ impl TryFrom<Props> for ComponentProps {
 
     fn try_from(props: Props) -> Result<Self, Self::Error> {
         Ok(Self {
-            props: validate(props)?,
+            props: validate(props, false)?,
             base_expr: None,
         })
     }
 }
 
-fn validate(props: Props) -> Result<Props, syn::Error> {
+/// Checks if the prop is an attribute or a listener which can be forwarded to a `#[rest]` prop.
+fn is_forwarded_prop(prop: &Prop) -> bool {
+    if prop.label.extended.is_empty() {
+        is_forwarded(&prop.label.name.unraw().to_string())
+    } else {
+        is_forwarded_dashed(&prop.label.to_string())
+    }
+}
+
+fn validate(props: Props, has_base_expr: bool) -> Result<Props, syn::Error> {
     props.check_no_duplicates()?;
     props.check_all(|prop| {
-        if !prop.label.extended.is_empty() {
+        if let Some(PropDirective::Bind(span)) = prop.directive {
+            Err(syn::Error::new(
+                span,
+                "`bind:` can only be used on elements (hint: pass the state as a prop instead)",
+            ))
+        } else if prop.label.extended.is_empty() {
+            Ok(())
+        } else if !is_forwarded_prop(prop) {
             Err(syn::Error::new_spanned(
                 &prop.label,
                 "expected a valid Rust identifier",
             ))
+        } else if has_base_expr {
+            Err(syn::Error::new_spanned(
+                &prop.label,
+                "expected a valid Rust identifier (attributes can't be forwarded to a `#[rest]` \
+                 prop when using a base props expression)",
+            ))
         } else {
             Ok(())
         }
@@ -2,9 +2,11 @@ use std::collections::HashSet;
 
 use once_cell::sync::Lazy;
 use syn::parse::{Parse, ParseStream};
-use syn::{Expr, ExprTuple};
+use syn::token::Brace;
+use syn::{Expr, ExprTuple, Token};
 
-use super::{Prop, Props, SpecialProps};
+use super::prop::strip_braces;
+use super::{Prop, PropDirective, Props, SpecialProps};
 
 pub enum ClassesForm {
     Tuple(ExprTuple),
@@ -27,11 +29,37 @@ pub struct ElementProps {
     pub value: Option<Prop>,
     pub checked: Option<Prop>,
     pub special: SpecialProps,
+    /// Attributes and listeners forwarded by a component, spread with `..{attrs}`
+    pub spread: Option<Expr>,
+    /// State bound with `bind:value`
+    pub bind_value: Option<Prop>,
+    /// State bound with `bind:checked`
+    pub bind_checked: Option<Prop>,
 }
 
 impl Parse for ElementProps {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut props = input.parse::<Props>()?;
+        let spread = if input.peek(Token![..]) {
+            Some(parse_spread(input)?)
+        } else {
+            None
+        };
+
+        let mut bindings =
+            props.drain_filter(|prop| matches!(prop.directive, Some(PropDirective::Bind(_))));
+        bindings.check_no_duplicates()?;
+        let bind_value = bindings.pop("value");
+        let bind_checked = bindings.pop("checked");
+        if let Some(prop) = bindings.first() {
+            return Err(syn::Error::new_spanned(
+                &prop.label,
+                format!(
+                    "cannot bind `{}` (hint: only `bind:value` and `bind:checked` are supported)",
+                    prop.label
+                ),
+            ));
+        }
 
         let listeners =
             props.drain_filter(|prop| LISTENER_SET.contains(prop.label.to_string().as_str()));
@@ -47,6 +75,12 @@ impl Parse for ElementProps {
             .map(|prop| ClassesForm::from_expr(prop.value));
         let value = props.pop("value");
         let checked = props.pop("checked");
+        if let (Some(checked), Some(_)) = (&checked, &bind_checked) {
+            return Err(syn::Error::new_spanned(
+                &checked.label,
+                "`checked` cannot be set together with `bind:checked`",
+            ));
+        }
         let special = props.special;
 
         Ok(Self {
@@ -57,11 +91,32 @@ impl Parse for ElementProps {
             booleans: booleans.into_vec(),
             value,
             special,
+            spread,
+            bind_value,
+            bind_checked,
         })
     }
 }
 
-static BOOLEAN_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+fn parse_spread(input: ParseStream) -> syn::Result<Expr> {
+    let dot_dot = input.parse::<Token![..]>()?;
+    if !input.peek(Brace) {
+        return Err(syn::Error::new_spanned(
+            dot_dot,
+            "expected an expression in braces after `..` (hint: `..{attrs}`)",
+        ));
+    }
+    let expr = strip_braces(input.parse()?)?;
+    if !input.is_empty() {
+        return Err(syn::Error::new_spanned(
+            expr,
+            "spread attributes must appear last in the list of attributes",
+        ));
+    }
+    Ok(expr)
+}
+
+pub(super) static BOOLEAN_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     [
         // Living Standard
         // From: https://html.spec.whatwg.org/#attributes-3
@@ -97,7 +152,7 @@ static BOOLEAN_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     .into()
 });
 
-static LISTENER_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+pub(super) static LISTENER_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     [
         // Living Standard
         // From: https://html.spec.whatwg.org/multipage/webappapis.html#globaleventhandlers
@@ -2,10 +2,14 @@ mod component;
 mod element;
 mod prop;
 mod prop_macro;
+mod rest;
+mod schema;
 
 pub use component::*;
 pub use element::*;
 pub use prop::*;
 pub use prop_macro::PropsMacroInput;
+pub use rest::{is_forwarded, is_forwarded_dashed, ForwardedAttributes};
+pub use schema::{check_element_props, unknown_attributes, UnknownAttribute, UNKNOWN_ATTRIBUTES};
 
 const CHILDREN_LABEL: &str = "children";
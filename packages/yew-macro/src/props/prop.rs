@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 use std::ops::{Deref, DerefMut};
 
-use proc_macro2::{Spacing, Span, TokenStream, TokenTree};
+use proc_macro2::{Ident, Spacing, Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned};
 use syn::parse::{Parse, ParseBuffer, ParseStream};
 use syn::spanned::Spanned;
@@ -14,6 +14,22 @@ use crate::stringify::Stringify;
 #[derive(Copy, Clone)]
 pub enum PropDirective {
     ApplyAsProperty(Token![~]),
+    /// `bind:value` or `bind:checked`, spanning `bind:`
+    Bind(Span),
+}
+
+impl PropDirective {
+    fn parse_bind(input: ParseStream) -> syn::Result<Option<Self>> {
+        let fork = input.fork();
+        match fork.parse::<Ident>() {
+            Ok(ident) if ident == "bind" && fork.peek(Token![:]) && !fork.peek(Token![::]) => {}
+            _ => return Ok(None),
+        }
+        let bind = input.parse::<Ident>()?;
+        let colon = input.parse::<Token![:]>()?;
+        let span = bind.span().join(colon.span).unwrap_or_else(|| bind.span());
+        Ok(Some(Self::Bind(span)))
+    }
 }
 
 pub struct Prop {
@@ -25,11 +41,11 @@ pub struct Prop {
 
 impl Parse for Prop {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let directive = input
-            .parse::<Token![~]>()
-            .map(PropDirective::ApplyAsProperty)
-            .ok();
-        if input.peek(Brace) {
+        let directive = match input.parse::<Token![~]>() {
+            Ok(tilde) => Some(PropDirective::ApplyAsProperty(tilde)),
+            Err(_) => PropDirective::parse_bind(input)?,
+        };
+        if input.peek(Brace) && !matches!(directive, Some(PropDirective::Bind(_))) {
             Self::parse_shorthand_prop_assignment(input, directive)
         } else {
             Self::parse_prop_assignment(input, directive)
@@ -138,7 +154,7 @@ fn parse_prop_value(input: &ParseBuffer) -> syn::Result<Expr> {
     }
 }
 
-fn strip_braces(block: ExprBlock) -> syn::Result<Expr> {
+pub(super) fn strip_braces(block: ExprBlock) -> syn::Result<Expr> {
     match block {
         ExprBlock {
             block: Block { mut stmts, .. },
@@ -0,0 +1,145 @@
+//! Attributes and listeners given to a component which aren't props of it are forwarded to its
+//! `#[rest]` prop, through the methods of `yew::html::ForwardedAttributes`.
+
+use std::collections::HashSet;
+
+use once_cell::sync::Lazy;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::{Field, Fields, ItemStruct};
+
+use super::element::{BOOLEAN_SET, LISTENER_SET};
+use super::schema::attribute_names;
+
+/// The attributes with a method in `ForwardedAttributes`, which are the ones in the schema with a
+/// valid identifier as name.
+static FORWARDED_ATTRIBUTES: Lazy<Vec<&'static str>> = Lazy::new(|| {
+    attribute_names()
+        .into_iter()
+        .filter(|name| {
+            name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && *name != "class"
+                && !LISTENER_SET.contains(name)
+        })
+        .collect()
+});
+
+static FORWARDED_SET: Lazy<HashSet<&'static str>> =
+    Lazy::new(|| FORWARDED_ATTRIBUTES.iter().copied().collect());
+
+/// The attributes in the schema which aren't valid identifiers, like `accept-charset`.
+static DASHED_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    attribute_names()
+        .into_iter()
+        .filter(|name| name.contains('-'))
+        .collect()
+});
+
+/// Checks if a prop with this label can be forwarded to a `#[rest]` prop by its own method.
+pub fn is_forwarded(label: &str) -> bool {
+    label == "class" || LISTENER_SET.contains(label) || FORWARDED_SET.contains(label)
+}
+
+/// Checks if a prop with this dashed label can be forwarded to a `#[rest]` prop.
+///
+/// Only `aria-*`, `data-*` and the dashed attributes of the schema are, so that typos in the
+/// names of props are still reported by the `html!` macro.
+pub fn is_forwarded_dashed(label: &str) -> bool {
+    label.starts_with("aria-") || label.starts_with("data-") || DASHED_SET.contains(label)
+}
+
+fn method_name(name: &str) -> Ident {
+    syn::parse_str::<Ident>(name).unwrap_or_else(|_| Ident::new_raw(name, Span::call_site()))
+}
+
+/// The `ForwardedAttributes` struct, to which a field and a method are added for each known
+/// attribute and listener.
+pub struct ForwardedAttributes {
+    item: ItemStruct,
+}
+
+impl Parse for ForwardedAttributes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let item = input.parse::<ItemStruct>()?;
+        if !matches!(item.fields, Fields::Named(_)) {
+            return Err(syn::Error::new_spanned(
+                item,
+                "expected a struct with named fields",
+            ));
+        }
+        Ok(Self { item })
+    }
+}
+
+impl ToTokens for ForwardedAttributes {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let mut listeners = LISTENER_SET.iter().copied().collect::<Vec<_>>();
+        listeners.sort_unstable();
+        let names = ["class"]
+            .into_iter()
+            .chain(listeners.iter().copied())
+            .chain(FORWARDED_ATTRIBUTES.iter().copied());
+
+        let mut item = self.item.clone();
+        if let Fields::Named(fields) = &mut item.fields {
+            fields.named.extend(names.map(|name| {
+                let name = method_name(name);
+                Field::parse_named
+                    .parse2(quote! { pub #name: () })
+                    .expect("failed to parse field")
+            }));
+        }
+
+        let listeners = listeners.into_iter().map(|name| {
+            let method = method_name(name);
+            quote! {
+                #[inline]
+                pub fn #method<Token>(
+                    &mut self,
+                    token: Token,
+                    value: impl crate::html::IntoEventCallback<crate::html::#method::Event>,
+                ) -> Token {
+                    self.__yew_rest_listener(token, crate::html::#method::Wrapper::__macro_new(value))
+                }
+            }
+        });
+
+        let attributes = FORWARDED_ATTRIBUTES.iter().map(|name| {
+            let method = method_name(name);
+            if *name == "checked" || BOOLEAN_SET.contains(name) {
+                quote! {
+                    #[inline]
+                    pub fn #method<Token>(&mut self, token: Token, value: bool) -> Token {
+                        self.__yew_rest_boolean(token, #name, value)
+                    }
+                }
+            } else {
+                quote! {
+                    #[inline]
+                    pub fn #method<Token>(
+                        &mut self,
+                        token: Token,
+                        value: impl crate::html::IntoPropValue<
+                            ::std::option::Option<crate::virtual_dom::AttrValue>
+                        >,
+                    ) -> Token {
+                        self.__yew_rest_attribute(token, #name, value)
+                    }
+                }
+            }
+        });
+
+        let ident = &item.ident;
+        tokens.extend(quote! {
+            #item
+
+            #[allow(non_snake_case)]
+            impl #ident {
+                #( #listeners )*
+                #( #attributes )*
+            }
+        });
+    }
+}
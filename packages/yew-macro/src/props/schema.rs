@@ -0,0 +1,1046 @@
+//! The attributes of known HTML, SVG and MathML elements, used to catch typos in attribute and
+//! listener names at compile time.
+//!
+//! Unknown listeners fail compilation, while unknown attributes are reported by the
+//! `unknown_attributes` lint, as libraries and browsers use attributes outside of the standards
+//! (e.g. `hx-get` or `autocorrect`). Elements which aren't in the schema (like custom elements)
+//! and dynamic tags aren't checked, and both checks are skipped for a whole macro invocation with
+//! `#![allow(unknown_attributes)]`.
+
+use std::collections::{HashMap, HashSet};
+
+use once_cell::sync::Lazy;
+
+use super::element::LISTENER_SET;
+use super::{ElementProps, Prop, PropDirective};
+use crate::html_tree::HtmlDashedName;
+
+/// The name of the check in `#![allow(...)]` attributes.
+pub const UNKNOWN_ATTRIBUTES: &str = "unknown_attributes";
+
+/// An attribute which isn't known for the tag of its element.
+pub struct UnknownAttribute<'a> {
+    pub prop: &'a Prop,
+    pub message: String,
+}
+
+impl UnknownAttribute<'_> {
+    /// Whether the attribute is named like an event listener, e.g. a misspelled one.
+    pub fn is_listener(&self) -> bool {
+        self.prop
+            .label
+            .to_ascii_lowercase_string()
+            .starts_with("on")
+    }
+}
+
+/// Returns the attributes and listeners of an element which aren't known for its tag.
+pub fn unknown_attributes<'a>(
+    tag: &HtmlDashedName,
+    props: &'a ElementProps,
+) -> Vec<UnknownAttribute<'a>> {
+    let schema = match ElementSchema::get(&tag.to_ascii_lowercase_string()) {
+        Some(m) => m,
+        None => return Vec::new(),
+    };
+
+    props
+        .attributes
+        .iter()
+        .chain(&props.booleans)
+        .chain(&props.value)
+        .chain(&props.checked)
+        // Properties are set on the element object, which may have any property.
+        .filter(|prop| !matches!(prop.directive, Some(PropDirective::ApplyAsProperty(_))))
+        .filter_map(|prop| {
+            schema
+                .check(tag, prop)
+                .map(|message| UnknownAttribute { prop, message })
+        })
+        .collect()
+}
+
+/// Checks that the listeners of an element are known for its tag.
+///
+/// Unknown attributes are reported by the `unknown_attributes` lint instead.
+pub fn check_element_props(tag: &HtmlDashedName, props: &ElementProps) -> syn::Result<()> {
+    let mut errors = unknown_attributes(tag, props)
+        .into_iter()
+        .filter(UnknownAttribute::is_listener)
+        .map(|m| syn::Error::new_spanned(&m.prop.label, m.message));
+    match errors.next() {
+        Some(mut error) => {
+            errors.for_each(|m| error.combine(m));
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns the names of all the attributes in the schema, in the case they are written in.
+pub fn attribute_names() -> Vec<&'static str> {
+    let mut names = HTML_ELEMENTS
+        .values()
+        .chain([
+            &*HTML_GLOBAL_ATTRIBUTES,
+            &*SVG_ATTRIBUTES,
+            &*MATHML_ATTRIBUTES,
+        ])
+        .flat_map(|m| m.values().copied())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Attribute names by their lowercase name, as attribute names are case-insensitive.
+type AttributeSet = HashMap<String, &'static str>;
+
+fn attribute_set(names: &[&'static str]) -> AttributeSet {
+    names.iter().map(|m| (m.to_ascii_lowercase(), *m)).collect()
+}
+
+/// The attributes allowed on an element.
+struct ElementSchema {
+    sets: Vec<&'static AttributeSet>,
+}
+
+impl ElementSchema {
+    fn get(tag_name: &str) -> Option<Self> {
+        let mut sets = Vec::new();
+        // Some tags, like `<a>`, exist in several namespaces.
+        if let Some(m) = HTML_ELEMENTS.get(tag_name) {
+            sets.extend([&*HTML_GLOBAL_ATTRIBUTES, m]);
+        }
+        if SVG_ELEMENTS.contains(tag_name) {
+            sets.push(&*SVG_ATTRIBUTES);
+        }
+        if MATHML_ELEMENTS.contains(tag_name) {
+            sets.push(&*MATHML_ATTRIBUTES);
+        }
+
+        (!sets.is_empty()).then_some(Self { sets })
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.sets.iter().any(|m| m.contains_key(name))
+    }
+
+    /// Returns why `prop` is unknown, if it is.
+    fn check(&self, tag: &HtmlDashedName, prop: &Prop) -> Option<String> {
+        let name = prop.label.to_ascii_lowercase_string();
+        if name.starts_with("data-") || name.starts_with("aria-") || self.contains(&name) {
+            return None;
+        }
+
+        let label = &prop.label;
+        let message = if name.starts_with("on") {
+            let hint = match closest(&name, LISTENER_SET.iter().map(|m| (*m, *m))) {
+                Some(m) => format!("did you mean `{m}`?"),
+                None => "see the listeners in `yew::html::listener` for the supported events"
+                    .to_string(),
+            };
+            format!("unknown event listener `{label}` for `<{tag}>` (hint: {hint})")
+        } else {
+            let candidates = self
+                .sets
+                .iter()
+                .flat_map(|m| m.iter().map(|(lowercase, m)| (lowercase.as_str(), *m)));
+            let hint = match closest(&name, candidates) {
+                Some(m) => format!("did you mean `{m}`?"),
+                None => format!(
+                    "use a `data-*` attribute for custom data, or \
+                     `#![allow({UNKNOWN_ATTRIBUTES})]` to skip this check"
+                ),
+            };
+            format!("unknown attribute `{label}` for `<{tag}>` (hint: {hint})")
+        };
+
+        Some(message)
+    }
+}
+
+/// Returns the candidate closest to the lowercase `name`, if it is close enough to be a typo.
+///
+/// Candidates are pairs of a lowercase name and the name to suggest.
+fn closest<'a>(
+    name: &str,
+    candidates: impl Iterator<Item = (&'a str, &'static str)>,
+) -> Option<&'static str> {
+    let max_distance = (name.len() / 3).max(1);
+    candidates
+        .map(|(lowercase, m)| (edit_distance(name, lowercase), m))
+        .filter(|(distance, _)| *distance <= max_distance)
+        // Ties are broken alphabetically, so suggestions don't depend on the iteration order.
+        .min()
+        .map(|(_, m)| m)
+}
+
+/// The edit distance between two strings, counting insertions, deletions, substitutions and
+/// transpositions of adjacent characters.
+fn edit_distance(lhs: &str, rhs: &str) -> usize {
+    let (lhs, rhs) = (lhs.as_bytes(), rhs.as_bytes());
+    let mut distances = vec![vec![0; rhs.len() + 1]; lhs.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=lhs.len() {
+        for j in 1..=rhs.len() {
+            let cost = usize::from(lhs[i - 1] != rhs[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && lhs[i - 1] == rhs[j - 2] && lhs[i - 2] == rhs[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[lhs.len()][rhs.len()]
+}
+
+static HTML_GLOBAL_ATTRIBUTES: Lazy<AttributeSet> = Lazy::new(|| {
+    attribute_set(&[
+        // Living Standard
+        // From: https://html.spec.whatwg.org/multipage/dom.html#global-attributes
+        "accesskey",
+        "autocapitalize",
+        "autofocus",
+        "class",
+        "contenteditable",
+        "dir",
+        "draggable",
+        "enterkeyhint",
+        "hidden",
+        "id",
+        "inert",
+        "inputmode",
+        "is",
+        "itemid",
+        "itemprop",
+        "itemref",
+        "itemscope",
+        "itemtype",
+        "lang",
+        "nonce",
+        "popover",
+        "slot",
+        "spellcheck",
+        "style",
+        "tabindex",
+        "title",
+        "translate",
+        "writingsuggestions",
+        // From: https://www.w3.org/TR/wai-aria-1.2/#host_general_role
+        "role",
+        // Shadow parts
+        // From: https://drafts.csswg.org/css-shadow-parts/
+        "exportparts",
+        "part",
+        // Namespaces
+        "xmlns",
+    ])
+});
+
+static HTML_ELEMENTS: Lazy<HashMap<&'static str, AttributeSet>> = Lazy::new(|| {
+    let elements: &[(&[&str], &[&str])] = &[
+        // Living Standard
+        // From: https://html.spec.whatwg.org/multipage/indices.html#attributes-3
+        (
+            &[
+                "abbr",
+                "address",
+                "article",
+                "aside",
+                "b",
+                "bdi",
+                "bdo",
+                "br",
+                "caption",
+                "cite",
+                "code",
+                "dd",
+                "dfn",
+                "div",
+                "dl",
+                "dt",
+                "em",
+                "figcaption",
+                "figure",
+                "footer",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "head",
+                "header",
+                "hgroup",
+                "hr",
+                "i",
+                "kbd",
+                "legend",
+                "main",
+                "mark",
+                "menu",
+                "nav",
+                "noscript",
+                "p",
+                "picture",
+                "pre",
+                "rp",
+                "rt",
+                "ruby",
+                "s",
+                "samp",
+                "search",
+                "section",
+                "small",
+                "span",
+                "strong",
+                "sub",
+                "summary",
+                "sup",
+                "tbody",
+                "tfoot",
+                "thead",
+                "tr",
+                "u",
+                "ul",
+                "var",
+                "wbr",
+            ],
+            &[
+                // Legacy presentational attributes, still supported by browsers.
+                "align", "valign", "bgcolor", "width", "height",
+            ],
+        ),
+        (
+            &["a"],
+            &[
+                "download",
+                "href",
+                "hreflang",
+                "media",
+                "ping",
+                "referrerpolicy",
+                "rel",
+                "target",
+                "type",
+            ],
+        ),
+        (
+            &["area"],
+            &[
+                "alt",
+                "coords",
+                "download",
+                "href",
+                "ping",
+                "referrerpolicy",
+                "rel",
+                "shape",
+                "target",
+            ],
+        ),
+        (
+            &["audio"],
+            &[
+                "autoplay",
+                "controls",
+                "crossorigin",
+                "loop",
+                "muted",
+                "preload",
+                "src",
+            ],
+        ),
+        (&["base"], &["href", "target"]),
+        (&["blockquote", "q"], &["cite"]),
+        (&["body"], &["bgcolor", "text", "link", "vlink", "alink"]),
+        (
+            &["button"],
+            &[
+                "disabled",
+                "form",
+                "formaction",
+                "formenctype",
+                "formmethod",
+                "formnovalidate",
+                "formtarget",
+                "name",
+                "popovertarget",
+                "popovertargetaction",
+                "type",
+                "value",
+            ],
+        ),
+        (&["canvas"], &["height", "width"]),
+        (&["col", "colgroup"], &["span", "width", "align", "valign"]),
+        (&["data"], &["value"]),
+        (&["datalist"], &[]),
+        (&["del", "ins"], &["cite", "datetime"]),
+        (&["details"], &["name", "open"]),
+        (&["dialog"], &["open"]),
+        (&["embed"], &["height", "src", "type", "width"]),
+        (&["fieldset"], &["disabled", "form", "name"]),
+        (
+            &["form"],
+            &[
+                "accept-charset",
+                "action",
+                "autocomplete",
+                "enctype",
+                "method",
+                "name",
+                "novalidate",
+                "rel",
+                "target",
+            ],
+        ),
+        (&["html"], &["manifest"]),
+        (
+            &["iframe"],
+            &[
+                "allow",
+                "allowfullscreen",
+                "frameborder",
+                "height",
+                "loading",
+                "name",
+                "referrerpolicy",
+                "sandbox",
+                "scrolling",
+                "src",
+                "srcdoc",
+                "width",
+            ],
+        ),
+        (
+            &["img"],
+            &[
+                "align",
+                "alt",
+                "border",
+                "crossorigin",
+                "decoding",
+                "fetchpriority",
+                "height",
+                "ismap",
+                "loading",
+                "referrerpolicy",
+                "sizes",
+                "src",
+                "srcset",
+                "usemap",
+                "width",
+            ],
+        ),
+        (
+            &["input"],
+            &[
+                "accept",
+                "alt",
+                "autocomplete",
+                "capture",
+                "checked",
+                "dirname",
+                "disabled",
+                "form",
+                "formaction",
+                "formenctype",
+                "formmethod",
+                "formnovalidate",
+                "formtarget",
+                "height",
+                "list",
+                "max",
+                "maxlength",
+                "min",
+                "minlength",
+                "multiple",
+                "name",
+                "pattern",
+                "placeholder",
+                "popovertarget",
+                "popovertargetaction",
+                "readonly",
+                "required",
+                "size",
+                "src",
+                "step",
+                "type",
+                "value",
+                "webkitdirectory",
+                "width",
+            ],
+        ),
+        (&["label"], &["for", "form"]),
+        (&["li"], &["value", "type"]),
+        (
+            &["link"],
+            &[
+                "as",
+                "blocking",
+                "color",
+                "crossorigin",
+                "disabled",
+                "fetchpriority",
+                "href",
+                "hreflang",
+                "imagesizes",
+                "imagesrcset",
+                "integrity",
+                "media",
+                "referrerpolicy",
+                "rel",
+                "sizes",
+                "type",
+            ],
+        ),
+        (&["map"], &["name"]),
+        (
+            &["meta"],
+            &[
+                "charset",
+                "content",
+                "http-equiv",
+                "media",
+                "name",
+                "property",
+            ],
+        ),
+        (
+            &["meter"],
+            &["high", "low", "max", "min", "optimum", "value", "form"],
+        ),
+        (
+            &["object"],
+            &["data", "form", "height", "name", "type", "usemap", "width"],
+        ),
+        (&["ol"], &["reversed", "start", "type"]),
+        (&["optgroup"], &["disabled", "label"]),
+        (&["option"], &["disabled", "label", "selected", "value"]),
+        (&["output"], &["for", "form", "name"]),
+        (&["param"], &["name", "value"]),
+        (&["progress"], &["max", "value"]),
+        (
+            &["script"],
+            &[
+                "async",
+                "blocking",
+                "crossorigin",
+                "defer",
+                "fetchpriority",
+                "integrity",
+                "nomodule",
+                "referrerpolicy",
+                "src",
+                "type",
+            ],
+        ),
+        (
+            &["select"],
+            &[
+                "autocomplete",
+                "disabled",
+                "form",
+                "multiple",
+                "name",
+                "required",
+                "size",
+            ],
+        ),
+        (&["slot"], &["name"]),
+        (
+            &["source"],
+            &["height", "media", "sizes", "src", "srcset", "type", "width"],
+        ),
+        (&["style"], &["blocking", "media"]),
+        (
+            &["table"],
+            &[
+                "align",
+                "bgcolor",
+                "border",
+                "cellpadding",
+                "cellspacing",
+                "frame",
+                "rules",
+                "summary",
+                "width",
+            ],
+        ),
+        (
+            &["td", "th"],
+            &[
+                "abbr", "align", "bgcolor", "colspan", "headers", "height", "nowrap", "rowspan",
+                "scope", "valign", "width",
+            ],
+        ),
+        (
+            &["template"],
+            &[
+                "shadowrootclonable",
+                "shadowrootdelegatesfocus",
+                "shadowrootmode",
+                "shadowrootserializable",
+            ],
+        ),
+        (
+            &["textarea"],
+            &[
+                "autocomplete",
+                "cols",
+                "dirname",
+                "disabled",
+                "form",
+                "maxlength",
+                "minlength",
+                "name",
+                "placeholder",
+                "readonly",
+                "required",
+                "rows",
+                "value",
+                "wrap",
+            ],
+        ),
+        (&["time"], &["datetime"]),
+        (&["title"], &[]),
+        (&["track"], &["default", "kind", "label", "src", "srclang"]),
+        (
+            &["video"],
+            &[
+                "autoplay",
+                "controls",
+                "crossorigin",
+                "height",
+                "loop",
+                "muted",
+                "playsinline",
+                "poster",
+                "preload",
+                "src",
+                "width",
+            ],
+        ),
+    ];
+
+    let mut map = HashMap::<_, AttributeSet>::new();
+    for (tags, attributes) in elements {
+        for tag in tags.iter() {
+            map.entry(*tag)
+                .or_default()
+                .extend(attribute_set(attributes));
+        }
+    }
+    map
+});
+
+static SVG_ELEMENTS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    [
+        // From: https://www.w3.org/TR/SVG2/eltindex.html
+        // and https://www.w3.org/TR/filter-effects-1/
+        "a",
+        "animate",
+        "animatemotion",
+        "animatetransform",
+        "circle",
+        "clippath",
+        "defs",
+        "desc",
+        "ellipse",
+        "feblend",
+        "fecolormatrix",
+        "fecomponenttransfer",
+        "fecomposite",
+        "feconvolvematrix",
+        "fediffuselighting",
+        "fedisplacementmap",
+        "fedistantlight",
+        "fedropshadow",
+        "feflood",
+        "fefunca",
+        "fefuncb",
+        "fefuncg",
+        "fefuncr",
+        "fegaussianblur",
+        "feimage",
+        "femerge",
+        "femergenode",
+        "femorphology",
+        "feoffset",
+        "fepointlight",
+        "fespecularlighting",
+        "fespotlight",
+        "fetile",
+        "feturbulence",
+        "filter",
+        "foreignobject",
+        "g",
+        "image",
+        "line",
+        "lineargradient",
+        "marker",
+        "mask",
+        "metadata",
+        "mpath",
+        "path",
+        "pattern",
+        "polygon",
+        "polyline",
+        "radialgradient",
+        "rect",
+        "script",
+        "set",
+        "stop",
+        "style",
+        "svg",
+        "switch",
+        "symbol",
+        "text",
+        "textpath",
+        "title",
+        "tspan",
+        "use",
+        "view",
+    ]
+    .into()
+});
+
+/// The attributes of all SVG elements. SVG elements have many shared attributes, so they aren't
+/// checked per element.
+static SVG_ATTRIBUTES: Lazy<AttributeSet> = Lazy::new(|| {
+    attribute_set(&[
+        // Core and styling attributes
+        // From: https://www.w3.org/TR/SVG2/attindex.html
+        "class",
+        "id",
+        "lang",
+        "style",
+        "tabindex",
+        "autofocus",
+        "role",
+        "xml:lang",
+        "xml:space",
+        "xmlns",
+        "xmlns:xlink",
+        "xlink:href",
+        "xlink:title",
+        "requiredExtensions",
+        "systemLanguage",
+        // Presentation attributes
+        // From: https://www.w3.org/TR/SVG2/styling.html#PresentationAttributes
+        "alignment-baseline",
+        "baseline-shift",
+        "clip",
+        "clip-path",
+        "clip-rule",
+        "color",
+        "color-interpolation",
+        "color-interpolation-filters",
+        "color-rendering",
+        "cursor",
+        "direction",
+        "display",
+        "dominant-baseline",
+        "fill",
+        "fill-opacity",
+        "fill-rule",
+        "filter",
+        "flood-color",
+        "flood-opacity",
+        "font",
+        "font-family",
+        "font-size",
+        "font-size-adjust",
+        "font-stretch",
+        "font-style",
+        "font-variant",
+        "font-weight",
+        "glyph-orientation-horizontal",
+        "glyph-orientation-vertical",
+        "image-rendering",
+        "letter-spacing",
+        "lighting-color",
+        "marker",
+        "marker-end",
+        "marker-mid",
+        "marker-start",
+        "mask",
+        "mask-type",
+        "opacity",
+        "overflow",
+        "paint-order",
+        "pointer-events",
+        "shape-rendering",
+        "stop-color",
+        "stop-opacity",
+        "stroke",
+        "stroke-dasharray",
+        "stroke-dashoffset",
+        "stroke-linecap",
+        "stroke-linejoin",
+        "stroke-miterlimit",
+        "stroke-opacity",
+        "stroke-width",
+        "text-anchor",
+        "text-decoration",
+        "text-overflow",
+        "text-rendering",
+        "transform",
+        "transform-box",
+        "transform-origin",
+        "unicode-bidi",
+        "vector-effect",
+        "visibility",
+        "white-space",
+        "word-spacing",
+        "writing-mode",
+        // Element attributes
+        "accumulate",
+        "additive",
+        "amplitude",
+        "attributeName",
+        "azimuth",
+        "baseFrequency",
+        "begin",
+        "bias",
+        "by",
+        "calcMode",
+        "clipPathUnits",
+        "crossorigin",
+        "cx",
+        "cy",
+        "d",
+        "decoding",
+        "diffuseConstant",
+        "divisor",
+        "download",
+        "dur",
+        "dx",
+        "dy",
+        "edgeMode",
+        "elevation",
+        "end",
+        "exponent",
+        "filterUnits",
+        "fr",
+        "from",
+        "fx",
+        "fy",
+        "gradientTransform",
+        "gradientUnits",
+        "height",
+        "href",
+        "hreflang",
+        "in",
+        "in2",
+        "intercept",
+        "k1",
+        "k2",
+        "k3",
+        "k4",
+        "kernelMatrix",
+        "kernelUnitLength",
+        "keyPoints",
+        "keySplines",
+        "keyTimes",
+        "lengthAdjust",
+        "limitingConeAngle",
+        "markerHeight",
+        "markerUnits",
+        "markerWidth",
+        "maskContentUnits",
+        "maskUnits",
+        "max",
+        "media",
+        "method",
+        "min",
+        "mode",
+        "numOctaves",
+        "offset",
+        "operator",
+        "order",
+        "orient",
+        "path",
+        "pathLength",
+        "patternContentUnits",
+        "patternTransform",
+        "patternUnits",
+        "ping",
+        "points",
+        "pointsAtX",
+        "pointsAtY",
+        "pointsAtZ",
+        "preserveAlpha",
+        "preserveAspectRatio",
+        "primitiveUnits",
+        "r",
+        "radius",
+        "refX",
+        "refY",
+        "referrerpolicy",
+        "rel",
+        "repeatCount",
+        "repeatDur",
+        "restart",
+        "result",
+        "rotate",
+        "rx",
+        "ry",
+        "scale",
+        "seed",
+        "side",
+        "spacing",
+        "specularConstant",
+        "specularExponent",
+        "spreadMethod",
+        "startOffset",
+        "stdDeviation",
+        "stitchTiles",
+        "surfaceScale",
+        "tableValues",
+        "target",
+        "targetX",
+        "targetY",
+        "textLength",
+        "to",
+        "type",
+        "values",
+        "version",
+        "viewBox",
+        "width",
+        "x",
+        "x1",
+        "x2",
+        "xChannelSelector",
+        "y",
+        "y1",
+        "y2",
+        "yChannelSelector",
+        "z",
+        "zoomAndPan",
+    ])
+});
+
+static MATHML_ELEMENTS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    [
+        // From: https://w3c.github.io/mathml-core/#mathml-elements-and-attributes
+        "annotation",
+        "annotation-xml",
+        "maction",
+        "math",
+        "merror",
+        "mfrac",
+        "mi",
+        "mmultiscripts",
+        "mn",
+        "mo",
+        "mover",
+        "mpadded",
+        "mphantom",
+        "mprescripts",
+        "mroot",
+        "mrow",
+        "ms",
+        "mspace",
+        "msqrt",
+        "mstyle",
+        "msub",
+        "msubsup",
+        "msup",
+        "mtable",
+        "mtd",
+        "mtext",
+        "mtr",
+        "munder",
+        "munderover",
+        "semantics",
+    ]
+    .into()
+});
+
+/// The attributes of all MathML elements.
+static MATHML_ATTRIBUTES: Lazy<AttributeSet> = Lazy::new(|| {
+    attribute_set(&[
+        // Global attributes
+        // From: https://w3c.github.io/mathml-core/#global-attributes
+        "autofocus",
+        "class",
+        "dir",
+        "displaystyle",
+        "id",
+        "mathbackground",
+        "mathcolor",
+        "mathsize",
+        "mathvariant",
+        "nonce",
+        "scriptlevel",
+        "style",
+        "tabindex",
+        "xmlns",
+        // Element attributes
+        "accent",
+        "accentunder",
+        "actiontype",
+        "alttext",
+        "columnalign",
+        "columnlines",
+        "columnspacing",
+        "columnspan",
+        "depth",
+        "display",
+        "encoding",
+        "fence",
+        "form",
+        "height",
+        "href",
+        "largeop",
+        "linethickness",
+        "lspace",
+        "maxsize",
+        "minsize",
+        "movablelimits",
+        "rowalign",
+        "rowlines",
+        "rowspacing",
+        "rowspan",
+        "rspace",
+        "selection",
+        "separator",
+        "stretchy",
+        "symmetric",
+        "voffset",
+        "width",
+    ])
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_suggests_typos() {
+        let candidates = || {
+            SVG_ATTRIBUTES
+                .iter()
+                .map(|(lowercase, m)| (lowercase.as_str(), *m))
+        };
+        assert_eq!(closest("viewbx", candidates()), Some("viewBox"));
+        assert_eq!(closest("strokewidth", candidates()), Some("stroke-width"));
+        assert_eq!(closest("colour", candidates()), Some("color"));
+        assert_eq!(closest("background", candidates()), None);
+        assert_eq!(
+            closest("onclik", LISTENER_SET.iter().map(|m| (*m, *m))),
+            Some("onclick")
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Expr, LitStr, Token};
+
+use crate::html_tree::HtmlDashedName;
+
+/// List of CSS properties.
+pub struct Style(Punctuated<Property, Token![,]>);
+
+impl Parse for Style {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let properties = input.parse_terminated(Property::parse, Token![,])?;
+
+        let mut names = Vec::with_capacity(properties.len());
+        for property in properties.iter() {
+            let name = property.name.value();
+            if names.contains(&name) {
+                return Err(syn::Error::new(
+                    property.name.span(),
+                    format!("the property `{name}` is set more than once"),
+                ));
+            }
+            names.push(name);
+        }
+
+        Ok(Self(properties))
+    }
+}
+
+impl ToTokens for Style {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let n = self.0.len();
+        let set_properties = self.0.iter().map(|Property { name, value }| {
+            quote_spanned! {value.span()=>
+                __yew_style.set(#name, #value);
+            }
+        });
+        tokens.extend(quote! {
+            {
+                let mut __yew_style = ::yew::html::Style::with_capacity(#n);
+                #(#set_properties)*
+                __yew_style
+            }
+        });
+    }
+}
+
+struct Property {
+    name: LitStr,
+    value: Expr,
+}
+
+impl Parse for Property {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = if input.peek(LitStr) {
+            let name = input.parse::<LitStr>()?;
+            let value = name.value();
+            if value.is_empty() || value.contains([':', ';']) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "expected the name of a CSS property",
+                ));
+            }
+            name
+        } else {
+            let name = input.parse::<HtmlDashedName>()?;
+            let value = name.to_string();
+            if value.contains(|c: char| c.is_ascii_uppercase() || c == '_') {
+                let fix = value
+                    .chars()
+                    .flat_map(|c| match c {
+                        '_' => vec!['-'],
+                        c if c.is_ascii_uppercase() => vec!['-', c.to_ascii_lowercase()],
+                        c => vec![c],
+                    })
+                    .collect::<String>();
+                return Err(syn::Error::new_spanned(
+                    name,
+                    format!("CSS properties are written in kebab-case (hint: use `{fix}`)"),
+                ));
+            }
+            name.to_lit_str()
+        };
+        input.parse::<Token![:]>()?;
+        let value = input.parse()?;
+        Ok(Self { name, value })
+    }
+}
@@ -0,0 +1,18 @@
+use yew::prelude::*;
+
+fn compile_fail() {
+    css!();
+    css!(color: red);
+    css!("color red;");
+    css!(".title { color: red;");
+    css!("color: red; }");
+    css!("@import url(style.css);");
+    css!(".a-${name} { color: red; }", name = 1);
+
+    css!("color: ${missing};");
+    css!("color: ${self};");
+    css!("color: red;", unused = 1);
+    css!("color: ${c}; margin: ${c};", c = 1, c = 2);
+}
+
+fn main() {}
@@ -0,0 +1,74 @@
+#![no_implicit_prelude]
+
+// Shadow primitives
+#[allow(non_camel_case_types)]
+pub struct bool;
+#[allow(non_camel_case_types)]
+pub struct char;
+#[allow(non_camel_case_types)]
+pub struct f32;
+#[allow(non_camel_case_types)]
+pub struct f64;
+#[allow(non_camel_case_types)]
+pub struct i128;
+#[allow(non_camel_case_types)]
+pub struct i16;
+#[allow(non_camel_case_types)]
+pub struct i32;
+#[allow(non_camel_case_types)]
+pub struct i64;
+#[allow(non_camel_case_types)]
+pub struct i8;
+#[allow(non_camel_case_types)]
+pub struct isize;
+#[allow(non_camel_case_types)]
+pub struct str;
+#[allow(non_camel_case_types)]
+pub struct u128;
+#[allow(non_camel_case_types)]
+pub struct u16;
+#[allow(non_camel_case_types)]
+pub struct u32;
+#[allow(non_camel_case_types)]
+pub struct u64;
+#[allow(non_camel_case_types)]
+pub struct u8;
+#[allow(non_camel_case_types)]
+pub struct usize;
+
+fn compile_pass() {
+    // declarations only
+    ::yew::css!("color: red;");
+    // empty
+    ::yew::css!("");
+
+    // nested rules and at-rules
+    ::yew::css!(
+        r#"
+        display: flex;
+        /* a comment */
+        &:hover, &.active { color: red; }
+        .child > span { content: "a { b }"; }
+        @media (max-width: 600px) { flex-direction: column; }
+        @keyframes fade { from { opacity: 0 } to { opacity: 1 } }
+        "#
+    );
+
+    // values captured from the scope
+    let size = 2;
+    let style = ::yew::css!("font-size: ${size}em;");
+    let _: ::yew::html::Style = style.vars();
+
+    // named arguments, with a trailing comma
+    ::yew::css!(
+        "margin: ${y} ${x}; padding: ${y};",
+        x = 4,
+        y = ::std::format!("{}px", 2),
+    );
+
+    // a style is used as a class
+    let _: ::yew::html::Classes = ::yew::classes!(&style, "other");
+    ::yew::html! { <div class={style} /> };
+}
+
+fn main() {}
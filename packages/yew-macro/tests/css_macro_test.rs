@@ -0,0 +1,7 @@
+#[allow(dead_code)]
+#[rustversion::attr(stable(1.64), test)]
+fn css_macro() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/css_macro/*-pass.rs");
+    t.compile_fail("tests/css_macro/*-fail.rs");
+}
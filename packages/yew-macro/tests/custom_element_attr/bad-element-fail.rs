@@ -0,0 +1,38 @@
+use yew::prelude::*;
+
+#[custom_element]
+struct NoName {}
+
+#[custom_element("counter")]
+struct NoDash {}
+
+#[custom_element("x-tuple")]
+struct Tuple(u32);
+
+#[custom_element("x-generic")]
+struct Generic<T> {
+    value: T,
+}
+
+#[custom_element("x-conflict")]
+struct Conflict {
+    #[property]
+    #[event]
+    value: u32,
+}
+
+#[custom_element("x-args")]
+struct Args {
+    #[property = "value"]
+    value: u32,
+}
+
+#[custom_element("x-refs")]
+struct Refs {
+    #[node_ref]
+    first: NodeRef,
+    #[node_ref]
+    second: NodeRef,
+}
+
+fn main() {}
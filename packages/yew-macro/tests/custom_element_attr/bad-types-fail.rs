@@ -0,0 +1,23 @@
+use yew::prelude::*;
+
+struct Opaque;
+
+#[derive(PartialEq)]
+struct Detail;
+
+#[custom_element("x-element")]
+struct Element {
+    #[property]
+    value: Opaque,
+    attr: Vec<u32>,
+    #[event]
+    onchange: Callback<Detail>,
+}
+
+impl PartialEq for Opaque {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+fn main() {}
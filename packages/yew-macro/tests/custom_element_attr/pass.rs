@@ -0,0 +1,61 @@
+#![no_implicit_prelude]
+
+#[::yew::html::custom_element("x-counter")]
+pub struct Counter {
+    /// Set as the `startValue` property
+    #[property]
+    pub start_value: ::std::primitive::u32,
+    #[prop_or_default]
+    #[property("labels")]
+    pub names: ::std::vec::Vec<::std::string::String>,
+    #[prop_or_default]
+    pub disabled: ::std::primitive::bool,
+    #[prop_or_default]
+    pub aria_label: ::std::option::Option<::yew::virtual_dom::AttrValue>,
+    #[prop_or_default]
+    #[attribute("data-kind")]
+    pub kind: ::yew::virtual_dom::AttrValue,
+    #[event("count-change")]
+    pub oncountchange: ::yew::Callback<::std::primitive::u32>,
+    #[prop_or_default]
+    #[event]
+    pub onreset: ::std::option::Option<::yew::Callback<()>>,
+    #[prop_or_default]
+    #[node_ref]
+    pub element: ::yew::NodeRef,
+    #[prop_or_default]
+    #[rest]
+    pub attrs: ::yew::html::RestAttributes,
+    #[prop_or_default]
+    pub children: ::yew::Html,
+}
+
+#[::yew::html::custom_element("x-empty")]
+struct Empty {}
+
+#[::yew::function_component]
+fn Comp() -> ::yew::Html {
+    ::yew::html! {
+        <>
+            <Counter start_value=3 oncountchange={|_: ::std::primitive::u32| {}} />
+            <Counter
+                start_value=3
+                names={::std::vec![]}
+                disabled=true
+                aria_label="Counter"
+                kind="small"
+                oncountchange={::yew::Callback::noop()}
+                onreset={::yew::Callback::noop()}
+                class="counter"
+                onclick={::yew::Callback::noop()}
+            >
+                <span>{ "Count" }</span>
+            </Counter>
+            <Empty />
+        </>
+    }
+}
+
+fn main() {
+    let _ = <CounterProps as ::yew::html::Properties>::builder;
+}
@@ -0,0 +1,7 @@
+#[allow(dead_code)]
+#[rustversion::attr(stable(1.64), test)]
+fn tests() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/custom_element_attr/pass.rs");
+    t.compile_fail("tests/custom_element_attr/*-fail.rs");
+}
@@ -0,0 +1,91 @@
+#![recursion_limit = "128"]
+
+use yew::prelude::*;
+
+mod t1 {
+    use super::*;
+    #[derive(Clone, Properties, PartialEq)]
+    pub struct Props {
+        // ERROR: props in a group need a default value
+        #[prop_group("action", exactly_one)]
+        href: AttrValue,
+    }
+}
+
+mod t2 {
+    use super::*;
+    #[derive(Clone, Properties, PartialEq)]
+    pub struct Props {
+        #[prop_or_default]
+        // ERROR: unknown kind of group
+        #[prop_group("action", exactly_two)]
+        href: Option<AttrValue>,
+    }
+}
+
+mod t3 {
+    use super::*;
+    #[derive(Clone, Properties, PartialEq)]
+    pub struct Props {
+        #[prop_or_default]
+        #[prop_group("action", exactly_one)]
+        href: Option<AttrValue>,
+        #[prop_or_default]
+        // ERROR: the group is declared as `exactly_one` on `href`
+        #[prop_group("action", at_most_one)]
+        onclick: Option<Callback<MouseEvent>>,
+    }
+}
+
+mod t4 {
+    use super::*;
+    #[derive(Clone, Properties, PartialEq)]
+    pub struct Props {
+        #[prop_or_default]
+        // ERROR: there is no prop named `hre`
+        #[prop(requires = "hre")]
+        target: Option<AttrValue>,
+        #[prop_or_default]
+        href: Option<AttrValue>,
+    }
+}
+
+mod t5 {
+    use super::*;
+    #[derive(Clone, Properties, PartialEq)]
+    pub struct Props {
+        #[prop_or_default]
+        // ERROR: unknown option
+        #[prop(require = "href")]
+        target: Option<AttrValue>,
+        #[prop_or_default]
+        href: Option<AttrValue>,
+    }
+}
+
+mod t6 {
+    use super::*;
+    #[derive(Clone, Properties, PartialEq)]
+    pub struct Props {
+        #[prop_or_default]
+        #[prop_group("action", exactly_one)]
+        href: Option<AttrValue>,
+        #[prop_or_default]
+        #[prop_group("action", exactly_one)]
+        onclick: Option<Callback<MouseEvent>>,
+        #[prop_or_default]
+        #[prop(requires = "href")]
+        target: Option<AttrValue>,
+    }
+
+    fn test() {
+        // ERROR: missing `href` or `onclick`
+        ::yew::props! { Props {} };
+        // ERROR: only one of `href` or `onclick` can be set
+        ::yew::props! { Props { href: "/", onclick: Callback::noop() } };
+        // ERROR: `target` requires `href`
+        ::yew::props! { Props { onclick: Callback::noop(), target: "_blank" } };
+    }
+}
+
+fn main() {}
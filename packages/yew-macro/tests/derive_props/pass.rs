@@ -273,4 +273,82 @@ mod value_into_some_value_in_props {
     }
 }
 
+
+mod prop_groups {
+    #[derive(::std::cmp::PartialEq, ::yew::Properties)]
+    struct Props<T: ::std::cmp::PartialEq> {
+        #[prop_or_default]
+        #[prop_group("action", exactly_one)]
+        href: ::std::option::Option<::yew::virtual_dom::AttrValue>,
+        #[prop_or_default]
+        #[prop_group("action", exactly_one)]
+        onclick: ::std::option::Option<::yew::Callback<()>>,
+        #[prop_or_default]
+        #[prop(requires = "href")]
+        target: ::std::option::Option<::yew::virtual_dom::AttrValue>,
+        #[prop_or_default]
+        #[prop_group("icon", at_most_one)]
+        #[prop(requires = "r#type")]
+        icon: ::std::option::Option<T>,
+        #[prop_or_default]
+        #[prop_group("icon", at_most_one)]
+        #[prop_group("content", at_least_one)]
+        label: ::std::option::Option<::yew::virtual_dom::AttrValue>,
+        #[prop_or_default]
+        #[prop_group("content", at_least_one)]
+        children: ::yew::Children,
+        r#type: ::yew::virtual_dom::AttrValue,
+    }
+
+    #[::yew::function_component]
+    fn Button(_props: &Props<u32>) -> ::yew::html::Html {
+        ::yew::html! {}
+    }
+
+    #[::yew::function_component]
+    fn Main() -> ::yew::html::Html {
+        ::yew::html! {<>
+            <Button href="/" label="Home" r#type="a" />
+            <Button href="/" target="_blank" icon=1 r#type="a">{ "Home" }</Button>
+            <Button onclick={::yew::Callback::noop()} r#type="button">{ "Click" }</Button>
+            <Button onclick={::yew::Callback::noop()} label="Click" r#type="button">{ "Click" }</Button>
+        </>}
+    }
+}
+
+mod rest_attributes {
+    #[derive(::std::cmp::PartialEq, ::yew::Properties)]
+    struct Props<T: ::std::cmp::PartialEq> {
+        value: T,
+        #[prop_or_default]
+        class: ::yew::Classes,
+        #[prop_or_default]
+        #[rest]
+        attrs: ::yew::html::RestAttributes,
+    }
+
+    #[::yew::function_component]
+    fn Input(props: &Props<::yew::virtual_dom::AttrValue>) -> ::yew::html::Html {
+        ::yew::html! {
+            <input
+                class={::std::clone::Clone::clone(&props.class)}
+                value={::std::clone::Clone::clone(&props.value)}
+                ..{&props.attrs}
+            />
+        }
+    }
+
+    #[::yew::function_component]
+    fn Main() -> ::yew::html::Html {
+        let attrs: ::yew::html::RestAttributes =
+            ::std::iter::FromIterator::from_iter([("title", "Name")]);
+        ::yew::html! {<>
+            <Input value="" />
+            <Input value="" class="wide" r#type="text" aria-label="Name" data-id="1" />
+            <Input value="" disabled=true oninput={::yew::Callback::noop()} />
+            <Input value="" placeholder="Name" attrs={attrs} />
+        </>}
+    }
+}
+
 fn main() {}
@@ -0,0 +1,27 @@
+use yew::prelude::*;
+
+mod t1 {
+    use super::*;
+
+    #[derive(Properties, PartialEq)]
+    pub struct Props {
+        #[rest]
+        attrs: RestAttributes,
+    }
+}
+
+mod t2 {
+    use super::*;
+
+    #[derive(Properties, PartialEq)]
+    pub struct Props {
+        #[prop_or_default]
+        #[rest]
+        attrs: RestAttributes,
+        #[prop_or_default]
+        #[rest]
+        other: RestAttributes,
+    }
+}
+
+fn main() {}
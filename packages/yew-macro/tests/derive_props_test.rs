@@ -4,4 +4,6 @@ fn derive_props() {
     let t = trybuild::TestCases::new();
     t.pass("tests/derive_props/pass.rs");
     t.compile_fail("tests/derive_props/fail.rs");
+    t.compile_fail("tests/derive_props/constraints-fail.rs");
+    t.compile_fail("tests/derive_props/rest-fail.rs");
 }
@@ -0,0 +1,83 @@
+// On stable, lints are emitted as uses of deprecated items.
+#![deny(deprecated)]
+#![allow(unused_variables)]
+
+use yew::prelude::*;
+
+#[function_component]
+fn Control() -> Html {
+    html! { <input /> }
+}
+
+fn main() {
+    let bad_a = html! {
+        <a>{ "I don't have a href attribute" }</a>
+    };
+    let bad_a_2 = html! {
+        <a href="#">{ "I have a malformed href attribute" }</a>
+    };
+    let bad_a_3 = html! {
+        <a href="javascript:void(0)">{ "I have a malformed href attribute" }</a>
+    };
+    let bad_img = html! {
+        <img src="img.jpeg"/>
+    };
+    let misformed_tagname = html! {
+        <tExTAreA />
+    };
+    let bad_label = html! {
+        <label>{ "I don't label anything" }</label>
+    };
+    let bad_button = html! {
+        <button />
+    };
+    let bad_aria = html! {
+        <div aria-lable="A typo" role="buton" />
+    };
+    let bad_onclick = html! {
+        <div onclick={Callback::from(|_| ())}>{ "I can't be reached with a keyboard" }</div>
+    };
+    let bad_ids = html! {
+        <>
+            <div id="title" />
+            <div id="title" />
+        </>
+    };
+    let nested = html! {
+        <div>
+            if true {
+                <img src="img.jpeg"/>
+            }
+        </div>
+    };
+    let bad_attributes = html! {
+        <input valeu="" hx-get="/items" />
+    };
+
+    let good_label = html! {
+        <>
+            <label for="name">{ "Name" }</label>
+            <label>{ "Name" }<input /></label>
+            <label>{ "Name" }<Control /></label>
+        </>
+    };
+    let good_button = html! {
+        <>
+            <button>{ "Save" }</button>
+            <button aria-label="Save" />
+        </>
+    };
+    let good_onclick = html! {
+        <>
+            <button onclick={Callback::from(|_| ())}>{ "Save" }</button>
+            <div role="button" tabindex="0" onclick={Callback::from(|_| ())}>{ "Save" }</div>
+        </>
+    };
+    let allowed = html! {
+        #![allow(img_alt, duplicate_id)]
+        <>
+            <img id="spacer" src="spacer.png" />
+            <img id="spacer" src="spacer.png" />
+        </>
+    };
+}
@@ -5,3 +5,13 @@ fn test_html_lints() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/html_lints/fail.rs");
 }
+
+// Lints are only applied if the macro is compiled with `--cfg yew_lints`, which requires
+// `--cfg trybuild_no_target` so that trybuild passes `RUSTFLAGS` to proc-macros.
+#[allow(dead_code)]
+#[cfg(yew_lints)]
+#[rustversion::attr(stable(1.64), test)]
+fn test_html_lints_stable() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/html_lints/stable-fail.rs");
+}
@@ -0,0 +1,12 @@
+use yew::prelude::*;
+
+fn compile_fail() {
+    // typos
+    html! { <button onclik={Callback::from(|_| ())} /> };
+    html! { <input onimput={Callback::from(|_| ())} /> };
+
+    // unknown listeners
+    html! { <div onmyevent={Callback::from(|_| ())} /> };
+}
+
+fn main() {}
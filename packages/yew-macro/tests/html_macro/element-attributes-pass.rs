@@ -0,0 +1,41 @@
+#![no_implicit_prelude]
+
+fn compile_pass() {
+    // known attributes
+    ::yew::html! { <input type="text" value="" placeholder="Name" autocomplete="name" /> };
+    ::yew::html! { <a href="https://yew.rs" target="_blank" rel="noopener">{ "Yew" }</a> };
+    ::yew::html! { <td colspan="2" /> };
+    ::yew::html! {
+        <svg viewBox="0 0 10 10" xmlns="http://www.w3.org/2000/svg">
+            <a href="#"><circle cx="5" cy="5" r="4" stroke-width="1" /></a>
+        </svg>
+    };
+    ::yew::html! { <math display="block"><mi mathvariant="normal">{ "x" }</mi></math> };
+
+    // `data-*` and `aria-*` attributes
+    ::yew::html! { <div data-anything="" aria-label="Label" /> };
+
+    // properties aren't checked
+    ::yew::html! { <div ~anything="" /> };
+
+    // custom elements and dynamic tags aren't checked
+    ::yew::html! { <my-element anything="" /> };
+    ::yew::html! { <@{"div"} anything="" /> };
+
+    // unknown attributes are only linted, as libraries and browsers use non-standard ones
+    ::yew::html! { <div hx-get="/items" hx-target="#items" x-data="{}" /> };
+    ::yew::html! { <input autocorrect="off" /> };
+    ::yew::html! { <div vocab="https://schema.org/" typeof="Person" elementtiming="hero" /> };
+
+    // the checks can be skipped for a macro invocation
+    ::yew::html! {
+        #![allow(unknown_attributes)]
+        <div anything="" x-data="" />
+    };
+    ::yew::html_nested! {
+        #![allow(unknown_attributes)]
+        <input valeu="" onmyevent="" />
+    };
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+struct Props {
+    value: AttrValue,
+}
+
+#[function_component]
+fn Child(_props: &Props) -> Html {
+    html! {}
+}
+
+#[function_component]
+fn Comp() -> Html {
+    let text = use_state(String::new);
+    let checked = use_state(|| false);
+
+    html! { <input bind:name={text.clone()} /> };
+    html! { <input bind:value={text.clone()} bind:value={text.clone()} /> };
+    html! { <div bind:value={text.clone()} /> };
+    html! { <textarea bind:checked={checked.clone()} /> };
+    html! { <input checked=true bind:checked={checked.clone()} /> };
+    html! { <Child bind:value={text.clone()} /> };
+    html! { <input bind:checked={text.clone()} /> };
+    html! { <input bind:value={checked} /> }
+}
+
+fn main() {}
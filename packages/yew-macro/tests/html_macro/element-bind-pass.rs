@@ -0,0 +1,50 @@
+#![no_implicit_prelude]
+
+struct Query(::yew::virtual_dom::AttrValue);
+
+impl ::yew::functional::Reducible for Query {
+    type Action = ::yew::virtual_dom::AttrValue;
+
+    fn reduce(self: ::std::rc::Rc<Self>, action: Self::Action) -> ::std::rc::Rc<Self> {
+        ::std::rc::Rc::new(Self(action))
+    }
+}
+
+impl ::yew::html::BindReducer<::yew::virtual_dom::AttrValue> for Query {
+    fn bound_value(&self) -> ::yew::virtual_dom::AttrValue {
+        ::std::clone::Clone::clone(&self.0)
+    }
+
+    fn update_action(value: ::yew::virtual_dom::AttrValue) -> Self::Action {
+        value
+    }
+}
+
+#[::yew::function_component]
+fn Form() -> ::yew::Html {
+    let name = ::yew::functional::use_state(::std::string::String::new);
+    let color = ::yew::functional::use_state(|| {
+        <::yew::virtual_dom::AttrValue as ::std::convert::From<_>>::from("red")
+    });
+    let subscribed = ::yew::functional::use_state(|| false);
+    let query = ::yew::functional::use_reducer(|| Query(::std::default::Default::default()));
+    let node_ref = ::yew::functional::use_node_ref();
+
+    ::yew::html! {
+        <form>
+            <input bind:value={name} oninput={::yew::Callback::noop()} />
+            <textarea bind:value={query} />
+            <select bind:value={::std::clone::Clone::clone(&color)}>
+                <option value="red">{ "Red" }</option>
+                <option value="blue">{ "Blue" }</option>
+            </select>
+            <input type="radio" value="red" bind:value={color} />
+            <input type="checkbox" bind:checked={subscribed} />
+            <input type="checkbox" bind:checked={::std::clone::Clone::clone(&node_ref)} />
+            <input bind:value={node_ref} />
+            <@{"input"} bind:value={<::yew::NodeRef as ::std::default::Default>::default()} />
+        </form>
+    }
+}
+
+fn main() {}
@@ -43,7 +43,7 @@ fn compile_fail() {
     html! { <input type={()} /> };
     html! { <input value={()} /> };
     html! { <a href={()} /> };
-    html! { <input string={NotToString} /> };
+    html! { <input accept={NotToString} /> };
     html! { <a media={Some(NotToString)} /> };
     html! { <a href={Some(5)} /> };
 
@@ -57,7 +57,7 @@ fn compile_fail() {
     html! { <input ref={Some(NodeRef::default())} /> };
     html! { <input onclick={Callback::from(|a: String| ())} /> };
 
-    html! { <input string={NotToString} /> };
+    html! { <input accept={NotToString} /> };
 
     html! { <input ref={()} /> };
     html! { <input ref={()} ref={()} /> };
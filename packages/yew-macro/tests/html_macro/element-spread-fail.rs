@@ -0,0 +1,39 @@
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+struct Props {
+    #[prop_or_default]
+    value: AttrValue,
+}
+
+#[function_component]
+fn NoRest(_props: &Props) -> Html {
+    html! {}
+}
+
+#[derive(Properties, PartialEq)]
+struct RestProps {
+    #[prop_or_default]
+    #[rest]
+    attrs: RestAttributes,
+}
+
+#[function_component]
+fn WithRest(_props: &RestProps) -> Html {
+    html! {}
+}
+
+fn compile_fail() {
+    let attrs = RestAttributes::new();
+
+    html! { <div ..attrs /> };
+    html! { <div ..{attrs.clone()} class="a" /> };
+    html! { <div ..{"attrs"} /> };
+
+    html! { <NoRest title="title" /> };
+    html! { <NoRest aria-label="label" /> };
+    html! { <NoRest aria-label="label" ..Props::default() /> };
+    html! { <WithRest not-an-attribute="value" /> };
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![no_implicit_prelude]
+
+fn compile_pass() {
+    let attrs = ::yew::html::RestAttributes::new();
+
+    ::yew::html! { <div ..{::std::clone::Clone::clone(&attrs)} /> };
+    ::yew::html! { <div class="a" id="b" onclick={::yew::Callback::noop()} ..{&attrs}></div> };
+    ::yew::html! { <input value="" checked=true ..{&attrs} /> };
+    ::yew::html! { <textarea value="" ..{&attrs} /> };
+    ::yew::html! { <@{"div"} ..{&attrs}>{ "dynamic" }</@> };
+}
+
+fn main() {}
@@ -49,6 +49,7 @@ fn compile_pass() {
     let attr_val_none: ::std::option::Option<::yew::virtual_dom::AttrValue> = ::std::option::Option::None;
 
     ::yew::html! {
+        #![allow(unknown_attributes)]
         <div>
             <div data-key="abc"></div>
             <div ref={&parent_ref}></div>
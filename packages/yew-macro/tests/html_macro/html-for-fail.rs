@@ -0,0 +1,9 @@
+use yew::prelude::*;
+
+fn compile_fail() {
+    html! { for x in 0..3 };
+    html! { for x in 42 { <p/> } };
+    html! { <ul>for in 0..3 { <li/> }</ul> };
+}
+
+fn main() {}
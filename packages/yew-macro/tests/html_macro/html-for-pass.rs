@@ -0,0 +1,81 @@
+#![no_implicit_prelude]
+
+// Shadow primitives
+#[allow(non_camel_case_types)]
+pub struct bool;
+#[allow(non_camel_case_types)]
+pub struct char;
+#[allow(non_camel_case_types)]
+pub struct f32;
+#[allow(non_camel_case_types)]
+pub struct f64;
+#[allow(non_camel_case_types)]
+pub struct i128;
+#[allow(non_camel_case_types)]
+pub struct i16;
+#[allow(non_camel_case_types)]
+pub struct i32;
+#[allow(non_camel_case_types)]
+pub struct i64;
+#[allow(non_camel_case_types)]
+pub struct i8;
+#[allow(non_camel_case_types)]
+pub struct isize;
+#[allow(non_camel_case_types)]
+pub struct str;
+#[allow(non_camel_case_types)]
+pub struct u128;
+#[allow(non_camel_case_types)]
+pub struct u16;
+#[allow(non_camel_case_types)]
+pub struct u32;
+#[allow(non_camel_case_types)]
+pub struct u64;
+#[allow(non_camel_case_types)]
+pub struct u8;
+#[allow(non_camel_case_types)]
+pub struct usize;
+
+struct Item {
+    id: ::std::primitive::u32,
+    name: &'static ::std::primitive::str,
+}
+
+fn compile_pass() {
+    let items = ::std::vec![Item { id: 1, name: "one" }, Item { id: 2, name: "two" }];
+
+    _ = ::yew::html! {
+        <ul>
+            for item in &items {
+                <li key={item.id}>{ item.name }</li>
+            }
+        </ul>
+    };
+
+    _ = ::yew::html! {
+        <dl>
+            for Item { id, name } in &items {
+                <dt key={*id}>{ id }</dt>
+                <dd>{ name }</dd>
+            }
+        </dl>
+    };
+
+    _ = ::yew::html! {
+        for (i, item) in ::std::iter::Iterator::enumerate(items.iter()) {
+            if i > 0 {
+                <hr/>
+            }
+            match item.id {
+                1 => <b>{ item.name }</b>,
+                _ => { { item.name } }
+            }
+        }
+    };
+
+    _ = ::yew::html! {
+        for ::std::iter::Iterator::map(items.iter(), |item| ::yew::html! { <p>{ item.name }</p> })
+    };
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use yew::prelude::*;
+
+fn compile_fail() {
+    html! { #![allow(img_alt_text)] <img src="spacer.png" /> };
+    html! { #![allow(img_alt = true)] <img src="spacer.png" /> };
+    html! { #![deny(img_alt)] <img src="spacer.png" /> };
+    html! { #![allow(img_alt)] "spacer" };
+    html! { #![allow(img_alt)] for vec![html! { <img src="spacer.png" /> }] };
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use yew::prelude::*;
+
+enum Status {
+    Loading,
+    Loaded,
+}
+
+fn compile_fail() {
+    html! {
+        match Status::Loading {
+            Some(_) => <p/>,
+            _ => <p/>,
+        }
+    };
+    html! {
+        match Status::Loaded {
+            Status::Loading => <p/>,
+            Status::Loaded => { { () } }
+        }
+    };
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use yew::prelude::*;
+
+enum Status {
+    Loading,
+    Loaded,
+}
+
+fn compile_fail() {
+    html! { match Status::Loading };
+    html! { match Status::Loading { Status::Loading } };
+    html! { match Status::Loading { Status::Loading => } };
+    html! { match Status::Loading { Status::Loading => <p/> Status::Loaded => <p/> } };
+    html! { match Status::Loading { Status::Loading => { 42 } Status::Loaded => <p/> } };
+}
+
+fn main() {}
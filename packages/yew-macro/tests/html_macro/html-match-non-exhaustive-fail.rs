@@ -0,0 +1,16 @@
+use yew::prelude::*;
+
+enum Status {
+    Loading,
+    Loaded,
+}
+
+fn compile_fail() {
+    html! {
+        match Status::Loading {
+            Status::Loading => <p/>,
+        }
+    };
+}
+
+fn main() {}
@@ -0,0 +1,73 @@
+#![no_implicit_prelude]
+
+// Shadow primitives
+#[allow(non_camel_case_types)]
+pub struct bool;
+#[allow(non_camel_case_types)]
+pub struct char;
+#[allow(non_camel_case_types)]
+pub struct f32;
+#[allow(non_camel_case_types)]
+pub struct f64;
+#[allow(non_camel_case_types)]
+pub struct i128;
+#[allow(non_camel_case_types)]
+pub struct i16;
+#[allow(non_camel_case_types)]
+pub struct i32;
+#[allow(non_camel_case_types)]
+pub struct i64;
+#[allow(non_camel_case_types)]
+pub struct i8;
+#[allow(non_camel_case_types)]
+pub struct isize;
+#[allow(non_camel_case_types)]
+pub struct str;
+#[allow(non_camel_case_types)]
+pub struct u128;
+#[allow(non_camel_case_types)]
+pub struct u16;
+#[allow(non_camel_case_types)]
+pub struct u32;
+#[allow(non_camel_case_types)]
+pub struct u64;
+#[allow(non_camel_case_types)]
+pub struct u8;
+#[allow(non_camel_case_types)]
+pub struct usize;
+
+enum Status {
+    Loading,
+    Loaded(&'static ::std::primitive::str),
+    Failed { code: ::std::primitive::u16 },
+}
+
+fn compile_pass() {
+    let status = Status::Loading;
+
+    _ = ::yew::html! {
+        match status {
+            Status::Loading => <p>{ "Loading" }</p>,
+            Status::Loaded(text) => { <p>{ text }</p><hr/> }
+            Status::Failed { code } if code == 404 => <></>,
+            Status::Failed { .. } => {}
+        }
+    };
+
+    _ = ::yew::html! {
+        <div>
+            match ::std::option::Option::Some(1) {
+                ::std::option::Option::Some(1) | ::std::option::Option::Some(2) => {
+                    if true {
+                        <p/>
+                    }
+                }
+                _ => match 3 {
+                    _ => <span/>
+                }
+            }
+        </div>
+    };
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use yew::prelude::*;
+
+fn compile_fail() {
+    style! { color };
+    style! { color: };
+    style! { color: "red" display: "none" };
+    style! { backgroundColor: "teal" };
+    style! { z_index: 1 };
+    style! { "color:": "red" };
+    style! { color: "red", color: "blue" };
+}
+
+fn main() {}
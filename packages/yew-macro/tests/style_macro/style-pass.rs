@@ -0,0 +1,59 @@
+#![no_implicit_prelude]
+
+// Shadow primitives
+#[allow(non_camel_case_types)]
+pub struct bool;
+#[allow(non_camel_case_types)]
+pub struct char;
+#[allow(non_camel_case_types)]
+pub struct f32;
+#[allow(non_camel_case_types)]
+pub struct f64;
+#[allow(non_camel_case_types)]
+pub struct i128;
+#[allow(non_camel_case_types)]
+pub struct i16;
+#[allow(non_camel_case_types)]
+pub struct i32;
+#[allow(non_camel_case_types)]
+pub struct i64;
+#[allow(non_camel_case_types)]
+pub struct i8;
+#[allow(non_camel_case_types)]
+pub struct isize;
+#[allow(non_camel_case_types)]
+pub struct str;
+#[allow(non_camel_case_types)]
+pub struct u128;
+#[allow(non_camel_case_types)]
+pub struct u16;
+#[allow(non_camel_case_types)]
+pub struct u32;
+#[allow(non_camel_case_types)]
+pub struct u64;
+#[allow(non_camel_case_types)]
+pub struct u8;
+#[allow(non_camel_case_types)]
+pub struct usize;
+
+fn compile_pass() {
+    // empty
+    ::yew::style!();
+    // single property, with a trailing comma
+    ::yew::style! { color: "red", };
+
+    // dashed names, custom properties and expressions
+    let width = 42;
+    let style: ::yew::html::Style = ::yew::style! {
+        background-color: "teal",
+        "--accent": ::std::format!("#{:06x}", 0xabcdef),
+        width: ::std::format!("{}px", width),
+        display: ::std::option::Option::Some("flex"),
+        visibility: ::std::option::Option::None::<&'static ::std::primitive::str>,
+    };
+
+    ::yew::html! { <div style={::std::clone::Clone::clone(&style)} /> };
+    ::yew::html! { <div style={&style} /> };
+}
+
+fn main() {}
@@ -1,4 +1,5 @@
 mod routable_derive;
+mod route_path;
 use routable_derive::{routable_derive_impl, Routable};
 use syn::parse_macro_input;
 
@@ -9,6 +10,23 @@ use syn::parse_macro_input;
 ///  `yew_router::Routable` trait and `const`s for the routes passed which are used with `Route`
 /// component.
 ///
+/// A route can contain dynamic segments (`:id` or `{id}`), dynamic segments constrained by a
+/// regular expression (`{id:\d+}`), optional segments (`:page?`, `{page?}` or `{page?:\d+}`)
+/// bound to `Option` fields, and wildcard segments (`*rest`). A variant can be marked with
+/// multiple `at` attributes, in which case the first route is used to build paths and the others
+/// act as aliases.
+///
+/// Routes are validated at compile time: every field must be bound by the routes of its variant,
+/// and no two variants can have routes matching the same paths.
+///
+/// Constrained segments are only considered to match the same paths if their constraints are the
+/// same regular expression once parsed, e.g. `{id:a|b}` and `{id:[ab]}`. Constraints written
+/// differently which match the same segments, such as `\d\d*` and `\d+`, are not detected: both
+/// routes rank equally, so the one declared first wins.
+///
+/// A variant rendered by a split module can be marked with `#[lazy("module")]`, see
+/// `yew_router::lazy` for details.
+///
 /// # Example
 ///
 /// ```
@@ -19,11 +37,16 @@ use syn::parse_macro_input;
 ///     Home,
 ///     #[at("/secure")]
 ///     Secure,
+///     #[at("/user/{id:\\d+}")]
+///     User { id: u64 },
+///     #[at("/posts/:page?")]
+///     #[at("/blog/:page?")]
+///     Posts { page: Option<u32> },
 ///     #[at("/404")]
 ///     NotFound,
 /// }
 /// ```
-#[proc_macro_derive(Routable, attributes(at, not_found))]
+#[proc_macro_derive(Routable, attributes(at, not_found, lazy))]
 pub fn routable_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as Routable);
     routable_derive_impl(input).into()
@@ -1,20 +1,40 @@
+use std::collections::HashMap;
+
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Data, DeriveInput, Fields, Ident, LitStr, Variant};
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, Type, TypePath, Variant};
+
+use crate::route_path::{RoutePath, Segment, ShapeSegment};
 
 const AT_ATTR_IDENT: &str = "at";
 const NOT_FOUND_ATTR_IDENT: &str = "not_found";
+const LAZY_ATTR_IDENT: &str = "lazy";
 
 pub struct Routable {
     ident: Ident,
-    ats: Vec<LitStr>,
-    variants: Punctuated<Variant, syn::token::Comma>,
+    variants: Vec<RoutableVariant>,
     not_found_route: Option<Ident>,
 }
 
+struct RoutableVariant {
+    ident: Ident,
+    fields: Vec<RouteField>,
+    /// All the routes passed to the `at` attributes, the first one being the canonical route.
+    ats: Vec<RoutePath>,
+    /// The split module passed to the `lazy` attribute.
+    lazy_module: Option<LitStr>,
+    is_unit: bool,
+}
+
+struct RouteField {
+    ident: Ident,
+    ty: Type,
+    is_option: bool,
+}
+
 impl Parse for Routable {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let DeriveInput { ident, data, .. } = input.parse()?;
@@ -35,12 +55,12 @@ impl Parse for Routable {
             }
         };
 
-        let (not_found_route, ats) = parse_variants_attributes(&data.variants)?;
+        let (not_found_route, variants) = parse_variants_attributes(&data.variants)?;
+        validate_unambiguous(&variants)?;
 
         Ok(Self {
             ident,
-            variants: data.variants,
-            ats,
+            variants,
             not_found_route,
         })
     }
@@ -48,9 +68,9 @@ impl Parse for Routable {
 
 fn parse_variants_attributes(
     variants: &Punctuated<Variant, syn::token::Comma>,
-) -> syn::Result<(Option<Ident>, Vec<LitStr>)> {
+) -> syn::Result<(Option<Ident>, Vec<RoutableVariant>)> {
     let mut not_founds = vec![];
-    let mut ats: Vec<LitStr> = vec![];
+    let mut routable_variants = vec![];
 
     let mut not_found_attrs = vec![];
 
@@ -68,40 +88,53 @@ fn parse_variants_attributes(
             .filter(|attr| attr.path().is_ident(AT_ATTR_IDENT))
             .collect::<Vec<_>>();
 
-        let attr = match at_attrs.len() {
-            1 => *at_attrs.first().unwrap(),
-            0 => {
-                return Err(syn::Error::new(
-                    variant.span(),
-                    format!("{AT_ATTR_IDENT} attribute must be present on every variant"),
-                ))
-            }
+        if at_attrs.is_empty() {
+            return Err(syn::Error::new(
+                variant.span(),
+                format!("{AT_ATTR_IDENT} attribute must be present on every variant"),
+            ));
+        }
+
+        let ats = at_attrs
+            .into_iter()
+            .map(|attr| RoutePath::parse(attr.parse_args::<LitStr>()?))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let fields = variant
+            .fields
+            .iter()
+            .map(|field| RouteField {
+                // named fields have idents
+                ident: field.ident.clone().unwrap(),
+                ty: field.ty.clone(),
+                is_option: is_option(&field.ty),
+            })
+            .collect::<Vec<_>>();
+
+        validate_fields(&fields, &ats)?;
+
+        let lazy_attrs = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(LAZY_ATTR_IDENT))
+            .collect::<Vec<_>>();
+        let lazy_module = match lazy_attrs.as_slice() {
+            [] => None,
+            [attr] => Some(attr.parse_args::<LitStr>()?),
             _ => {
                 return Err(syn::Error::new_spanned(
-                    quote! { #(#at_attrs)* },
-                    format!("only one {AT_ATTR_IDENT} attribute must be present"),
+                    quote! { #(#lazy_attrs)* },
+                    format!("only one {LAZY_ATTR_IDENT} attribute must be present"),
                 ))
             }
         };
 
-        let lit = attr.parse_args::<LitStr>()?;
-        let val = lit.value();
-
-        if val.find('#').is_some() {
-            return Err(syn::Error::new_spanned(
-                lit,
-                "You cannot use `#` in your routes. Please consider `HashRouter` instead.",
-            ));
-        }
-
-        if !val.starts_with('/') {
-            return Err(syn::Error::new_spanned(
-                lit,
-                "relative paths are not supported at this moment.",
-            ));
-        }
-
-        ats.push(lit);
+        routable_variants.push(RoutableVariant {
+            ident: variant.ident.clone(),
+            fields,
+            ats,
+            lazy_module,
+            is_unit: matches!(variant.fields, Fields::Unit),
+        });
 
         for attr in attrs.iter() {
             if attr.path().is_ident(NOT_FOUND_ATTR_IDENT) {
@@ -118,33 +151,164 @@ fn parse_variants_attributes(
         ));
     }
 
-    Ok((not_founds.into_iter().next(), ats))
+    Ok((not_founds.into_iter().next(), routable_variants))
+}
+
+/// Checks whether a type is syntactically an `Option<T>`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Makes sure every field can be recovered from each route of a variant.
+///
+/// The canonical (first) route is used to build paths, so it must bind every field, with
+/// `Option` fields bound to optional segments. Aliases must bind every non-`Option` field,
+/// otherwise they can never be matched.
+fn validate_fields(fields: &[RouteField], ats: &[RoutePath]) -> syn::Result<()> {
+    for (i, at) in ats.iter().enumerate() {
+        for field in fields.iter() {
+            let name = field.ident.to_string();
+            let optional = match at.segment(&name) {
+                Some(Segment::Param { optional, .. }) => *optional,
+                Some(_) => false,
+                None if i == 0 => {
+                    return Err(syn::Error::new_spanned(
+                        &at.lit,
+                        format!("field `{name}` is not bound by route `{}`", at.lit.value()),
+                    ))
+                }
+                None if field.is_option => continue,
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        &at.lit,
+                        format!(
+                            "route `{}` is unreachable: it does not bind field `{name}`",
+                            at.lit.value()
+                        ),
+                    ))
+                }
+            };
+
+            if optional && !field.is_option {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    format!(
+                        "field `{name}` must be an `Option` as it is bound to an optional segment"
+                    ),
+                ));
+            }
+
+            if i == 0 && !optional && field.is_option {
+                return Err(syn::Error::new_spanned(
+                    &at.lit,
+                    format!(
+                        "field `{name}` is an `Option` and must be bound to an optional segment, \
+                         e.g. `:{name}?`"
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Makes sure no two routes match the same paths.
+///
+/// Routes are ranked by specificity, so they only conflict if they have the same shape.
+fn validate_unambiguous(variants: &[RoutableVariant]) -> syn::Result<()> {
+    let mut seen: HashMap<Vec<ShapeSegment>, (&Ident, &LitStr)> = HashMap::new();
+
+    for variant in variants.iter() {
+        for at in variant.ats.iter() {
+            let shapes = at.shapes();
+            let mut covered_by_self = None;
+            let mut fully_covered = true;
+
+            for shape in shapes.iter() {
+                match seen.get(shape) {
+                    Some((ident, lit)) if *ident != &variant.ident => {
+                        return Err(syn::Error::new_spanned(
+                            &at.lit,
+                            format!(
+                                "route `{}` is ambiguous: route `{}` of variant `{ident}` matches \
+                                 the same paths",
+                                at.lit.value(),
+                                lit.value(),
+                            ),
+                        ));
+                    }
+                    Some((_, lit)) => covered_by_self = Some(*lit),
+                    None => fully_covered = false,
+                }
+            }
+
+            if let (true, Some(lit)) = (fully_covered, covered_by_self) {
+                return Err(syn::Error::new_spanned(
+                    &at.lit,
+                    format!(
+                        "route `{}` is unreachable: route `{}` already matches the same paths",
+                        at.lit.value(),
+                        lit.value(),
+                    ),
+                ));
+            }
+
+            for shape in shapes.into_iter() {
+                seen.entry(shape).or_insert((&variant.ident, &at.lit));
+            }
+        }
+    }
+
+    Ok(())
 }
 
 impl Routable {
     fn build_from_path(&self) -> TokenStream {
-        let from_path_matches = self.variants.iter().enumerate().map(|(i, variant)| {
+        let from_path_matches = self.variants.iter().map(|variant| {
             let ident = &variant.ident;
-            let right = match &variant.fields {
-                Fields::Unit => quote! { Self::#ident },
-                Fields::Named(field) => {
-                    let fields = field.named.iter().map(|it| {
-                        // named fields have idents
-                        it.ident.as_ref().unwrap()
-                    });
-                    quote! { Self::#ident { #(#fields: {
-                        let param = params.get(stringify!(#fields))?;
+            let right = if variant.is_unit {
+                quote! { Self::#ident }
+            } else {
+                let fields = variant.fields.iter().map(|field| {
+                    let name = &field.ident;
+                    let parse = quote! {
                         let param = &*::yew_router::__macro::decode_for_url(param).ok()?;
                         let param = param.parse().ok()?;
                         param
-                    },)* } }
-                }
-                Fields::Unnamed(_) => unreachable!(), // already checked
+                    };
+
+                    if field.is_option {
+                        quote! {
+                            #name: match params.get(stringify!(#name)) {
+                                ::std::option::Option::Some(param) => {
+                                    ::std::option::Option::Some({ #parse })
+                                }
+                                ::std::option::Option::None => ::std::option::Option::None,
+                            }
+                        }
+                    } else {
+                        quote! {
+                            #name: {
+                                let param = params.get(stringify!(#name))?;
+                                #parse
+                            }
+                        }
+                    }
+                });
+                quote! { Self::#ident { #(#fields,)* } }
             };
 
-            let left = self.ats.get(i).unwrap();
+            let left = variant.ats.iter().map(|at| &at.lit);
             quote! {
-                #left => ::std::option::Option::Some(#right)
+                #(#left)|* => ::std::option::Option::Some(#right)
             }
         });
 
@@ -159,32 +323,62 @@ impl Routable {
     }
 
     fn build_to_path(&self) -> TokenStream {
-        let to_path_matches = self.variants.iter().enumerate().map(|(i, variant)| {
+        let to_path_matches = self.variants.iter().map(|variant| {
             let ident = &variant.ident;
-            let mut right = self.ats.get(i).unwrap().value();
-
-            match &variant.fields {
-                Fields::Unit => quote! { Self::#ident => ::std::string::ToString::to_string(#right) },
-                Fields::Named(field) => {
-                    let fields = field
-                        .named
-                        .iter()
-                        .map(|it| it.ident.as_ref().unwrap())
-                        .collect::<Vec<_>>();
-
-                    for field in fields.iter() {
-                        // :param -> {param}
-                        // *param -> {param}
-                        // so we can pass it to `format!("...", param)`
-                        right = right.replace(&format!(":{field}"), &format!("{{{field}}}"));
-                        right = right.replace(&format!("*{field}"), &format!("{{{field}}}"));
+            let at = &variant.ats[0];
+
+            if variant.is_unit {
+                let right = &at.lit;
+                return quote! { Self::#ident => ::std::string::ToString::to_string(#right) };
+            }
+
+            let fields = variant.fields.iter().map(|it| &it.ident).collect::<Vec<_>>();
+            let segments = at.segments.iter().map(|segment| {
+                let field = segment
+                    .name()
+                    .and_then(|name| variant.fields.iter().find(|it| it.ident == name));
+
+                match (segment, field) {
+                    (Segment::Static(s), _) => quote! {
+                        path.push('/');
+                        path.push_str(#s);
+                    },
+                    (_, Some(RouteField { ident, is_option: true, .. })) => quote! {
+                        if let ::std::option::Option::Some(#ident) = #ident {
+                            path.push('/');
+                            path.push_str(&::yew_router::__macro::encode_for_url(&::std::format!("{}", #ident)));
+                        }
+                    },
+                    (_, Some(RouteField { ident, .. })) => quote! {
+                        path.push('/');
+                        path.push_str(&::yew_router::__macro::encode_for_url(&::std::format!("{}", #ident)));
+                    },
+                    // parameters without a matching field are left for nested routers to match
+                    (Segment::Param { optional: true, .. }, None) => TokenStream::new(),
+                    (Segment::Param { raw, .. }, None) => quote! {
+                        path.push('/');
+                        path.push_str(#raw);
+                    },
+                    (Segment::Wildcard { name }, None) => {
+                        let raw = format!("*{name}");
+                        quote! {
+                            path.push('/');
+                            path.push_str(#raw);
+                        }
                     }
+                }
+            });
+            let trailing_slash = at.trailing_slash;
 
-                    quote! {
-                        Self::#ident { #(#fields),* } => ::std::format!(#right, #(#fields = ::yew_router::__macro::encode_for_url(&::std::format!("{}", #fields))),*)
+            quote! {
+                Self::#ident { #(#fields),* } => {
+                    let mut path = ::std::string::String::new();
+                    #(#segments)*
+                    if path.is_empty() || #trailing_slash {
+                        path.push('/');
                     }
+                    path
                 }
-                Fields::Unnamed(_) => unreachable!(), // already checked
             }
         });
 
@@ -198,16 +392,47 @@ impl Routable {
     }
 }
 
+impl Routable {
+    fn build_lazy_module(&self) -> TokenStream {
+        if self.variants.iter().all(|it| it.lazy_module.is_none()) {
+            return TokenStream::new();
+        }
+
+        let lazy_module_matches = self.variants.iter().filter_map(|variant| {
+            let ident = &variant.ident;
+            let module = variant.lazy_module.as_ref()?;
+
+            Some(quote! {
+                Self::#ident { .. } => ::std::option::Option::Some(#module)
+            })
+        });
+
+        quote! {
+            fn lazy_module(&self) -> ::std::option::Option<&'static str> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#lazy_module_matches,)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+    }
+}
+
 pub fn routable_derive_impl(input: Routable) -> TokenStream {
     let Routable {
-        ats,
+        variants,
         not_found_route,
         ident,
-        ..
     } = &input;
 
+    let ats = variants
+        .iter()
+        .flat_map(|variant| variant.ats.iter().map(|at| &at.lit));
+
     let from_path = input.build_from_path();
     let to_path = input.build_to_path();
+    let lazy_module = input.build_lazy_module();
 
     let maybe_not_found_route = match not_found_route {
         Some(route) => quote! { ::std::option::Option::Some(Self::#route) },
@@ -232,6 +457,7 @@ pub fn routable_derive_impl(input: Routable) -> TokenStream {
         impl ::yew_router::Routable for #ident {
             #from_path
             #to_path
+            #lazy_module
 
             fn routes() -> ::std::vec::Vec<&'static str> {
                 ::std::vec![#(#ats),*]
@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+
+use syn::LitStr;
+
+/// A segment of a route passed to the `at` attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// `/posts`
+    Static(String),
+    /// `/:id`, `/:id?`, `/{id}`, `/{id?}`, `/{id:\d+}` or `/{id?:\d+}`
+    Param {
+        name: String,
+        /// The constraint as printed from its parsed form, so constraints written differently,
+        /// like `a|b` and `[ab]`, compare equal.
+        constraint: Option<String>,
+        optional: bool,
+        raw: String,
+    },
+    /// `/*rest`, or `/*` which isn't bound to a field
+    Wildcard { name: String },
+}
+
+impl Segment {
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::Static(_) => None,
+            Self::Wildcard { name } if name.is_empty() => None,
+            Self::Param { name, .. } | Self::Wildcard { name } => Some(name),
+        }
+    }
+}
+
+/// The shape of a concrete route, used to detect routes that match the same paths.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ShapeSegment {
+    Static(String),
+    Param(Option<String>),
+    Wildcard,
+}
+
+/// A route passed to the `at` attribute.
+pub struct RoutePath {
+    pub lit: LitStr,
+    pub segments: Vec<Segment>,
+    pub trailing_slash: bool,
+}
+
+impl RoutePath {
+    pub fn parse(lit: LitStr) -> syn::Result<Self> {
+        let val = lit.value();
+
+        if val.find('#').is_some() {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "You cannot use `#` in your routes. Please consider `HashRouter` instead.",
+            ));
+        }
+
+        if !val.starts_with('/') {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "relative paths are not supported at this moment.",
+            ));
+        }
+
+        let path = val.strip_suffix('/').unwrap_or(&val);
+        let trailing_slash = path.len() != val.len() && !path.is_empty();
+
+        let mut segments = Vec::new();
+        let mut names = HashSet::new();
+
+        if let Some(path) = path.strip_prefix('/') {
+            for segment in path.split('/') {
+                let segment = parse_segment(&lit, segment)?;
+
+                if let Some(name) = segment.name() {
+                    if !names.insert(name.to_string()) {
+                        return Err(syn::Error::new_spanned(
+                            &lit,
+                            format!("route parameter `{name}` is declared more than once"),
+                        ));
+                    }
+                }
+
+                segments.push(segment);
+            }
+        }
+
+        Ok(Self {
+            lit,
+            segments,
+            trailing_slash,
+        })
+    }
+
+    pub fn segment(&self, name: &str) -> Option<&Segment> {
+        self.segments.iter().find(|it| it.name() == Some(name))
+    }
+
+    /// Returns the shapes of all concrete routes this route expands to, by including or leaving out
+    /// each of its optional segments.
+    pub fn shapes(&self) -> Vec<Vec<ShapeSegment>> {
+        let mut shapes = vec![vec![]];
+
+        for segment in self.segments.iter() {
+            let shape_segment = match segment {
+                Segment::Static(s) => ShapeSegment::Static(s.clone()),
+                Segment::Param { constraint, .. } => ShapeSegment::Param(constraint.clone()),
+                Segment::Wildcard { .. } => ShapeSegment::Wildcard,
+            };
+
+            match segment {
+                Segment::Param { optional: true, .. } => {
+                    let with_segment = shapes.iter().cloned().map(|mut shape: Vec<_>| {
+                        shape.push(shape_segment.clone());
+                        shape
+                    });
+                    shapes = shapes.iter().cloned().chain(with_segment).collect();
+                }
+                _ => shapes
+                    .iter_mut()
+                    .for_each(|shape| shape.push(shape_segment.clone())),
+            }
+        }
+
+        shapes
+    }
+}
+
+fn parse_segment(lit: &LitStr, segment: &str) -> syn::Result<Segment> {
+    let err = |msg: String| Err(syn::Error::new_spanned(lit, msg));
+
+    if let Some(name) = segment.strip_prefix('*') {
+        if name.ends_with('?') {
+            return err(format!("wildcard segment `{segment}` cannot be optional"));
+        }
+        if !name.is_empty() {
+            validate_name(lit, segment, name)?;
+        }
+
+        return Ok(Segment::Wildcard {
+            name: name.to_string(),
+        });
+    }
+
+    if let Some(name) = segment.strip_prefix(':') {
+        let (name, optional) = match name.strip_suffix('?') {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        validate_name(lit, segment, name)?;
+
+        return Ok(Segment::Param {
+            name: name.to_string(),
+            constraint: None,
+            optional,
+            raw: segment.to_string(),
+        });
+    }
+
+    if let Some(inner) = segment.strip_prefix('{') {
+        let inner = match inner.strip_suffix('}') {
+            Some(inner) => inner,
+            None => return err(format!("unclosed `{{` in route segment `{segment}`")),
+        };
+
+        let (name, constraint) = match inner.split_once(':') {
+            Some((name, constraint)) => (name, Some(constraint)),
+            None => (inner, None),
+        };
+        let (name, optional) = match name.strip_suffix('?') {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        validate_name(lit, segment, name)?;
+
+        let constraint = match constraint {
+            Some("") => return err(format!("route parameter `{name}` has an empty constraint")),
+            Some(constraint) => match regex_syntax::Parser::new().parse(constraint) {
+                Ok(hir) => Some(hir.to_string()),
+                Err(e) => {
+                    return err(format!(
+                        "invalid constraint for route parameter `{name}`:\n{e}"
+                    ))
+                }
+            },
+            None => None,
+        };
+
+        return Ok(Segment::Param {
+            name: name.to_string(),
+            constraint,
+            optional,
+            raw: segment.to_string(),
+        });
+    }
+
+    if segment.contains(['{', '}', '?']) {
+        return err(format!(
+            "invalid route segment `{segment}`, route parameters must span a whole segment"
+        ));
+    }
+
+    Ok(Segment::Static(segment.to_string()))
+}
+
+fn validate_name(lit: &LitStr, segment: &str, name: &str) -> syn::Result<()> {
+    if name.is_empty() {
+        return Err(syn::Error::new_spanned(
+            lit,
+            format!("route segment `{segment}` must be named"),
+        ));
+    }
+
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(syn::Error::new_spanned(
+            lit,
+            format!("invalid route parameter name `{name}` in segment `{segment}`"),
+        ));
+    }
+
+    Ok(())
+}
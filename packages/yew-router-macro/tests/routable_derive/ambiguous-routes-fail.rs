@@ -0,0 +1,32 @@
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum Routes {
+    #[at("/posts/:id")]
+    Post { id: u32 },
+    #[at("/posts/:slug")]
+    PostBySlug { slug: String },
+}
+
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum OptionalRoutes {
+    #[at("/posts/:page?")]
+    Posts { page: Option<u32> },
+    #[at("/posts")]
+    AllPosts,
+}
+
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum AliasRoutes {
+    #[at("/")]
+    #[at("/")]
+    Home,
+}
+
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum ConstrainedRoutes {
+    #[at("/tags/{tag:a|b}")]
+    Tag { tag: String },
+    #[at("/tags/{name:[ab]}")]
+    TagByName { name: String },
+}
+
+fn main() {}
@@ -3,11 +3,4 @@ enum Routes {
     One,
 }
 
-#[derive(yew_router::Routable)]
-enum RoutesTwo {
-    #[at("/one")]
-    #[at("/two")]
-    One,
-}
-
 fn main() {}
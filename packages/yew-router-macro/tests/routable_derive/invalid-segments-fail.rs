@@ -0,0 +1,31 @@
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum BadConstraint {
+    #[at("/user/{id:\\d+(}")]
+    User { id: u32 },
+}
+
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum UnclosedBrace {
+    #[at("/user/{id")]
+    User { id: u32 },
+}
+
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum PartialSegment {
+    #[at("/user-{id}")]
+    User { id: u32 },
+}
+
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum OptionalWildcard {
+    #[at("/files/*path?")]
+    Files { path: String },
+}
+
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum DuplicateParam {
+    #[at("/:id/:id")]
+    Item { id: u32 },
+}
+
+fn main() {}
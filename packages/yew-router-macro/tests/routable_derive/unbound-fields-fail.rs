@@ -0,0 +1,26 @@
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum Unbound {
+    #[at("/user")]
+    User { id: u32 },
+}
+
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum RequiredOptional {
+    #[at("/posts/:page?")]
+    Posts { page: u32 },
+}
+
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum OptionalRequired {
+    #[at("/posts/:page")]
+    Posts { page: Option<u32> },
+}
+
+#[derive(Debug, PartialEq, Clone, yew_router::Routable)]
+enum UnreachableAlias {
+    #[at("/user/:id")]
+    #[at("/me")]
+    User { id: u32 },
+}
+
+fn main() {}
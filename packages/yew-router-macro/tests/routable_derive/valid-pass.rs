@@ -17,8 +17,27 @@ enum Routes {
 enum MoreRoutes {
     #[at("/subpath/*rest")]
     Subpath { rest: ::std::string::String },
+    #[at("/settings/*")]
+    Settings,
     #[at("/*all")]
     CatchAll { all: ::std::string::String },
 }
 
+#[derive(Debug, PartialEq, Clone, ::yew_router::Routable)]
+enum ConstrainedRoutes {
+    #[at("/")]
+    #[at("/home")]
+    Home,
+    #[at("/user/{id:\\d+}")]
+    User { id: u32 },
+    #[at("/user/{name}")]
+    UserByName { name: ::std::string::String },
+    #[at("/posts/:page?")]
+    #[at("/p/{page?:\\d+}")]
+    Posts { page: ::std::option::Option<u32> },
+    #[at("/admin")]
+    #[lazy("admin")]
+    Admin,
+}
+
 fn main() {}
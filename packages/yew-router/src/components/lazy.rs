@@ -0,0 +1,88 @@
+use wasm_bindgen::{JsCast, JsValue};
+use yew::prelude::*;
+use yew::suspense::use_future_with;
+
+use crate::lazy::{use_lazy_loader, LazyError};
+
+/// Props for [`Lazy`]
+#[derive(Properties, Clone, PartialEq)]
+pub struct LazyProps {
+    /// Name of the module in the [`LazyManifest`](crate::lazy::LazyManifest).
+    pub module: AttrValue,
+    /// Name of the function exported by the module that mounts its content.
+    #[prop_or(AttrValue::Static("mount"))]
+    pub export: AttrValue,
+    /// Value passed to the mount function of the module, `undefined` if unset.
+    #[prop_or_default]
+    pub props: Option<JsValue>,
+    /// CSS classes to add to the element the module is mounted into.
+    #[prop_or_default]
+    pub classes: Classes,
+}
+
+/// A component that loads a split module and mounts it into a `<div>` element.
+///
+/// The component suspends until the module is loaded, so it should be rendered under a
+/// [`Suspense`](yew::suspense::Suspense) whose fallback is shown in the meantime. The mount
+/// function of the module is called with the element and [`props`](LazyProps::props). If it
+/// returns a function, that function is called when the component is unmounted.
+///
+/// Modules can only be loaded in a browser, an empty element is rendered otherwise (e.g.: during
+/// server-side rendering).
+///
+/// See the [`lazy`](crate::lazy) module for details.
+#[function_component]
+pub fn Lazy(props: &LazyProps) -> HtmlResult {
+    let loader = use_lazy_loader();
+    let host = use_node_ref();
+
+    let module = use_future_with(props.module.clone(), move |module| async move {
+        match loader {
+            Some(loader) => loader.load(&module).await,
+            None => Err(LazyError::NotRegistered(module.to_string())),
+        }
+    })?;
+    let module = match &*module {
+        Ok(m) => Some(m.clone()),
+        Err(LazyError::Unsupported) => None,
+        Err(e) => {
+            tracing::error!("{}", e);
+            None
+        }
+    };
+
+    use_effect_with((module, props.export.clone(), props.props.clone()), {
+        let host = host.clone();
+        move |(module, export, props)| {
+            let unmount = module.as_ref().zip(host.get()).and_then(|(module, host)| {
+                let mount = match module.function(export) {
+                    Some(m) => m,
+                    None => {
+                        tracing::error!("module does not export `{}`", export);
+                        return None;
+                    }
+                };
+
+                let undefined = JsValue::UNDEFINED;
+                let props = props.as_ref().unwrap_or(&undefined);
+                match mount.call2(&JsValue::NULL, &host, props) {
+                    Ok(unmount) => unmount.dyn_into::<js_sys::Function>().ok(),
+                    Err(e) => {
+                        tracing::error!("failed to mount module: {:?}", e);
+                        None
+                    }
+                }
+            });
+
+            move || {
+                if let Some(unmount) = unmount {
+                    let _ = unmount.call0(&JsValue::NULL);
+                }
+            }
+        }
+    });
+
+    Ok(html! {
+        <div class={props.classes.clone()} ref={host} />
+    })
+}
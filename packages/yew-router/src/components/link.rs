@@ -1,9 +1,16 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use serde::Serialize;
-use wasm_bindgen::UnwrapThrowExt;
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry};
 use yew::prelude::*;
-use yew::virtual_dom::AttrValue;
+use yew::virtual_dom::{AttrValue, VNode};
 
+use crate::lazy::use_lazy_loader;
 use crate::navigator::NavigatorKind;
+use crate::prefetch::use_prefetcher;
 use crate::prelude::*;
 use crate::{utils, Routable};
 
@@ -31,11 +38,33 @@ where
     /// [`NodeRef`](yew::html::NodeRef) for the `<a>` element.
     #[prop_or_default]
     pub anchor_ref: NodeRef,
+    /// When to prefetch the route, see the [`prefetch`](crate::prefetch) module.
+    #[prop_or_default]
+    pub prefetch: Prefetch,
+    /// Where to open the route, e.g. `_blank`.
+    ///
+    /// Clicking the link only navigates within the application when the target is `_self` or
+    /// unset, other targets are left to the browser.
+    #[prop_or_default]
+    pub target: Option<AttrValue>,
+    /// The `rel` attribute of the anchor element.
+    #[prop_or_default]
+    pub rel: Option<AttrValue>,
+    /// The `title` attribute of the anchor element.
+    #[prop_or_default]
+    pub title: Option<AttrValue>,
+    /// Additional attributes of the anchor element, e.g. `aria-*` or `data-*` attributes.
+    #[prop_or_default]
+    pub attrs: Vec<(&'static str, AttrValue)>,
     #[prop_or_default]
     pub children: Html,
 }
 
 /// A wrapper around `<a>` tag to be used with [`Router`](crate::Router)
+///
+/// The route can be prefetched before the link is clicked, as configured by the
+/// [`prefetch`](LinkProps::prefetch) prop. By default, it is prefetched when the link is hovered or
+/// focused. See the [`prefetch`](crate::prefetch) module for details.
 #[function_component]
 pub fn Link<R, Q = (), S = ()>(props: &LinkProps<R, Q, S>) -> Html
 where
@@ -50,21 +79,124 @@ where
         state,
         disabled,
         anchor_ref,
+        prefetch,
+        target,
+        rel,
+        title,
+        attrs,
         children,
     } = props.clone();
 
+    let LinkHandlers {
+        href,
+        onclick,
+        onmouseenter,
+        onfocus,
+    } = use_link_handlers(
+        to,
+        query,
+        state,
+        target.clone(),
+        prefetch,
+        anchor_ref.clone(),
+    );
+
+    let anchor = html! {
+        // `disabled` isn't an attribute of `<a>`, but it is set for styling.
+        #![allow(unknown_attributes)]
+        <a class={classes}
+            {href}
+            {target}
+            {rel}
+            {title}
+            {onclick}
+            {onmouseenter}
+            {onfocus}
+            {disabled}
+            ref={anchor_ref}
+        >
+            { children }
+        </a>
+    };
+
+    with_attrs(anchor, attrs)
+}
+
+/// Adds the attributes of the `attrs` prop to an anchor.
+pub(crate) fn with_attrs(mut anchor: Html, attrs: Vec<(&'static str, AttrValue)>) -> Html {
+    if let VNode::VTag(tag) = &mut anchor {
+        for (key, value) in attrs {
+            tag.add_attribute(key, value);
+        }
+    }
+
+    anchor
+}
+
+/// The attributes shared by [`Link`] and [`NavLink`](super::NavLink).
+pub(crate) struct LinkHandlers {
+    pub href: AttrValue,
+    pub onclick: Callback<MouseEvent>,
+    pub onmouseenter: Option<Callback<MouseEvent>>,
+    pub onfocus: Option<Callback<FocusEvent>>,
+}
+
+#[hook]
+pub(crate) fn use_link_handlers<R, Q, S>(
+    to: R,
+    query: Option<Q>,
+    state: Option<S>,
+    target: Option<AttrValue>,
+    prefetch: Prefetch,
+    anchor_ref: NodeRef,
+) -> LinkHandlers
+where
+    R: Routable + 'static,
+    Q: Clone + PartialEq + Serialize + 'static,
+    S: Clone + PartialEq + 'static,
+{
     let navigator = use_navigator().expect_throw("failed to get navigator");
+    let lazy_loader = use_lazy_loader();
+    let prefetcher = use_prefetcher::<R>();
+
+    let lazy_loader = to.lazy_module().zip(lazy_loader);
+    let prefetch_route = (lazy_loader.is_some() || prefetcher.is_some()).then(|| {
+        let to = to.clone();
+        Callback::from(move |_| {
+            if let Some((module, loader)) = &lazy_loader {
+                loader.preload(module);
+            }
+            if let Some(prefetcher) = &prefetcher {
+                prefetcher.prefetch(&to);
+            }
+        })
+    });
+
+    let (onmouseenter, onfocus) = match (prefetch, &prefetch_route) {
+        (Prefetch::Hover, Some(prefetch_route)) => (
+            Some(prefetch_route.reform(|_: MouseEvent| ())),
+            Some(prefetch_route.reform(|_: FocusEvent| ())),
+        ),
+        _ => (None, None),
+    };
+    use_prefetch_when_visible(
+        anchor_ref,
+        prefetch_route.filter(|_| prefetch == Prefetch::Visible),
+    );
 
     let onclick = {
         let navigator = navigator.clone();
         let to = to.clone();
         let query = query.clone();
-        let state = state.clone();
 
         Callback::from(move |e: MouseEvent| {
             if e.meta_key() || e.ctrl_key() || e.shift_key() || e.alt_key() {
                 return;
             }
+            // Let the browser open the route in another browsing context.
+            if matches!(target.as_deref(), Some(target) if target != "_self") {
+                return;
+            }
             e.prevent_default();
             match (&state, &query) {
                 (None, None) => {
@@ -102,14 +234,62 @@ where
         AttrValue::from(path)
     };
 
-    html! {
-        <a class={classes}
-            {href}
-            {onclick}
-            {disabled}
-            ref={anchor_ref}
-        >
-            { children }
-        </a>
+    LinkHandlers {
+        href,
+        onclick,
+        onmouseenter,
+        onfocus,
     }
 }
+
+/// Prefetches a route once its anchor is scrolled into the viewport.
+#[hook]
+fn use_prefetch_when_visible(anchor_ref: NodeRef, prefetch_route: Option<Callback<()>>) {
+    let latest = use_mut_ref(|| None);
+    let enabled = prefetch_route.is_some();
+    *latest.borrow_mut() = prefetch_route;
+
+    use_effect_with((anchor_ref, enabled), move |(anchor_ref, enabled)| {
+        let observer = enabled
+            .then(|| observe_visibility(anchor_ref, latest))
+            .flatten();
+
+        move || {
+            if let Some((observer, _)) = observer {
+                observer.disconnect();
+            }
+        }
+    });
+}
+
+type OnIntersect = Closure<dyn Fn(js_sys::Array, IntersectionObserver)>;
+
+fn observe_visibility(
+    anchor_ref: &NodeRef,
+    prefetch_route: Rc<RefCell<Option<Callback<()>>>>,
+) -> Option<(IntersectionObserver, OnIntersect)> {
+    let anchor = anchor_ref.cast::<Element>()?;
+
+    let on_intersect = OnIntersect::new(
+        move |entries: js_sys::Array, observer: IntersectionObserver| {
+            let visible = entries.iter().any(|entry| {
+                entry
+                    .unchecked_into::<IntersectionObserverEntry>()
+                    .is_intersecting()
+            });
+
+            if visible {
+                observer.disconnect();
+                let prefetch_route = prefetch_route.borrow().clone();
+                if let Some(prefetch_route) = prefetch_route {
+                    prefetch_route.emit(());
+                }
+            }
+        },
+    );
+    // IntersectionObserver is not supported by every browser.
+    let observer = IntersectionObserver::new(on_intersect.as_ref().unchecked_ref()).ok()?;
+    observer.observe(&anchor);
+
+    Some((observer, on_intersect))
+}
@@ -1,6 +1,10 @@
 //! Components to interface with [Router][crate::Router].
 
+mod lazy;
 mod link;
+mod nav_link;
 mod redirect;
+pub use lazy::*;
 pub use link::*;
+pub use nav_link::*;
 pub use redirect::*;
@@ -0,0 +1,141 @@
+use serde::Serialize;
+use yew::prelude::*;
+
+use super::link::{use_link_handlers, with_attrs, LinkHandlers};
+use crate::hooks::use_route_match;
+use crate::prefetch::Prefetch;
+use crate::Routable;
+
+/// Props for [`NavLink`]
+#[derive(Properties, Clone, PartialEq)]
+pub struct NavLinkProps<R, Q = (), S = ()>
+where
+    R: Routable,
+    Q: Clone + PartialEq + Serialize,
+    S: Clone + PartialEq,
+{
+    /// CSS classes to add to the anchor element (optional).
+    #[prop_or_default]
+    pub classes: Classes,
+    /// CSS classes to add to the anchor element when the route is active.
+    ///
+    /// Defaults to `active`.
+    #[prop_or_else(|| classes!("active"))]
+    pub active_classes: Classes,
+    /// Only consider the route active when it matches the whole path.
+    ///
+    /// By default, the route is also active when it matches the leading segments of the path,
+    /// e.g. a link to `/posts` is active on `/posts/1`.
+    #[prop_or_default]
+    pub exact: bool,
+    /// Route that will be pushed when the anchor is clicked.
+    pub to: R,
+    /// Route query data
+    #[prop_or_default]
+    pub query: Option<Q>,
+    /// Route state data
+    #[prop_or_default]
+    pub state: Option<S>,
+    #[prop_or_default]
+    pub disabled: bool,
+    /// [`NodeRef`](yew::html::NodeRef) for the `<a>` element.
+    #[prop_or_default]
+    pub anchor_ref: NodeRef,
+    /// When to prefetch the route, see the [`prefetch`](crate::prefetch) module.
+    #[prop_or_default]
+    pub prefetch: Prefetch,
+    /// Where to open the route, see [`LinkProps::target`](super::LinkProps::target).
+    #[prop_or_default]
+    pub target: Option<AttrValue>,
+    /// The `rel` attribute of the anchor element.
+    #[prop_or_default]
+    pub rel: Option<AttrValue>,
+    /// The `title` attribute of the anchor element.
+    #[prop_or_default]
+    pub title: Option<AttrValue>,
+    /// Additional attributes of the anchor element, e.g. `aria-*` or `data-*` attributes.
+    #[prop_or_default]
+    pub attrs: Vec<(&'static str, AttrValue)>,
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// A [`Link`](super::Link) that knows whether its route is active.
+///
+/// When the route matches the current location, the
+/// [`active_classes`](NavLinkProps::active_classes) are added to the anchor and
+/// `aria-current="page"` is set. The query of the location is not taken into account.
+///
+/// A link to `/` is only active on `/`, as every path starts with it.
+#[function_component]
+pub fn NavLink<R, Q = (), S = ()>(props: &NavLinkProps<R, Q, S>) -> Html
+where
+    R: Routable + 'static,
+    Q: Clone + PartialEq + Serialize + 'static,
+    S: Clone + PartialEq + 'static,
+{
+    let NavLinkProps {
+        classes,
+        active_classes,
+        exact,
+        to,
+        query,
+        state,
+        disabled,
+        anchor_ref,
+        prefetch,
+        target,
+        rel,
+        title,
+        attrs,
+        children,
+    } = props.clone();
+
+    let path = to.to_path();
+    let route_match = use_route_match(&path);
+    let active = match route_match {
+        Some(m) => m.is_exact() || (!exact && path != "/"),
+        None => false,
+    };
+
+    let LinkHandlers {
+        href,
+        onclick,
+        onmouseenter,
+        onfocus,
+    } = use_link_handlers(
+        to,
+        query,
+        state,
+        target.clone(),
+        prefetch,
+        anchor_ref.clone(),
+    );
+
+    let (classes, aria_current) = if active {
+        (classes!(classes, active_classes), Some("page"))
+    } else {
+        (classes, None)
+    };
+
+    let anchor = html! {
+        // `disabled` isn't an attribute of `<a>`, but it is set for styling.
+        #![allow(unknown_attributes)]
+        <a class={classes}
+            {href}
+            {target}
+            {rel}
+            {title}
+            {onclick}
+            {onmouseenter}
+            {onfocus}
+            {disabled}
+            aria-current={aria_current}
+            ref={anchor_ref}
+        >
+            { children }
+        </a>
+    };
+
+    with_attrs(anchor, attrs)
+}
@@ -1,7 +1,8 @@
 use wasm_bindgen::UnwrapThrowExt;
 use yew::prelude::*;
 
-use crate::hooks::use_navigator;
+use crate::hooks::{use_navigator, use_server_response};
+use crate::response::RedirectStatus;
 use crate::Routable;
 
 /// Props for [`Redirect`]
@@ -9,9 +10,15 @@ use crate::Routable;
 pub struct RedirectProps<R: Routable> {
     /// Route that will be pushed when the component is rendered.
     pub to: R,
+    /// Status of the redirect when rendered on the server.
+    #[prop_or_default]
+    pub status: RedirectStatus,
 }
 
 /// A component that will redirect to specified route when rendered.
+///
+/// When rendered with a router that has been given a
+/// [`ServerResponse`](crate::response::ServerResponse), the redirect is recorded in the response.
 #[function_component(Redirect)]
 pub fn redirect<R>(props: &RedirectProps<R>) -> Html
 where
@@ -19,6 +26,11 @@ where
 {
     let history = use_navigator().expect_throw("failed to read history.");
 
+    if let Some(response) = use_server_response() {
+        let path = props.to.to_path();
+        response.set_redirect(history.prefix_basename(&path), props.status);
+    }
+
     let target_route = props.to.clone();
     use_effect(move || {
         history.push(&target_route);
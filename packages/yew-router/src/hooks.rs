@@ -1,11 +1,15 @@
 //! Hooks to access router state and navigate between pages.
 
+use std::collections::HashMap;
+
 use yew::prelude::*;
 
+use crate::__macro::{decode_for_url, PathPattern};
 use crate::history::*;
 use crate::navigator::Navigator;
+use crate::response::ServerResponse;
 use crate::routable::Routable;
-use crate::router::{LocationContext, NavigatorContext};
+use crate::router::{LocationContext, NavigatorContext, ResponseContext};
 
 /// A hook to access the [`Navigator`].
 #[hook]
@@ -19,6 +23,15 @@ pub fn use_location() -> Option<Location> {
     Some(use_context::<LocationContext>()?.location())
 }
 
+/// A hook to access the [`ServerResponse`] passed to the router.
+///
+/// This hook will return [`None`] unless the application is rendered with a router that has been
+/// given a [`ServerResponse`], which is usually only the case during server-side rendering.
+#[hook]
+pub fn use_server_response() -> Option<ServerResponse> {
+    use_context::<ResponseContext>()?.response()
+}
+
 /// A hook to access the current route.
 ///
 /// This hook will return [`None`] if there's no available location or none of the routes match.
@@ -38,3 +51,64 @@ where
 
     R::recognize(&path)
 }
+
+/// The result of [`use_route_match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteMatch {
+    params: HashMap<String, String>,
+    exact: bool,
+}
+
+impl RouteMatch {
+    /// Returns the decoded parameters of the match.
+    pub fn params(&self) -> &HashMap<String, String> {
+        &self.params
+    }
+
+    /// Returns a decoded parameter of the match.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(|m| m.as_str())
+    }
+
+    /// Returns `true` if the pattern matches the whole path, and `false` if it only matches its
+    /// leading segments.
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+/// A hook to match a route pattern against the current location.
+///
+/// The pattern uses the same syntax as the `at` attribute of
+/// [`Routable`](crate::Routable). It matches if it matches the whole path or its leading segments,
+/// so `/posts` matches both `/posts` and `/posts/1`. Use [`RouteMatch::is_exact`] to tell them
+/// apart. The basename of the router is stripped from the path before matching.
+///
+/// This hook will return [`None`] if there's no available location, the pattern does not
+/// match, or the pattern has a constraint which is not a valid regular expression.
+#[hook]
+pub fn use_route_match(pattern: &str) -> Option<RouteMatch> {
+    let pattern = use_memo(pattern.to_string(), |pattern| {
+        PathPattern::parse(pattern)
+            .map_err(|e| tracing::error!("invalid route pattern `{}`: {}", pattern, e))
+            .ok()
+    });
+    let pattern = pattern.as_ref().as_ref()?;
+    let navigator = use_navigator()?;
+    let location = use_location()?;
+    let path = navigator.strip_basename(location.path().into());
+
+    let params = pattern.match_path(&path, true)?;
+    let exact = pattern.match_path(&path, false).is_some();
+
+    Some(RouteMatch {
+        params: params
+            .into_iter()
+            .map(|(name, value)| {
+                let value = decode_for_url(value).map_or_else(|_| value.into(), |m| m.into_owned());
+                (name.to_string(), value)
+            })
+            .collect(),
+        exact,
+    })
+}
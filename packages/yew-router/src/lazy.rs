@@ -0,0 +1,317 @@
+//! Route-based code splitting.
+//!
+//! Parts of an application can be built as separate wasm modules (e.g.: with
+//! `wasm-bindgen --target web`) and loaded the first time they are rendered. A [`LazyManifest`]
+//! maps module names to the urls of their JavaScript glue and is provided to the application with
+//! [`LazyProvider`]. The [`Lazy`](crate::components::Lazy) component then loads a module,
+//! suspending until it is ready, and mounts it into an element it renders.
+//!
+//! Routes can be associated with a module with the `#[lazy("module")]` attribute. A
+//! [`Link`](crate::components::Link) to such a route preloads the module when it prefetches the
+//! route, see the [`prefetch`](crate::prefetch) module.
+//!
+//! # Split modules
+//!
+//! A split module exports a function that mounts its content into an element and optionally
+//! returns a function that unmounts it:
+//!
+//! ```ignore
+//! #[wasm_bindgen]
+//! pub fn mount(root: web_sys::Element, _props: JsValue) -> JsValue {
+//!     let handle = yew::Renderer::<AdminPage>::with_root(root).render();
+//!     let handle = std::cell::RefCell::new(Some(handle));
+//!
+//!     Closure::once_into_js(move || {
+//!         if let Some(handle) = handle.borrow_mut().take() {
+//!             handle.destroy();
+//!         }
+//!     })
+//! }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! # use yew::prelude::*;
+//! # use yew_router::prelude::*;
+//! use yew_router::lazy::{LazyManifest, LazyProvider};
+//!
+//! #[derive(Clone, PartialEq, Routable)]
+//! enum Route {
+//!     #[at("/")]
+//!     Home,
+//!     #[at("/admin")]
+//!     #[lazy("admin")]
+//!     Admin,
+//! }
+//!
+//! fn switch(route: Route) -> Html {
+//!     match route {
+//!         Route::Home => html! { <h1>{ "Home" }</h1> },
+//!         Route::Admin => html! { <Lazy module="admin" /> },
+//!     }
+//! }
+//!
+//! #[function_component]
+//! fn App() -> Html {
+//!     let manifest = LazyManifest::new().module("admin", "/admin/admin.js");
+//!
+//!     html! {
+//!         <LazyProvider {manifest}>
+//!             <BrowserRouter>
+//!                 <Suspense fallback={html! { <p>{ "Loading..." }</p> }}>
+//!                     <Switch<Route> render={switch} />
+//!                 </Suspense>
+//!             </BrowserRouter>
+//!         </LazyProvider>
+//!     }
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
+use wasm_bindgen::JsValue;
+use yew::prelude::*;
+
+/// Maps the names of split modules to the urls they are loaded from.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LazyManifest {
+    modules: HashMap<String, String>,
+}
+
+impl LazyManifest {
+    /// Creates an empty manifest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a module, loaded from the JavaScript glue at `url`.
+    pub fn module(mut self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        self.modules.insert(name.into(), url.into());
+        self
+    }
+
+    /// Returns the url of a module.
+    pub fn url(&self, name: &str) -> Option<&str> {
+        self.modules.get(name).map(|m| m.as_str())
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for LazyManifest
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self {
+            modules: iter
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        }
+    }
+}
+
+/// An error that occurred while loading a split module.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LazyError {
+    /// The module is not registered in the manifest.
+    NotRegistered(String),
+    /// The module failed to load.
+    Load {
+        /// The name of the module.
+        module: String,
+        /// The reason reported by the browser.
+        reason: String,
+    },
+    /// Split modules can only be loaded in a browser.
+    Unsupported,
+}
+
+impl fmt::Display for LazyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotRegistered(module) => write!(f, "module `{module}` is not registered"),
+            Self::Load { module, reason } => {
+                write!(f, "failed to load module `{module}`: {reason}")
+            }
+            Self::Unsupported => f.write_str("split modules can only be loaded in a browser"),
+        }
+    }
+}
+
+impl std::error::Error for LazyError {}
+
+/// A loaded split module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LazyModule {
+    exports: JsValue,
+}
+
+impl LazyModule {
+    /// Returns the namespace object of the module.
+    pub fn exports(&self) -> &JsValue {
+        &self.exports
+    }
+
+    /// Returns an exported function of the module.
+    pub fn function(&self, name: &str) -> Option<js_sys::Function> {
+        use wasm_bindgen::JsCast;
+
+        js_sys::Reflect::get(&self.exports, &JsValue::from_str(name))
+            .ok()
+            .and_then(|m| m.dyn_into().ok())
+    }
+}
+
+type LoadFuture = Shared<LocalBoxFuture<'static, Result<LazyModule, LazyError>>>;
+
+#[derive(Default)]
+struct LoaderInner {
+    manifest: LazyManifest,
+    modules: HashMap<String, LoadFuture>,
+}
+
+/// Loads split modules registered in a [`LazyManifest`].
+///
+/// Each module is only loaded once, subsequent loads resolve to the same module.
+#[derive(Clone)]
+pub struct LazyLoader {
+    inner: Rc<RefCell<LoaderInner>>,
+}
+
+impl PartialEq for LazyLoader {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl fmt::Debug for LazyLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyLoader")
+            .field("manifest", &self.inner.borrow().manifest)
+            .finish()
+    }
+}
+
+impl LazyLoader {
+    /// Creates a loader for the modules of a manifest.
+    pub fn new(manifest: LazyManifest) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(LoaderInner {
+                manifest,
+                modules: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Loads a module.
+    ///
+    /// A module that failed to load is loaded again on the next call.
+    pub fn load(
+        &self,
+        name: &str,
+    ) -> impl std::future::Future<Output = Result<LazyModule, LazyError>> {
+        let mut inner = self.inner.borrow_mut();
+
+        if let Some(m) = inner.modules.get(name) {
+            if !matches!(m.peek(), Some(Err(_))) {
+                return m.clone();
+            }
+        }
+
+        let fut = match inner.manifest.url(name) {
+            Some(url) => import_module(name.to_string(), url.to_string()).boxed_local(),
+            None => futures::future::ready(Err(LazyError::NotRegistered(name.to_string())))
+                .boxed_local(),
+        }
+        .shared();
+        inner.modules.insert(name.to_string(), fut.clone());
+
+        fut
+    }
+
+    /// Starts loading a module in the background.
+    pub fn preload(&self, name: &str) {
+        let fut = self.load(name);
+        yew::platform::spawn_local(async move {
+            if let Err(e) = fut.await {
+                tracing::warn!("failed to preload module: {}", e);
+            }
+        });
+    }
+
+    fn set_manifest(&self, manifest: LazyManifest) {
+        self.inner.borrow_mut().manifest = manifest;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen::prelude::wasm_bindgen(inline_js = r#"
+export function import_module(url) {
+    // Initialises the wasm of the module once its JavaScript glue is imported.
+    return import(url).then((m) =>
+        Promise.resolve(typeof m.default === "function" ? m.default() : undefined).then(() => m)
+    );
+}
+"#)]
+extern "C" {
+    #[wasm_bindgen(js_name = import_module)]
+    fn import_glue(url: &str) -> js_sys::Promise;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn import_module(name: String, url: String) -> Result<LazyModule, LazyError> {
+    let exports = wasm_bindgen_futures::JsFuture::from(import_glue(&url))
+        .await
+        .map_err(|e| LazyError::Load {
+            module: name,
+            reason: e.as_string().unwrap_or_else(|| format!("{e:?}")),
+        })?;
+
+    Ok(LazyModule { exports })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn import_module(_name: String, _url: String) -> Result<LazyModule, LazyError> {
+    Err(LazyError::Unsupported)
+}
+
+/// Props for [`LazyProvider`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct LazyProviderProps {
+    pub manifest: LazyManifest,
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// Provides a [`LazyLoader`] for the modules of a [`LazyManifest`] to its children.
+#[function_component(LazyProvider)]
+pub fn lazy_provider(props: &LazyProviderProps) -> Html {
+    let loader = use_memo((), {
+        let manifest = props.manifest.clone();
+        move |_| LazyLoader::new(manifest)
+    });
+
+    // Modules already loaded are kept when the manifest changes.
+    use_effect_with(props.manifest.clone(), {
+        let loader = loader.clone();
+        move |manifest| loader.set_manifest(manifest.clone())
+    });
+
+    html! {
+        <ContextProvider<LazyLoader> context={(*loader).clone()}>
+            {props.children.clone()}
+        </ContextProvider<LazyLoader>>
+    }
+}
+
+/// A hook to access the [`LazyLoader`] provided by [`LazyProvider`].
+#[hook]
+pub fn use_lazy_loader() -> Option<LazyLoader> {
+    use_context::<LazyLoader>()
+}
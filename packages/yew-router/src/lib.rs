@@ -70,7 +70,10 @@ extern crate self as yew_router;
 pub mod __macro;
 pub mod components;
 pub mod hooks;
+pub mod lazy;
 pub mod navigator;
+pub mod prefetch;
+pub mod response;
 mod routable;
 pub mod router;
 pub mod scope_ext;
@@ -78,7 +81,7 @@ pub mod switch;
 pub mod utils;
 
 pub use routable::{AnyRoute, Routable};
-pub use router::{BrowserRouter, HashRouter, Router};
+pub use router::{BrowserRouter, HashRouter, Router, StaticRouter};
 pub use switch::Switch;
 
 pub mod history {
@@ -95,12 +98,14 @@ pub mod prelude {
     //!
     //! This module re-exports the frequently used types from the crate.
 
-    pub use crate::components::{Link, Redirect};
+    pub use crate::components::{Lazy, Link, NavLink, Redirect};
     pub use crate::history::Location;
     pub use crate::hooks::*;
     pub use crate::navigator::{NavigationError, NavigationResult, Navigator};
+    pub use crate::prefetch::Prefetch;
+    pub use crate::response::{RedirectStatus, ServerResponse};
     pub use crate::scope_ext::{LocationHandle, NavigatorHandle, RouterScopeExt};
     #[doc(no_inline)]
     pub use crate::Routable;
-    pub use crate::{BrowserRouter, HashRouter, Router, Switch};
+    pub use crate::{BrowserRouter, HashRouter, Router, StaticRouter, Switch};
 }
@@ -1,30 +1,401 @@
+use std::collections::HashMap;
+
+use regex::Regex;
 pub use urlencoding::{decode as decode_for_url, encode as encode_for_url};
 
 use crate::utils::strip_slash_suffix;
 use crate::Routable;
 
-// re-export Router because the macro needs to access it
-pub type Router = route_recognizer::Router<String>;
+/// A router that matches pathnames against the routes of a [`Routable`].
+///
+/// Routes support the following segments:
+///
+/// - static segments: `/posts`
+/// - dynamic segments: `/:id` or `/{id}`
+/// - dynamic segments constrained by a regular expression: `/{id:\d+}`
+/// - optional dynamic segments: `/:page?`, `/{page?}` or `/{page?:\d+}`
+/// - wildcard segments, matching one or more segments: `/*rest`
+///
+/// When multiple routes match a pathname, the most specific route wins. Routes are ranked segment
+/// by segment: a static segment ranks above a constrained segment, which ranks above an
+/// unconstrained dynamic segment, which ranks above a wildcard. Routes that rank equally are
+/// ordered by their declaration order.
+#[derive(Debug)]
+pub struct Router {
+    routes: Vec<RoutePattern>,
+}
+
+impl Router {
+    fn new() -> Self {
+        Self { routes: Vec::new() }
+    }
+
+    fn add(&mut self, path: &'static str) {
+        match RoutePattern::parse(path) {
+            Ok(route) => self.routes.push(route),
+            Err(e) => tracing::error!("route `{}` is never matched: {}", path, e),
+        }
+    }
+
+    /// Returns all routes matching the pathname, ordered from the most specific to the least.
+    fn recognize<'a>(&'a self, pathname: &'a str) -> Vec<RouteMatch<'a>> {
+        let segments = split_segments(pathname);
+
+        let mut matches = self
+            .routes
+            .iter()
+            .filter_map(|route| route.recognize(pathname, &segments))
+            .collect::<Vec<_>>();
+        // sort_by is stable, so declaration order breaks ties.
+        matches.sort_by(|a, b| b.rank.cmp(&a.rank));
+
+        matches
+    }
+}
+
+#[derive(Debug)]
+struct RoutePattern {
+    path: &'static str,
+    pattern: PathPattern,
+}
+
+/// A parsed route, see [`Router`] for the supported syntax.
+#[derive(Debug)]
+pub(crate) struct PathPattern {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug)]
+enum Segment {
+    Static(String),
+    Param {
+        name: String,
+        constraint: Option<Regex>,
+        optional: bool,
+    },
+    Wildcard {
+        name: String,
+    },
+}
 
-/// Build a `route_recognizer::Router` from a `Routable` type.
+/// The rank of a single matched segment, higher is more specific.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum SegmentRank {
+    Wildcard,
+    Param,
+    Constrained,
+    Static,
+}
+
+struct RouteMatch<'a> {
+    path: &'static str,
+    params: HashMap<&'a str, &'a str>,
+    rank: Vec<SegmentRank>,
+}
+
+impl RoutePattern {
+    fn parse(path: &'static str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            path,
+            pattern: PathPattern::parse(path)?,
+        })
+    }
+
+    fn recognize<'a>(
+        &'a self,
+        pathname: &'a str,
+        segments: &[(usize, &'a str)],
+    ) -> Option<RouteMatch<'a>> {
+        let mut params = HashMap::new();
+        let mut rank = Vec::new();
+
+        let matched = match_segments(
+            &self.pattern.segments,
+            pathname,
+            segments,
+            false,
+            &mut params,
+            &mut rank,
+        );
+        if !matched {
+            return None;
+        }
+
+        Some(RouteMatch {
+            path: self.path,
+            params,
+            rank,
+        })
+    }
+}
+
+impl PathPattern {
+    /// Parses a route, failing if the constraint of a parameter is not a valid regular expression.
+    pub(crate) fn parse(path: &str) -> Result<Self, regex::Error> {
+        let segments = split_segments(strip_slash_suffix(path))
+            .into_iter()
+            .map(|(_, segment)| Segment::parse(segment))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { segments })
+    }
+
+    /// Matches a pathname, returning the parameters of the match.
+    ///
+    /// If `prefix` is `true`, the pattern only needs to match the leading segments of the pathname.
+    pub(crate) fn match_path<'a>(
+        &'a self,
+        pathname: &'a str,
+        prefix: bool,
+    ) -> Option<HashMap<&'a str, &'a str>> {
+        let pathname = strip_slash_suffix(pathname);
+        let segments = split_segments(pathname);
+        let mut params = HashMap::new();
+
+        match_segments(
+            &self.segments,
+            pathname,
+            &segments,
+            prefix,
+            &mut params,
+            &mut Vec::new(),
+        )
+        .then_some(params)
+    }
+}
+
+impl Segment {
+    fn parse(segment: &str) -> Result<Self, regex::Error> {
+        if let Some(name) = segment.strip_prefix('*') {
+            return Ok(Self::Wildcard {
+                name: name.to_string(),
+            });
+        }
+
+        if let Some(name) = segment.strip_prefix(':') {
+            return match name.strip_suffix('?') {
+                Some(name) => Self::param(name, None, true),
+                None => Self::param(name, None, false),
+            };
+        }
+
+        if let Some(inner) = segment
+            .strip_prefix('{')
+            .and_then(|inner| inner.strip_suffix('}'))
+        {
+            let (name, constraint) = match inner.split_once(':') {
+                Some((name, constraint)) => (name, Some(constraint)),
+                None => (inner, None),
+            };
+            return match name.strip_suffix('?') {
+                Some(name) => Self::param(name, constraint, true),
+                None => Self::param(name, constraint, false),
+            };
+        }
+
+        Ok(Self::Static(segment.to_string()))
+    }
+
+    fn param(name: &str, constraint: Option<&str>, optional: bool) -> Result<Self, regex::Error> {
+        let constraint = constraint
+            .map(|constraint| Regex::new(&format!("^(?:{constraint})$")))
+            .transpose()?;
+
+        Ok(Self::Param {
+            name: name.to_string(),
+            constraint,
+            optional,
+        })
+    }
+}
+
+/// Splits a pathname into its segments, along with the byte offset each segment starts at.
+fn split_segments(pathname: &str) -> Vec<(usize, &str)> {
+    let rest = match pathname.strip_prefix('/') {
+        Some(rest) if !rest.is_empty() => rest,
+        _ => return Vec::new(),
+    };
+
+    let mut offset = 1;
+    rest.split('/')
+        .map(|segment| {
+            let start = offset;
+            offset += segment.len() + 1;
+            (start, segment)
+        })
+        .collect()
+}
+
+fn match_segments<'a>(
+    patterns: &'a [Segment],
+    pathname: &'a str,
+    segments: &[(usize, &'a str)],
+    prefix: bool,
+    params: &mut HashMap<&'a str, &'a str>,
+    rank: &mut Vec<SegmentRank>,
+) -> bool {
+    let (pattern, rest_patterns) = match patterns.split_first() {
+        Some(it) => it,
+        None => return prefix || segments.is_empty(),
+    };
+
+    match pattern {
+        Segment::Static(expected) => match segments.split_first() {
+            Some(((_, segment), rest)) if segment == expected => {
+                rank.push(SegmentRank::Static);
+                if match_segments(rest_patterns, pathname, rest, prefix, params, rank) {
+                    return true;
+                }
+                rank.pop();
+                false
+            }
+            _ => false,
+        },
+        Segment::Param {
+            name,
+            constraint,
+            optional,
+        } => {
+            if let Some(((_, segment), rest)) = segments.split_first() {
+                let accepted = !segment.is_empty()
+                    && constraint.as_ref().map_or(true, |re| re.is_match(segment));
+
+                if accepted {
+                    rank.push(if constraint.is_some() {
+                        SegmentRank::Constrained
+                    } else {
+                        SegmentRank::Param
+                    });
+                    params.insert(name.as_str(), *segment);
+                    if match_segments(rest_patterns, pathname, rest, prefix, params, rank) {
+                        return true;
+                    }
+                    params.remove(name.as_str());
+                    rank.pop();
+                }
+            }
+
+            *optional && match_segments(rest_patterns, pathname, segments, prefix, params, rank)
+        }
+        Segment::Wildcard { name } => {
+            // Wildcards are greedy: try consuming as many segments as possible first.
+            for len in (1..=segments.len()).rev() {
+                let (consumed, rest) = segments.split_at(len);
+                let start = consumed[0].0;
+                let (last_start, last) = consumed[len - 1];
+                let value = &pathname[start..last_start + last.len()];
+
+                if value.is_empty() {
+                    continue;
+                }
+
+                rank.push(SegmentRank::Wildcard);
+                params.insert(name.as_str(), value);
+                if match_segments(rest_patterns, pathname, rest, prefix, params, rank) {
+                    return true;
+                }
+                params.remove(name.as_str());
+                rank.pop();
+            }
+
+            false
+        }
+    }
+}
+
+/// Build a [`Router`] from a `Routable` type.
 pub fn build_router<R: Routable>() -> Router {
     let mut router = Router::new();
-    R::routes().iter().for_each(|path| {
-        let stripped_route = strip_slash_suffix(path);
-        router.add(stripped_route, path.to_string());
-    });
+    R::routes().into_iter().for_each(|path| router.add(path));
 
     router
 }
 
-/// Use a `route_recognizer::Router` to build the route of a `Routable`
+/// Use a [`Router`] to build the route of a `Routable`.
+///
+/// Matching routes are tried from the most specific to the least specific one. If the parameters
+/// of a route fail to deserialize, the next matching route is tried before falling back to the
+/// `not_found` route.
 pub fn recognize_with_router<R: Routable>(router: &Router, pathname: &str) -> Option<R> {
     let pathname = strip_slash_suffix(pathname);
-    let matched = router.recognize(pathname);
 
-    match matched {
-        Ok(matched) => R::from_path(matched.handler(), &matched.params().into_iter().collect())
-            .or_else(R::not_found_route),
-        Err(_) => R::not_found_route(),
+    router
+        .recognize(pathname)
+        .into_iter()
+        .find_map(|matched| R::from_path(matched.path, &matched.params))
+        .or_else(R::not_found_route)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched_paths(routes: &[&'static str], pathname: &str) -> Vec<&'static str> {
+        let mut router = Router::new();
+        routes.iter().for_each(|path| router.add(path));
+
+        router
+            .recognize(strip_slash_suffix(pathname))
+            .into_iter()
+            .map(|m| m.path)
+            .collect()
+    }
+
+    #[test]
+    fn ranks_by_specificity() {
+        let routes = ["/*rest", "/posts/:id", "/posts/{id:\\d+}", "/posts/new"];
+
+        assert_eq!(
+            matched_paths(&routes, "/posts/new"),
+            vec!["/posts/new", "/posts/:id", "/*rest"]
+        );
+        assert_eq!(
+            matched_paths(&routes, "/posts/42"),
+            vec!["/posts/{id:\\d+}", "/posts/:id", "/*rest"]
+        );
+    }
+
+    #[test]
+    fn optional_segments() {
+        let routes = ["/list/:page?", "/"];
+
+        assert_eq!(matched_paths(&routes, "/list"), vec!["/list/:page?"]);
+        assert_eq!(matched_paths(&routes, "/list/2"), vec!["/list/:page?"]);
+        assert_eq!(matched_paths(&routes, "/list/2/3"), Vec::<&str>::new());
+        assert_eq!(matched_paths(&routes, "/"), vec!["/"]);
+    }
+
+    #[test]
+    fn prefix_matches() {
+        let pattern = PathPattern::parse("/posts/:id").unwrap();
+
+        assert_eq!(pattern.match_path("/posts/1/edit", false), None);
+        assert_eq!(
+            pattern.match_path("/posts/1/edit", true),
+            Some(HashMap::from([("id", "1")]))
+        );
+        assert_eq!(pattern.match_path("/posts", true), None);
+    }
+
+    #[test]
+    fn wildcard_captures_multiple_segments() {
+        let mut router = Router::new();
+        router.add("/files/*path/raw");
+
+        let matches = router.recognize("/files/a/b/c/raw");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].params.get("path"), Some(&"a/b/c"));
+
+        assert!(router.recognize("/files/raw").is_empty());
+    }
+
+    #[test]
+    fn invalid_constraints_are_rejected() {
+        assert!(PathPattern::parse("/posts/{id:(\\d+}").is_err());
+
+        assert_eq!(
+            matched_paths(&["/posts/{id:(\\d+}", "/posts/:id"], "/posts/1"),
+            vec!["/posts/:id"]
+        );
     }
 }
@@ -0,0 +1,227 @@
+//! Fetching the resources of a route ahead of navigation.
+//!
+//! A [`Link`](crate::components::Link) can start loading what its route needs before it is
+//! clicked, as configured by its [`prefetch`](crate::components::LinkProps::prefetch) prop. When
+//! the link prefetches, the split module of the route is preloaded (see the [`lazy`](crate::lazy)
+//! module) and the route is passed to the loader registered with [`PrefetchProvider`], which can
+//! warm caches or start fetching data.
+//!
+//! Each route is only passed to the loader once, until it is [forgotten](Prefetcher::forget).
+//!
+//! # Example
+//!
+//! ```
+//! # use yew::prelude::*;
+//! # use yew_router::prelude::*;
+//! use yew_router::prefetch::PrefetchProvider;
+//!
+//! #[derive(Clone, PartialEq, Routable)]
+//! enum Route {
+//!     #[at("/")]
+//!     Home,
+//!     #[at("/posts/:id")]
+//!     Post { id: u32 },
+//! }
+//!
+//! fn load(route: Route) {
+//!     if let Route::Post { id } = route {
+//!         // Start fetching post `id` into a cache.
+//!     }
+//! }
+//!
+//! #[function_component]
+//! fn App() -> Html {
+//!     html! {
+//!         <BrowserRouter>
+//!             <PrefetchProvider<Route> loader={load}>
+//!                 <Link<Route> to={Route::Post { id: 1 }} prefetch={Prefetch::Visible}>
+//!                     { "First post" }
+//!                 </Link<Route>>
+//!             </PrefetchProvider<Route>>
+//!         </BrowserRouter>
+//!     }
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::Routable;
+
+/// When a [`Link`](crate::components::Link) prefetches its route.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Prefetch {
+    /// When the link is hovered or focused.
+    #[default]
+    Hover,
+    /// When the link is scrolled into the viewport.
+    Visible,
+    /// Never.
+    None,
+}
+
+/// Passes routes that are about to be navigated to to a loader.
+///
+/// A `Prefetcher` is provided by [`PrefetchProvider`] and can be accessed with
+/// [`use_prefetcher`].
+pub struct Prefetcher<R> {
+    inner: Rc<PrefetcherInner<R>>,
+}
+
+struct PrefetcherInner<R> {
+    // Replaced when the loader of the provider changes, without changing the prefetcher.
+    loader: RefCell<Callback<R>>,
+    prefetched: RefCell<HashSet<String>>,
+}
+
+impl<R> Clone for Prefetcher<R> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<R> PartialEq for Prefetcher<R> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<R> fmt::Debug for Prefetcher<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Prefetcher")
+            .field("prefetched", &self.inner.prefetched.borrow())
+            .finish()
+    }
+}
+
+impl<R: Routable> Prefetcher<R> {
+    /// Creates a prefetcher passing routes to `loader`.
+    pub fn new(loader: Callback<R>) -> Self {
+        Self {
+            inner: Rc::new(PrefetcherInner {
+                loader: RefCell::new(loader),
+                prefetched: RefCell::default(),
+            }),
+        }
+    }
+
+    /// Passes a route to the loader, unless it has already been prefetched.
+    pub fn prefetch(&self, route: &R) {
+        if self.inner.prefetched.borrow_mut().insert(route.to_path()) {
+            let loader = self.inner.loader.borrow().clone();
+            loader.emit(route.clone());
+        }
+    }
+
+    /// Returns `true` if the route has been prefetched.
+    pub fn is_prefetched(&self, route: &R) -> bool {
+        self.inner.prefetched.borrow().contains(&route.to_path())
+    }
+
+    /// Forgets that a route has been prefetched, so it is passed to the loader again.
+    pub fn forget(&self, route: &R) {
+        self.inner.prefetched.borrow_mut().remove(&route.to_path());
+    }
+
+    /// Forgets all prefetched routes.
+    pub fn clear(&self) {
+        self.inner.prefetched.borrow_mut().clear();
+    }
+
+    fn set_loader(&self, loader: Callback<R>) {
+        *self.inner.loader.borrow_mut() = loader;
+    }
+}
+
+/// Props for [`PrefetchProvider`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct PrefetchProviderProps<R: Routable> {
+    /// Called with the routes links prefetch.
+    pub loader: Callback<R>,
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// Provides a [`Prefetcher`] for routes of type `R` to its children.
+#[function_component]
+pub fn PrefetchProvider<R>(props: &PrefetchProviderProps<R>) -> Html
+where
+    R: Routable + 'static,
+{
+    let prefetcher = use_memo((), |_| Prefetcher::<R>::new(props.loader.clone()));
+
+    // The loader is replaced in place, so a loader created on each render does not re-render the
+    // links consuming the prefetcher. Prefetched routes are kept when the loader changes.
+    prefetcher.set_loader(props.loader.clone());
+
+    html! {
+        <ContextProvider<Prefetcher<R>> context={(*prefetcher).clone()}>
+            {props.children.clone()}
+        </ContextProvider<Prefetcher<R>>>
+    }
+}
+
+/// A hook to access the [`Prefetcher`] for routes of type `R` provided by [`PrefetchProvider`].
+#[hook]
+pub fn use_prefetcher<R>() -> Option<Prefetcher<R>>
+where
+    R: Routable + 'static,
+{
+    use_context::<Prefetcher<R>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Routable)]
+    enum Route {
+        #[at("/posts/:id")]
+        Post { id: u32 },
+    }
+
+    #[test]
+    fn routes_are_prefetched_once() {
+        let loaded = Rc::new(Cell::new(0));
+        let prefetcher = Prefetcher::new({
+            let loaded = loaded.clone();
+            Callback::from(move |_: Route| loaded.set(loaded.get() + 1))
+        });
+
+        prefetcher.prefetch(&Route::Post { id: 1 });
+        prefetcher.prefetch(&Route::Post { id: 1 });
+        assert_eq!(loaded.get(), 1);
+        assert!(prefetcher.is_prefetched(&Route::Post { id: 1 }));
+
+        prefetcher.prefetch(&Route::Post { id: 2 });
+        assert_eq!(loaded.get(), 2);
+
+        prefetcher.forget(&Route::Post { id: 1 });
+        prefetcher.prefetch(&Route::Post { id: 1 });
+        assert_eq!(loaded.get(), 3);
+    }
+
+    #[test]
+    fn replacing_the_loader_keeps_the_prefetcher() {
+        let loaded = Rc::new(Cell::new(0));
+        let prefetcher = Prefetcher::new(Callback::from(|_: Route| {}));
+        let consumer = prefetcher.clone();
+
+        prefetcher.set_loader({
+            let loaded = loaded.clone();
+            Callback::from(move |_: Route| loaded.set(loaded.get() + 1))
+        });
+        assert_eq!(prefetcher, consumer);
+
+        consumer.prefetch(&Route::Post { id: 1 });
+        assert_eq!(loaded.get(), 1);
+    }
+}
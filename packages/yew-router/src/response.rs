@@ -0,0 +1,195 @@
+//! Communicating routing outcomes of server-side rendering to the HTTP layer.
+//!
+//! When rendering with a [`ServerRenderer`](yew::ServerRenderer), components cannot influence the
+//! HTTP response that carries the rendered markup. A [`ServerResponse`] passed to a router
+//! collects the redirect and status code requested by components during rendering, so the server
+//! can read them once the rendering has finished.
+//!
+//! # Example
+//!
+//! ```
+//! # use yew::prelude::*;
+//! # use yew_router::prelude::*;
+//! # use yew_router::response::ServerResponse;
+//! #[derive(Properties, PartialEq)]
+//! struct ServerAppProps {
+//!     url: AttrValue,
+//!     response: ServerResponse,
+//! }
+//!
+//! #[function_component]
+//! fn ServerApp(props: &ServerAppProps) -> Html {
+//!     html! {
+//!         <StaticRouter url={props.url.clone()} response={props.response.clone()}>
+//!             // ...
+//!         </StaticRouter>
+//!     }
+//! }
+//!
+//! # async fn render(url: String) {
+//! let response = ServerResponse::new();
+//! let renderer = yew::ServerRenderer::<ServerApp>::with_props({
+//!     let response = response.clone();
+//!     move || ServerAppProps {
+//!         url: url.into(),
+//!         response,
+//!     }
+//! });
+//! let body = renderer.render().await;
+//!
+//! match response.redirect() {
+//!     Some(redirect) => { /* respond with redirect.status() and a `Location` header */ }
+//!     None => { /* respond with response.status().unwrap_or(200) and body */ }
+//! }
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// The status code of a redirect issued during server-side rendering.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RedirectStatus {
+    /// `301 Moved Permanently`
+    MovedPermanently,
+    /// `302 Found`
+    #[default]
+    Found,
+    /// `307 Temporary Redirect`
+    TemporaryRedirect,
+}
+
+impl RedirectStatus {
+    /// Returns the HTTP status code.
+    pub fn as_u16(&self) -> u16 {
+        match self {
+            Self::MovedPermanently => 301,
+            Self::Found => 302,
+            Self::TemporaryRedirect => 307,
+        }
+    }
+}
+
+/// A redirect issued during server-side rendering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redirection {
+    location: String,
+    status: RedirectStatus,
+}
+
+impl Redirection {
+    /// Returns the url to redirect to, including the basename of the router.
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Returns the status of the redirect.
+    pub fn status(&self) -> RedirectStatus {
+        self.status
+    }
+}
+
+#[derive(Debug, Default)]
+struct ResponseState {
+    status: Option<u16>,
+    redirect: Option<Redirection>,
+}
+
+/// Records the redirect and status code requested by components during server-side rendering.
+///
+/// A `ServerResponse` is passed to a router via its `response` prop, and can be accessed by
+/// components with [`use_server_response`](crate::hooks::use_server_response).
+///
+/// It is cheap to clone and can be sent to the thread rendering the application. Clones refer to
+/// the same response.
+#[derive(Default, Clone)]
+pub struct ServerResponse {
+    inner: Arc<Mutex<ResponseState>>,
+}
+
+impl ServerResponse {
+    /// Creates a new response without status and redirect.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the status code requested by components, if any.
+    pub fn status(&self) -> Option<u16> {
+        self.inner.lock().unwrap().status
+    }
+
+    /// Sets the status code of the response, e.g. `404` or `410`.
+    ///
+    /// The last status code set during rendering wins.
+    pub fn set_status(&self, status: u16) {
+        self.inner.lock().unwrap().status = Some(status);
+    }
+
+    /// Sets the status code of the response unless one has already been set.
+    pub fn set_default_status(&self, status: u16) {
+        self.inner.lock().unwrap().status.get_or_insert(status);
+    }
+
+    /// Returns the redirect requested by components, if any.
+    pub fn redirect(&self) -> Option<Redirection> {
+        self.inner.lock().unwrap().redirect.clone()
+    }
+
+    /// Requests a redirect to `location`.
+    ///
+    /// Only the first redirect issued during rendering is recorded.
+    pub fn set_redirect(&self, location: impl Into<String>, status: RedirectStatus) {
+        self.inner
+            .lock()
+            .unwrap()
+            .redirect
+            .get_or_insert_with(|| Redirection {
+                location: location.into(),
+                status,
+            });
+    }
+}
+
+impl PartialEq for ServerResponse {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl fmt::Debug for ServerResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.lock().unwrap();
+
+        f.debug_struct("ServerResponse")
+            .field("status", &inner.status)
+            .field("redirect", &inner.redirect)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_redirect_wins() {
+        let response = ServerResponse::new();
+        response.set_redirect("/login", RedirectStatus::TemporaryRedirect);
+        response.set_redirect("/home", RedirectStatus::Found);
+
+        let redirect = response.redirect().unwrap();
+        assert_eq!(redirect.location(), "/login");
+        assert_eq!(redirect.status().as_u16(), 307);
+    }
+
+    #[test]
+    fn default_status_does_not_override() {
+        let response = ServerResponse::new();
+        response.set_default_status(404);
+        assert_eq!(response.status(), Some(404));
+
+        response.set_status(410);
+        response.set_default_status(404);
+        assert_eq!(response.clone().status(), Some(410));
+    }
+}
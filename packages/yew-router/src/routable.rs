@@ -28,6 +28,13 @@ pub trait Routable: Clone + PartialEq {
 
     /// Match a route based on the path
     fn recognize(pathname: &str) -> Option<Self>;
+
+    /// The split module rendering this route, if any.
+    ///
+    /// See the [`lazy`](crate::lazy) module for details.
+    fn lazy_module(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 /// A special route that accepts any route.
@@ -1,11 +1,13 @@
 //! Router Component.
 use std::rc::Rc;
 
+use gloo::history::query::Raw;
 use yew::prelude::*;
 use yew::virtual_dom::AttrValue;
 
-use crate::history::{AnyHistory, BrowserHistory, HashHistory, History, Location};
+use crate::history::{AnyHistory, BrowserHistory, HashHistory, History, Location, MemoryHistory};
 use crate::navigator::Navigator;
+use crate::response::ServerResponse;
 use crate::utils::{base_url, strip_slash_suffix};
 
 /// Props for [`Router`].
@@ -16,6 +18,9 @@ pub struct RouterProps {
     pub history: AnyHistory,
     #[prop_or_default]
     pub basename: Option<AttrValue>,
+    /// Collects the redirect and status code requested while rendering on the server.
+    #[prop_or_default]
+    pub response: Option<ServerResponse>,
 }
 
 #[derive(Clone)]
@@ -60,6 +65,17 @@ impl NavigatorContext {
     }
 }
 
+#[derive(Clone, PartialEq)]
+pub(crate) struct ResponseContext {
+    response: Option<ServerResponse>,
+}
+
+impl ResponseContext {
+    pub fn response(&self) -> Option<ServerResponse> {
+        self.response.clone()
+    }
+}
+
 /// The base router.
 ///
 /// The implementation is separated to make sure <Router /> has the same virtual dom layout as
@@ -70,6 +86,7 @@ fn base_router(props: &RouterProps) -> Html {
         history,
         children,
         basename,
+        response,
     } = props.clone();
 
     let loc_ctx = use_reducer(|| LocationContext {
@@ -104,10 +121,16 @@ fn base_router(props: &RouterProps) -> Html {
         });
     }
 
+    // The response context is always provided so the layout is the same on the server and on the
+    // client during hydration.
+    let resp_ctx = ResponseContext { response };
+
     html! {
         <ContextProvider<NavigatorContext> context={navi_ctx}>
             <ContextProvider<LocationContext> context={(*loc_ctx).clone()}>
-                {children}
+                <ContextProvider<ResponseContext> context={resp_ctx}>
+                    {children}
+                </ContextProvider<ResponseContext>>
             </ContextProvider<LocationContext>>
         </ContextProvider<NavigatorContext>>
     }
@@ -178,3 +201,47 @@ pub fn hash_router(props: &ConcreteRouterProps) -> Html {
         </BaseRouter>
     }
 }
+
+/// Props for [`StaticRouter`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct StaticRouterProps {
+    #[prop_or_default]
+    pub children: Html,
+    /// The url being rendered, including the query string.
+    pub url: AttrValue,
+    #[prop_or_default]
+    pub basename: Option<AttrValue>,
+    /// Collects the redirect and status code requested while rendering.
+    #[prop_or_default]
+    pub response: Option<ServerResponse>,
+}
+
+/// A [`Router`] that renders a fixed url with a [`MemoryHistory`].
+///
+/// This Router is meant to be used for server-side rendering. Pass a
+/// [`ServerResponse`] to find out whether the rendered page requested a redirect or a status
+/// code other than `200`. See the [`response`](crate::response) module for details.
+#[function_component(StaticRouter)]
+pub fn static_router(props: &StaticRouterProps) -> Html {
+    let StaticRouterProps {
+        children,
+        url,
+        basename,
+        response,
+    } = props.clone();
+    let history = use_memo(url, |url| {
+        let history = MemoryHistory::new();
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        history
+            .replace_with_query(path, Raw(query))
+            .expect("raw queries are infallible");
+
+        AnyHistory::from(history)
+    });
+
+    html! {
+        <BaseRouter history={(*history).clone()} {basename} {response}>
+            {children}
+        </BaseRouter>
+    }
+}
@@ -23,6 +23,10 @@ where
 /// If such a route is provided, it redirects to the specified route.
 /// Otherwise `html! {}` is rendered and a message is logged to console
 /// stating that no route can be matched.
+///
+/// When rendered with a router that has been given a
+/// [`ServerResponse`](crate::response::ServerResponse), rendering the `not_found` route sets the
+/// status of the response to `404`, unless a status has already been set.
 /// See the [crate level document][crate] for more information.
 #[function_component]
 pub fn Switch<R>(props: &SwitchProps<R>) -> Html
@@ -30,6 +34,7 @@ where
     R: Routable + 'static,
 {
     let route = use_route::<R>();
+    let response = use_server_response();
 
     let route = props
         .pathname
@@ -37,6 +42,12 @@ where
         .and_then(|p| R::recognize(p))
         .or(route);
 
+    if let Some(response) = response {
+        if route.is_none() || route == R::not_found_route() {
+            response.set_default_status(404);
+        }
+    }
+
     match route {
         Some(route) => props.render.emit(route),
         None => {
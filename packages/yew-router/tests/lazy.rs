@@ -0,0 +1,40 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use tokio::test;
+use yew::prelude::*;
+use yew_router::lazy::{LazyError, LazyLoader, LazyManifest, LazyProvider};
+use yew_router::prelude::*;
+
+#[test]
+async fn unregistered_module() {
+    let loader = LazyLoader::new(LazyManifest::new().module("admin", "/admin/admin.js"));
+
+    assert_eq!(
+        loader.load("settings").await,
+        Err(LazyError::NotRegistered("settings".to_string()))
+    );
+    assert_eq!(loader.load("admin").await, Err(LazyError::Unsupported));
+}
+
+#[test]
+async fn lazy_renders_empty_host_on_server() {
+    #[function_component]
+    fn App() -> Html {
+        let manifest = LazyManifest::new().module("admin", "/admin/admin.js");
+
+        html! {
+            <LazyProvider {manifest}>
+                <Suspense fallback={html! { "Loading..." }}>
+                    <Lazy module="admin" classes="admin" />
+                </Suspense>
+            </LazyProvider>
+        }
+    }
+
+    let s = yew::ServerRenderer::<App>::new()
+        .hydratable(false)
+        .render()
+        .await;
+
+    assert_eq!(s, r#"<div class="admin"></div>"#);
+}
@@ -0,0 +1,57 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use tokio::test;
+use yew::prelude::*;
+use yew_router::prefetch::PrefetchProvider;
+use yew_router::prelude::*;
+
+#[derive(Routable, Debug, Clone, PartialEq)]
+enum Routes {
+    #[at("/")]
+    Home,
+    #[at("/posts/:id")]
+    Post { id: u32 },
+}
+
+fn load(route: Routes) {
+    panic!("{route:?} must not be prefetched during server-side rendering");
+}
+
+#[function_component]
+fn Nav() -> Html {
+    html! {
+        <StaticRouter url="/">
+            <PrefetchProvider<Routes> loader={load}>
+                <Link<Routes>
+                    to={Routes::Post { id: 1 }}
+                    target="_blank"
+                    rel="noopener"
+                    title="First post"
+                    attrs={vec![("aria-label", AttrValue::from("Read the first post"))]}
+                    prefetch={Prefetch::Visible}
+                >
+                    { "Post" }
+                </Link<Routes>>
+                <NavLink<Routes> to={Routes::Home} attrs={vec![("data-nav", AttrValue::from("home"))]}>
+                    { "Home" }
+                </NavLink<Routes>>
+            </PrefetchProvider<Routes>>
+        </StaticRouter>
+    }
+}
+
+#[test]
+async fn anchor_attributes_are_passed_through() {
+    let s = yew::ServerRenderer::<Nav>::new()
+        .hydratable(false)
+        .render()
+        .await;
+
+    assert_eq!(
+        s,
+        concat!(
+            r#"<a href="/posts/1" target="_blank" rel="noopener" title="First post" aria-label="Read the first post">Post</a>"#,
+            r#"<a href="/" aria-current="page" class="active" data-nav="home">Home</a>"#,
+        )
+    );
+}
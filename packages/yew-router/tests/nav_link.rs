@@ -0,0 +1,108 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use tokio::test;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Routable, Debug, Clone, PartialEq)]
+enum Routes {
+    #[at("/")]
+    Home,
+    #[at("/posts")]
+    Posts,
+    #[at("/posts/:id")]
+    Post { id: u32 },
+}
+
+#[derive(Properties, PartialEq)]
+struct NavProps {
+    url: AttrValue,
+    basename: Option<AttrValue>,
+}
+
+#[function_component]
+fn Post() -> Html {
+    let route_match = use_route_match("/posts/:id");
+    let id = route_match.as_ref().and_then(|m| m.param("id"));
+
+    html! { <p>{ format!("{:?}", id) }</p> }
+}
+
+#[function_component]
+fn Nav(props: &NavProps) -> Html {
+    html! {
+        <StaticRouter url={props.url.clone()} basename={props.basename.clone()}>
+            <NavLink<Routes> to={Routes::Home}>{ "Home" }</NavLink<Routes>>
+            <NavLink<Routes> to={Routes::Posts} classes="nav" active_classes="is-active">
+                { "Posts" }
+            </NavLink<Routes>>
+            <NavLink<Routes> to={Routes::Posts} exact=true>{ "All Posts" }</NavLink<Routes>>
+            <Post />
+        </StaticRouter>
+    }
+}
+
+async fn render(url: &'static str, basename: Option<&'static str>) -> String {
+    yew::ServerRenderer::<Nav>::with_props(move || NavProps {
+        url: url.into(),
+        basename: basename.map(AttrValue::from),
+    })
+    .hydratable(false)
+    .render()
+    .await
+}
+
+#[test]
+async fn root_is_only_active_on_root() {
+    assert_eq!(
+        render("/", None).await,
+        r#"<a href="/" aria-current="page" class="active">Home</a><a href="/posts" class="nav">Posts</a><a href="/posts">All Posts</a><p>None</p>"#
+    );
+}
+
+#[test]
+async fn prefix_and_exact_matches() {
+    assert_eq!(
+        render("/posts", None).await,
+        r#"<a href="/">Home</a><a href="/posts" aria-current="page" class="nav is-active">Posts</a><a href="/posts" aria-current="page" class="active">All Posts</a><p>None</p>"#
+    );
+    assert_eq!(
+        render("/posts/42/", None).await,
+        r#"<a href="/">Home</a><a href="/posts" aria-current="page" class="nav is-active">Posts</a><a href="/posts">All Posts</a><p>Some("42")</p>"#
+    );
+}
+
+#[test]
+async fn basename_is_stripped() {
+    assert_eq!(
+        render("/app/posts/7", Some("/app")).await,
+        r#"<a href="/app/">Home</a><a href="/app/posts" aria-current="page" class="nav is-active">Posts</a><a href="/app/posts">All Posts</a><p>Some("7")</p>"#
+    );
+}
+
+#[function_component]
+fn InvalidPattern() -> Html {
+    let route_match = use_route_match("/posts/{id:(\\d+}");
+    let post_match = use_route_match("/posts/:id");
+
+    html! { <p>{ format!("{} {}", route_match.is_some(), post_match.is_some()) }</p> }
+}
+
+#[function_component]
+fn InvalidPatternApp() -> Html {
+    html! {
+        <StaticRouter url="/posts/42">
+            <InvalidPattern />
+        </StaticRouter>
+    }
+}
+
+#[test]
+async fn invalid_patterns_do_not_match() {
+    let s = yew::ServerRenderer::<InvalidPatternApp>::new()
+        .hydratable(false)
+        .render()
+        .await;
+
+    assert_eq!(s, "<p>false true</p>");
+}
@@ -71,3 +71,142 @@ fn router_url_encoding() {
         AppRoute::recognize("/search/a%2Fb/")
     );
 }
+
+#[test]
+fn router_regex_constraints() {
+    #[derive(Routable, Debug, Clone, PartialEq)]
+    enum AppRoute {
+        #[at("/user/{id:\\d+}")]
+        User { id: u64 },
+        #[at("/user/:name")]
+        UserByName { name: String },
+        #[at("/user/new")]
+        NewUser,
+    }
+
+    assert_eq!(
+        Some(AppRoute::User { id: 42 }),
+        AppRoute::recognize("/user/42")
+    );
+    assert_eq!(
+        Some(AppRoute::UserByName {
+            name: "ferris".to_string()
+        }),
+        AppRoute::recognize("/user/ferris")
+    );
+    assert_eq!(Some(AppRoute::NewUser), AppRoute::recognize("/user/new"));
+    assert_eq!(AppRoute::User { id: 42 }.to_path(), "/user/42");
+}
+
+#[test]
+fn router_falls_through_when_parsing_fails() {
+    #[derive(Routable, Debug, Clone, PartialEq)]
+    enum AppRoute {
+        #[at("/{id:\\d+}")]
+        Article { id: u8 },
+        #[at("/*path")]
+        Other { path: String },
+    }
+
+    assert_eq!(Some(AppRoute::Article { id: 1 }), AppRoute::recognize("/1"));
+    // 1000 overflows `u8`, so the next matching route is used.
+    assert_eq!(
+        Some(AppRoute::Other {
+            path: "1000".to_string()
+        }),
+        AppRoute::recognize("/1000")
+    );
+}
+
+#[test]
+fn router_optional_segments() {
+    #[derive(Routable, Debug, Clone, PartialEq)]
+    enum AppRoute {
+        #[at("/posts/:page?")]
+        Posts { page: Option<u32> },
+        #[at("/posts/{id:\\d+}/edit")]
+        EditPost { id: u32 },
+    }
+
+    assert_eq!(
+        Some(AppRoute::Posts { page: None }),
+        AppRoute::recognize("/posts")
+    );
+    assert_eq!(
+        Some(AppRoute::Posts { page: Some(3) }),
+        AppRoute::recognize("/posts/3/")
+    );
+    assert_eq!(
+        Some(AppRoute::EditPost { id: 3 }),
+        AppRoute::recognize("/posts/3/edit")
+    );
+    assert_eq!(None, AppRoute::recognize("/posts/three"));
+
+    assert_eq!(AppRoute::Posts { page: None }.to_path(), "/posts");
+    assert_eq!(AppRoute::Posts { page: Some(2) }.to_path(), "/posts/2");
+}
+
+#[test]
+fn router_unnamed_wildcard() {
+    #[derive(Routable, Debug, Clone, PartialEq)]
+    enum AppRoute {
+        #[at("/settings")]
+        Settings,
+        #[at("/settings/*")]
+        SettingsPages,
+    }
+
+    assert_eq!(Some(AppRoute::Settings), AppRoute::recognize("/settings"));
+    assert_eq!(
+        Some(AppRoute::SettingsPages),
+        AppRoute::recognize("/settings/profile/email")
+    );
+    assert_eq!(AppRoute::SettingsPages.to_path(), "/settings/*");
+}
+
+#[test]
+fn router_aliases() {
+    #[derive(Routable, Debug, Clone, PartialEq)]
+    enum AppRoute {
+        #[at("/")]
+        #[at("/home")]
+        #[at("/index.html")]
+        Home,
+        #[at("/profile/:id")]
+        #[at("/u/:id")]
+        Profile { id: u32 },
+    }
+
+    assert_eq!(Some(AppRoute::Home), AppRoute::recognize("/"));
+    assert_eq!(Some(AppRoute::Home), AppRoute::recognize("/home"));
+    assert_eq!(Some(AppRoute::Home), AppRoute::recognize("/index.html"));
+    assert_eq!(
+        Some(AppRoute::Profile { id: 7 }),
+        AppRoute::recognize("/u/7")
+    );
+
+    // The first route is the canonical one.
+    assert_eq!(AppRoute::Home.to_path(), "/");
+    assert_eq!(AppRoute::Profile { id: 7 }.to_path(), "/profile/7");
+}
+
+#[test]
+fn router_lazy_module() {
+    #[derive(Routable, Debug, Clone, PartialEq)]
+    enum AppRoute {
+        #[at("/")]
+        Home,
+        #[at("/admin/:section")]
+        #[lazy("admin")]
+        Admin { section: String },
+    }
+
+    assert_eq!(AppRoute::Home.lazy_module(), None);
+    assert_eq!(
+        AppRoute::Admin {
+            section: "users".to_string()
+        }
+        .lazy_module(),
+        Some("admin")
+    );
+}
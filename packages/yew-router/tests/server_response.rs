@@ -0,0 +1,109 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use tokio::test;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Routable, Debug, Clone, PartialEq)]
+enum AppRoute {
+    #[at("/")]
+    Home,
+    #[at("/old-home")]
+    OldHome,
+    #[at("/gone")]
+    Gone,
+    #[at("/404")]
+    #[not_found]
+    NotFound,
+}
+
+#[function_component]
+fn GonePage() -> Html {
+    if let Some(response) = use_server_response() {
+        response.set_status(410);
+    }
+
+    html! { <h1>{ "Gone" }</h1> }
+}
+
+fn switch(route: AppRoute) -> Html {
+    match route {
+        AppRoute::Home => html! { <h1>{ "Home" }</h1> },
+        AppRoute::OldHome => html! {
+            <Redirect<AppRoute> to={AppRoute::Home} status={RedirectStatus::MovedPermanently} />
+        },
+        AppRoute::Gone => html! { <GonePage /> },
+        AppRoute::NotFound => html! { <h1>{ "Not Found" }</h1> },
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct ServerAppProps {
+    url: AttrValue,
+    basename: Option<AttrValue>,
+    response: ServerResponse,
+}
+
+#[function_component]
+fn ServerApp(props: &ServerAppProps) -> Html {
+    html! {
+        <StaticRouter
+            url={props.url.clone()}
+            basename={props.basename.clone()}
+            response={props.response.clone()}
+        >
+            <Switch<AppRoute> render={switch} />
+        </StaticRouter>
+    }
+}
+
+async fn render(url: &'static str, basename: Option<&'static str>) -> (String, ServerResponse) {
+    let response = ServerResponse::new();
+    let body = yew::ServerRenderer::<ServerApp>::with_props({
+        let response = response.clone();
+        move || ServerAppProps {
+            url: url.into(),
+            basename: basename.map(AttrValue::from),
+            response,
+        }
+    })
+    .hydratable(false)
+    .render()
+    .await;
+
+    (body, response)
+}
+
+#[test]
+async fn matched_route_has_no_status() {
+    let (body, response) = render("/?lang=en", None).await;
+
+    assert_eq!(body, "<h1>Home</h1>");
+    assert_eq!(response.status(), None);
+    assert_eq!(response.redirect(), None);
+}
+
+#[test]
+async fn not_found_sets_404() {
+    let (body, response) = render("/does/not/exist", None).await;
+
+    assert_eq!(body, "<h1>Not Found</h1>");
+    assert_eq!(response.status(), Some(404));
+}
+
+#[test]
+async fn status_set_by_component() {
+    let (_, response) = render("/gone", None).await;
+
+    assert_eq!(response.status(), Some(410));
+}
+
+#[test]
+async fn redirect_is_recorded() {
+    let (_, response) = render("/app/old-home", Some("/app")).await;
+
+    let redirect = response.redirect().unwrap();
+    assert_eq!(redirect.location(), "/app/");
+    assert_eq!(redirect.status(), RedirectStatus::MovedPermanently);
+    assert_eq!(redirect.status().as_u16(), 301);
+}
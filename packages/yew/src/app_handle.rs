@@ -41,6 +41,8 @@ where
             DynamicDomSlot::new_debug_trapped(),
             props,
         );
+        #[cfg(feature = "hmr")]
+        crate::hmr::register_app(Box::new(app.scope.clone()));
 
         app
     }
@@ -66,6 +68,8 @@ where
         skip_all,
     )]
     pub fn destroy(self) {
+        #[cfg(feature = "hmr")]
+        crate::hmr::unregister_app(self.scope.id);
         self.scope.destroy(false)
     }
 }
@@ -107,6 +111,7 @@ mod feat_hydration {
                 scope: Scope::new(None),
             };
 
+            crate::style::feat_hydration::hoist_styles(&host);
             let mut fragment = Fragment::collect_children(&host);
             let hosting_root = BSubtree::create_root(&host);
 
@@ -125,6 +130,8 @@ mod feat_hydration {
             for node in fragment.iter() {
                 host.remove_child(node).unwrap();
             }
+            #[cfg(feature = "hmr")]
+            crate::hmr::register_app(Box::new(app.scope.clone()));
 
             app
         }
@@ -25,6 +25,12 @@ impl BComp {
     pub fn key(&self) -> Option<&Key> {
         self.key.as_ref()
     }
+
+    /// Calls `f` with the scope of the component
+    #[cfg(feature = "hmr")]
+    pub fn for_each_component(&self, f: &mut dyn FnMut(&dyn Scoped)) {
+        f(self.scope.as_ref())
+    }
 }
 
 impl fmt::Debug for BComp {
@@ -145,6 +145,14 @@ impl BList {
         self.key.as_ref()
     }
 
+    /// Calls `f` with the scopes of the components in the fragment, in render order
+    #[cfg(feature = "hmr")]
+    pub fn for_each_component(&self, f: &mut dyn FnMut(&dyn crate::html::Scoped)) {
+        for child in self.rev_children.iter().rev() {
+            child.for_each_component(f);
+        }
+    }
+
     /// Diff and patch unkeyed child lists
     fn apply_unkeyed(
         root: &BSubtree,
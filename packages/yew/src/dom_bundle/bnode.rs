@@ -43,6 +43,21 @@ impl BNode {
             Self::Raw(_) => None,
         }
     }
+
+    /// Calls `f` with the scopes of the components of the node, in render order
+    ///
+    /// The components rendered by these components are not visited.
+    #[cfg(feature = "hmr")]
+    pub fn for_each_component(&self, f: &mut dyn FnMut(&dyn crate::html::Scoped)) {
+        match self {
+            Self::Tag(btag) => btag.for_each_component(f),
+            Self::Comp(bcomp) => bcomp.for_each_component(f),
+            Self::List(blist) => blist.for_each_component(f),
+            Self::Portal(bportal) => bportal.for_each_component(f),
+            Self::Suspense(bsusp) => bsusp.for_each_component(f),
+            Self::Text(_) | Self::Ref(_) | Self::Raw(_) => {}
+        }
+    }
 }
 
 impl ReconcileTarget for BNode {
@@ -116,6 +116,12 @@ impl BPortal {
     pub fn key(&self) -> Option<&Key> {
         self.node.key()
     }
+
+    /// Calls `f` with the scopes of the components in the portal, in render order
+    #[cfg(feature = "hmr")]
+    pub fn for_each_component(&self, f: &mut dyn FnMut(&dyn crate::html::Scoped)) {
+        self.node.for_each_component(f)
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -33,6 +33,14 @@ impl BSuspense {
     pub fn key(&self) -> Option<&Key> {
         self.key.as_ref()
     }
+
+    /// Calls `f` with the scopes of the suspended components, in render order
+    ///
+    /// The components of the fallback are not included.
+    #[cfg(feature = "hmr")]
+    pub fn for_each_component(&self, f: &mut dyn FnMut(&dyn crate::html::Scoped)) {
+        self.children_bundle.for_each_component(f)
+    }
 }
 
 impl ReconcileTarget for BSuspense {
@@ -2,12 +2,16 @@ use std::collections::HashMap;
 use std::ops::Deref;
 
 use indexmap::IndexMap;
-use wasm_bindgen::{intern, JsValue};
-use web_sys::{Element, HtmlInputElement as InputElement, HtmlTextAreaElement as TextAreaElement};
+use wasm_bindgen::{intern, JsCast, JsValue};
+use web_sys::{
+    CssStyleDeclaration, Element, HtmlInputElement as InputElement,
+    HtmlTextAreaElement as TextAreaElement,
+};
 use yew::AttrValue;
 
 use super::Apply;
 use crate::dom_bundle::BSubtree;
+use crate::html::declarations;
 use crate::virtual_dom::vtag::{InputFields, Value};
 use crate::virtual_dom::{ApplyAttributeAs, Attributes};
 
@@ -89,6 +93,29 @@ impl Apply for InputFields {
 }
 
 impl Attributes {
+    /// Sets the `value` property again, once the children of the element are attached.
+    ///
+    /// The value of a `<select>` selects one of its options, so it has no effect before they
+    /// exist.
+    pub(super) fn reapply_value_property(&self, el: &Element) {
+        let value = match self {
+            Self::Static(arr) => arr
+                .iter()
+                .find(|(k, ..)| *k == "value")
+                .map(|(_, v, apply_as)| (*v, *apply_as)),
+            Self::Dynamic { keys, values } => keys
+                .iter()
+                .zip(values.iter())
+                .find(|(k, _)| **k == "value")
+                .and_then(|(_, v)| v.as_ref())
+                .map(|(v, apply_as)| (v.as_str(), *apply_as)),
+            Self::IndexMap(m) => m.get("value").map(|(v, apply_as)| (v.as_str(), *apply_as)),
+        };
+        if let Some((value, ApplyAttributeAs::Property)) = value {
+            Self::set(el, "value", value, ApplyAttributeAs::Property);
+        }
+    }
+
     #[cold]
     fn apply_diff_index_maps(
         el: &Element,
@@ -99,7 +126,12 @@ impl Attributes {
             match old.get(key) {
                 Some(old_value) => {
                     if value != old_value {
-                        Self::set(el, key, value.0.as_ref(), value.1);
+                        Self::update(
+                            el,
+                            key,
+                            (old_value.0.as_ref(), old_value.1),
+                            (value.0.as_ref(), value.1),
+                        );
                     }
                 }
                 None => Self::set(el, key, value.0.as_ref(), value.1),
@@ -145,11 +177,13 @@ impl Attributes {
 
         // Update existing or set new
         for (k, new) in new.iter() {
-            if match old.get(k) {
-                Some(old) => old != new,
-                None => true,
-            } {
-                Self::set(el, k, new.0, new.1);
+            match old.get(k) {
+                Some(old) => {
+                    if old != new {
+                        Self::update(el, k, (old.0, old.1), (new.0, new.1));
+                    }
+                }
+                None => Self::set(el, k, new.0, new.1),
             }
         }
 
@@ -174,6 +208,106 @@ impl Attributes {
         }
     }
 
+    /// Updates an attribute which is already set.
+    ///
+    /// The `style` attribute is updated property by property, so that the browser doesn't have to
+    /// parse the whole attribute again, e.g. when a single property is animated.
+    fn update(
+        el: &Element,
+        key: &str,
+        old: (&str, ApplyAttributeAs),
+        new: (&str, ApplyAttributeAs),
+    ) {
+        let updated = old.1 == ApplyAttributeAs::Attribute
+            && new.1 == ApplyAttributeAs::Attribute
+            && key == "style"
+            && Self::update_style(el, old.0, new.0);
+        if !updated {
+            Self::set(el, key, new.0, new.1);
+        }
+    }
+
+    /// Applies the difference between two values of the `style` attribute with
+    /// `style.setProperty()` and `style.removeProperty()`.
+    ///
+    /// Returns `false` if the values can't be compared property by property.
+    fn update_style(el: &Element, old: &str, new: &str) -> bool {
+        // Whether `name` is a shorthand of `other`, e.g. `margin` of `margin-top`.
+        fn is_shorthand(name: &str, other: &str) -> bool {
+            other.len() > name.len()
+                && other.starts_with(name)
+                && other.as_bytes()[name.len()] == b'-'
+        }
+
+        fn parse(style: &str) -> Option<Vec<(&str, &str)>> {
+            let declarations = declarations(style).collect::<Option<Vec<_>>>()?;
+            // Repeated properties are fallbacks, and longhands are overridden by their shorthands
+            // depending on their order, which only the browser can resolve.
+            let independent = declarations.iter().enumerate().all(|(i, (name, _))| {
+                declarations[..i].iter().all(|(other, _)| {
+                    other != name && !is_shorthand(name, other) && !is_shorthand(other, name)
+                })
+            });
+            independent.then_some(declarations)
+        }
+
+        // The properties of `a` that are also declared in `b`, in order.
+        fn kept<'a>(a: &[(&'a str, &str)], b: &[(&str, &str)]) -> Vec<&'a str> {
+            a.iter()
+                .map(|(name, _)| *name)
+                .filter(|name| b.iter().any(|(other, _)| other == name))
+                .collect()
+        }
+
+        let (old, new) = match (parse(old), parse(new)) {
+            (Some(old), Some(new)) => (old, new),
+            _ => return false,
+        };
+        // Reordered declarations may override each other differently.
+        if kept(&old, &new) != kept(&new, &old) {
+            return false;
+        }
+        let style = match js_sys::Reflect::get(el.as_ref(), &JsValue::from_str("style"))
+            .ok()
+            .and_then(|style| style.dyn_into::<CssStyleDeclaration>().ok())
+        {
+            Some(style) => style,
+            None => return false,
+        };
+
+        let mut removed = false;
+        for (name, _) in old.iter() {
+            if !new.iter().any(|(other, _)| other == name) {
+                style
+                    .remove_property(name)
+                    .expect("could not remove style property");
+                removed = true;
+            }
+        }
+        // A changed declaration may override the later ones in ways not detected above, e.g.
+        // `font` resets `line-height`, so they are applied again.
+        let changed = match removed {
+            true => 0,
+            false => new
+                .iter()
+                .position(|m| !old.contains(m))
+                .unwrap_or(new.len()),
+        };
+        for (name, value) in new[changed..].iter() {
+            let important = value.len() >= 10
+                && value.is_char_boundary(value.len() - 10)
+                && value[value.len() - 10..].eq_ignore_ascii_case("!important");
+            let (value, priority) = match important {
+                true => (value[..value.len() - 10].trim_end(), "important"),
+                false => (*value, ""),
+            };
+            style
+                .set_property_with_priority(name, value, priority)
+                .expect("could not set style property");
+        }
+        true
+    }
+
     fn remove(el: &Element, key: &str, apply_as: ApplyAttributeAs) {
         match apply_as {
             ApplyAttributeAs::Attribute => el
@@ -255,7 +389,12 @@ impl Apply for Attributes {
                     match unsafe { (new_v.get_unchecked(i), old_v.get_unchecked(i)) } {
                         (Some(new), Some(old)) => {
                             if new != old {
-                                set!(new);
+                                Self::update(
+                                    el,
+                                    key!(),
+                                    (old.0.as_ref(), old.1),
+                                    (new.0.as_ref(), new.1),
+                                );
                             }
                         }
                         (Some(new), None) => set!(new),
@@ -359,6 +498,83 @@ mod tests {
         assert_eq!(element.get_attribute("class").unwrap(), "thing");
     }
 
+    #[test]
+    fn style_is_updated_by_property() {
+        let (element, btree) = create_element();
+        let mut attrs = Attributes::Static(&[(
+            "style",
+            "color: red; margin: 0",
+            ApplyAttributeAs::Attribute,
+        )])
+        .apply(&btree, &element);
+
+        // Properties set outside of the attribute are kept.
+        let style = Reflect::get(element.as_ref(), &JsValue::from_str("style"))
+            .unwrap()
+            .unchecked_into::<CssStyleDeclaration>();
+        style.set_property("padding", "1px").unwrap();
+
+        Attributes::Static(&[(
+            "style",
+            "color: blue !important",
+            ApplyAttributeAs::Attribute,
+        )])
+        .apply_diff(&btree, &element, &mut attrs);
+
+        assert_eq!(style.get_property_value("color").unwrap(), "blue");
+        assert_eq!(style.get_property_priority("color"), "important");
+        assert_eq!(style.get_property_value("margin").unwrap(), "");
+        assert_eq!(style.get_property_value("padding").unwrap(), "1px");
+    }
+
+    #[test]
+    fn style_overrides_are_kept() {
+        let (element, btree) = create_element();
+        let style = Reflect::get(element.as_ref(), &JsValue::from_str("style"))
+            .unwrap()
+            .unchecked_into::<CssStyleDeclaration>();
+
+        let mut attrs = Attributes::Static(&[(
+            "style",
+            "margin: 1px; margin-top: 5px",
+            ApplyAttributeAs::Attribute,
+        )])
+        .apply(&btree, &element);
+        // Updating the shorthand must not reset `margin-top`.
+        Attributes::Static(&[(
+            "style",
+            "margin: 2px; margin-top: 5px",
+            ApplyAttributeAs::Attribute,
+        )])
+        .apply_diff(&btree, &element, &mut attrs);
+        assert_eq!(style.get_property_value("margin-left").unwrap(), "2px");
+        assert_eq!(style.get_property_value("margin-top").unwrap(), "5px");
+
+        let mut attrs = Attributes::Static(&[(
+            "style",
+            "font: 12px serif; line-height: 2",
+            ApplyAttributeAs::Attribute,
+        )])
+        .apply(&btree, &element);
+        // `font` resets `line-height`, which is applied again.
+        Attributes::Static(&[(
+            "style",
+            "font: 14px serif; line-height: 2",
+            ApplyAttributeAs::Attribute,
+        )])
+        .apply_diff(&btree, &element, &mut attrs);
+        assert_eq!(style.get_property_value("line-height").unwrap(), "2");
+
+        // Reordered, `font` now resets `line-height`.
+        Attributes::Static(&[(
+            "style",
+            "line-height: 2; font: 14px serif",
+            ApplyAttributeAs::Attribute,
+        )])
+        .apply_diff(&btree, &element, &mut attrs);
+        assert_eq!(style.get_property_value("line-height").unwrap(), "normal");
+    }
+
     #[test]
     async fn macro_syntax_works() {
         #[function_component]
@@ -136,6 +136,9 @@ impl Reconcilable for VTag {
             }
             VTagInner::Other { children, tag } => {
                 let (_, child_bundle) = children.attach(root, parent_scope, &el, DomSlot::at_end());
+                if tag.eq_ignore_ascii_case("select") {
+                    attributes.reapply_value_property(&el);
+                }
                 BTagInner::Other { child_bundle, tag }
             }
         };
@@ -208,10 +211,14 @@ impl Reconcilable for VTag {
             (
                 VTagInner::Other { children: new, .. },
                 BTagInner::Other {
-                    child_bundle: old, ..
+                    child_bundle: old,
+                    tag: name,
                 },
             ) => {
                 new.reconcile(root, parent_scope, el, DomSlot::at_end(), old);
+                if name.eq_ignore_ascii_case("select") {
+                    tag.attributes.reapply_value_property(el);
+                }
             }
             // Can not happen, because we checked for tag equability above
             _ => unsafe { unreachable_unchecked() },
@@ -289,6 +296,14 @@ impl BTag {
         self.key.as_ref()
     }
 
+    /// Calls `f` with the scopes of the components in the children of the tag, in render order
+    #[cfg(feature = "hmr")]
+    pub fn for_each_component(&self, f: &mut dyn FnMut(&dyn crate::html::Scoped)) {
+        if let BTagInner::Other { child_bundle, .. } = &self.inner {
+            child_bundle.for_each_component(f);
+        }
+    }
+
     #[cfg(target_arch = "wasm32")]
     #[cfg(test)]
     fn reference(&self) -> &Element {
@@ -386,6 +401,10 @@ mod feat_hydration {
 
                     assert!(nodes.is_empty(), "expected EOF, found node.");
 
+                    if tag.eq_ignore_ascii_case("select") {
+                        attributes.reapply_value_property(&el);
+                    }
+
                     BTagInner::Other { child_bundle, tag }
                 }
             };
@@ -459,14 +478,17 @@ mod tests {
     #[test]
     fn it_compares_attributes_static() {
         let a = html! {
+            #![allow(unknown_attributes)]
             <div a="test"></div>
         };
 
         let b = html! {
+            #![allow(unknown_attributes)]
             <div a="test"></div>
         };
 
         let c = html! {
+            #![allow(unknown_attributes)]
             <div a="fail"></div>
         };
 
@@ -477,14 +499,17 @@ mod tests {
     #[test]
     fn it_compares_attributes_dynamic() {
         let a = html! {
+            #![allow(unknown_attributes)]
             <div a={"test".to_owned()}></div>
         };
 
         let b = html! {
+            #![allow(unknown_attributes)]
             <div a={"test".to_owned()}></div>
         };
 
         let c = html! {
+            #![allow(unknown_attributes)]
             <div a={"fail".to_owned()}></div>
         };
 
@@ -692,6 +717,7 @@ mod tests {
     #[test]
     fn it_allows_aria_attributes() {
         let a = html! {
+            #![allow(unknown_attributes)]
             <p aria-controls="it-works">
                 <a class="btn btn-primary"
                    data-toggle="collapse"
@@ -756,6 +782,38 @@ mod tests {
         test_set_class_name(|| html! { <div class={"ferris the crab".to_owned()}></div> });
     }
 
+    #[test]
+    fn select_value_selects_option() {
+        let (root, scope, parent) = setup_parent();
+        let select_value = |elem: &BNode| {
+            let select = assert_btag_ref(elem).reference().clone();
+            js_sys::Reflect::get(&select, &"value".into())
+                .unwrap()
+                .as_string()
+        };
+
+        let elem = html! {
+            <select ~value="b">
+                <option value="a" />
+                <option value="b" />
+            </select>
+        };
+        let (_, mut elem) = elem.attach(&root, &scope, &parent, DomSlot::at_end());
+        assert_eq!(select_value(&elem).as_deref(), Some("b"));
+
+        // the option is added together with the value
+        let next_elem = html! {
+            <select ~value="c">
+                <option value="a" />
+                <option value="b" />
+                <option value="c" />
+            </select>
+        };
+        let elem_vtag = assert_vtag(next_elem);
+        elem_vtag.reconcile_node(&root, &scope, &parent, DomSlot::at_end(), &mut elem);
+        assert_eq!(select_value(&elem).as_deref(), Some("c"));
+    }
+
     #[test]
     fn controlled_input_synced() {
         let (root, scope, parent) = setup_parent();
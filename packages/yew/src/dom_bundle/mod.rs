@@ -75,6 +75,12 @@ impl Bundle {
     pub fn detach(self, root: &BSubtree, parent: &Element, parent_to_detach: bool) {
         self.0.detach(root, parent, parent_to_detach);
     }
+
+    /// Calls `f` with the scopes of the components rendered in the bundle, in render order.
+    #[cfg(feature = "hmr")]
+    pub fn for_each_component(&self, f: &mut dyn FnMut(&dyn crate::html::Scoped)) {
+        self.0.for_each_component(f);
+    }
 }
 
 #[cfg(feature = "hydration")]
@@ -2,6 +2,7 @@ mod use_callback;
 mod use_context;
 mod use_effect;
 mod use_force_update;
+mod use_hmr_state;
 mod use_memo;
 mod use_prepared_state;
 mod use_reducer;
@@ -13,6 +14,7 @@ pub use use_callback::*;
 pub use use_context::*;
 pub use use_effect::*;
 pub use use_force_update::*;
+pub use use_hmr_state::*;
 pub use use_memo::*;
 pub use use_prepared_state::*;
 pub use use_reducer::*;
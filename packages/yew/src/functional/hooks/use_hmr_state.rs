@@ -0,0 +1,79 @@
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{use_state, UseStateHandle};
+use crate::functional::{Hook, HookContext};
+
+/// This hook is used to manage a state that is kept when the application is hot reloaded.
+///
+/// With the `hmr` feature, the value of the state is saved when the application is swapped to a
+/// new build, and restored in the component at the same position of the new build, if it can
+/// still be deserialized. Refer to the [`hmr`](crate::hmr) module for details.
+///
+/// Without the feature, this hook is the same as [`use_state`](super::use_state()).
+///
+/// # Example
+///
+/// ```rust
+/// use yew::prelude::*;
+///
+/// #[function_component]
+/// fn Counter() -> Html {
+///     let counter = use_hmr_state(|| 0);
+///     let onclick = {
+///         let counter = counter.clone();
+///         Callback::from(move |_| counter.set(*counter + 1))
+///     };
+///
+///     html! {
+///         <button {onclick}>{ *counter }</button>
+///     }
+/// }
+/// ```
+pub fn use_hmr_state<'hook, T, F>(init_fn: F) -> impl 'hook + Hook<Output = UseStateHandle<T>>
+where
+    T: Serialize + DeserializeOwned + 'static,
+    F: 'hook + FnOnce() -> T,
+{
+    struct HookProvider<'hook, T, F>
+    where
+        T: Serialize + DeserializeOwned + 'static,
+        F: 'hook + FnOnce() -> T,
+    {
+        _marker: PhantomData<&'hook T>,
+
+        init_fn: F,
+    }
+
+    impl<'hook, T, F> Hook for HookProvider<'hook, T, F>
+    where
+        T: Serialize + DeserializeOwned + 'static,
+        F: 'hook + FnOnce() -> T,
+    {
+        type Output = UseStateHandle<T>;
+
+        #[cfg(feature = "hmr")]
+        fn run(self, ctx: &mut HookContext) -> Self::Output {
+            let Self { init_fn, .. } = self;
+
+            let state = ctx.next_state(|_| crate::hmr::HotStateCell::new());
+            let restored = state.restore(ctx.scope.id);
+            let handle = use_state(move || restored.unwrap_or_else(init_fn)).run(ctx);
+            state.set_handle(handle.clone());
+
+            handle
+        }
+
+        #[cfg(not(feature = "hmr"))]
+        fn run(self, ctx: &mut HookContext) -> Self::Output {
+            use_state(self.init_fn).run(ctx)
+        }
+    }
+
+    HookProvider {
+        _marker: PhantomData,
+        init_fn,
+    }
+}
@@ -0,0 +1,459 @@
+//! Hot module reloading of components in development.
+//!
+//! With the `hmr` feature, the mounted apps can be swapped to a new build of the application
+//! without reloading the page. The state of [`use_hmr_state`](crate::functional::use_hmr_state)
+//! hooks is saved before the apps of the current build are destroyed, and restored when the new
+//! build mounts its apps. Everything else, like the state of struct components or of other hooks,
+//! starts from scratch.
+//!
+//! The states are keyed by the position of their component in the tree: the path of component
+//! types from the root of the app, with the index of each component among the components of the
+//! same type rendered by its parent. A state is only restored if its component is found at the same
+//! position, with the same type, and if it can still be deserialized.
+//!
+//! This feature is meant for development only: [`connect`] does nothing in release builds, but
+//! the feature should not be enabled for them either.
+//!
+//! # Protocol
+//!
+//! [`connect`] connects to a dev server over a websocket. The dev server watches the sources of
+//! the application, rebuilds it when they change, and sends one of these text messages:
+//!
+//! - `{"type": "update", "module": "/app-2.js", "wasm": "/app-2_bg.wasm"}` once the new build is
+//!   ready. The current apps are saved and destroyed, then `module` is imported and its default
+//!   export is called with `wasm`, which is what the JavaScript of `wasm-bindgen --target web`
+//!   expects. `wasm` can be omitted to load the binary next to the module. The files of each build
+//!   need distinct URLs, so they are not served from the cache of the browser.
+//! - `{"type": "reload"}` to reload the page, when the change can not be hot reloaded.
+//!
+//! Other messages are ignored. Dev servers with another loader can call [`save_and_unmount`]
+//! before loading the new build themselves.
+//!
+//! ```no_run
+//! use yew::prelude::*;
+//!
+//! #[function_component]
+//! fn App() -> Html {
+//!     // keeps its value when the app is hot reloaded
+//!     let count = use_hmr_state(|| 0);
+//!     let onclick = {
+//!         let count = count.clone();
+//!         Callback::from(move |_| count.set(*count + 1))
+//!     };
+//!
+//!     html! { <button {onclick}>{ *count }</button> }
+//! }
+//!
+//! fn main() {
+//!     #[cfg(debug_assertions)]
+//!     yew::hmr::connect("ws://localhost:8081/hmr");
+//!     yew::Renderer::<App>::new().render();
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{MessageEvent, WebSocket};
+
+use crate::functional::UseStateHandle;
+use crate::html::Scoped;
+use crate::scheduler;
+
+/// The global property holding the saved state between two builds.
+const SAVED_STATE_PROPERTY: &str = "__yew_hmr_state";
+
+#[wasm_bindgen(inline_js = r#"
+export function load_build(module, wasm) {
+    import(module)
+        .then((build) => build.default(wasm))
+        .catch((err) => console.error("failed to load the new build", err));
+}
+"#)]
+extern "C" {
+    fn load_build(module: &str, wasm: Option<String>);
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry::load());
+    static SOCKET: RefCell<Option<WebSocket>> = RefCell::default();
+}
+
+/// The saved states, by component key.
+type SavedStates = HashMap<String, Vec<Option<SavedState>>>;
+
+/// The state of a hook, as saved by the previous build.
+#[derive(Serialize, Deserialize)]
+struct SavedState {
+    type_name: String,
+    value: Vec<u8>,
+}
+
+/// A state that can be saved for the next build.
+pub(crate) trait HotState {
+    /// Serializes the current value of the state.
+    fn save(&self) -> Option<Vec<u8>>;
+}
+
+struct ComponentEntry {
+    key: Rc<str>,
+    /// The number of children created by the component, by type
+    children: HashMap<&'static str, usize>,
+    states: Vec<(&'static str, Weak<dyn HotState>)>,
+}
+
+#[derive(Default)]
+struct Registry {
+    /// The mounted components, by id
+    components: HashMap<usize, ComponentEntry>,
+    /// The number of apps created by this build, by type
+    roots: HashMap<&'static str, usize>,
+    /// The mounted apps, in mounting order
+    apps: Vec<Box<dyn Scoped>>,
+    /// The states saved by the previous build
+    saved: SavedStates,
+}
+
+impl Registry {
+    /// Creates a registry with the states saved by the previous build, if any.
+    fn load() -> Self {
+        let global = js_sys::global();
+        let property = JsValue::from_str(SAVED_STATE_PROPERTY);
+        let saved = js_sys::Reflect::get(&global, &property)
+            .ok()
+            .and_then(|saved| saved.dyn_into::<js_sys::Uint8Array>().ok());
+        let _ = js_sys::Reflect::delete_property(&global, &property);
+
+        let saved = saved
+            .and_then(|saved| match bincode::deserialize(&saved.to_vec()) {
+                Ok(saved) => Some(saved),
+                Err(err) => {
+                    tracing::warn!("failed to load the state of the previous build: {err}");
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            saved,
+            ..Self::default()
+        }
+    }
+
+    /// Saves the states of the mounted apps.
+    fn save(&self) -> SavedStates {
+        let mut saved = SavedStates::new();
+        let mut roots = HashMap::new();
+        for app in self.apps.iter() {
+            let key = component_key(None, app.type_name(), &mut roots);
+            self.save_component(app.as_ref(), key, &mut saved);
+        }
+        saved
+    }
+
+    fn save_component(&self, scope: &dyn Scoped, key: String, saved: &mut SavedStates) {
+        if let Some(component) = self.components.get(&scope.id()) {
+            let states: Vec<_> = component
+                .states
+                .iter()
+                .map(|(type_name, state)| {
+                    let value = state.upgrade()?.save()?;
+                    Some(SavedState {
+                        type_name: type_name.to_string(),
+                        value,
+                    })
+                })
+                .collect();
+            if !states.is_empty() {
+                saved.insert(key.clone(), states);
+            }
+        }
+
+        let mut children = HashMap::new();
+        scope.for_each_child(&mut |child| {
+            let key = component_key(Some(&key), child.type_name(), &mut children);
+            self.save_component(child, key, saved);
+        });
+    }
+}
+
+/// Returns the key of the next component of type `type_name` rendered by the component keyed
+/// `parent`, or the next app if there is no parent.
+fn component_key(
+    parent: Option<&str>,
+    type_name: &'static str,
+    siblings: &mut HashMap<&'static str, usize>,
+) -> String {
+    let index = siblings.entry(type_name).or_default();
+    let key = match parent {
+        Some(parent) => format!("{parent}/{type_name}#{index}"),
+        None => format!("{type_name}#{index}"),
+    };
+    *index += 1;
+    key
+}
+
+/// Registers a component created by this build.
+pub(crate) fn register_component(id: usize, parent: Option<usize>, type_name: &'static str) {
+    REGISTRY.with(|registry| {
+        let registry = &mut *registry.borrow_mut();
+        let key = match parent.and_then(|parent| registry.components.get_mut(&parent)) {
+            Some(parent) => component_key(Some(&parent.key), type_name, &mut parent.children),
+            None => component_key(None, type_name, &mut registry.roots),
+        };
+        registry.components.insert(
+            id,
+            ComponentEntry {
+                key: key.into(),
+                children: HashMap::new(),
+                states: Vec::new(),
+            },
+        );
+    })
+}
+
+/// Unregisters a destroyed component.
+pub(crate) fn unregister_component(id: usize) {
+    REGISTRY.with(|registry| registry.borrow_mut().components.remove(&id));
+}
+
+/// Registers an app mounted by this build.
+pub(crate) fn register_app(app: Box<dyn Scoped>) {
+    REGISTRY.with(|registry| registry.borrow_mut().apps.push(app));
+}
+
+/// Unregisters a destroyed app.
+pub(crate) fn unregister_app(id: usize) {
+    REGISTRY.with(|registry| registry.borrow_mut().apps.retain(|app| app.id() != id));
+}
+
+/// Registers the next state of a component, and returns its value saved by the previous build.
+fn register_state<T>(comp_id: usize, state: Weak<dyn HotState>) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    let type_name = std::any::type_name::<T>();
+    let saved = REGISTRY.with(|registry| {
+        let registry = &mut *registry.borrow_mut();
+        let component = registry.components.get_mut(&comp_id)?;
+        let index = component.states.len();
+        component.states.push((type_name, state));
+
+        registry
+            .saved
+            .get_mut(&*component.key)?
+            .get_mut(index)?
+            .take()
+            .filter(|saved| saved.type_name == type_name)
+    })?;
+
+    match bincode::deserialize(&saved.value) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            tracing::warn!("failed to restore a state of type {type_name}: {err}");
+            None
+        }
+    }
+}
+
+/// The state of a [`use_hmr_state`](crate::functional::use_hmr_state) hook.
+pub(crate) struct HotStateCell<T> {
+    handle: RefCell<Option<UseStateHandle<T>>>,
+}
+
+impl<T> HotStateCell<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            handle: RefCell::new(None),
+        }
+    }
+
+    /// Registers the state when the component is first rendered, and returns its value saved by
+    /// the previous build.
+    pub(crate) fn restore(self: &Rc<Self>, comp_id: usize) -> Option<T> {
+        if self.handle.borrow().is_some() {
+            return None;
+        }
+        let state = Rc::downgrade(self);
+        register_state(comp_id, state)
+    }
+
+    /// Sets the handle holding the current value.
+    pub(crate) fn set_handle(&self, handle: UseStateHandle<T>) {
+        *self.handle.borrow_mut() = Some(handle);
+    }
+}
+
+impl<T> HotState for HotStateCell<T>
+where
+    T: Serialize,
+{
+    fn save(&self) -> Option<Vec<u8>> {
+        let handle = self.handle.borrow();
+        match bincode::serialize(&**handle.as_ref()?) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                tracing::warn!(
+                    "failed to save a state of type {}: {err}",
+                    std::any::type_name::<T>()
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Saves the state of the mounted apps for the next build, and destroys them.
+///
+/// The next build restores the state when it mounts its apps in the same page. This is done by
+/// [`connect`] when the dev server sends an update, and only needs to be called by dev servers
+/// loading the new build themselves.
+pub fn save_and_unmount() {
+    // the states are saved as of the last render
+    scheduler::start_now();
+    let apps = REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        match bincode::serialize(&registry.save()) {
+            Ok(saved) => {
+                let saved = js_sys::Uint8Array::from(saved.as_slice());
+                let _ = js_sys::Reflect::set(
+                    &js_sys::global(),
+                    &JsValue::from_str(SAVED_STATE_PROPERTY),
+                    &saved,
+                );
+            }
+            Err(err) => tracing::warn!("failed to save the state of the apps: {err}"),
+        }
+        std::mem::take(&mut registry.apps)
+    });
+
+    for app in apps {
+        app.destroy_boxed(false);
+    }
+    // the apps must be gone before the new build mounts its apps in the same elements
+    scheduler::start_now();
+}
+
+/// Connects to the dev server at `url`, to hot reload the application when it is rebuilt.
+///
+/// Refer to the [module documentation](self) for the messages sent by the dev server. Does nothing
+/// in release builds.
+pub fn connect(url: &str) {
+    if !cfg!(debug_assertions) {
+        tracing::warn!("hot module reloading is disabled in release builds");
+        return;
+    }
+
+    let socket = match WebSocket::new(url) {
+        Ok(socket) => socket,
+        Err(err) => {
+            tracing::warn!("failed to connect to the dev server at {url}: {err:?}");
+            return;
+        }
+    };
+    let onmessage = Closure::<dyn Fn(MessageEvent)>::new(|event: MessageEvent| {
+        if let Some(message) = event.data().as_string() {
+            handle_message(&message);
+        }
+    });
+    socket.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    // the socket is closed when the next build is loaded
+    onmessage.forget();
+
+    if let Some(previous) = SOCKET.with(|current| current.replace(Some(socket))) {
+        let _ = previous.close();
+    }
+}
+
+fn handle_message(message: &str) {
+    let message = match js_sys::JSON::parse(message) {
+        Ok(message) => message,
+        Err(_) => return,
+    };
+    let field = |name: &str| {
+        js_sys::Reflect::get(&message, &JsValue::from_str(name))
+            .ok()
+            .and_then(|value| value.as_string())
+    };
+
+    match field("type").as_deref() {
+        Some("update") => match field("module") {
+            Some(module) => {
+                if let Some(socket) = SOCKET.with(|socket| socket.take()) {
+                    let _ = socket.close();
+                }
+                save_and_unmount();
+                load_build(&module, field("wasm"));
+            }
+            None => tracing::warn!("the update sent by the dev server has no module"),
+        },
+        Some("reload") => {
+            let _ = gloo::utils::window().location().reload();
+        }
+        _ => {}
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    use super::*;
+    use crate::prelude::*;
+    use crate::Renderer;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[function_component]
+    fn Counter() -> Html {
+        let count = use_hmr_state(|| 0_u32);
+        let onclick = {
+            let count = count.clone();
+            Callback::from(move |_| count.set(*count + 1))
+        };
+
+        html! { <button {onclick}>{ *count }</button> }
+    }
+
+    #[function_component]
+    fn App() -> Html {
+        html! { <><Counter /><Counter /></> }
+    }
+
+    #[test]
+    fn restores_state_in_next_build() {
+        let document = gloo::utils::document();
+        let host = document.create_element("div").unwrap();
+        document.body().unwrap().append_child(&host).unwrap();
+
+        Renderer::<App>::with_root(host.clone()).render();
+        scheduler::start_now();
+        let button = host
+            .last_element_child()
+            .unwrap()
+            .dyn_into::<web_sys::HtmlElement>()
+            .unwrap();
+        button.click();
+        button.click();
+        scheduler::start_now();
+        assert_eq!(host.inner_html(), "<button>0</button><button>2</button>");
+
+        save_and_unmount();
+        assert_eq!(host.inner_html(), "");
+
+        // the registry of the next build
+        REGISTRY.with(|registry| *registry.borrow_mut() = Registry::load());
+        Renderer::<App>::with_root(host.clone()).render();
+        scheduler::start_now();
+        assert_eq!(host.inner_html(), "<button>0</button><button>2</button>");
+    }
+}
@@ -0,0 +1,190 @@
+use std::rc::Rc;
+
+use wasm_bindgen::JsValue;
+
+use super::{IntoPropValue, NodeRef};
+use crate::functional::{Reducible, UseReducerHandle, UseStateHandle};
+use crate::virtual_dom::{AttrValue, Listener};
+use crate::Callback;
+
+/// A state which a form element can be bound to, with `bind:value` or `bind:checked`.
+///
+/// `bind:value` binds an `AttrValue` to the value of an `<input>`, a `<textarea>` or a `<select>`.
+/// On a radio button, the button is checked when the bound value is equal to its own `value`, and
+/// the bound value is set to it when it gets checked. `bind:checked` binds a `bool` to a checkbox.
+///
+/// ```
+/// use yew::prelude::*;
+///
+/// #[function_component]
+/// fn Form() -> Html {
+///     let name = use_state(String::new);
+///     let subscribed = use_state(|| false);
+///
+///     html! {
+///         <form>
+///             <input bind:value={name.clone()} />
+///             <input type="checkbox" bind:checked={subscribed.clone()} />
+///             <p>{ format!("{} ({})", *name, *subscribed) }</p>
+///         </form>
+///     }
+/// }
+/// ```
+///
+/// This is implemented for [`UseStateHandle`], for [`UseReducerHandle`] of reducers implementing
+/// [`BindReducer`], and for [`NodeRef`], which leaves the element uncontrolled and is set to it
+/// instead.
+pub trait Bind<T> {
+    /// Returns the value to set on the element, or `None` to leave it uncontrolled.
+    fn bound(&self) -> Option<T>;
+
+    /// Updates the state with the value of the element, after the user changed it.
+    fn update(&self, value: T);
+
+    /// Returns the node ref to set to the element.
+    fn node_ref(&self) -> Option<NodeRef> {
+        None
+    }
+}
+
+impl<T> Bind<AttrValue> for UseStateHandle<T>
+where
+    T: Clone + IntoPropValue<Option<AttrValue>> + From<String>,
+{
+    fn bound(&self) -> Option<AttrValue> {
+        (**self).clone().into_prop_value()
+    }
+
+    fn update(&self, value: AttrValue) {
+        self.set(T::from(value.to_string()))
+    }
+}
+
+impl Bind<bool> for UseStateHandle<bool> {
+    fn bound(&self) -> Option<bool> {
+        Some(**self)
+    }
+
+    fn update(&self, value: bool) {
+        self.set(value)
+    }
+}
+
+/// A reducer whose state can be bound to a form element with `bind:value` or `bind:checked`.
+///
+/// ```
+/// use std::rc::Rc;
+///
+/// use yew::html::BindReducer;
+/// use yew::prelude::*;
+///
+/// struct Search {
+///     query: AttrValue,
+/// }
+///
+/// enum SearchAction {
+///     SetQuery(AttrValue),
+/// }
+///
+/// impl Reducible for Search {
+///     type Action = SearchAction;
+///
+///     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+///         match action {
+///             SearchAction::SetQuery(query) => Self { query }.into(),
+///         }
+///     }
+/// }
+///
+/// impl BindReducer<AttrValue> for Search {
+///     fn bound_value(&self) -> AttrValue {
+///         self.query.clone()
+///     }
+///
+///     fn update_action(value: AttrValue) -> SearchAction {
+///         SearchAction::SetQuery(value)
+///     }
+/// }
+///
+/// #[function_component]
+/// fn SearchBox() -> Html {
+///     let search = use_reducer(|| Search {
+///         query: AttrValue::default(),
+///     });
+///
+///     html! { <input type="search" bind:value={search} /> }
+/// }
+/// ```
+pub trait BindReducer<T>: Reducible {
+    /// Returns the value to set on the element.
+    fn bound_value(&self) -> T;
+
+    /// Returns the action updating the state with the value of the element.
+    fn update_action(value: T) -> Self::Action;
+}
+
+impl<R, T> Bind<T> for UseReducerHandle<R>
+where
+    R: BindReducer<T>,
+{
+    fn bound(&self) -> Option<T> {
+        Some((**self).bound_value())
+    }
+
+    fn update(&self, value: T) {
+        self.dispatch(R::update_action(value))
+    }
+}
+
+impl<T> Bind<T> for NodeRef {
+    fn bound(&self) -> Option<T> {
+        None
+    }
+
+    fn update(&self, _value: T) {}
+
+    fn node_ref(&self) -> Option<NodeRef> {
+        Some(self.clone())
+    }
+}
+
+/// Reads a property of the target of an event.
+fn target_property(event: &web_sys::Event, name: &str) -> Option<JsValue> {
+    let target = event.target()?;
+    js_sys::Reflect::get(&target, &JsValue::from_str(name)).ok()
+}
+
+/// Creates the listener updating a `bind:value` binding.
+pub(crate) fn value_listener<B>(binding: B, radio: bool) -> Option<Rc<dyn Listener>>
+where
+    B: Bind<AttrValue> + 'static,
+{
+    if radio {
+        super::onchange::Wrapper::__macro_new(Callback::from(move |event: web_sys::Event| {
+            let checked = target_property(&event, "checked").and_then(|v| v.as_bool());
+            if checked == Some(true) {
+                if let Some(value) = target_property(&event, "value").and_then(|v| v.as_string()) {
+                    binding.update(value.into());
+                }
+            }
+        }))
+    } else {
+        super::oninput::Wrapper::__macro_new(Callback::from(move |event: web_sys::InputEvent| {
+            if let Some(value) = target_property(&event, "value").and_then(|v| v.as_string()) {
+                binding.update(value.into());
+            }
+        }))
+    }
+}
+
+/// Creates the listener updating a `bind:checked` binding.
+pub(crate) fn checked_listener<B>(binding: B) -> Option<Rc<dyn Listener>>
+where
+    B: Bind<bool> + 'static,
+{
+    super::onchange::Wrapper::__macro_new(Callback::from(move |event: web_sys::Event| {
+        if let Some(checked) = target_property(&event, "checked").and_then(|v| v.as_bool()) {
+            binding.update(checked);
+        }
+    }))
+}
@@ -97,6 +97,16 @@ impl std::fmt::Debug for ComponentRenderState {
 
 #[cfg(feature = "csr")]
 impl ComponentRenderState {
+    /// Returns whether the component is rendered on the server.
+    #[cfg(feature = "hmr")]
+    fn is_ssr(&self) -> bool {
+        #[cfg(feature = "ssr")]
+        if let Self::Ssr { .. } = self {
+            return true;
+        }
+        false
+    }
+
     pub(crate) fn shift(&mut self, next_parent: Element, next_slot: DomSlot) {
         match self {
             #[cfg(feature = "csr")]
@@ -251,6 +261,14 @@ impl ComponentState {
         #[cfg(feature = "hydration")] prepared_state: Option<String>,
     ) -> Self {
         let comp_id = scope.id;
+        #[cfg(feature = "hmr")]
+        if !initial_render_state.is_ssr() {
+            crate::hmr::register_component(
+                comp_id,
+                scope.get_parent().map(|parent| parent.id),
+                std::any::type_name::<COMP>(),
+            );
+        }
         #[cfg(feature = "hydration")]
         let creation_mode = {
             match initial_render_state {
@@ -380,6 +398,8 @@ impl ComponentState {
     )]
     fn destroy(mut self, parent_to_detach: bool) {
         self.inner.destroy();
+        #[cfg(feature = "hmr")]
+        crate::hmr::unregister_component(self.comp_id);
         self.resume_existing_suspension();
 
         match self.render_state {
@@ -430,7 +450,13 @@ impl ComponentState {
         fields(component.id = self.comp_id)
     )]
     fn render(&mut self, shared_state: &Shared<Option<ComponentState>>) {
-        match self.inner.view() {
+        #[cfg(feature = "ssr")]
+        let view =
+            crate::style::feat_ssr::with_collector(|| self.inner.any_scope(), || self.inner.view());
+        #[cfg(not(feature = "ssr"))]
+        let view = self.inner.view();
+
+        match view {
             Ok(vnode) => self.commit_render(shared_state, vnode),
             Err(RenderError::Suspended(susp)) => self.suspend(shared_state, susp),
         };
@@ -92,6 +92,90 @@ mod __macro {
     #[derive(Debug)]
     pub struct AssertAllProps;
 
+    /// Type-level state of a prop which takes part in a prop group or a `requires` constraint.
+    /// Each such prop `P` has a struct in the props module, like for required props, and the
+    /// tokens returned by the builder implement `PropState<P>`, recording if it was set.
+    /// The constraints are checked as bounds on the [`HasAllProps`] impl of the props.
+    pub trait PropState<P> {
+        /// Either [`PropSet`] or [`PropUnset`]
+        type State;
+    }
+
+    /// State of a prop which has been given to the builder
+    #[derive(Debug)]
+    pub struct PropSet;
+
+    /// State of a prop which has not been given to the builder
+    #[derive(Debug)]
+    pub struct PropUnset;
+
+    impl<P> PropState<P> for AssertAllProps {
+        type State = PropUnset;
+    }
+
+    /// Counts the props of a group which have been set, adding the state in `Self` to `N`.
+    pub trait CountProp<N> {
+        /// Either `N` or `OneMoreProp<N>`
+        type Count;
+    }
+
+    /// No props of a group have been set
+    #[derive(Debug)]
+    pub struct NoProp;
+
+    /// One more prop of a group than in `N` has been set
+    #[derive(Debug)]
+    pub struct OneMoreProp<N>(std::marker::PhantomData<N>);
+
+    impl<N> CountProp<N> for PropSet {
+        type Count = OneMoreProp<N>;
+    }
+
+    impl<N> CountProp<N> for PropUnset {
+        type Count = N;
+    }
+
+    /// Checks that at least one of the props in `Group` was set, given their count.
+    /// `Group` is a struct in the props module, named after the props in the group.
+    #[rustversion::attr(
+        since(1.78),
+        diagnostic::on_unimplemented(
+            message = "missing props: `{Group}`",
+            label = "expected at least one of these props"
+        )
+    )]
+    pub trait AtLeastOneOf<Group> {}
+
+    impl<Group, N> AtLeastOneOf<Group> for OneMoreProp<N> {}
+
+    /// Checks that at most one of the props in `Group` was set, given their count.
+    #[rustversion::attr(
+        since(1.78),
+        diagnostic::on_unimplemented(
+            message = "conflicting props: `{Group}`",
+            label = "expected at most one of these props"
+        )
+    )]
+    pub trait AtMostOneOf<Group> {}
+
+    impl<Group> AtMostOneOf<Group> for NoProp {}
+
+    impl<Group> AtMostOneOf<Group> for OneMoreProp<NoProp> {}
+
+    /// Checks that `Required` was set if `Prop` was, given a tuple of their states.
+    #[rustversion::attr(
+        since(1.78),
+        diagnostic::on_unimplemented(
+            message = "missing the prop `{Required}`, which is required by `{Prop}`",
+            label = "expected `{Required}`"
+        )
+    )]
+    pub trait PropRequires<Prop, Required> {}
+
+    impl<Prop, Required, S> PropRequires<Prop, Required> for (PropUnset, S) {}
+
+    impl<Prop, Required> PropRequires<Prop, Required> for (PropSet, PropSet) {}
+
     /// Builder for when a component has no properties
     #[derive(Debug, PartialEq, Eq)]
     pub struct EmptyBuilder;
@@ -116,4 +200,7 @@ mod __macro {
 }
 
 #[doc(hidden)]
-pub use __macro::{AllPropsFor, AssertAllProps, Buildable, HasAllProps, HasProp};
+pub use __macro::{
+    AllPropsFor, AssertAllProps, AtLeastOneOf, AtMostOneOf, Buildable, CountProp, HasAllProps,
+    HasProp, NoProp, OneMoreProp, PropRequires, PropSet, PropState, PropUnset,
+};
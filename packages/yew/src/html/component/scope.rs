@@ -24,6 +24,8 @@ pub struct AnyScope {
     type_id: TypeId,
     parent: Option<Rc<AnyScope>>,
     typed_scope: Rc<dyn Any>,
+    #[cfg(any(feature = "hmr", feature = "ssr"))]
+    pub(crate) id: usize,
 }
 
 impl fmt::Debug for AnyScope {
@@ -37,6 +39,8 @@ impl<COMP: BaseComponent> From<Scope<COMP>> for AnyScope {
         AnyScope {
             type_id: TypeId::of::<COMP>(),
             parent: scope.parent.clone(),
+            #[cfg(any(feature = "hmr", feature = "ssr"))]
+            id: scope.id,
             typed_scope: Rc::new(scope),
         }
     }
@@ -519,6 +523,8 @@ mod feat_csr {
                 type_id: TypeId::of::<()>(),
                 parent: None,
                 typed_scope: Rc::new(()),
+                #[cfg(any(feature = "hmr", feature = "ssr"))]
+                id: usize::MAX,
             }
         }
     }
@@ -593,6 +599,15 @@ mod feat_csr {
         /// Process an event to destroy a component
         fn destroy(self, parent_to_detach: bool);
         fn destroy_boxed(self: Box<Self>, parent_to_detach: bool);
+        /// Get the id of the component
+        #[cfg(feature = "hmr")]
+        fn id(&self) -> usize;
+        /// Get the type name of the component
+        #[cfg(feature = "hmr")]
+        fn type_name(&self) -> &'static str;
+        /// Call `f` with the scopes of the components rendered by this component, in render order
+        #[cfg(feature = "hmr")]
+        fn for_each_child(&self, f: &mut dyn FnMut(&dyn Scoped));
     }
 
     impl<COMP: BaseComponent> Scoped for Scope<COMP> {
@@ -631,6 +646,30 @@ mod feat_csr {
                 render_state.render_state.shift(parent, slot)
             }
         }
+
+        #[cfg(feature = "hmr")]
+        fn id(&self) -> usize {
+            self.id
+        }
+
+        #[cfg(feature = "hmr")]
+        fn type_name(&self) -> &'static str {
+            std::any::type_name::<COMP>()
+        }
+
+        #[cfg(feature = "hmr")]
+        fn for_each_child(&self, f: &mut dyn FnMut(&dyn Scoped)) {
+            if let Some(render_state) = self.render_state() {
+                match &*render_state {
+                    ComponentRenderState::Render { bundle, .. } => bundle.for_each_component(f),
+                    // the component has not been rendered yet
+                    #[cfg(feature = "hydration")]
+                    ComponentRenderState::Hydration { .. } => {}
+                    #[cfg(feature = "ssr")]
+                    ComponentRenderState::Ssr { .. } => {}
+                }
+            }
+        }
     }
 }
 #[cfg(feature = "csr")]
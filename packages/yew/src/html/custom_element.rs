@@ -0,0 +1,455 @@
+use std::borrow::Cow;
+use std::rc::Rc;
+
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::virtual_dom::{AttrValue, Listener, ListenerKind};
+use crate::Callback;
+
+/// Declares a [custom element](https://developer.mozilla.org/en-US/docs/Web/API/Web_components)
+/// with typed props and events.
+///
+/// The attribute is applied to a struct whose fields describe the interface of the element. It
+/// turns the struct into a component rendering the element, which can be used in the [`html!`]
+/// macro like any other component. The fields become the props of the component, and are
+/// available as a `Properties` struct named after the component with a `Props` suffix.
+///
+/// Each field is bound to the element depending on its attribute:
+///
+/// - Fields without attributes are set as attributes of the element. The attribute name is the
+///   name of the field with `_` replaced by `-`, or can be given with `#[attribute("name")]`. The
+///   values are converted with `IntoPropValue<Option<AttrValue>>`, and `bool` fields are boolean
+///   attributes.
+/// - `#[property]` fields are set as JavaScript properties of the element, which can hold any value
+///   implementing [`ToJsValue`]. The property name is the name of the field in camelCase, or can be
+///   given with `#[property("name")]`. A property is only set again when its value changes.
+/// - `#[event]` fields are callbacks of type `Callback<T>` or `Option<Callback<T>>`, called with the
+///   `detail` of the `CustomEvent`s dispatched by the element, converted with [`FromJsValue`]. The
+///   event name is the name of the field without the `on` prefix, or can be given with
+///   `#[event("name")]`.
+/// - A `#[node_ref]` field of type [`NodeRef`](crate::NodeRef) is set to the element.
+/// - A field named `children` is rendered as the children of the element, and a `#[rest]` field
+///   of type [`RestAttributes`](crate::html::RestAttributes) is spread on it.
+///
+/// The props attributes, like `#[prop_or_default]`, can be used on all the fields.
+///
+/// # Example
+///
+/// ```
+/// use yew::prelude::*;
+///
+/// #[custom_element("color-picker")]
+/// pub struct ColorPicker {
+///     /// The color, set as the `value` property.
+///     #[property]
+///     pub value: String,
+///     /// The colors to choose from, set as the `swatches` property.
+///     #[prop_or_default]
+///     #[property]
+///     pub swatches: Vec<String>,
+///     /// Set as the `disabled` attribute.
+///     #[prop_or_default]
+///     pub disabled: bool,
+///     /// Called with the detail of the `color-change` events.
+///     #[prop_or_default]
+///     #[event("color-change")]
+///     pub onchange: Option<Callback<String>>,
+///     #[prop_or_default]
+///     pub children: Html,
+/// }
+///
+/// #[function_component]
+/// fn App() -> Html {
+///     let color = use_state(|| "teal".to_string());
+///     let onchange = {
+///         let color = color.clone();
+///         Callback::from(move |value| color.set(value))
+///     };
+///
+///     html! {
+///         <ColorPicker value={(*color).clone()} {onchange}>
+///             { "Pick a color" }
+///         </ColorPicker>
+///     }
+/// }
+/// ```
+///
+/// [`html!`]: crate::html!
+pub use yew_macro::custom_element;
+
+/// A value which can be set as a JavaScript property of an element.
+#[rustversion::attr(
+    since(1.78),
+    diagnostic::on_unimplemented(
+        message = "`{Self}` can't be converted to a JavaScript value",
+        label = "can't be set as a property",
+        note = "implement `ToJsValue` for `{Self}`, or use a `JsValue`"
+    )
+)]
+pub trait ToJsValue {
+    /// Converts the value to a JavaScript value.
+    fn to_js_value(&self) -> JsValue;
+}
+
+/// A value which can be converted from a JavaScript value, like the detail of a custom event.
+#[rustversion::attr(
+    since(1.78),
+    diagnostic::on_unimplemented(
+        message = "`{Self}` can't be converted from a JavaScript value",
+        label = "can't be the detail of an event",
+        note = "implement `FromJsValue` for `{Self}`, or use a `JsValue`"
+    )
+)]
+pub trait FromJsValue: Sized {
+    /// Converts a JavaScript value, or returns `None` if it isn't of the expected type.
+    fn from_js_value(value: &JsValue) -> Option<Self>;
+}
+
+impl ToJsValue for JsValue {
+    fn to_js_value(&self) -> JsValue {
+        self.clone()
+    }
+}
+
+impl FromJsValue for JsValue {
+    fn from_js_value(value: &JsValue) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+impl FromJsValue for () {
+    fn from_js_value(_value: &JsValue) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl ToJsValue for bool {
+    fn to_js_value(&self) -> JsValue {
+        JsValue::from_bool(*self)
+    }
+}
+
+impl FromJsValue for bool {
+    fn from_js_value(value: &JsValue) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+impl ToJsValue for str {
+    fn to_js_value(&self) -> JsValue {
+        JsValue::from_str(self)
+    }
+}
+
+impl ToJsValue for String {
+    fn to_js_value(&self) -> JsValue {
+        JsValue::from_str(self)
+    }
+}
+
+impl FromJsValue for String {
+    fn from_js_value(value: &JsValue) -> Option<Self> {
+        value.as_string()
+    }
+}
+
+impl ToJsValue for AttrValue {
+    fn to_js_value(&self) -> JsValue {
+        JsValue::from_str(self)
+    }
+}
+
+impl FromJsValue for AttrValue {
+    fn from_js_value(value: &JsValue) -> Option<Self> {
+        value.as_string().map(AttrValue::from)
+    }
+}
+
+macro_rules! impl_js_value_for_float {
+    ($($ty:ty)*) => {$(
+        impl ToJsValue for $ty {
+            fn to_js_value(&self) -> JsValue {
+                JsValue::from_f64(*self as f64)
+            }
+        }
+
+        impl FromJsValue for $ty {
+            fn from_js_value(value: &JsValue) -> Option<Self> {
+                value.as_f64().map(|value| value as $ty)
+            }
+        }
+    )*};
+}
+
+impl_js_value_for_float!(f32 f64);
+
+macro_rules! impl_js_value_for_int {
+    ($($ty:ty)*) => {$(
+        impl ToJsValue for $ty {
+            fn to_js_value(&self) -> JsValue {
+                JsValue::from_f64(*self as f64)
+            }
+        }
+
+        impl FromJsValue for $ty {
+            fn from_js_value(value: &JsValue) -> Option<Self> {
+                // numbers which aren't integers or don't fit in the type are rejected
+                value
+                    .as_f64()
+                    .filter(|value| {
+                        value.fract() == 0.0
+                            && *value >= <$ty>::MIN as f64
+                            && *value <= <$ty>::MAX as f64
+                    })
+                    .map(|value| value as $ty)
+            }
+        }
+    )*};
+}
+
+impl_js_value_for_int!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+
+macro_rules! impl_js_value_for_js_type {
+    ($($ty:path)*) => {$(
+        impl ToJsValue for $ty {
+            fn to_js_value(&self) -> JsValue {
+                AsRef::<JsValue>::as_ref(self).clone()
+            }
+        }
+
+        impl FromJsValue for $ty {
+            fn from_js_value(value: &JsValue) -> Option<Self> {
+                value.dyn_ref::<$ty>().cloned()
+            }
+        }
+    )*};
+}
+
+impl_js_value_for_js_type! {
+    js_sys::Array
+    js_sys::ArrayBuffer
+    js_sys::Date
+    js_sys::Function
+    js_sys::JsString
+    js_sys::Map
+    js_sys::Object
+    js_sys::Set
+    js_sys::Uint8Array
+    web_sys::Element
+}
+
+/// `None` is converted to `null`, and both `null` and `undefined` are converted to `None`.
+impl<T: ToJsValue> ToJsValue for Option<T> {
+    fn to_js_value(&self) -> JsValue {
+        match self {
+            Some(value) => value.to_js_value(),
+            None => JsValue::NULL,
+        }
+    }
+}
+
+impl<T: FromJsValue> FromJsValue for Option<T> {
+    fn from_js_value(value: &JsValue) -> Option<Self> {
+        if value.is_null() || value.is_undefined() {
+            Some(None)
+        } else {
+            T::from_js_value(value).map(Some)
+        }
+    }
+}
+
+/// Vectors are converted to arrays.
+impl<T: ToJsValue> ToJsValue for Vec<T> {
+    fn to_js_value(&self) -> JsValue {
+        self.iter()
+            .map(ToJsValue::to_js_value)
+            .collect::<js_sys::Array>()
+            .into()
+    }
+}
+
+impl<T: FromJsValue> FromJsValue for Vec<T> {
+    fn from_js_value(value: &JsValue) -> Option<Self> {
+        value
+            .dyn_ref::<js_sys::Array>()?
+            .iter()
+            .map(|value| T::from_js_value(&value))
+            .collect()
+    }
+}
+
+impl<T: ToJsValue + ?Sized> ToJsValue for &T {
+    fn to_js_value(&self) -> JsValue {
+        (**self).to_js_value()
+    }
+}
+
+impl<T: ToJsValue + ?Sized> ToJsValue for Rc<T> {
+    fn to_js_value(&self) -> JsValue {
+        (**self).to_js_value()
+    }
+}
+
+/// A listener calling a callback with the detail of the `CustomEvent`s of a kind.
+struct CustomEventListener<T> {
+    kind: &'static str,
+    callback: Callback<T>,
+}
+
+impl<T: FromJsValue + 'static> Listener for CustomEventListener<T> {
+    fn kind(&self) -> ListenerKind {
+        ListenerKind::other(Cow::Borrowed(self.kind))
+    }
+
+    fn handle(&self, event: web_sys::Event) {
+        // events which aren't custom events have no detail
+        let detail = event
+            .dyn_ref::<web_sys::CustomEvent>()
+            .map(web_sys::CustomEvent::detail)
+            .unwrap_or(JsValue::UNDEFINED);
+        match T::from_js_value(&detail) {
+            Some(detail) => self.callback.emit(detail),
+            None => tracing::warn!(
+                "the detail of a `{}` event has an unexpected type and was ignored",
+                self.kind
+            ),
+        }
+    }
+
+    fn passive(&self) -> bool {
+        false
+    }
+}
+
+#[doc(hidden)]
+pub fn __macro_event_listener<T>(
+    kind: &'static str,
+    callback: Option<Callback<T>>,
+) -> Option<Rc<dyn Listener>>
+where
+    T: FromJsValue + 'static,
+{
+    let callback = callback?;
+    Some(Rc::new(CustomEventListener { kind, callback }))
+}
+
+#[doc(hidden)]
+pub fn __macro_set_property<T>(node: &web_sys::Node, name: &str, value: &T)
+where
+    T: ToJsValue + ?Sized,
+{
+    js_sys::Reflect::set(node, &JsValue::from_str(name), &value.to_js_value())
+        .expect("failed to set a property of a custom element");
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "ssr")]
+#[cfg(test)]
+mod ssr_tests {
+    use tokio::test;
+
+    use crate::prelude::*;
+    use crate::ServerRenderer;
+
+    #[custom_element("yew-slider")]
+    struct Slider {
+        #[property]
+        value: f64,
+        #[prop_or_default]
+        disabled: bool,
+        #[prop_or_default]
+        aria_label: Option<AttrValue>,
+        #[prop_or_default]
+        #[attribute("data-step")]
+        step: Option<AttrValue>,
+        #[prop_or_default]
+        #[event("value-change")]
+        onchange: Option<Callback<f64>>,
+        #[prop_or_default]
+        #[rest]
+        attrs: RestAttributes,
+        #[prop_or_default]
+        children: Html,
+    }
+
+    #[test]
+    async fn test_custom_element() {
+        #[function_component]
+        fn Comp() -> Html {
+            html! {
+                <Slider value=0.5 disabled=true aria_label="Volume" class="wide">
+                    <span>{ "Volume" }</span>
+                </Slider>
+            }
+        }
+
+        let s = ServerRenderer::<Comp>::new()
+            .hydratable(false)
+            .render()
+            .await;
+
+        assert_eq!(
+            s,
+            r#"<yew-slider disabled="disabled" aria-label="Volume" class="wide"><span>Volume</span></yew-slider>"#
+        );
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[cfg(feature = "csr")]
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+    use web_sys::{CustomEvent, CustomEventInit};
+
+    use crate::prelude::*;
+    use crate::{scheduler, Renderer};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[custom_element("yew-test-counter")]
+    struct Counter {
+        #[property]
+        start_value: u32,
+        #[event("count-change")]
+        oncountchange: Callback<u32>,
+        #[node_ref]
+        element: NodeRef,
+    }
+
+    #[test]
+    fn sets_properties_and_listens_to_events() {
+        thread_local! {
+            static COUNT: Cell<u32> = Cell::new(0);
+        }
+
+        let element = NodeRef::default();
+        let root = gloo::utils::document().create_element("div").unwrap();
+        gloo::utils::body().append_child(&root).unwrap();
+        let _app = Renderer::<Counter>::with_root_and_props(
+            root,
+            CounterProps {
+                start_value: 3,
+                oncountchange: Callback::from(|count| COUNT.with(|c| c.set(count))),
+                element: element.clone(),
+            },
+        )
+        .render();
+        scheduler::start_now();
+
+        let el = element.get().unwrap();
+        let start_value = js_sys::Reflect::get(&el, &JsValue::from_str("startValue")).unwrap();
+        assert_eq!(start_value.as_f64(), Some(3.0));
+
+        let mut init = CustomEventInit::new();
+        init.detail(&JsValue::from_f64(7.0));
+        let event = CustomEvent::new_with_event_init_dict("count-change", &init).unwrap();
+        el.unchecked_into::<web_sys::EventTarget>()
+            .dispatch_event(&event)
+            .unwrap();
+        assert_eq!(COUNT.with(Cell::get), 7);
+    }
+}
@@ -1,19 +1,27 @@
 //! The main html module which defines components, listeners, and class helpers.
 
+pub(crate) mod bind;
 mod classes;
 mod component;
 mod conversion;
+mod custom_element;
 mod error;
 mod listener;
+mod rest;
+mod style;
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
+pub use bind::*;
 pub use classes::*;
 pub use component::*;
 pub use conversion::*;
+pub use custom_element::*;
 pub use error::*;
 pub use listener::*;
+pub use rest::*;
+pub use style::*;
 use wasm_bindgen::JsValue;
 use web_sys::{Element, Node};
 
@@ -0,0 +1,317 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::rc::Rc;
+
+use implicit_clone::ImplicitClone;
+use indexmap::IndexMap;
+
+use super::{Classes, IntoPropValue};
+use crate::virtual_dom::{AttrValue, Listener};
+
+/// Attributes and listeners which are forwarded by a component to one of its elements.
+///
+/// A prop of this type marked with `#[rest]` collects all the attributes and listeners given to
+/// the component which aren't props of it. They can then be spread on an element with
+/// `<div ..{attrs} />`:
+///
+/// ```
+/// use yew::prelude::*;
+///
+/// #[derive(Properties, PartialEq)]
+/// struct ButtonProps {
+///     #[prop_or_default]
+///     #[rest]
+///     attrs: RestAttributes,
+///     children: Html,
+/// }
+///
+/// #[function_component]
+/// fn Button(props: &ButtonProps) -> Html {
+///     html! {
+///         <button class="button" ..{props.attrs.clone()}>{ props.children.clone() }</button>
+///     }
+/// }
+///
+/// # #[function_component]
+/// # fn App() -> Html {
+/// html! {
+///     <Button class="primary" aria-label="Save" onclick={Callback::from(|_| ())}>
+///         { "Save" }
+///     </Button>
+/// }
+/// # }
+/// ```
+///
+/// When spread, the classes are added to the ones of the element, the listeners are added to its
+/// listeners, and the other attributes replace attributes of the element with the same name.
+#[derive(Clone, Default)]
+pub struct RestAttributes {
+    attributes: Rc<IndexMap<AttrValue, AttrValue>>,
+    classes: Classes,
+    listeners: Rc<Vec<Rc<dyn Listener>>>,
+}
+
+impl ImplicitClone for RestAttributes {}
+
+impl RestAttributes {
+    /// Creates empty rest attributes.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of an attribute.
+    ///
+    /// If the value is `None`, the attribute is removed instead. The `class` attribute is added to
+    /// the classes.
+    pub fn set<K, V>(&mut self, name: K, value: V)
+    where
+        K: Into<AttrValue>,
+        V: IntoPropValue<Option<AttrValue>>,
+    {
+        let name = name.into();
+        match value.into_prop_value() {
+            Some(value) if name == "class" => self.classes.push(value),
+            Some(value) => {
+                Rc::make_mut(&mut self.attributes).insert(name, value);
+            }
+            None => {
+                self.remove(&name);
+            }
+        }
+    }
+
+    /// Removes an attribute, returning its value.
+    pub fn remove(&mut self, name: &str) -> Option<AttrValue> {
+        if !self.attributes.contains_key(name) {
+            return None;
+        }
+        Rc::make_mut(&mut self.attributes).shift_remove(name)
+    }
+
+    /// Returns the value of an attribute.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&AttrValue> {
+        self.attributes.get(name)
+    }
+
+    /// Returns an iterator over the attributes and their values, except for `class`.
+    pub fn iter(&self) -> impl Iterator<Item = (&AttrValue, &AttrValue)> {
+        self.attributes.iter()
+    }
+
+    /// Returns the classes.
+    #[inline]
+    pub fn classes(&self) -> &Classes {
+        &self.classes
+    }
+
+    /// Adds classes.
+    pub fn push_class<T: Into<Classes>>(&mut self, class: T) {
+        self.classes.push(class);
+    }
+
+    /// Returns the listeners.
+    #[inline]
+    pub fn listeners(&self) -> &[Rc<dyn Listener>] {
+        &self.listeners
+    }
+
+    /// Adds a listener.
+    pub fn add_listener(&mut self, listener: Rc<dyn Listener>) {
+        Rc::make_mut(&mut self.listeners).push(listener);
+    }
+
+    /// Check there are no attributes, classes or listeners.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.classes.is_empty() && self.listeners.is_empty()
+    }
+}
+
+impl fmt::Debug for RestAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RestAttributes")
+            .field("attributes", &self.attributes)
+            .field("classes", &self.classes)
+            .field("listeners", &self.listeners)
+            .finish()
+    }
+}
+
+impl PartialEq for RestAttributes {
+    fn eq(&self, other: &Self) -> bool {
+        self.attributes == other.attributes
+            && self.classes == other.classes
+            && self.listeners.len() == other.listeners.len()
+            && self
+                .listeners
+                .iter()
+                .zip(other.listeners.iter())
+                .all(|(lhs, rhs)| {
+                    #[allow(clippy::vtable_address_comparisons)]
+                    Rc::ptr_eq(lhs, rhs)
+                })
+    }
+}
+
+impl<K: Into<AttrValue>, V: IntoPropValue<Option<AttrValue>>> Extend<(K, V)> for RestAttributes {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|(name, value)| self.set(name, value))
+    }
+}
+
+impl Extend<RestAttributes> for RestAttributes {
+    fn extend<I: IntoIterator<Item = RestAttributes>>(&mut self, iter: I) {
+        for other in iter {
+            if self.is_empty() {
+                *self = other;
+                continue;
+            }
+            if !other.attributes.is_empty() {
+                let attributes = Rc::make_mut(&mut self.attributes);
+                for (name, value) in other.attributes.iter() {
+                    attributes.insert(name.clone(), value.clone());
+                }
+            }
+            self.classes.push(other.classes);
+            if !other.listeners.is_empty() {
+                Rc::make_mut(&mut self.listeners).extend(other.listeners.iter().cloned());
+            }
+        }
+    }
+}
+
+impl<K: Into<AttrValue>, V: IntoPropValue<Option<AttrValue>>> FromIterator<(K, V)>
+    for RestAttributes
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut attrs = Self::new();
+        attrs.extend(iter);
+        attrs
+    }
+}
+
+yew_macro::__forwarded_attributes! {
+    /// Forwards attributes and listeners given to a component to its `#[rest]` prop.
+    ///
+    /// The builders of props with a `#[rest]` prop dereference to this type, so the `html!` macro
+    /// calls its methods for the attributes and listeners which aren't props of the component.
+    /// There is a method and a field for each attribute and listener known by the `html!` macro,
+    /// the fields are only used to check the labels given to the component. Attributes with other
+    /// names, like `aria-label`, go through `__yew_rest_attribute`.
+    #[doc(hidden)]
+    #[derive(Default)]
+    pub struct ForwardedAttributes {
+        rest: RestAttributes,
+    }
+}
+
+impl fmt::Debug for ForwardedAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ForwardedAttributes")
+            .field(&self.rest)
+            .finish()
+    }
+}
+
+impl ForwardedAttributes {
+    /// Sets the `#[rest]` prop itself, merging it with the attributes forwarded so far.
+    #[inline]
+    pub fn __yew_extend(&mut self, attrs: RestAttributes) {
+        self.rest.extend([attrs]);
+    }
+
+    #[inline]
+    pub fn __yew_into_rest(self) -> RestAttributes {
+        self.rest
+    }
+
+    #[inline]
+    pub fn __yew_rest_attribute<Token>(
+        &mut self,
+        token: Token,
+        name: &'static str,
+        value: impl IntoPropValue<Option<AttrValue>>,
+    ) -> Token {
+        self.rest.set(name, value);
+        token
+    }
+
+    #[inline]
+    pub fn __yew_rest_boolean<Token>(
+        &mut self,
+        token: Token,
+        name: &'static str,
+        value: bool,
+    ) -> Token {
+        if value {
+            self.rest.set(name, name);
+        } else {
+            self.rest.remove(name);
+        }
+        token
+    }
+
+    #[inline]
+    pub fn __yew_rest_listener<Token>(
+        &mut self,
+        token: Token,
+        listener: Option<Rc<dyn Listener>>,
+    ) -> Token {
+        if let Some(listener) = listener {
+            self.rest.add_listener(listener);
+        }
+        token
+    }
+
+    #[inline]
+    pub fn class<Token>(&mut self, token: Token, value: impl Into<Classes>) -> Token {
+        self.rest.push_class(value);
+        token
+    }
+}
+
+/// The type the `html!` macro checks the labels of attributes and listeners given to a component
+/// against.
+///
+/// This is the props themselves, unless they have a `#[rest]` prop, in which case it's
+/// [`ForwardedAttributes`].
+#[doc(hidden)]
+pub trait PropLabels {
+    type Labels;
+}
+
+impl PropLabels for () {
+    type Labels = ();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sets_and_removes_attributes() {
+        let mut subject = RestAttributes::new();
+        assert!(subject.is_empty());
+        subject.set("aria-label", "Save");
+        subject.set("title", String::from("Save the file"));
+        subject.set("class", "primary");
+        assert_eq!(subject.get("aria-label").unwrap(), "Save");
+        assert_eq!(subject.get("class"), None);
+        assert_eq!(subject.classes(), &Classes::from("primary"));
+        subject.set("title", None::<AttrValue>);
+        assert_eq!(subject.iter().count(), 1);
+    }
+
+    #[test]
+    fn can_be_extended_with_other_attributes() {
+        let mut subject: RestAttributes = [("id", "a"), ("class", "a")].into_iter().collect();
+        subject.extend([[("id", "b"), ("class", "b"), ("title", "b")]
+            .into_iter()
+            .collect::<RestAttributes>()]);
+        assert_eq!(subject.get("id").unwrap(), "b");
+        assert_eq!(subject.get("title").unwrap(), "b");
+        assert_eq!(subject.classes(), &Classes::from("a b"));
+    }
+}
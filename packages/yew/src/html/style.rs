@@ -0,0 +1,293 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::iter::FromIterator;
+use std::rc::Rc;
+
+use implicit_clone::ImplicitClone;
+use indexmap::IndexMap;
+
+use super::IntoPropValue;
+use crate::virtual_dom::AttrValue;
+
+/// A set of CSS properties for the `style` attribute, cheap to clone.
+///
+/// The preferred way of creating this is using the [`style!`][yew::style!] macro.
+///
+/// When the `style` attribute of an element changes, only the properties which changed are
+/// updated with `style.setProperty()` and `style.removeProperty()`, instead of rewriting the whole
+/// attribute.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Style {
+    properties: Rc<IndexMap<AttrValue, AttrValue>>,
+}
+
+impl ImplicitClone for Style {}
+
+impl Style {
+    /// Creates an empty style.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty style with capacity for n properties.
+    #[inline]
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            properties: Rc::new(IndexMap::with_capacity(n)),
+        }
+    }
+
+    /// Sets the value of a property.
+    ///
+    /// If the value is `None`, the property is removed instead.
+    pub fn set<K, V>(&mut self, name: K, value: V)
+    where
+        K: Into<AttrValue>,
+        V: IntoPropValue<Option<AttrValue>>,
+    {
+        let name = name.into();
+        match value.into_prop_value() {
+            Some(value) => {
+                Rc::make_mut(&mut self.properties).insert(name, value);
+            }
+            None => {
+                self.remove(&name);
+            }
+        }
+    }
+
+    /// Removes a property, returning its value.
+    pub fn remove(&mut self, name: &str) -> Option<AttrValue> {
+        if !self.properties.contains_key(name) {
+            return None;
+        }
+        Rc::make_mut(&mut self.properties).shift_remove(name)
+    }
+
+    /// Returns the value of a property.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&AttrValue> {
+        self.properties.get(name)
+    }
+
+    /// Check the style contains a property.
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.properties.contains_key(name)
+    }
+
+    /// Returns the number of properties.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    /// Check the style is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    /// Returns an iterator over the properties and their values.
+    pub fn iter(&self) -> impl Iterator<Item = (&AttrValue, &AttrValue)> {
+        self.properties.iter()
+    }
+}
+
+/// Splits the value of a `style` attribute into its declarations.
+///
+/// Yields `None` for declarations which are not of the form `name: value`.
+pub(crate) fn declarations(style: &str) -> impl Iterator<Item = Option<(&str, &str)>> {
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut ends = Vec::new();
+    for (i, c) in style.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                ends.push((start, i));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    ends.push((start, style.len()));
+
+    ends.into_iter()
+        .map(move |(start, end)| style[start..end].trim())
+        .filter(|declaration| !declaration.is_empty())
+        .map(|declaration| {
+            declaration
+                .split_once(':')
+                .map(|(name, value)| (name.trim(), value.trim()))
+                .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+        })
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.properties.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{name}: {value}")?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoPropValue<AttrValue> for Style {
+    #[inline]
+    fn into_prop_value(self) -> AttrValue {
+        self.to_string().into()
+    }
+}
+
+impl IntoPropValue<Option<AttrValue>> for Style {
+    #[inline]
+    fn into_prop_value(self) -> Option<AttrValue> {
+        (&self).into_prop_value()
+    }
+}
+
+impl IntoPropValue<Option<AttrValue>> for &Style {
+    #[inline]
+    fn into_prop_value(self) -> Option<AttrValue> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.to_string().into())
+        }
+    }
+}
+
+impl<K: Into<AttrValue>, V: IntoPropValue<Option<AttrValue>>> Extend<(K, V)> for Style {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|(name, value)| self.set(name, value))
+    }
+}
+
+impl Extend<Style> for Style {
+    fn extend<I: IntoIterator<Item = Style>>(&mut self, iter: I) {
+        for other in iter {
+            if self.is_empty() {
+                *self = other;
+            } else {
+                let properties = Rc::make_mut(&mut self.properties);
+                for (name, value) in other.properties.iter() {
+                    properties.insert(name.clone(), value.clone());
+                }
+            }
+        }
+    }
+}
+
+impl<K: Into<AttrValue>, V: IntoPropValue<Option<AttrValue>>> FromIterator<(K, V)> for Style {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut style = Self::new();
+        style.extend(iter);
+        style
+    }
+}
+
+impl IntoIterator for Style {
+    type IntoIter = indexmap::map::IntoIter<AttrValue, AttrValue>;
+    type Item = (AttrValue, AttrValue);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Rc::try_unwrap(self.properties)
+            .unwrap_or_else(|properties| (*properties).clone())
+            .into_iter()
+    }
+}
+
+impl From<&str> for Style {
+    fn from(t: &str) -> Self {
+        declarations(t)
+            .flatten()
+            .map(|(name, value)| (AttrValue::from(name.to_owned()), value.to_owned()))
+            .collect()
+    }
+}
+
+impl From<String> for Style {
+    fn from(t: String) -> Self {
+        Self::from(t.as_str())
+    }
+}
+
+impl From<&String> for Style {
+    fn from(t: &String) -> Self {
+        Self::from(t.as_str())
+    }
+}
+
+impl From<Cow<'static, str>> for Style {
+    fn from(t: Cow<'static, str>) -> Self {
+        Self::from(&*t)
+    }
+}
+
+impl From<AttrValue> for Style {
+    fn from(t: AttrValue) -> Self {
+        Self::from(t.as_str())
+    }
+}
+
+impl From<&AttrValue> for Style {
+    fn from(t: &AttrValue) -> Self {
+        Self::from(t.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_is_initially_empty() {
+        let subject = Style::new();
+        assert!(subject.is_empty());
+        assert_eq!(
+            IntoPropValue::<Option<AttrValue>>::into_prop_value(subject),
+            None
+        );
+    }
+
+    #[test]
+    fn it_sets_and_removes_values() {
+        let mut subject = Style::new();
+        subject.set("color", "red");
+        subject.set("margin", String::from("0 auto"));
+        assert_eq!(subject.get("color").unwrap(), "red");
+        subject.set("color", None::<AttrValue>);
+        assert!(!subject.contains("color"));
+        assert_eq!(subject.to_string(), "margin: 0 auto");
+    }
+
+    #[test]
+    fn it_is_parsed_from_strings() {
+        let subject = Style::from(r#"color: red;; background: url("a;b.png") ; invalid"#);
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.get("background").unwrap(), r#"url("a;b.png")"#);
+        assert_eq!(
+            subject.to_string(),
+            r#"color: red; background: url("a;b.png")"#
+        );
+    }
+
+    #[test]
+    fn can_be_extended_with_another_style() {
+        let mut subject = Style::from("color: red; padding: 0");
+        subject.extend([Style::from("color: blue; margin: 0")]);
+        assert_eq!(subject.to_string(), "color: blue; padding: 0; margin: 0");
+    }
+}
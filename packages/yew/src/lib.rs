@@ -27,6 +27,8 @@
 //!   are making a Yew application (not a library).
 //! - `ssr`: Enables Server-side Rendering support and [`ServerRenderer`].
 //! - `hydration`: Enables Hydration support.
+//! - `hmr`: Enables [hot module reloading](hmr) of components. Only enable this feature in
+//!   development builds.
 //!
 //! ## Example
 //!
@@ -113,6 +115,42 @@ extern crate self as yew;
 /// # }
 /// ```
 pub use yew_macro::classes;
+/// This macro creates a [`ScopedStyle`](style::ScopedStyle) from a scoped style sheet.
+///
+/// The CSS is parsed at compile time and scoped to a class name derived from its contents.
+/// Declarations at the top level apply to the element the style is added to, and nested rules
+/// apply to its descendants, or to the element itself with `&`. Rust values can be interpolated
+/// with `${name}`. They are passed to the style sheet as CSS variables, either from variables in
+/// scope or from named arguments.
+///
+/// The style sheet is added to the document once, the first time it is used. See the
+/// [`style`](mod@style) module for more information.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # fn test() {
+/// let size = 2;
+/// let style = css!(
+///     r#"
+///     font-size: ${size}em;
+///     color: ${color};
+///     &:hover { color: black; }
+///     .icon { margin-right: 4px; }
+///     @media (max-width: 600px) { font-size: 1em; }
+///     "#,
+///     color = "teal",
+/// );
+///
+/// html! {
+///     <button class={classes!(&style, "primary")} style={style.vars()}>
+///         <span class="icon" />{ "Click me" }
+///     </button>
+/// };
+/// # }
+/// ```
+pub use yew_macro::css;
 /// This macro implements JSX-like templates.
 ///
 /// This macro always returns [`Html`].
@@ -267,10 +305,36 @@ pub use yew_macro::html_nested;
 /// [`Properties`]: ./html/trait.Properties.html
 /// [Yew Docs]: https://yew.rs/concepts/components/properties
 pub use yew_macro::props;
+/// This macro provides a convenient way to create a [`Style`](html::Style) for the `style`
+/// attribute.
+///
+/// The macro takes a list of `property: value` pairs. Property names are written in kebab-case,
+/// or as string literals for custom properties. Each value can be of any type that implements
+/// `IntoPropValue<Option<AttrValue>>`, and properties whose value is `None` are left out.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # fn test() {
+/// let progress = 0.4;
+/// let hidden = false;
+///
+/// html! {
+///     <div style={style! {
+///         width: format!("{}%", progress * 100.0),
+///         background-color: "teal",
+///         display: hidden.then_some("none"),
+///         "--accent": "gold",
+///     }} />
+/// };
+/// # }
+/// ```
+pub use yew_macro::style;
 
 /// This module contains macros which implements html! macro and JSX-like templates
 pub mod macros {
-    pub use crate::{classes, html, html_nested, props};
+    pub use crate::{classes, css, html, html_nested, props, style};
 }
 
 pub mod callback;
@@ -278,12 +342,15 @@ pub mod context;
 #[cfg(feature = "csr")]
 mod dom_bundle;
 pub mod functional;
+#[cfg(feature = "hmr")]
+pub mod hmr;
 pub mod html;
 pub mod platform;
 pub mod scheduler;
 mod sealed;
 #[cfg(feature = "ssr")]
 mod server_renderer;
+pub mod style;
 pub mod suspense;
 pub mod utils;
 pub mod virtual_dom;
@@ -294,6 +361,8 @@ pub use server_renderer::*;
 mod app_handle;
 #[cfg(feature = "csr")]
 mod renderer;
+#[cfg(feature = "csr")]
+mod web_component;
 
 #[cfg(feature = "csr")]
 #[cfg(test)]
@@ -317,6 +386,8 @@ pub mod events {
 pub use crate::app_handle::AppHandle;
 #[cfg(feature = "csr")]
 pub use crate::renderer::{set_custom_panic_hook, Renderer};
+#[cfg(feature = "csr")]
+pub use crate::web_component::WebComponent;
 
 pub mod prelude {
     //! The Yew Prelude
@@ -335,10 +406,10 @@ pub mod prelude {
     pub use crate::events::*;
     pub use crate::functional::*;
     pub use crate::html::{
-        create_portal, BaseComponent, Children, ChildrenWithProps, Classes, Component, Context,
-        Html, HtmlResult, NodeRef, Properties, ToHtml,
+        create_portal, custom_element, BaseComponent, Children, ChildrenWithProps, Classes,
+        Component, Context, Html, HtmlResult, NodeRef, Properties, RestAttributes, Style, ToHtml,
     };
-    pub use crate::macros::{classes, html, html_nested};
+    pub use crate::macros::{classes, css, html, html_nested, style};
     pub use crate::suspense::Suspense;
     pub use crate::virtual_dom::AttrValue;
 }
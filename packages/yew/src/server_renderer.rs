@@ -1,4 +1,4 @@
-use std::fmt;
+use std::fmt::{self, Write};
 use std::future::Future;
 
 use futures::pin_mut;
@@ -8,6 +8,8 @@ use tracing::Instrument;
 use crate::html::{BaseComponent, Scope};
 use crate::platform::fmt::BufStream;
 use crate::platform::{LocalHandle, Runtime};
+use crate::style::feat_ssr::StyleCollector;
+use crate::style::RenderedStyles;
 
 #[cfg(feature = "ssr")]
 pub(crate) mod feat_ssr {
@@ -49,6 +51,10 @@ pub(crate) mod feat_ssr {
 /// - `wasm-bindgen-futures` is selected as the backend of Yew runtime.
 /// - running within a [`Runtime`](crate::platform::Runtime).
 /// - running within a tokio [`LocalSet`](struct@tokio::task::LocalSet).
+///
+/// The style sheets created with [`css!`](crate::css) while rendering are written after the
+/// application, unless they are collected with `styles`. See [`yew::style`](crate::style) for more
+/// information.
 #[cfg(feature = "ssr")]
 #[derive(Debug)]
 pub struct LocalServerRenderer<COMP>
@@ -57,6 +63,7 @@ where
 {
     props: COMP::Properties,
     hydratable: bool,
+    styles: Option<RenderedStyles>,
 }
 
 impl<COMP> Default for LocalServerRenderer<COMP>
@@ -89,6 +96,7 @@ where
         Self {
             props,
             hydratable: true,
+            styles: None,
         }
     }
 
@@ -104,6 +112,16 @@ where
         self
     }
 
+    /// Collects the style sheets used by the application into `styles`, instead of writing them
+    /// after it.
+    ///
+    /// The style sheets can then be written in `<head>`, once the application is rendered.
+    pub fn styles(mut self, styles: RenderedStyles) -> Self {
+        self.styles = Some(styles);
+
+        self
+    }
+
     /// Renders Yew Application.
     pub async fn render(self) -> String {
         let s = self.render_stream();
@@ -129,6 +147,11 @@ where
         BufStream::new(move |mut w| async move {
             let render_span = tracing::debug_span!("render_stream_item");
             render_span.follows_from(outer_span);
+            let (styles, write_styles) = match self.styles {
+                Some(m) => (m, false),
+                None => (RenderedStyles::default(), true),
+            };
+            let collector = StyleCollector::new(scope.id, &styles);
             scope
                 .render_into_stream(
                     &mut w,
@@ -138,6 +161,11 @@ where
                 )
                 .instrument(render_span)
                 .await;
+            drop(collector);
+
+            if write_styles {
+                let _ = write!(w, "{styles}");
+            }
         })
     }
 
@@ -178,6 +206,10 @@ where
 /// the rendering process has finished.
 ///
 /// See [`yew::platform`] for more information.
+///
+/// The style sheets created with [`css!`](crate::css) while rendering are written after the
+/// application, unless they are collected with `styles`. See [`yew::style`](crate::style) for more
+/// information.
 #[cfg(feature = "ssr")]
 pub struct ServerRenderer<COMP>
 where
@@ -185,6 +217,7 @@ where
 {
     create_props: Box<dyn Send + FnOnce() -> COMP::Properties>,
     hydratable: bool,
+    styles: Option<RenderedStyles>,
     rt: Option<Runtime>,
 }
 
@@ -235,6 +268,7 @@ where
         Self {
             create_props: Box::new(create_props),
             hydratable: true,
+            styles: None,
             rt: None,
         }
     }
@@ -258,21 +292,32 @@ where
         self
     }
 
+    /// Collects the style sheets used by the application into `styles`, instead of writing them
+    /// after it.
+    ///
+    /// The style sheets can then be written in `<head>`, once the application is rendered.
+    pub fn styles(mut self, styles: RenderedStyles) -> Self {
+        self.styles = Some(styles);
+
+        self
+    }
+
     /// Renders Yew Application.
     pub async fn render(self) -> String {
         let Self {
             create_props,
             hydratable,
+            styles,
             rt,
         } = self;
 
         let (tx, rx) = futures::channel::oneshot::channel();
         let create_task = move || async move {
             let props = create_props();
-            let s = LocalServerRenderer::<COMP>::with_props(props)
-                .hydratable(hydratable)
-                .render()
-                .await;
+            let mut renderer =
+                LocalServerRenderer::<COMP>::with_props(props).hydratable(hydratable);
+            renderer.styles = styles;
+            let s = renderer.render().await;
 
             let _ = tx.send(s);
         };
@@ -314,15 +359,17 @@ where
         let Self {
             create_props,
             hydratable,
+            styles,
             rt,
         } = self;
 
         let (tx, rx) = futures::channel::mpsc::unbounded();
         let create_task = move || async move {
             let props = create_props();
-            let s = LocalServerRenderer::<COMP>::with_props(props)
-                .hydratable(hydratable)
-                .render_stream();
+            let mut renderer =
+                LocalServerRenderer::<COMP>::with_props(props).hydratable(hydratable);
+            renderer.styles = styles;
+            let s = renderer.render_stream();
             pin_mut!(s);
 
             while let Some(m) = s.next().await {
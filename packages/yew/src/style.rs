@@ -0,0 +1,544 @@
+//! This module contains the types used by the [`css!`](crate::css) macro.
+//!
+//! A style sheet written with `css!` is scoped to a class name which is derived from its
+//! contents. It is added to the document the first time it is used:
+//!
+//! - When rendering on the client, a `<style>` element is appended to `<head>`.
+//! - When rendering on the server, the style sheets used by the application are written after it
+//!   by the [`ServerRenderer`](crate::ServerRenderer). Hydration moves them into `<head>`.
+//!   Alternatively, they can be collected into [`RenderedStyles`] and written in `<head>` by the
+//!   server, so the application isn't displayed before its styles are loaded.
+
+use std::fmt;
+
+use crate::html::{Classes, Style};
+
+/// The attribute which marks the `<style>` elements added by Yew.
+#[cfg(any(
+    all(feature = "csr", target_arch = "wasm32"),
+    feature = "hydration",
+    feature = "ssr"
+))]
+pub(crate) const STYLE_ATTRIBUTE: &str = "data-yew-style";
+
+/// A style sheet, created by the [`css!`](crate::css) macro.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StyleSheet {
+    class: &'static str,
+    css: &'static str,
+}
+
+impl StyleSheet {
+    #[doc(hidden)]
+    pub const fn __new(class: &'static str, css: &'static str) -> Self {
+        Self { class, css }
+    }
+
+    /// Returns the class name the style sheet is scoped to.
+    pub fn class(&self) -> &'static str {
+        self.class
+    }
+
+    /// Returns the scoped CSS of the style sheet.
+    pub fn css(&self) -> &'static str {
+        self.css
+    }
+
+    // The return is needed when the style sheet can also be injected.
+    #[allow(clippy::needless_return)]
+    fn register(&'static self) {
+        #[cfg(feature = "ssr")]
+        {
+            if feat_ssr::collect(self) {
+                return;
+            }
+        }
+
+        #[cfg(all(feature = "csr", target_arch = "wasm32"))]
+        feat_csr::inject(self);
+    }
+}
+
+/// A scoped style, created by the [`css!`](crate::css) macro.
+///
+/// It is applied to an element by adding its class, and its interpolated values with the `style`
+/// attribute:
+///
+/// ```
+/// # use yew::prelude::*;
+/// #[function_component]
+/// fn Title() -> Html {
+///     let color = "rebeccapurple";
+///     let style = css!("color: ${color}; &:hover { text-decoration: underline; }");
+///
+///     html! {
+///         <h1 class={&style} style={style.vars()}>{ "Hello" }</h1>
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopedStyle {
+    sheet: &'static StyleSheet,
+    vars: Style,
+}
+
+impl ScopedStyle {
+    #[doc(hidden)]
+    pub fn __new(sheet: &'static StyleSheet, vars: &[(&'static str, &dyn fmt::Display)]) -> Self {
+        sheet.register();
+
+        let vars = vars
+            .iter()
+            .map(|(name, value)| (*name, value.to_string()))
+            .filter(|(name, value)| {
+                let contained = is_contained(value);
+                if !contained {
+                    tracing::warn!("the value of the CSS variable `{name}` is not set: {value:?}");
+                }
+                contained
+            })
+            .collect();
+
+        Self { sheet, vars }
+    }
+
+    /// Returns the class name of the style.
+    pub fn class(&self) -> &'static str {
+        self.sheet.class
+    }
+
+    /// Returns the style sheet of the style.
+    pub fn sheet(&self) -> &'static StyleSheet {
+        self.sheet
+    }
+
+    /// Returns the CSS variables which hold the interpolated values of the style, to be used as
+    /// the `style` attribute of the element.
+    ///
+    /// The returned style is empty if the style has no interpolated values. Values which would end
+    /// their declaration, e.g. with a `;` or an unclosed string, are left out.
+    pub fn vars(&self) -> Style {
+        self.vars.clone()
+    }
+}
+
+/// Returns whether `value` can be the value of a declaration without ending it, i.e. it has no
+/// `;`, `{` or `}` outside of strings, and its strings, comments and parentheses are closed.
+fn is_contained(value: &str) -> bool {
+    let mut chars = value.chars().peekable();
+    let mut quote = None;
+    let mut depth = 0usize;
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (_, '\\') => {
+                if chars.next().is_none() {
+                    return false;
+                }
+            }
+            (Some(_), '\n') => return false,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '/') if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = None;
+                loop {
+                    match chars.next() {
+                        Some('/') if prev == Some('*') => break,
+                        Some(c) => prev = Some(c),
+                        None => return false,
+                    }
+                }
+            }
+            (None, '(') => depth += 1,
+            (None, ')') => match depth.checked_sub(1) {
+                Some(m) => depth = m,
+                None => return false,
+            },
+            (None, ';' | '{' | '}') => return false,
+            (None, _) => {}
+        }
+    }
+
+    quote.is_none() && depth == 0
+}
+
+impl From<ScopedStyle> for Classes {
+    fn from(style: ScopedStyle) -> Self {
+        style.class().into()
+    }
+}
+
+impl From<&ScopedStyle> for Classes {
+    fn from(style: &ScopedStyle) -> Self {
+        style.class().into()
+    }
+}
+
+#[cfg(all(feature = "csr", target_arch = "wasm32"))]
+mod feat_csr {
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+
+    use super::*;
+
+    thread_local! {
+        static INJECTED: RefCell<HashSet<&'static str>> = RefCell::default();
+    }
+
+    pub(super) fn inject(sheet: &'static StyleSheet) {
+        if !INJECTED.with(|m| m.borrow_mut().insert(sheet.class)) {
+            return;
+        }
+
+        let document = gloo::utils::document();
+        // The style sheet may have been rendered on the server.
+        let selector = format!("style[{STYLE_ATTRIBUTE}=\"{}\"]", sheet.class);
+        if let Ok(Some(_)) = document.query_selector(&selector) {
+            return;
+        }
+
+        let element = document
+            .create_element("style")
+            .expect("failed to create style element");
+        element
+            .set_attribute(STYLE_ATTRIBUTE, sheet.class)
+            .expect("failed to set attribute");
+        element.set_text_content(Some(sheet.css));
+        gloo::utils::head()
+            .append_child(&element)
+            .expect("failed to append style element");
+    }
+}
+
+#[cfg(feature = "hydration")]
+pub(crate) mod feat_hydration {
+    use web_sys::Element;
+
+    use super::*;
+
+    /// Moves the style sheets rendered on the server out of the host, so they are not removed by
+    /// the hydration.
+    pub(crate) fn hoist_styles(host: &Element) {
+        let children = host.children();
+        let styles = (0..children.length())
+            .filter_map(|i| children.item(i))
+            .filter(|m| {
+                m.tag_name().eq_ignore_ascii_case("style") && m.has_attribute(STYLE_ATTRIBUTE)
+            })
+            .collect::<Vec<_>>();
+
+        if styles.is_empty() {
+            return;
+        }
+
+        let head = gloo::utils::head();
+        for style in styles {
+            head.append_child(&style)
+                .expect("failed to move style element");
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use feat_ssr::RenderedStyles;
+
+#[cfg(feature = "ssr")]
+pub(crate) mod feat_ssr {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::iter;
+    use std::sync::{Arc, Mutex, Weak};
+
+    use super::*;
+    use crate::html::AnyScope;
+
+    type Sheets = Mutex<Vec<&'static StyleSheet>>;
+
+    thread_local! {
+        // The style sheets of the applications being rendered, by the id of their root scope.
+        static COLLECTORS: RefCell<HashMap<usize, Weak<Sheets>>> = RefCell::default();
+        // The style sheets of the application of the component being rendered.
+        static CURRENT: RefCell<Option<Arc<Sheets>>> = const { RefCell::new(None) };
+    }
+
+    /// The style sheets used by an application rendered on the server.
+    ///
+    /// The [`ServerRenderer`](crate::ServerRenderer) writes them after the application, unless
+    /// they are collected with [`ServerRenderer::styles`](crate::ServerRenderer::styles). They
+    /// can then be written in `<head>`, so the application isn't displayed before its styles are
+    /// loaded.
+    ///
+    /// The style sheets are complete once the application is rendered. Its `Display`
+    /// implementation writes them as `<style>` elements.
+    #[derive(Debug, Clone, Default)]
+    pub struct RenderedStyles {
+        sheets: Arc<Sheets>,
+    }
+
+    impl RenderedStyles {
+        /// Returns the style sheets used by the application, in the order they were first used.
+        pub fn sheets(&self) -> Vec<&'static StyleSheet> {
+            self.sheets.lock().unwrap().clone()
+        }
+    }
+
+    impl fmt::Display for RenderedStyles {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            for sheet in self.sheets.lock().unwrap().iter() {
+                write!(
+                    f,
+                    r#"<style {STYLE_ATTRIBUTE}="{}">{}</style>"#,
+                    sheet.class, sheet.css
+                )?;
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Collects the style sheets used while rendering an application on the server into
+    /// `styles`, until it is dropped.
+    pub(crate) struct StyleCollector {
+        root: usize,
+        // Kept alive while the application is rendered.
+        _sheets: Arc<Sheets>,
+    }
+
+    impl StyleCollector {
+        /// Creates a collector for the application with the root scope `root`.
+        pub(crate) fn new(root: usize, styles: &RenderedStyles) -> Self {
+            let sheets = styles.sheets.clone();
+            COLLECTORS.with(|m| m.borrow_mut().insert(root, Arc::downgrade(&sheets)));
+
+            Self {
+                root,
+                _sheets: sheets,
+            }
+        }
+    }
+
+    impl Drop for StyleCollector {
+        fn drop(&mut self) {
+            COLLECTORS.with(|m| m.borrow_mut().remove(&self.root));
+        }
+    }
+
+    /// Restores the style sheets of the previous component when dropped.
+    struct RestoreCurrent(Option<Arc<Sheets>>);
+
+    impl Drop for RestoreCurrent {
+        fn drop(&mut self) {
+            CURRENT.with(|m| *m.borrow_mut() = self.0.take());
+        }
+    }
+
+    /// Calls `render`, which renders the component of `scope`, collecting the style sheets it
+    /// uses for the application of the component.
+    ///
+    /// The components of applications rendered at the same time on the same thread are rendered
+    /// in turns, so each render is matched to its application with the root of its scope.
+    pub(crate) fn with_collector<T>(
+        scope: impl FnOnce() -> AnyScope,
+        render: impl FnOnce() -> T,
+    ) -> T {
+        if COLLECTORS.with(|m| m.borrow().is_empty()) {
+            return render();
+        }
+
+        let scope = scope();
+        let root = iter::successors(Some(&scope), |m| m.get_parent())
+            .last()
+            .map(|m| m.id);
+        let sheets = root
+            .and_then(|root| COLLECTORS.with(|m| m.borrow().get(&root).and_then(Weak::upgrade)));
+
+        let _restore = RestoreCurrent(CURRENT.with(|m| m.replace(sheets)));
+        render()
+    }
+
+    /// Adds a style sheet to the application of the component being rendered. Returns `false`
+    /// if the component isn't rendered on the server.
+    pub(super) fn collect(sheet: &'static StyleSheet) -> bool {
+        CURRENT.with(|m| match *m.borrow() {
+            Some(ref sheets) => {
+                let mut sheets = sheets.lock().unwrap();
+                if !sheets.contains(&sheet) {
+                    sheets.push(sheet);
+                }
+                true
+            }
+            None => false,
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "ssr")]
+#[cfg(test)]
+mod ssr_tests {
+    use tokio::test;
+
+    use crate::prelude::*;
+    use crate::style::RenderedStyles;
+    use crate::ServerRenderer;
+
+    #[test]
+    async fn test_styles_are_collected() {
+        #[function_component]
+        fn Item() -> Html {
+            let style = css!("padding: 4px;");
+            html! { <li class={style} /> }
+        }
+
+        #[function_component]
+        fn Comp() -> Html {
+            let color = "red";
+            let style = css!("color: ${color}; li { margin: 0 }");
+            html! {
+                <ul class={&style} style={style.vars()}>
+                    <Item />
+                    <Item />
+                </ul>
+            }
+        }
+
+        let s = ServerRenderer::<Comp>::new()
+            .hydratable(false)
+            .render()
+            .await;
+
+        assert_eq!(
+            s,
+            concat!(
+                r#"<ul style="--yew-7c7379dc-color: red" class="yew-7c7379dc">"#,
+                r#"<li class="yew-e4ac3786"></li><li class="yew-e4ac3786"></li></ul>"#,
+                r#"<style data-yew-style="yew-7c7379dc">"#,
+                r#".yew-7c7379dc{color:var(--yew-7c7379dc-color)}.yew-7c7379dc li{margin:0}"#,
+                r#"</style>"#,
+                r#"<style data-yew-style="yew-e4ac3786">.yew-e4ac3786{padding:4px}</style>"#,
+            )
+        );
+    }
+
+    #[test]
+    async fn test_unused_styles_are_not_collected() {
+        #[function_component]
+        fn Unused() -> Html {
+            let style = css!("display: none;");
+            html! { <div class={style} /> }
+        }
+
+        #[function_component]
+        fn Comp() -> Html {
+            html! { <div /> }
+        }
+
+        let _ = ServerRenderer::<Unused>::new().render().await;
+        let s = ServerRenderer::<Comp>::new()
+            .hydratable(false)
+            .render()
+            .await;
+
+        assert_eq!(s, "<div></div>");
+    }
+
+    #[test]
+    async fn test_values_cannot_escape_their_declaration() {
+        #[function_component]
+        fn Comp() -> Html {
+            let color = "red; background: url(x)";
+            let width = "calc(100% - 4px)";
+            let style = css!("color: ${color}; width: ${width};");
+            html! { <p class={&style} style={style.vars()} /> }
+        }
+
+        let s = ServerRenderer::<Comp>::new()
+            .hydratable(false)
+            .render()
+            .await;
+
+        assert!(!s.contains("background"));
+        assert!(s.contains("-width: calc(100% - 4px)"));
+        assert!(!s.contains("-color:"));
+    }
+
+    #[test]
+    async fn test_styles_can_be_written_in_head() {
+        #[function_component]
+        fn Comp() -> Html {
+            let style = css!("margin: 0;");
+            html! { <main class={style} /> }
+        }
+
+        let styles = RenderedStyles::default();
+        let body = ServerRenderer::<Comp>::new()
+            .hydratable(false)
+            .styles(styles.clone())
+            .render()
+            .await;
+
+        assert_eq!(body, r#"<main class="yew-73aa04e2"></main>"#);
+        assert_eq!(styles.sheets().len(), 1);
+        assert_eq!(
+            styles.to_string(),
+            r#"<style data-yew-style="yew-73aa04e2">.yew-73aa04e2{margin:0}</style>"#
+        );
+    }
+
+    #[test]
+    async fn test_styles_are_collected_by_their_render() {
+        use std::time::Duration;
+
+        use crate::platform::time::sleep;
+        use crate::suspense::{use_future, Suspense};
+        use crate::LocalServerRenderer;
+
+        #[function_component]
+        fn Late() -> HtmlResult {
+            use_future(|| sleep(Duration::from_millis(10)))?;
+            let style = css!("color: blue;");
+            Ok(html! { <p class={style} /> })
+        }
+
+        #[function_component]
+        fn Slow() -> Html {
+            let style = css!("color: red;");
+            html! {
+                <div class={style}>
+                    <Suspense><Late /></Suspense>
+                </div>
+            }
+        }
+
+        #[function_component]
+        fn Fast() -> Html {
+            let style = css!("color: green;");
+            html! { <div class={style} /> }
+        }
+
+        let local = tokio::task::LocalSet::new();
+        let (slow, fast) = local
+            .run_until(async {
+                let slow = LocalServerRenderer::<Slow>::new()
+                    .hydratable(false)
+                    .render();
+                let fast = async {
+                    sleep(Duration::from_millis(5)).await;
+                    LocalServerRenderer::<Fast>::new()
+                        .hydratable(false)
+                        .render()
+                        .await
+                };
+                futures::join!(slow, fast)
+            })
+            .await;
+
+        assert!(slow.contains("{color:red}"));
+        assert!(slow.contains("{color:blue}"));
+        assert!(!slow.contains("{color:green}"));
+        assert!(fast.contains("{color:green}"));
+        assert!(!fast.contains("{color:red}"));
+    }
+}
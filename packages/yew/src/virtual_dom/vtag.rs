@@ -10,7 +10,7 @@ use std::rc::Rc;
 use web_sys::{HtmlInputElement as InputElement, HtmlTextAreaElement as TextAreaElement};
 
 use super::{ApplyAttributeAs, AttrValue, Attributes, Key, Listener, Listeners, VNode};
-use crate::html::{IntoPropValue, NodeRef};
+use crate::html::{bind, Bind, Classes, IntoPropValue, NodeRef, RestAttributes};
 
 /// SVG namespace string used for creating svg elements
 pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
@@ -403,6 +403,96 @@ impl VTag {
         );
     }
 
+    /// Spreads the attributes and listeners forwarded by a component with `..{attrs}`.
+    ///
+    /// The classes are added to the `class` attribute and the listeners to the listeners of the
+    /// element, while the other attributes replace the ones with the same name.
+    #[doc(hidden)]
+    pub fn __macro_spread(&mut self, rest: &RestAttributes) {
+        for (name, value) in rest.iter() {
+            match (&mut self.inner, name.as_str()) {
+                (VTagInner::Input(_) | VTagInner::Textarea { .. }, "value") => {
+                    self.set_value(value.clone())
+                }
+                (VTagInner::Input(_), "checked") => self.set_checked(true),
+                _ => {
+                    self.attributes
+                        .get_mut_index_map()
+                        .insert(name.clone(), (value.clone(), ApplyAttributeAs::Attribute));
+                }
+            }
+        }
+
+        if !rest.classes().is_empty() {
+            let attributes = self.attributes.get_mut_index_map();
+            let mut classes = attributes
+                .get("class")
+                .map(|(class, _)| Classes::from(class.clone()))
+                .unwrap_or_default();
+            classes.push(rest.classes().clone());
+            attributes.insert(
+                AttrValue::Static("class"),
+                (classes.into_prop_value(), ApplyAttributeAs::Attribute),
+            );
+        }
+
+        for listener in rest.listeners() {
+            self.add_listener(listener.clone());
+        }
+    }
+
+    /// Binds the value of an `<input>`, a `<textarea>` or a `<select>` with `bind:value`.
+    ///
+    /// A radio button is checked when the bound value is its own value instead.
+    #[doc(hidden)]
+    pub fn __macro_bind_value<B>(&mut self, binding: &B)
+    where
+        B: Bind<AttrValue> + Clone + 'static,
+    {
+        let radio = matches!(self.inner, VTagInner::Input(_))
+            && self
+                .attributes
+                .iter()
+                .any(|(k, v)| k == "type" && v.eq_ignore_ascii_case("radio"));
+        match (&self.inner, binding.bound()) {
+            (VTagInner::Other { tag, .. }, _) if !tag.eq_ignore_ascii_case("select") => return,
+            (_, None) => {}
+            (_, Some(value)) if radio => {
+                let checked = self.value() == Some(&value);
+                self.set_checked(checked);
+            }
+            (VTagInner::Other { .. }, Some(value)) => self.add_property("value", value),
+            (_, Some(value)) => self.set_value(value),
+        }
+        if let Some(node_ref) = binding.node_ref() {
+            self.node_ref = node_ref;
+        }
+        if let Some(listener) = bind::value_listener(binding.clone(), radio) {
+            self.add_listener(listener);
+        }
+    }
+
+    /// Binds the checked state of an `<input>` with `bind:checked`.
+    #[doc(hidden)]
+    pub fn __macro_bind_checked<B>(&mut self, binding: &B)
+    where
+        B: Bind<bool> + Clone + 'static,
+    {
+        if !matches!(self.inner, VTagInner::Input(_)) {
+            return;
+        }
+        match binding.bound() {
+            Some(checked) => self.set_checked(checked),
+            None => self.preserve_checked(),
+        }
+        if let Some(node_ref) = binding.node_ref() {
+            self.node_ref = node_ref;
+        }
+        if let Some(listener) = bind::checked_listener(binding.clone()) {
+            self.add_listener(listener);
+        }
+    }
+
     /// Add event listener on the [VTag]'s  [Element](web_sys::Element).
     /// Returns `true` if the listener has been added, `false` otherwise.
     pub fn add_listener(&mut self, listener: Rc<dyn Listener>) -> bool {
@@ -679,4 +769,105 @@ mod ssr_tests {
             r#"<style>html { background: black } body > a { color: white } </style>"#
         );
     }
+
+    #[test]
+    async fn test_style_attribute() {
+        #[function_component]
+        fn Comp() -> Html {
+            let hidden = false;
+            html! {
+                <div style={style! {
+                    color: "red",
+                    display: hidden.then_some("none"),
+                    font-family: r#""Fira Sans", serif"#,
+                }}></div>
+            }
+        }
+
+        let s = ServerRenderer::<Comp>::new()
+            .hydratable(false)
+            .render()
+            .await;
+
+        assert_eq!(
+            s,
+            r#"<div style="color: red; font-family: &quot;Fira Sans&quot;, serif"></div>"#
+        );
+    }
+
+    #[test]
+    async fn test_spread_attributes() {
+        #[derive(Properties, PartialEq)]
+        struct FieldProps {
+            #[prop_or_default]
+            #[rest]
+            attrs: RestAttributes,
+            #[prop_or_default]
+            r#type: Option<AttrValue>,
+        }
+
+        #[function_component]
+        fn Field(props: &FieldProps) -> Html {
+            html! {
+                <input class="field" type={props.r#type.clone()} ..{&props.attrs} />
+            }
+        }
+
+        #[function_component]
+        fn Comp() -> Html {
+            html! {
+                <Field
+                    class="wide"
+                    r#type="email"
+                    aria-label="Email"
+                    disabled=true
+                    placeholder="you@example.com"
+                    value="hi"
+                    onclick={Callback::from(|_| ())}
+                />
+            }
+        }
+
+        let s = ServerRenderer::<Comp>::new()
+            .hydratable(false)
+            .render()
+            .await;
+
+        assert_eq!(
+            s,
+            r#"<input value="hi" type="email" class="field wide" aria-label="Email" disabled="disabled" placeholder="you@example.com">"#
+        );
+    }
+
+    #[test]
+    async fn test_bindings() {
+        #[function_component]
+        fn Comp() -> Html {
+            let name = use_state(|| String::from("Yew"));
+            let color = use_state(|| AttrValue::from("blue"));
+            let subscribed = use_state(|| true);
+            let node_ref = use_node_ref();
+
+            html! {
+                <form>
+                    <input bind:value={name.clone()} />
+                    <textarea bind:value={name} />
+                    <input type="radio" value="red" bind:value={color.clone()} />
+                    <input type="radio" value="blue" bind:value={color} />
+                    <input type="checkbox" bind:checked={subscribed} />
+                    <input bind:value={node_ref} />
+                </form>
+            }
+        }
+
+        let s = ServerRenderer::<Comp>::new()
+            .hydratable(false)
+            .render()
+            .await;
+
+        assert_eq!(
+            s,
+            r#"<form><input value="Yew"><textarea>Yew</textarea><input value="red" type="radio"><input value="blue" checked type="radio"><input checked type="checkbox"><input></form>"#
+        );
+    }
 }
@@ -0,0 +1,293 @@
+//! Defines Yew components as [Web Components](https://developer.mozilla.org/en-US/docs/Web/API/Web_components).
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use slab::Slab;
+use wasm_bindgen::prelude::*;
+use web_sys::{Element, ShadowRootInit, ShadowRootMode};
+
+use crate::app_handle::AppHandle;
+use crate::html::BaseComponent;
+use crate::renderer::set_default_panic_hook;
+
+#[wasm_bindgen(inline_js = r#"
+export function define_custom_element(name, observed, connect, changed, disconnect) {
+    customElements.define(name, class extends HTMLElement {
+        static get observedAttributes() {
+            return observed;
+        }
+
+        connectedCallback() {
+            this.__yew_instance = connect(this);
+        }
+
+        attributeChangedCallback() {
+            if (this.__yew_instance !== undefined) {
+                changed(this.__yew_instance, this);
+            }
+        }
+
+        disconnectedCallback() {
+            if (this.__yew_instance !== undefined) {
+                disconnect(this.__yew_instance);
+                this.__yew_instance = undefined;
+            }
+        }
+    });
+}
+"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn define_custom_element(
+        name: &str,
+        observed: js_sys::Array,
+        connect: &JsValue,
+        changed: &JsValue,
+        disconnect: &JsValue,
+    ) -> Result<(), JsValue>;
+}
+
+/// Creates the properties of the component from the element.
+type PropsFn<P> = Rc<dyn Fn(&Element) -> P>;
+
+/// Defines a custom element rendering a Yew component, with
+/// [`customElements.define`](https://developer.mozilla.org/en-US/docs/Web/API/CustomElementRegistry/define).
+///
+/// The component is rendered in the element when it is connected to the document, and destroyed
+/// when it is disconnected. Its properties are created from the element, and updated whenever one
+/// of the observed attributes changes.
+///
+/// ```no_run
+/// use yew::prelude::*;
+/// use yew::WebComponent;
+///
+/// #[derive(Properties, PartialEq)]
+/// pub struct GreetingProps {
+///     pub name: AttrValue,
+/// }
+///
+/// #[function_component]
+/// fn Greeting(props: &GreetingProps) -> Html {
+///     html! { <p>{ "Hello, " }{ &props.name }</p> }
+/// }
+///
+/// fn main() {
+///     // can now be used as `<yew-greeting name="Ferris"></yew-greeting>`
+///     WebComponent::<Greeting>::with_props("yew-greeting", |element| GreetingProps {
+///         name: element.get_attribute("name").unwrap_or_default().into(),
+///     })
+///     .observed_attributes(["name"])
+///     .with_shadow_root()
+///     .define();
+/// }
+/// ```
+#[must_use = "WebComponent does nothing unless define() is called."]
+pub struct WebComponent<COMP>
+where
+    COMP: BaseComponent + 'static,
+{
+    name: &'static str,
+    observed_attributes: Vec<&'static str>,
+    shadow_root: bool,
+    props: PropsFn<COMP::Properties>,
+}
+
+impl<COMP> fmt::Debug for WebComponent<COMP>
+where
+    COMP: BaseComponent + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebComponent")
+            .field("name", &self.name)
+            .field("observed_attributes", &self.observed_attributes)
+            .field("shadow_root", &self.shadow_root)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<COMP> WebComponent<COMP>
+where
+    COMP: BaseComponent + 'static,
+    COMP::Properties: Default,
+{
+    /// Creates a [WebComponent] named `name` rendering the component with default properties.
+    pub fn new(name: &'static str) -> Self {
+        Self::with_props(name, |_| Default::default())
+    }
+}
+
+impl<COMP> WebComponent<COMP>
+where
+    COMP: BaseComponent + 'static,
+{
+    /// Creates a [WebComponent] named `name` rendering the component with properties created from
+    /// the element.
+    pub fn with_props<F>(name: &'static str, props: F) -> Self
+    where
+        F: Fn(&Element) -> COMP::Properties + 'static,
+    {
+        Self {
+            name,
+            observed_attributes: Vec::new(),
+            shadow_root: false,
+            props: Rc::new(props),
+        }
+    }
+
+    /// Sets the attributes whose changes update the properties of the component.
+    pub fn observed_attributes(
+        mut self,
+        attributes: impl IntoIterator<Item = &'static str>,
+    ) -> Self {
+        self.observed_attributes.extend(attributes);
+        self
+    }
+
+    /// Renders the component in an open shadow root attached to the element, instead of the
+    /// element itself.
+    ///
+    /// The component is rendered in a `<div>` with `display: contents` in the shadow root.
+    pub fn with_shadow_root(mut self) -> Self {
+        self.shadow_root = true;
+        self
+    }
+
+    /// Defines the custom element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is not a valid custom element name, or if it is already defined.
+    pub fn define(self) {
+        let Self {
+            name,
+            observed_attributes,
+            shadow_root,
+            props,
+        } = self;
+        set_default_panic_hook();
+        let instances = Rc::new(RefCell::new(Slab::<AppHandle<COMP>>::new()));
+
+        let connect = {
+            let instances = instances.clone();
+            let props = props.clone();
+            Closure::<dyn Fn(Element) -> u32>::new(move |element: Element| {
+                let root = if shadow_root {
+                    shadow_root_container(&element)
+                } else {
+                    element.clone()
+                };
+                let app =
+                    crate::Renderer::<COMP>::with_root_and_props(root, props(&element)).render();
+                // the instances are not borrowed while rendering, which can connect other elements
+                instances.borrow_mut().insert(app) as u32
+            })
+        };
+        let changed = {
+            let instances = instances.clone();
+            Closure::<dyn Fn(u32, Element)>::new(move |instance: u32, element: Element| {
+                let scope = instances
+                    .borrow()
+                    .get(instance as usize)
+                    .map(|app| app.scope.clone());
+                if let Some(scope) = scope {
+                    AppHandle { scope }.update(props(&element));
+                }
+            })
+        };
+        let disconnect = Closure::<dyn Fn(u32)>::new(move |instance: u32| {
+            let app = instances.borrow_mut().try_remove(instance as usize);
+            if let Some(app) = app {
+                app.destroy();
+            }
+        });
+
+        let observed_attributes = observed_attributes
+            .into_iter()
+            .map(JsValue::from_str)
+            .collect();
+        define_custom_element(
+            name,
+            observed_attributes,
+            connect.as_ref(),
+            changed.as_ref(),
+            disconnect.as_ref(),
+        )
+        .unwrap_or_else(|err| panic!("failed to define the custom element `{name}`: {err:?}"));
+
+        // the element stays defined for the lifetime of the page
+        connect.forget();
+        changed.forget();
+        disconnect.forget();
+    }
+}
+
+/// Returns the container to render in, in the shadow root of the element.
+fn shadow_root_container(element: &Element) -> Element {
+    let shadow_root = element.shadow_root().unwrap_or_else(|| {
+        element
+            .attach_shadow(&ShadowRootInit::new(ShadowRootMode::Open))
+            .expect("failed to attach a shadow root")
+    });
+    if let Some(container) = shadow_root.first_element_child() {
+        return container;
+    }
+
+    let container = gloo::utils::document()
+        .create_element("div")
+        .expect("failed to create an element");
+    container
+        .set_attribute("style", "display: contents")
+        .expect("failed to set an attribute");
+    shadow_root
+        .append_child(&container)
+        .expect("failed to append a child");
+    container
+}
+
+#[cfg(target_arch = "wasm32")]
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    use super::*;
+    use crate::prelude::*;
+    use crate::scheduler;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Properties, PartialEq)]
+    struct GreetingProps {
+        name: AttrValue,
+    }
+
+    #[function_component]
+    fn Greeting(props: &GreetingProps) -> Html {
+        html! { <p>{ "Hello, " }{ &props.name }</p> }
+    }
+
+    #[test]
+    fn renders_component_in_custom_element() {
+        WebComponent::<Greeting>::with_props("yew-test-greeting", |element| GreetingProps {
+            name: element.get_attribute("name").unwrap_or_default().into(),
+        })
+        .observed_attributes(["name"])
+        .define();
+
+        let document = gloo::utils::document();
+        let element = document.create_element("yew-test-greeting").unwrap();
+        element.set_attribute("name", "Ferris").unwrap();
+        document.body().unwrap().append_child(&element).unwrap();
+        scheduler::start_now();
+        assert_eq!(element.inner_html(), "<p>Hello, Ferris</p>");
+
+        element.set_attribute("name", "Yew").unwrap();
+        scheduler::start_now();
+        assert_eq!(element.inner_html(), "<p>Hello, Yew</p>");
+
+        element.remove();
+        scheduler::start_now();
+        assert_eq!(element.inner_html(), "");
+    }
+}
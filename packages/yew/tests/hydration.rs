@@ -1077,3 +1077,39 @@ async fn hydrate_empty() {
     let result = obtain_result_by_id("output");
     assert_eq!(result.as_str(), r#"<div>after</div><div>after</div>"#);
 }
+
+#[wasm_bindgen_test]
+async fn hydration_with_styles() {
+    #[function_component]
+    fn App() -> Html {
+        let style = css!("color: teal;");
+
+        html! {
+            <div class={style}>{"Styled"}</div>
+        }
+    }
+
+    let s = ServerRenderer::<App>::new().render().await;
+
+    gloo::utils::document()
+        .query_selector("#output")
+        .unwrap()
+        .unwrap()
+        .set_inner_html(&s);
+
+    sleep(Duration::ZERO).await;
+
+    Renderer::<App>::with_root(gloo::utils::document().get_element_by_id("output").unwrap())
+        .hydrate();
+
+    sleep(Duration::ZERO).await;
+
+    let result = obtain_result_by_id("output");
+
+    // the style sheet rendered on the server is moved into the head.
+    assert_eq!(result, r#"<div class="yew-ba4d6287">Styled</div>"#);
+    let styles = gloo::utils::head()
+        .query_selector_all(r#"style[data-yew-style="yew-ba4d6287"]"#)
+        .unwrap();
+    assert_eq!(styles.length(), 1);
+}
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::fs::File;
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
@@ -74,7 +73,7 @@ async fn bench_router_app() -> Duration {
     for _ in 0..TOTAL {
         yew::LocalServerRenderer::<ServerApp>::with_props(ServerAppProps {
             url: "/".into(),
-            queries: HashMap::new(),
+            response: Default::default(),
         })
         .render()
         .await;
@@ -85,6 +85,8 @@ module.exports = {
                         'concepts/html/elements',
                         'concepts/html/events',
                         'concepts/html/classes',
+                        'concepts/html/styles',
+                        'concepts/html/custom-elements',
                         'concepts/html/fragments',
                         'concepts/html/lists',
                         'concepts/html/literals-and-expressions',
@@ -129,6 +131,7 @@ module.exports = {
                 'advanced-topics/portals',
                 'advanced-topics/server-side-rendering',
                 'advanced-topics/immutable',
+                'advanced-topics/hot-module-reloading',
             ],
         },
         {
@@ -154,6 +157,7 @@ module.exports = {
                     type: 'category',
                     label: 'yew',
                     items: [
+                        'migration-guides/yew/from-0_21_0-to-0_22_0',
                         'migration-guides/yew/from-0_20_0-to-0_21_0',
                         'migration-guides/yew/from-0_19_0-to-0_20_0',
                         'migration-guides/yew/from-0_18_0-to-0_19_0',
@@ -163,6 +167,7 @@ module.exports = {
                     type: 'category',
                     label: 'yew-agent',
                     items: [
+                        'migration-guides/yew-agent/from-0_2_0-to-0_3_0',
                         'migration-guides/yew-agent/from-0_1_0-to-0_2_0',
                         'migration-guides/yew-agent/from-0_0_0-to-0_1_0',
                     ],